@@ -0,0 +1,93 @@
+use crate::App;
+
+/// The function that is called when a command is executed.
+pub type CommandAction = Box<dyn Fn(&mut App)>;
+
+/// A command that can be executed from the command palette.
+pub struct Command {
+    /// The name that is matched against the palette input.
+    pub name: String,
+    /// A short description shown next to the name.
+    pub description: String,
+    /// The key that executes the command from the leader menu.
+    pub leader_key: Option<char>,
+    action: CommandAction,
+}
+
+/// The commands of the command palette. Each module registers its own
+/// commands, so the palette doesn't need to know about them. A command that
+/// needs the state of a module should set a
+/// [`Navigation`](crate::state::Navigation) request.
+#[derive(Default)]
+pub struct CommandRegistry {
+    commands: Vec<Command>,
+}
+
+impl CommandRegistry {
+    /// Register a command. Commands are kept sorted by name, and registering a
+    /// name again replaces the command.
+    pub fn register<F>(&mut self, name: &str, description: &str, action: F)
+    where
+        F: Fn(&mut App) + 'static,
+    {
+        let command = Command {
+            name: name.to_string(),
+            description: description.to_string(),
+            leader_key: None,
+            action: Box::new(action),
+        };
+        match self
+            .commands
+            .binary_search_by(|c| c.name.as_str().cmp(name))
+        {
+            Ok(index) => self.commands[index] = command,
+            Err(index) => self.commands.insert(index, command),
+        }
+    }
+
+    /// Execute a registered command with a key after the leader key. A key that
+    /// is already used by another command is moved to this command.
+    pub fn leader(&mut self, key: char, name: &str) {
+        for command in self.commands.iter_mut() {
+            if command.leader_key == Some(key) {
+                command.leader_key = None;
+            }
+            if command.name == name {
+                command.leader_key = Some(key);
+            }
+        }
+    }
+
+    /// The commands that have a leader key, sorted by the key.
+    pub fn leader_commands(&self) -> Vec<&Command> {
+        let mut commands = self
+            .commands
+            .iter()
+            .filter(|c| c.leader_key.is_some())
+            .collect::<Vec<&Command>>();
+        commands.sort_by_key(|c| c.leader_key);
+        commands
+    }
+
+    /// The registered commands, sorted by name.
+    pub fn commands(&self) -> &[Command] {
+        &self.commands
+    }
+
+    /// Get a command by its name.
+    pub fn get(&self, name: &str) -> Option<&Command> {
+        self.commands.iter().find(|c| c.name == name)
+    }
+
+    /// Execute a command by its name. Returns false if there is no command with
+    /// the name.
+    pub fn execute(&self, name: &str, app: &mut App) -> bool {
+        match self.get(name) {
+            Some(command) => {
+                (command.action)(app);
+                true
+            }
+            None => false,
+        }
+    }
+}
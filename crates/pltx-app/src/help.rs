@@ -0,0 +1,25 @@
+/// A group of keybinds that are shown together in the help popup, such as the
+/// keybinds of a screen.
+#[derive(Clone, Debug)]
+pub struct KeybindSection {
+    /// The title of the section.
+    pub title: &'static str,
+    /// The keys and what they do.
+    pub keybinds: Vec<(String, &'static str)>,
+}
+
+impl KeybindSection {
+    /// Create an empty section.
+    pub fn new(title: &'static str) -> Self {
+        Self {
+            title,
+            keybinds: vec![],
+        }
+    }
+
+    /// Add a key and a description of what it does.
+    pub fn keybind(mut self, key: impl ToString, description: &'static str) -> Self {
+        self.keybinds.push((key.to_string(), description));
+        self
+    }
+}
@@ -1,16 +1,23 @@
 //! Contains the application state. The [`App`] is passed to all functions
 //! that require state throughout the application.
 
-use pltx_config::{Config, ProfileConfig};
+use color_eyre::Result;
+use pltx_config::{ColorSupport, Config, ProfileConfig};
 use pltx_database::Database;
-use state::{AppModule, AppPopup, Mode, ModeColors, View};
+use state::{AppModule, AppPopup, Mode, ModeColors, Navigation, View};
 
+mod command;
+mod help;
 mod module;
+mod notification;
 /// Application state that affects what is rendered on the screen.
 pub mod state;
 mod widget;
 
+pub use command::*;
+pub use help::*;
 pub use module::*;
+pub use notification::*;
 pub use widget::*;
 
 /// The position of the debug pane on the screen.
@@ -85,6 +92,9 @@ pub struct App {
     /// The user configuration after it has been merged with the base
     /// configuration.
     pub config: Config,
+    /// The colors that the terminal can show, which the colors of the config
+    /// are changed to.
+    pub color_support: ColorSupport,
     /// The merged profile config values to determine which files should be used
     /// for handling data.
     pub profile: ProfileConfig,
@@ -99,6 +109,11 @@ pub struct App {
     pub popup: AppPopup,
     /// The breadcrumbs shown in the titlebar.
     pub breadcrumbs: Vec<String>,
+    /// A pending request to navigate to another module.
+    pub navigation: Option<Navigation>,
+    /// The notifications shown as toasts, from oldest to newest. Use the
+    /// `notify_*` methods to add to them.
+    pub notifications: Vec<Notification>,
     /// The database state and utility methods.
     pub db: Database,
     /// The debug state.
@@ -109,18 +124,23 @@ pub struct App {
 
 impl App {
     /// New a new instance of the application.
-    pub fn new(config: Config, profile: ProfileConfig) -> App {
+    pub fn new(mut config: Config, profile: ProfileConfig) -> App {
         let debug_enabled = &config.log_level == "debug";
         let db_file = profile.db_file.to_owned();
+        let color_support = ColorSupport::detect();
+        color_support.apply(&mut config);
 
         App {
             config,
+            color_support,
             profile,
             view: View::Default,
             mode: Mode::Normal,
             module: AppModule::Home,
             popup: AppPopup::None,
             breadcrumbs: vec![],
+            navigation: None,
+            notifications: vec![],
             db: Database::init(db_file),
             debug: DebugMode {
                 enabled: debug_enabled,
@@ -132,13 +152,67 @@ impl App {
         }
     }
 
+    /// Use the config and database of another profile. The database of the
+    /// current profile is closed, and the state is reset to the dashboard.
+    pub fn switch_profile(&mut self, config: Config, profile: ProfileConfig) -> Result<()> {
+        self.db.end_session()?;
+        self.db = Database::init(profile.db_file.to_owned());
+        self.db.start_session()?;
+        self.debug.enabled = &config.log_level == "debug";
+        self.set_config(config);
+        self.profile = profile;
+        self.view = View::Default;
+        self.mode = Mode::Normal;
+        self.module = AppModule::Home;
+        self.popup = AppPopup::None;
+        self.breadcrumbs.clear();
+        self.navigation = None;
+        Ok(())
+    }
+
+    /// Use a new config, such as one that was just saved. Monochrome mode stays
+    /// enabled, since it can also be enabled from the command line, and the
+    /// colors are changed to ones that the terminal can show.
+    pub fn set_config(&mut self, mut config: Config) {
+        config.monochrome |= self.config.monochrome;
+        self.color_support.apply(&mut config);
+        self.config = config;
+    }
+
+    /// An app with the same config and a database in memory, to render
+    /// widgets with other config values, such as a preview of colors.
+    pub fn preview(&self) -> App {
+        App {
+            config: self.config.clone(),
+            color_support: self.color_support,
+            profile: self.profile.clone(),
+            view: View::Default,
+            mode: Mode::Normal,
+            module: AppModule::Home,
+            popup: AppPopup::None,
+            breadcrumbs: vec![],
+            navigation: None,
+            notifications: vec![],
+            db: Database::in_memory(),
+            debug: DebugMode {
+                enabled: false,
+                show: false,
+                min_preview: false,
+                position: DebugPosition::TopRight,
+            },
+            exit: false,
+        }
+    }
+
     /// Exit the application on next frame render.
     pub fn exit(&mut self) {
         self.exit = true
     }
 
     /// Handle the tick event.
-    pub fn tick(&self) {}
+    pub fn tick(&mut self) {
+        self.notifications.retain(|n| !n.is_expired());
+    }
 
     /// Returns the current mode's colors.
     pub fn mode_colors(&self) -> ModeColors {
@@ -0,0 +1,77 @@
+use std::time::{Duration, Instant};
+
+use crate::App;
+
+/// How long a notification is shown before it's dismissed.
+pub const NOTIFICATION_DURATION: Duration = Duration::from_secs(4);
+
+/// The maximum number of notifications that are kept at once. The oldest are
+/// dropped first.
+const MAX_NOTIFICATIONS: usize = 5;
+
+/// The severity of a notification, which determines its color.
+#[allow(missing_docs)]
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum NotificationLevel {
+    Info,
+    Warning,
+    Error,
+}
+
+/// A message that is shown to the user as a toast.
+#[derive(Clone, Debug)]
+pub struct Notification {
+    /// The message shown in the toast.
+    pub message: String,
+    /// The severity of the notification.
+    pub level: NotificationLevel,
+    /// When the notification was created.
+    pub created_at: Instant,
+}
+
+impl Notification {
+    /// Returns true if the notification has been shown for longer than
+    /// [`NOTIFICATION_DURATION`].
+    pub fn is_expired(&self) -> bool {
+        self.created_at.elapsed() >= NOTIFICATION_DURATION
+    }
+}
+
+impl App {
+    /// Show an informational notification.
+    pub fn notify_info<T: Into<String>>(&mut self, message: T) {
+        self.notify(NotificationLevel::Info, message.into());
+    }
+
+    /// Show a warning notification.
+    pub fn notify_warning<T: Into<String>>(&mut self, message: T) {
+        self.notify(NotificationLevel::Warning, message.into());
+    }
+
+    /// Show an error notification.
+    pub fn notify_error<T: Into<String>>(&mut self, message: T) {
+        self.notify(NotificationLevel::Error, message.into());
+    }
+
+    fn notify(&mut self, level: NotificationLevel, message: String) {
+        self.notifications.push(Notification {
+            message,
+            level,
+            created_at: Instant::now(),
+        });
+        if self.notifications.len() > MAX_NOTIFICATIONS {
+            self.notifications.remove(0);
+        }
+    }
+
+    /// Dismiss the most recent notification. Returns false if there were no
+    /// notifications to dismiss.
+    pub fn dismiss_notification(&mut self) -> bool {
+        self.notifications.pop().is_some()
+    }
+
+    /// Returns true if there are notifications that haven't expired.
+    pub fn has_notifications(&self) -> bool {
+        self.notifications.iter().any(|n| !n.is_expired())
+    }
+}
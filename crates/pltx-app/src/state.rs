@@ -5,6 +5,15 @@ use ratatui::style::Color;
 
 /// The mode the application is in. The mode status is shown at the bottom left
 /// of the status bar.
+///
+/// Every mode returns to [`Normal`](Mode::Normal), which is the only mode the
+/// others are entered from:
+///
+/// - Normal -> Insert: focusing a text input, such as pressing `i`. Escape
+///   returns to normal.
+/// - Normal -> Interactive: focusing a form input that is navigated rather than
+///   typed in, such as a dropdown or checklist. `[` or enter returns to normal.
+/// - Normal -> Delete: pressing `d` on an item. `y` or `n` returns to normal.
 #[derive(PartialEq, Clone, Copy)]
 pub enum Mode {
     /// Default mode mainly for navigation.
@@ -13,6 +22,10 @@ pub enum Mode {
     Insert,
     /// For prompting the user to delete the selected item.
     Delete,
+    /// For navigating within an input, such as the options of a list. Keys are
+    /// directed to the input rather than the popup that contains it, so keys
+    /// such as `q` don't close the popup.
+    Interactive,
 }
 
 /// Used to get the mode properties (colors).
@@ -39,6 +52,11 @@ impl Mode {
         *self = Mode::Delete;
     }
 
+    /// Sets the mode to [`Interactive`](Mode::Interactive).
+    pub fn interactive(&mut self) {
+        *self = Mode::Interactive;
+    }
+
     /// Returns true if the mode is [`Normal`](Mode::Normal).
     pub fn is_normal(&self) -> bool {
         self == &Mode::Normal
@@ -54,6 +72,11 @@ impl Mode {
         self == &Mode::Delete
     }
 
+    /// Returns true if the mode is [`Interactive`](Mode::Interactive).
+    pub fn is_interactive(&self) -> bool {
+        self == &Mode::Interactive
+    }
+
     /// Returns a modes colors.
     pub fn colors(&self, colors: &ColorsConfig) -> ModeColors {
         ModeColors {
@@ -61,11 +84,13 @@ impl Mode {
                 Mode::Normal => colors.status_bar_normal_mode_fg,
                 Mode::Insert => colors.status_bar_insert_mode_fg,
                 Mode::Delete => colors.status_bar_delete_mode_fg,
+                Mode::Interactive => colors.status_bar_interactive_mode_fg,
             },
             bg: match *self {
                 Mode::Normal => colors.status_bar_normal_mode_bg,
                 Mode::Insert => colors.status_bar_insert_mode_bg,
                 Mode::Delete => colors.status_bar_delete_mode_bg,
+                Mode::Interactive => colors.status_bar_interactive_mode_bg,
             },
         }
     }
@@ -80,6 +105,7 @@ impl fmt::Display for Mode {
                 Mode::Normal => "Normal",
                 Mode::Insert => "Insert",
                 Mode::Delete => "Delete",
+                Mode::Interactive => "Interactive",
             }
         )
     }
@@ -167,4 +193,52 @@ pub struct ModuleText<'a> {
 pub enum AppPopup {
     #[default]
     None,
+    WhatsNew,
+    Help,
+    WhichKey,
+    SwitchProfile,
+    Search,
+}
+
+/// A request to show something in a module. Commands and other modules can't
+/// access a module, so the request is set on the app and handled after the key
+/// event.
+#[derive(PartialEq, Clone)]
+pub enum Navigation {
+    /// Open the dashboard of the home module.
+    Dashboard,
+    /// Open the calendar of the home module.
+    Calendar,
+    /// Open the settings of the home module.
+    Settings,
+    /// Open the help of the home module.
+    Help,
+    /// Open the log viewer of the home module.
+    Logs,
+    /// Open a project.
+    OpenProject {
+        #[allow(missing_docs)]
+        project_id: i32,
+    },
+    /// Open the card positions of the open project, if there is one.
+    DebugPositions,
+    /// Pick a profile to switch to.
+    SwitchProfile,
+    /// Open the editor of a new card in the open project, if there is one.
+    NewCard,
+    /// Open the editor of a new list in the open project, if there is one.
+    NewList,
+    /// Export the open project to Markdown, if there is one.
+    ExportProject,
+    /// Import the projects of the JSON export file.
+    ImportJson,
+    /// Open a project with one of its cards focused.
+    OpenCard {
+        #[allow(missing_docs)]
+        project_id: i32,
+        #[allow(missing_docs)]
+        card_id: i32,
+    },
+    /// Search all projects and cards.
+    Search,
 }
@@ -12,8 +12,14 @@ include!("src/config.rs");
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DefaultConfig {
     pub log_level: String,
+    pub log_format: String,
     pub default_profile: String,
+    pub mouse: bool,
+    pub monochrome: bool,
+    pub refresh_interval: u64,
     pub colors: ColorsConfig<String, String>,
+    pub datetime: DateTimeConfig<String>,
+    pub keybinds: KeybindsConfig<String, u64>,
     pub modules: ModulesConfig,
     pub profiles: Vec<ProfileConfig<String>>,
 }
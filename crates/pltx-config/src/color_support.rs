@@ -0,0 +1,170 @@
+use std::{env, fmt};
+
+use ratatui::style::Color;
+
+use crate::Config;
+
+/// The named colors of the terminal with the RGB values of xterm, which most
+/// terminals use for them.
+const ANSI_16_COLORS: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// The levels of each channel of the 6x6x6 color cube of 256 color terminals.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// The colors that the terminal can show.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorSupport {
+    TrueColor,
+    Ansi256,
+    Ansi16,
+    /// Colors are disabled with the `NO_COLOR` environment variable.
+    NoColor,
+}
+
+impl ColorSupport {
+    /// Detect the colors of the terminal from the environment.
+    pub fn detect() -> Self {
+        Self::from_env(
+            env::var("NO_COLOR").ok().as_deref(),
+            env::var("COLORTERM").ok().as_deref(),
+            env::var("TERM").ok().as_deref(),
+        )
+    }
+
+    /// The colors of a terminal with the `NO_COLOR`, `COLORTERM`, and `TERM`
+    /// environment variables. `NO_COLOR` disables colors when it isn't empty.
+    ///
+    /// ```
+    /// # use pltx_config::ColorSupport;
+    /// let support = ColorSupport::from_env(None, Some("truecolor"), Some("xterm-256color"));
+    /// assert_eq!(support, ColorSupport::TrueColor);
+    ///
+    /// let support = ColorSupport::from_env(None, None, Some("xterm-256color"));
+    /// assert_eq!(support, ColorSupport::Ansi256);
+    ///
+    /// let support = ColorSupport::from_env(None, None, Some("linux"));
+    /// assert_eq!(support, ColorSupport::Ansi16);
+    ///
+    /// let support = ColorSupport::from_env(Some("1"), Some("truecolor"), None);
+    /// assert_eq!(support, ColorSupport::NoColor);
+    /// assert_eq!(
+    ///     ColorSupport::from_env(Some(""), None, None),
+    ///     ColorSupport::Ansi16
+    /// );
+    /// ```
+    pub fn from_env(no_color: Option<&str>, colorterm: Option<&str>, term: Option<&str>) -> Self {
+        if no_color.is_some_and(|v| !v.is_empty()) {
+            ColorSupport::NoColor
+        } else if matches!(colorterm, Some("truecolor" | "24bit")) {
+            ColorSupport::TrueColor
+        } else if term.is_some_and(|t| t.contains("256color")) {
+            ColorSupport::Ansi256
+        } else {
+            ColorSupport::Ansi16
+        }
+    }
+
+    /// The nearest color that the terminal can show. Only RGB colors are
+    /// changed.
+    ///
+    /// ```
+    /// # use pltx_config::ColorSupport;
+    /// # use ratatui::style::Color;
+    /// let color = Color::Rgb(0xff, 0x87, 0x00);
+    /// assert_eq!(ColorSupport::TrueColor.downsample(color), color);
+    /// assert_eq!(ColorSupport::Ansi256.downsample(color), Color::Indexed(208));
+    /// assert_eq!(
+    ///     ColorSupport::Ansi256.downsample(Color::Rgb(8, 8, 8)),
+    ///     Color::Indexed(232)
+    /// );
+    /// assert_eq!(ColorSupport::Ansi16.downsample(color), Color::Yellow);
+    /// assert_eq!(ColorSupport::Ansi16.downsample(Color::Reset), Color::Reset);
+    /// ```
+    pub fn downsample(self, color: Color) -> Color {
+        let Color::Rgb(r, g, b) = color else {
+            return color;
+        };
+        match self {
+            ColorSupport::TrueColor | ColorSupport::NoColor => color,
+            ColorSupport::Ansi256 => nearest_256_color((r, g, b)),
+            ColorSupport::Ansi16 => ANSI_16_COLORS
+                .iter()
+                .min_by_key(|(_, rgb)| distance((r, g, b), *rgb))
+                .map(|(color, _)| *color)
+                .unwrap_or(color),
+        }
+    }
+
+    /// Make the colors of the config ones that the terminal can show, or use
+    /// monochrome mode if colors are disabled.
+    pub fn apply(self, config: &mut Config) {
+        if self == ColorSupport::NoColor || config.monochrome {
+            config.set_monochrome();
+        } else if self != ColorSupport::TrueColor {
+            config.colors.map(|color| self.downsample(color));
+        }
+    }
+}
+
+impl fmt::Display for ColorSupport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                ColorSupport::TrueColor => "truecolor",
+                ColorSupport::Ansi256 => "256 colors",
+                ColorSupport::Ansi16 => "16 colors",
+                ColorSupport::NoColor => "none (NO_COLOR)",
+            }
+        )
+    }
+}
+
+/// The squared distance between two RGB colors.
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let channel = |a: u8, b: u8| (a as i32 - b as i32).pow(2) as u32;
+    channel(a.0, b.0) + channel(a.1, b.1) + channel(a.2, b.2)
+}
+
+/// The nearest color of the color cube or the grayscale ramp of 256 color
+/// terminals. The first 16 colors are left out, as terminals change them.
+fn nearest_256_color(rgb: (u8, u8, u8)) -> Color {
+    let level = |channel: u8| {
+        (0..CUBE_LEVELS.len())
+            .min_by_key(|&i| (CUBE_LEVELS[i] as i32 - channel as i32).abs())
+            .unwrap_or(0)
+    };
+    let (r, g, b) = (level(rgb.0), level(rgb.1), level(rgb.2));
+    let cube = (CUBE_LEVELS[r], CUBE_LEVELS[g], CUBE_LEVELS[b]);
+    let cube_index = 16 + 36 * r + 6 * g + b;
+
+    // The grayscale ramp goes from 8 to 238 in steps of 10.
+    let average = (rgb.0 as u32 + rgb.1 as u32 + rgb.2 as u32) / 3;
+    let gray_step = (average.saturating_sub(3) / 10).min(23) as u8;
+    let gray_level = 8 + gray_step * 10;
+    let gray = (gray_level, gray_level, gray_level);
+
+    if distance(rgb, gray) < distance(rgb, cube) {
+        Color::Indexed(232 + gray_step)
+    } else {
+        Color::Indexed(cube_index as u8)
+    }
+}
@@ -1,10 +1,13 @@
+use std::collections::BTreeMap;
+
 use ratatui::style::Color;
 use serde::{Deserialize, Serialize};
 
 /// The base/merged colors config.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct ColorsConfig<S = String, C = Color> {
-    // TODO: color presets to be implemented
+    /// The name of a color preset, whose colors are overridden by the other
+    /// colors of the config.
     pub preset: S,
     pub fg: C,
     pub bg: C,
@@ -47,6 +50,9 @@ pub struct ColorsConfig<S = String, C = Color> {
     pub status_bar_insert_mode_fg: C,
     pub status_bar_delete_mode_bg: C,
     pub status_bar_delete_mode_fg: C,
+    pub status_bar_interactive_mode_bg: C,
+    pub status_bar_interactive_mode_fg: C,
+    pub label_overflow_fg: C,
 }
 
 /// The base/merged home module config.
@@ -58,29 +64,75 @@ pub struct HomeModule<S> {
 
 /// The base/merged project management config.
 #[derive(Clone, Debug, Deserialize, Serialize)]
-pub struct ProjectManagementModule<N = i32, C = String> {
+pub struct ProjectManagementModule<N = i32, C = String, B = bool> {
     pub max_lists: N,
     pub due_soon_days: N,
+    pub day_start_hour: N,
+    pub default_time: C,
     pub completed_char: C,
     pub overdue_char: C,
     pub due_soon_char: C,
     pub in_progress_char: C,
     pub important_char: C,
+    pub urgent_char: C,
+    pub medium_priority_char: C,
+    pub low_priority_char: C,
     pub default_char: C,
+    pub toggle_list_collapse_key: C,
+    pub collapse_all_lists_key: C,
+    pub expand_all_lists_key: C,
+    pub toggle_list_zoom_key: C,
+    pub next_status_key: C,
+    pub prev_status_key: C,
+    pub status_stepper_wrap: B,
+    pub list_dividers: B,
+    pub max_single_list_width: N,
+    pub min_list_width: N,
+    pub wrap_card_titles: B,
+    pub show_progress_bar: B,
+    pub label_overflow_total: B,
+    pub auto_complete_on_all_subtasks: B,
+    pub reopen_on_unchecked_subtask: B,
+    pub confirm_discard_new: B,
+    pub card_viewer_initial_section: C,
+    pub trash_retention_days: N,
+    pub enforce_wip_limits: B,
 }
 
 /// The base/merged modules config.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct ModulesConfig<T = String> {
     pub home: HomeModule<T>,
-    pub project_management: ProjectManagementModule<i32, T>,
+    pub project_management: ProjectManagementModule<i32, T, bool>,
 }
 
 /// The user modules config.
 #[derive(Deserialize, Serialize)]
 pub struct ModulesConfigFile {
     pub home: Option<HomeModule<Option<String>>>,
-    pub project_management: Option<ProjectManagementModule<Option<i32>, Option<String>>>,
+    pub project_management:
+        Option<ProjectManagementModule<Option<i32>, Option<String>, Option<bool>>>,
+}
+
+/// The base/merged datetime config.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct DateTimeConfig<S = String> {
+    pub date_format: S,
+    pub time_format: S,
+}
+
+/// The base/merged keybinds config.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct KeybindsConfig<K = String, N = u64> {
+    pub quit: K,
+    pub help: K,
+    pub command_palette: K,
+    pub up: K,
+    pub down: K,
+    pub left: K,
+    pub right: K,
+    pub leader: K,
+    pub leader_timeout: N,
 }
 
 /// The base/merged profile config
@@ -107,8 +159,18 @@ impl From<ProfileConfig<&'static str>> for ProfileConfig<String> {
 #[derive(Deserialize, Serialize)]
 pub struct ConfigFile {
     pub log_level: Option<String>,
+    pub log_format: Option<String>,
+    pub log_levels: Option<BTreeMap<String, String>>,
     pub default_profile: Option<String>,
+    pub mouse: Option<bool>,
+    pub monochrome: Option<bool>,
+    pub refresh_interval: Option<u64>,
     pub profiles: Option<Vec<ProfileConfig<Option<String>>>>,
     pub colors: Option<ColorsConfig<Option<String>, Option<String>>>,
+    pub datetime: Option<DateTimeConfig<Option<String>>>,
+    pub keybinds: Option<KeybindsConfig<Option<String>, Option<u64>>>,
     pub modules: Option<ModulesConfigFile>,
+    /// The keys of the file that aren't config keys.
+    #[serde(skip)]
+    pub unknown_keys: Vec<String>,
 }
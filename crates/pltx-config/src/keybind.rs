@@ -0,0 +1,146 @@
+use std::{fmt, str::FromStr};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Deserialize, Serialize};
+
+const KEY_NAMES: [(&str, KeyCode); 15] = [
+    ("enter", KeyCode::Enter),
+    ("esc", KeyCode::Esc),
+    ("tab", KeyCode::Tab),
+    ("backtab", KeyCode::BackTab),
+    ("backspace", KeyCode::Backspace),
+    ("delete", KeyCode::Delete),
+    ("space", KeyCode::Char(' ')),
+    ("up", KeyCode::Up),
+    ("down", KeyCode::Down),
+    ("left", KeyCode::Left),
+    ("right", KeyCode::Right),
+    ("home", KeyCode::Home),
+    ("end", KeyCode::End),
+    ("pageup", KeyCode::PageUp),
+    ("pagedown", KeyCode::PageDown),
+];
+
+const MODIFIER_NAMES: [(&str, KeyModifiers); 3] = [
+    ("ctrl-", KeyModifiers::CONTROL),
+    ("alt-", KeyModifiers::ALT),
+    ("shift-", KeyModifiers::SHIFT),
+];
+
+/// A key with its modifiers, which is written in the config as a character or
+/// the name of a key, after any modifiers.
+///
+/// ```
+/// # use std::str::FromStr;
+/// # use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+/// # use pltx_config::KeyBind;
+/// let quit = KeyBind::from_str("ctrl-q").unwrap();
+/// assert!(quit.matches(&KeyEvent::new(KeyCode::Char('q'), KeyModifiers::CONTROL)));
+/// assert!(!quit.matches(&KeyEvent::from(KeyCode::Char('q'))));
+/// assert_eq!(quit.to_string(), "ctrl-q");
+///
+/// // Shift is part of the character.
+/// let help = KeyBind::from_str("?").unwrap();
+/// assert!(help.matches(&KeyEvent::new(KeyCode::Char('?'), KeyModifiers::SHIFT)));
+///
+/// assert_eq!(
+///     KeyBind::from_str("pagedown").unwrap().code,
+///     KeyCode::PageDown
+/// );
+/// assert_eq!(KeyBind::from_str("f5").unwrap().code, KeyCode::F(5));
+/// assert!(KeyBind::from_str("ctrl-").is_err());
+/// assert!(KeyBind::from_str("hyper-x").is_err());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct KeyBind {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyBind {
+    /// Whether a key event is this key. Shift is ignored for characters, since
+    /// it is already part of the character.
+    pub fn matches(&self, key_event: &KeyEvent) -> bool {
+        let ignored = match self.code {
+            KeyCode::Char(_) => KeyModifiers::SHIFT,
+            _ => KeyModifiers::NONE,
+        };
+        key_event.code == self.code
+            && key_event.modifiers.difference(ignored) == self.modifiers.difference(ignored)
+    }
+}
+
+impl FromStr for KeyBind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut key = s;
+        let mut modifiers = KeyModifiers::NONE;
+        // A single character is always the key, so "-" can be bound.
+        while key.chars().count() > 1 {
+            match MODIFIER_NAMES
+                .iter()
+                .find(|(name, _)| key.to_lowercase().starts_with(name))
+            {
+                Some((name, modifier)) => {
+                    modifiers |= *modifier;
+                    key = &key[name.len()..];
+                }
+                None => break,
+            }
+        }
+
+        let mut chars = key.chars();
+        let code = match (chars.next(), chars.next()) {
+            (None, _) => return Err(String::from("no key is given")),
+            (Some(char), None) => KeyCode::Char(char),
+            _ => {
+                let name = key.to_lowercase();
+                match KEY_NAMES.iter().find(|(n, _)| *n == name) {
+                    Some((_, code)) => *code,
+                    None => match name.strip_prefix('f').map(u8::from_str) {
+                        Some(Ok(n)) if (1..=12).contains(&n) => KeyCode::F(n),
+                        _ => return Err(format!("\"{}\" is not a key", key)),
+                    },
+                }
+            }
+        };
+
+        Ok(Self { code, modifiers })
+    }
+}
+
+impl TryFrom<String> for KeyBind {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::from_str(&value)
+    }
+}
+
+impl From<KeyBind> for String {
+    fn from(value: KeyBind) -> Self {
+        value.to_string()
+    }
+}
+
+impl fmt::Display for KeyBind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (name, modifier) in MODIFIER_NAMES {
+            if self.modifiers.contains(modifier) {
+                write!(f, "{}", name)?;
+            }
+        }
+        match self.code {
+            KeyCode::F(n) => write!(f, "f{}", n),
+            code => match KEY_NAMES.iter().find(|(_, c)| *c == code) {
+                Some((name, _)) => write!(f, "{}", name),
+                None => match code {
+                    KeyCode::Char(char) => write!(f, "{}", char),
+                    _ => write!(f, "{:?}", code),
+                },
+            },
+        }
+    }
+}
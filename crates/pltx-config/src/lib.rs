@@ -1,27 +1,64 @@
 //! Configuration should not be more than three levels deep, e.g.,
 //! `config.one.two.three`.
 
-use std::str::FromStr;
+use std::{collections::BTreeMap, fs, io, path::Path, str::FromStr};
 
-use color_eyre::Result;
-use pltx_utils::dirs;
-use ratatui::style::Color;
-
-const COLOR_PRESETS: [&str; 1] = ["default"];
+use color_eyre::{eyre::eyre, Result};
+use pltx_utils::{dirs, DateTime};
+use ratatui::style::{Color, Style, Stylize};
+use tracing_subscriber::filter::{Directive, EnvFilter};
 
+mod color_support;
 mod config;
+mod keybind;
+mod preset;
+mod theme;
 
 include!("generated_config.rs");
 
+pub use color_support::*;
 pub use config::*;
+pub use keybind::*;
+pub use preset::*;
 use serde::{Deserialize, Serialize};
+pub use theme::*;
+
+/// The levels that the `log_level` config can be set to.
+pub const LOG_LEVELS: [&str; 4] = ["debug", "info", "warn", "error"];
+
+/// The formats that the `log_format` config can be set to. Logs are written as
+/// readable text or as JSON lines.
+pub const LOG_FORMATS: [&str; 2] = ["text", "json"];
+
+/// The documented base config, which the base config is also generated from.
+const BASE_CONFIG_FILE: &str = include_str!("../../../docs/config.toml");
+
+/// The first line of the base config file, which is only true of the docs.
+const BASE_CONFIG_FILE_HEADER: &str = "# The file is located in `docs/config.toml`.";
+
+/// The first lines of the starter config, which replace the header of the
+/// base config file.
+const STARTER_CONFIG_HEADER: &str = "\
+# The config of pltx. Uncomment a config and change its value to override
+# the default.
+";
+
+/// The configs that are maps of any names to values, so their keys aren't
+/// checked.
+const MAP_CONFIGS: [&str; 1] = ["log_levels"];
 
 /// The main base/merged config.
 #[derive(Clone, Deserialize, Serialize)]
 pub struct DefaultConfig {
     pub log_level: &'static str,
+    pub log_format: &'static str,
     pub default_profile: &'static str,
+    pub mouse: bool,
+    pub monochrome: bool,
+    pub refresh_interval: u64,
     pub colors: ColorsConfig<&'static str, &'static str>,
+    pub datetime: DateTimeConfig<&'static str>,
+    pub keybinds: KeybindsConfig<&'static str, u64>,
     pub modules: ModulesConfig<&'static str>,
     pub profiles: [ProfileConfig<&'static str>; 2],
 }
@@ -29,10 +66,394 @@ pub struct DefaultConfig {
 #[derive(Clone, Deserialize, Serialize)]
 pub struct Config {
     pub log_level: String,
+    pub log_format: String,
+    /// The levels of targets, such as a crate or a span, that override the
+    /// log level. The base config has none, so it isn't in [`DefaultConfig`].
+    #[serde(default)]
+    pub log_levels: BTreeMap<String, String>,
     pub default_profile: String,
+    pub mouse: bool,
+    pub monochrome: bool,
+    pub refresh_interval: u64,
     pub colors: ColorsConfig,
+    pub datetime: DateTimeConfig,
+    pub keybinds: KeybindsConfig<KeyBind>,
     pub modules: ModulesConfig,
     pub profiles: Vec<ProfileConfig>,
+    /// The keys of the config file that aren't config keys, such as a typo,
+    /// which have no effect.
+    #[serde(skip)]
+    pub unknown_keys: Vec<String>,
+}
+
+impl ColorsConfig {
+    /// Set a color by its name in the config, such as `primary`, from a hex or
+    /// named color.
+    ///
+    /// ```
+    /// # use pltx_config::{base_config, Config};
+    /// # use ratatui::style::Color;
+    /// let mut colors = Config::from(base_config()).colors;
+    /// colors.set("primary", "#ff0000").unwrap();
+    /// assert_eq!(colors.primary, Color::Rgb(255, 0, 0));
+    ///
+    /// assert!(colors.set("primary", "not a color").is_err());
+    /// assert!(colors.set("not_a_color", "#ff0000").is_err());
+    /// assert_eq!(colors.primary, Color::Rgb(255, 0, 0));
+    /// ```
+    pub fn set(&mut self, name: &str, color: &str) -> Result<()> {
+        let mut colors = serde_json::to_value(&*self)?;
+        let value = colors
+            .get_mut(name)
+            .filter(|_| name != "preset")
+            .ok_or_else(|| eyre!("there is no color \"{}\"", name))?;
+        *value = serde_json::Value::String(color.to_string());
+        *self = serde_json::from_value(colors)?;
+        Ok(())
+    }
+
+    /// Replace every color with the result of the function.
+    ///
+    /// ```
+    /// # use pltx_config::{base_config, Config};
+    /// # use ratatui::style::Color;
+    /// let mut colors = Config::from(base_config()).colors;
+    /// colors.map(|_| Color::Indexed(42));
+    /// assert_eq!(colors.primary, Color::Indexed(42));
+    /// assert_eq!(colors.status_bar_bg, Color::Indexed(42));
+    /// assert_eq!(colors.preset, "default");
+    /// ```
+    pub fn map(&mut self, f: impl Fn(Color) -> Color) {
+        let mut colors = serde_json::to_value(&*self).unwrap();
+        if let Some(colors) = colors.as_object_mut() {
+            for (name, value) in colors.iter_mut() {
+                if name == "preset" {
+                    continue;
+                }
+                if let Some(color) = value.as_str().and_then(|c| Color::from_str(c).ok()) {
+                    *value = serde_json::Value::String(f(color).to_string());
+                }
+            }
+        }
+        *self = serde_json::from_value(colors).unwrap();
+    }
+}
+
+impl DateTimeConfig {
+    /// The format of a date with a time.
+    pub fn datetime_format(&self) -> String {
+        format!("{} {}", self.date_format, self.time_format)
+    }
+
+    /// Check that the formats only contain valid specifiers.
+    fn validate(&self) -> Result<()> {
+        for (name, format) in [
+            ("date_format", &self.date_format),
+            ("time_format", &self.time_format),
+        ] {
+            if !DateTime::is_valid_format(format) {
+                return Err(eyre!(
+                    "the datetime.{} config \"{}\" is not a valid format",
+                    name,
+                    format
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Config {
+    /// Check the values that can't be checked while they're merged.
+    fn validate(&self) -> Result<()> {
+        for (name, value, options) in [
+            ("log_level", &self.log_level, LOG_LEVELS.as_slice()),
+            ("log_format", &self.log_format, LOG_FORMATS.as_slice()),
+        ] {
+            if !options.contains(&value.as_str()) {
+                return Err(eyre!(
+                    "the {} config \"{}\" is not one of: {}",
+                    name,
+                    value,
+                    options.join(", ")
+                ));
+            }
+        }
+        self.log_directives()?;
+        self.datetime.validate()?;
+        self.modules.project_management.validate()
+    }
+
+    /// The filter of the logs, which logs events at the log level unless the
+    /// level of their target is overridden.
+    ///
+    /// ```
+    /// # use pltx_config::{base_config, Config};
+    /// let mut config = Config::from(base_config());
+    /// config
+    ///     .log_levels
+    ///     .insert(String::from("pltx_database"), String::from("debug"));
+    /// config
+    ///     .log_levels
+    ///     .insert(String::from("[project management]"), String::from("warn"));
+    /// assert_eq!(
+    ///     config.log_filter().unwrap().to_string(),
+    ///     "pltx_database=debug,info,[project management]=warn"
+    /// );
+    ///
+    /// config
+    ///     .log_levels
+    ///     .insert(String::from("pltx_home"), String::from("verbose"));
+    /// assert_eq!(
+    ///     config.log_filter().err().unwrap().to_string(),
+    ///     "the log_levels config of \"pltx_home\" has the level \"verbose\", which is not one of: \
+    ///      debug, info, warn, error"
+    /// );
+    /// ```
+    pub fn log_filter(&self) -> Result<EnvFilter> {
+        let filter = self
+            .log_directives()?
+            .into_iter()
+            .fold(EnvFilter::new(&self.log_level), |filter, directive| {
+                filter.add_directive(directive)
+            });
+        Ok(filter)
+    }
+
+    /// The config in the format of the config file, with every value that is
+    /// in effect.
+    ///
+    /// ```
+    /// # use pltx_config::{base_config, Config, ConfigFile};
+    /// let config = Config::from(base_config());
+    /// let toml = config.to_toml().unwrap();
+    /// assert!(toml.contains("[modules.project_management]"));
+    ///
+    /// let parsed: ConfigFile = toml::from_str(&toml).unwrap();
+    /// assert_eq!(parsed.refresh_interval, Some(config.refresh_interval));
+    /// ```
+    pub fn to_toml(&self) -> Result<String> {
+        Ok(toml::to_string(self)?)
+    }
+
+    /// A warning that lists the unknown keys of the config file, if it has
+    /// any.
+    pub fn unknown_keys_warning(&self) -> Option<String> {
+        if self.unknown_keys.is_empty() {
+            return None;
+        }
+        Some(format!(
+            "Unknown config keys, which have no effect: {}",
+            self.unknown_keys.join(", ")
+        ))
+    }
+
+    /// The directives of the targets with their own log level.
+    fn log_directives(&self) -> Result<Vec<Directive>> {
+        self.log_levels
+            .iter()
+            .map(|(target, level)| {
+                if !LOG_LEVELS.contains(&level.as_str()) {
+                    return Err(eyre!(
+                        "the log_levels config of \"{}\" has the level \"{}\", which is not one \
+                         of: {}",
+                        target,
+                        level,
+                        LOG_LEVELS.join(", ")
+                    ));
+                }
+                if target.is_empty() || target.contains([',', '=']) {
+                    return Err(eyre!(
+                        "the log_levels config \"{}\" is not a target or a span",
+                        target
+                    ));
+                }
+                Directive::from_str(&format!("{}={}", target, level)).map_err(|e| {
+                    eyre!(
+                        "the log_levels config \"{}\" is not a target or a span: {}",
+                        target,
+                        e
+                    )
+                })
+            })
+            .collect()
+    }
+
+    /// Replace all colors with the default colors of the terminal, which is
+    /// used for terminals that don't render colors well. The preset is kept.
+    ///
+    /// ```
+    /// # use pltx_config::{base_config, Config};
+    /// # use ratatui::style::{Color, Modifier, Style};
+    /// let mut config = Config::from(base_config());
+    /// assert_eq!(
+    ///     config.focus_style(Color::Blue),
+    ///     Style::new().bg(Color::Blue)
+    /// );
+    ///
+    /// config.set_monochrome();
+    /// assert!(config.monochrome);
+    /// assert_eq!(config.colors.primary, Color::White);
+    /// assert_eq!(config.colors.border, Color::Gray);
+    /// assert_eq!(config.colors.input_focus_bg, Color::Reset);
+    /// assert_eq!(
+    ///     config.focus_style(Color::Blue),
+    ///     Style::new().add_modifier(Modifier::REVERSED)
+    /// );
+    /// assert!(config
+    ///     .active_style(config.colors.border_active)
+    ///     .add_modifier
+    ///     .contains(Modifier::BOLD));
+    /// ```
+    pub fn set_monochrome(&mut self) {
+        self.monochrome = true;
+        self.colors = monochrome_colors(self.colors.preset.clone());
+    }
+
+    /// The style of a focused or active item, which is marked by the background
+    /// color. In monochrome mode the item is reversed instead.
+    pub fn focus_style(&self, bg: Color) -> Style {
+        if self.monochrome {
+            Style::new().reversed()
+        } else {
+            Style::new().bg(bg)
+        }
+    }
+
+    /// The style of text or a border that is marked by its color, such as the
+    /// active tab or the border of the focused pane. In monochrome mode it's
+    /// also bold.
+    pub fn active_style(&self, fg: Color) -> Style {
+        if self.monochrome {
+            Style::new().fg(fg).bold()
+        } else {
+            Style::new().fg(fg)
+        }
+    }
+}
+
+/// The colors of monochrome mode. Text is the default color of the terminal,
+/// emphasized text is white, muted text and borders are gray, and backgrounds
+/// are the default of the terminal.
+fn monochrome_colors(preset: String) -> ColorsConfig {
+    let text = Color::Reset;
+    let emphasis = Color::White;
+    let muted = Color::Gray;
+    let bg = Color::Reset;
+    ColorsConfig {
+        preset,
+        fg: text,
+        bg,
+        secondary_fg: muted,
+        tertiary_fg: muted,
+        highlight_fg: emphasis,
+        primary: emphasis,
+        success: emphasis,
+        warning: emphasis,
+        danger: emphasis,
+        date_fg: text,
+        time_fg: text,
+        input_fg: text,
+        input_bg: bg,
+        input_focus_fg: emphasis,
+        input_focus_bg: bg,
+        input_cursor_fg: text,
+        input_cursor_bg: bg,
+        input_cursor_insert_fg: text,
+        input_cursor_insert_bg: bg,
+        active_fg: emphasis,
+        active_bg: bg,
+        border: muted,
+        border_active: emphasis,
+        border_insert: emphasis,
+        popup_bg: bg,
+        popup_border: emphasis,
+        keybind_key: emphasis,
+        keybind_fg: muted,
+        title_bar_bg: bg,
+        title_bar_fg: emphasis,
+        tab_fg: muted,
+        tab_active_fg: emphasis,
+        tab_border: muted,
+        status_bar_bg: bg,
+        status_bar_fg: text,
+        status_bar_normal_mode_bg: bg,
+        status_bar_normal_mode_fg: text,
+        status_bar_insert_mode_bg: bg,
+        status_bar_insert_mode_fg: text,
+        status_bar_delete_mode_bg: bg,
+        status_bar_delete_mode_fg: text,
+        status_bar_interactive_mode_bg: bg,
+        status_bar_interactive_mode_fg: text,
+        label_overflow_fg: muted,
+    }
+}
+
+impl ProjectManagementModule {
+    /// Check that the default time is a valid time.
+    fn validate(&self) -> Result<()> {
+        if !DateTime::is_valid_time(&self.default_time) {
+            return Err(eyre!(
+                "the modules.project_management.default_time config \"{}\" is not a time in the \
+                 format HH:MM",
+                self.default_time
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl ProfileConfig {
+    /// Resolve the files of the profile to absolute paths, creating any missing
+    /// directories. The config file is relative to `config_dir`, the database
+    /// to `data_dir`, and the log file to `cache_dir`. Absolute paths are kept.
+    ///
+    /// ```
+    /// # use pltx_config::ProfileConfig;
+    /// let dir = std::env::temp_dir().join("pltx-profile-resolve");
+    /// let profile = ProfileConfig {
+    ///     name: String::from("work"),
+    ///     config_file: String::from("work.toml"),
+    ///     db_file: String::from("work/data.db"),
+    ///     log_file: String::from("logs/work.log"),
+    /// };
+    ///
+    /// let resolved = profile
+    ///     .resolve(&dir.join("config"), &dir.join("data"), &dir.join("cache"))
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     resolved.db_file,
+    ///     dir.join("data")
+    ///         .join("work")
+    ///         .join("data.db")
+    ///         .to_string_lossy()
+    /// );
+    /// assert!(dir.join("config").is_dir());
+    /// assert!(dir.join("data").join("work").is_dir());
+    /// assert!(dir.join("cache").join("logs").is_dir());
+    /// assert!(std::path::Path::new(&resolved.config_file).is_absolute());
+    /// ```
+    pub fn resolve(
+        &self,
+        config_dir: &Path,
+        data_dir: &Path,
+        cache_dir: &Path,
+    ) -> io::Result<Self> {
+        let resolve = |dir: &Path, file: &str| -> io::Result<String> {
+            let path = dir.join(file);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            Ok(path.to_string_lossy().to_string())
+        };
+
+        Ok(ProfileConfig {
+            name: self.name.to_string(),
+            config_file: resolve(config_dir, &self.config_file)?,
+            db_file: resolve(data_dir, &self.db_file)?,
+            log_file: resolve(cache_dir, &self.log_file)?,
+        })
+    }
 }
 
 impl From<DefaultConfig> for Config {
@@ -42,14 +463,192 @@ impl From<DefaultConfig> for Config {
     }
 }
 
-fn read_config_file(filename: &str) -> Result<Option<ConfigFile>> {
-    let config_file = dirs::config_dir().join(filename);
-    let config_contents: Option<String> = std::fs::read_to_string(config_file).ok();
-    let config_toml: Option<ConfigFile> = match config_contents {
-        Some(contents) => toml::from_str(&contents).expect("the config is invalid"),
-        None => None,
+/// Read and parse a config file, which is none if the file doesn't exist. The
+/// error of a file that can't be read or parsed has the path of the file and
+/// the line and column of the syntax error.
+///
+/// ```
+/// # use pltx_config::read_config_file;
+/// let dir = std::env::temp_dir().join("pltx-read-config");
+/// std::fs::create_dir_all(&dir).unwrap();
+/// let config_file = dir.join("config.toml");
+/// let path = config_file.to_string_lossy().to_string();
+///
+/// let _ = std::fs::remove_file(&config_file);
+/// assert!(read_config_file(&path).unwrap().is_none());
+///
+/// std::fs::write(&config_file, "mouse = true\n").unwrap();
+/// assert_eq!(read_config_file(&path).unwrap().unwrap().mouse, Some(true));
+///
+/// std::fs::write(
+///     &config_file,
+///     "mouse = true\n[colors\nprimary = \"#ff0000\"\n",
+/// )
+/// .unwrap();
+/// let error = read_config_file(&path).err().unwrap().to_string();
+/// assert!(error.contains(&path));
+/// assert!(error.contains("line 2, column 8"));
+/// ```
+pub fn read_config_file(path: &str) -> Result<Option<ConfigFile>> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(eyre!("failed to read the config file {}: {}", path, e)),
     };
-    Ok(config_toml)
+    let invalid = |e: toml::de::Error| eyre!("the config file {} is invalid: {}", path, e);
+    let mut config_toml: ConfigFile = toml::from_str(&contents).map_err(invalid)?;
+    let table: toml::Table = toml::from_str(&contents).map_err(invalid)?;
+    config_toml.unknown_keys = unknown_config_keys(&table);
+    Ok(Some(config_toml))
+}
+
+/// A config file that documents every config with its default value. The
+/// values are commented out, so the defaults are used until one is changed.
+/// It's made from the file that the base config is generated from, so the
+/// defaults are always the ones of the app.
+///
+/// ```
+/// # use pltx_config::{starter_config, ConfigFile};
+/// let starter = starter_config();
+/// assert!(starter.contains("\n# mouse = false\n"));
+/// assert!(starter.contains("\n[colors]\n"));
+///
+/// let parsed: ConfigFile = toml::from_str(&starter).unwrap();
+/// assert_eq!(parsed.mouse, None);
+/// assert!(parsed.profiles.is_none());
+/// ```
+pub fn starter_config() -> String {
+    let mut starter = String::from(STARTER_CONFIG_HEADER);
+    for line in BASE_CONFIG_FILE.lines() {
+        if line == BASE_CONFIG_FILE_HEADER {
+            continue;
+        }
+        // Tables are kept, except for the arrays of profiles, which would
+        // replace the base profiles with empty ones.
+        let is_table = line.starts_with('[') && !line.starts_with("[[");
+        if line.is_empty() || line.starts_with('#') || is_table {
+            starter.push_str(line);
+        } else {
+            starter.push_str("# ");
+            starter.push_str(line);
+        }
+        starter.push('\n');
+    }
+    starter
+}
+
+/// Write the starter config to the config file of a profile. Nothing is
+/// written, and false is returned, if the file already exists.
+///
+/// ```
+/// # use pltx_config::{init_config_file, ProfileConfig};
+/// let dir = std::env::temp_dir().join("pltx-init-config");
+/// std::fs::create_dir_all(&dir).unwrap();
+/// let profile = ProfileConfig {
+///     name: String::from("default"),
+///     config_file: dir.join("config.toml").to_string_lossy().to_string(),
+///     db_file: String::from("data.db"),
+///     log_file: String::from("debug.log"),
+/// };
+/// let _ = std::fs::remove_file(&profile.config_file);
+///
+/// assert!(init_config_file(&profile).unwrap());
+/// std::fs::write(&profile.config_file, "mouse = true\n").unwrap();
+/// assert!(!init_config_file(&profile).unwrap());
+/// let contents = std::fs::read_to_string(&profile.config_file).unwrap();
+/// assert_eq!(contents, "mouse = true\n");
+/// ```
+pub fn init_config_file(profile: &ProfileConfig) -> Result<bool> {
+    let path = Path::new(&profile.config_file);
+    if path.exists() {
+        return Ok(false);
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, starter_config())?;
+    Ok(true)
+}
+
+/// The keys of a config file that aren't config keys, which are ignored when
+/// the file is parsed. The keys of the items of the profiles are checked
+/// against the keys of the base profiles.
+///
+/// ```
+/// # use pltx_config::unknown_config_keys;
+/// let table = toml::from_str(
+///     r#"
+///     mouse = true
+///     sparkles = true
+///
+///     [log_levels]
+///     pltx_database = "debug"
+///
+///     [modules.project_management]
+///     max_lists = 5
+///     max_list = 5
+///
+///     [[profiles]]
+///     name = "work"
+///     db = "work.db"
+///     "#,
+/// )
+/// .unwrap();
+/// assert_eq!(
+///     unknown_config_keys(&table),
+///     [
+///         "modules.project_management.max_list",
+///         "profiles.db",
+///         "sparkles"
+///     ]
+/// );
+/// ```
+pub fn unknown_config_keys(table: &toml::Table) -> Vec<String> {
+    let base = serde_json::to_value(Config::from(base_config())).unwrap_or_default();
+    let mut unknown = vec![];
+    collect_unknown_keys(table, &base, "", &mut unknown);
+    unknown
+}
+
+/// Collect the keys of a table that the base config doesn't have. The depth is
+/// bounded by the depth of the base config, as tables that aren't in it are
+/// reported rather than checked.
+fn collect_unknown_keys(
+    table: &toml::Table,
+    base: &serde_json::Value,
+    prefix: &str,
+    unknown: &mut Vec<String>,
+) {
+    for (key, value) in table {
+        let path = if prefix.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+        let Some(base) = base.get(key) else {
+            if !unknown.contains(&path) {
+                unknown.push(path);
+            }
+            continue;
+        };
+        if MAP_CONFIGS.contains(&path.as_str()) {
+            continue;
+        }
+        match (value, base) {
+            (toml::Value::Table(table), _) => collect_unknown_keys(table, base, &path, unknown),
+            (toml::Value::Array(items), serde_json::Value::Array(base_items)) => {
+                let Some(base) = base_items.first() else {
+                    continue;
+                };
+                for item in items {
+                    if let toml::Value::Table(table) = item {
+                        collect_unknown_keys(table, base, &path, unknown);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
 }
 
 /// Get a ratatui compatible color from a hex color.
@@ -66,17 +665,44 @@ fn color_op(color_op: Option<String>, base_config_color: Color) -> Color {
     }
 }
 
+/// Parse a keybind if one is provided (from user config), otherwise return the
+/// base config value.
+fn keybind_op(
+    name: &str,
+    keybind: Option<String>,
+    base_config_keybind: KeyBind,
+) -> Result<KeyBind> {
+    match keybind {
+        Some(keybind) => KeyBind::from_str(&keybind).map_err(|e| {
+            eyre!(
+                "the keybinds.{} config \"{}\" is invalid: {}",
+                name,
+                keybind,
+                e
+            )
+        }),
+        None => Ok(base_config_keybind),
+    }
+}
+
 // TODO: Optimisation. There is lots of clones to reduce the level of nesting.
 // Try to not nest too deeply to keep the code easier to read and maintain.
 /// Merge the user config with the base config.
-fn merge_config(user_config: ConfigFile, base_config: Config) -> Config {
+fn merge_config(user_config: ConfigFile, mut base_config: Config) -> Result<Config> {
+    // The preset replaces the base config, which the user config overrides.
+    let preset = user_config
+        .colors
+        .as_ref()
+        .and_then(|c| c.preset.as_deref())
+        .filter(|p| is_color_preset(p));
+    if let Some(preset) = preset {
+        apply_color_preset(&mut base_config, preset)?;
+    }
+
     let colors = user_config.colors.map(|a| {
         let b = base_config.colors.clone();
         ColorsConfig {
-            preset: a
-                .preset
-                .filter(|p| COLOR_PRESETS.iter().any(|&cp| cp == p))
-                .unwrap_or(b.preset),
+            preset: a.preset.filter(|p| is_color_preset(p)).unwrap_or(b.preset),
             fg: color_op(a.fg, b.fg),
             secondary_fg: color_op(a.secondary_fg, b.secondary_fg),
             tertiary_fg: color_op(a.tertiary_fg, b.tertiary_fg),
@@ -136,6 +762,15 @@ fn merge_config(user_config: ConfigFile, base_config: Config) -> Config {
                 a.status_bar_delete_mode_fg,
                 b.status_bar_delete_mode_fg,
             ),
+            status_bar_interactive_mode_bg: color_op(
+                a.status_bar_interactive_mode_bg,
+                b.status_bar_interactive_mode_bg,
+            ),
+            status_bar_interactive_mode_fg: color_op(
+                a.status_bar_interactive_mode_fg,
+                b.status_bar_interactive_mode_fg,
+            ),
+            label_overflow_fg: color_op(a.label_overflow_fg, b.label_overflow_fg),
         }
     });
 
@@ -155,12 +790,46 @@ fn merge_config(user_config: ConfigFile, base_config: Config) -> Config {
             ProjectManagementModule {
                 max_lists: a.max_lists.unwrap_or(b.max_lists),
                 due_soon_days: a.due_soon_days.unwrap_or(b.due_soon_days),
+                day_start_hour: a.day_start_hour.unwrap_or(b.day_start_hour),
+                default_time: a.default_time.unwrap_or(b.default_time),
                 completed_char: a.completed_char.unwrap_or(b.completed_char),
                 overdue_char: a.overdue_char.unwrap_or(b.overdue_char),
                 due_soon_char: a.due_soon_char.unwrap_or(b.due_soon_char),
                 in_progress_char: a.in_progress_char.unwrap_or(b.in_progress_char),
                 important_char: a.important_char.unwrap_or(b.important_char),
+                urgent_char: a.urgent_char.unwrap_or(b.urgent_char),
+                medium_priority_char: a.medium_priority_char.unwrap_or(b.medium_priority_char),
+                low_priority_char: a.low_priority_char.unwrap_or(b.low_priority_char),
                 default_char: a.default_char.unwrap_or(b.default_char),
+                toggle_list_collapse_key: a
+                    .toggle_list_collapse_key
+                    .unwrap_or(b.toggle_list_collapse_key),
+                collapse_all_lists_key: a
+                    .collapse_all_lists_key
+                    .unwrap_or(b.collapse_all_lists_key),
+                expand_all_lists_key: a.expand_all_lists_key.unwrap_or(b.expand_all_lists_key),
+                toggle_list_zoom_key: a.toggle_list_zoom_key.unwrap_or(b.toggle_list_zoom_key),
+                next_status_key: a.next_status_key.unwrap_or(b.next_status_key),
+                prev_status_key: a.prev_status_key.unwrap_or(b.prev_status_key),
+                status_stepper_wrap: a.status_stepper_wrap.unwrap_or(b.status_stepper_wrap),
+                list_dividers: a.list_dividers.unwrap_or(b.list_dividers),
+                max_single_list_width: a.max_single_list_width.unwrap_or(b.max_single_list_width),
+                min_list_width: a.min_list_width.unwrap_or(b.min_list_width),
+                wrap_card_titles: a.wrap_card_titles.unwrap_or(b.wrap_card_titles),
+                show_progress_bar: a.show_progress_bar.unwrap_or(b.show_progress_bar),
+                label_overflow_total: a.label_overflow_total.unwrap_or(b.label_overflow_total),
+                auto_complete_on_all_subtasks: a
+                    .auto_complete_on_all_subtasks
+                    .unwrap_or(b.auto_complete_on_all_subtasks),
+                reopen_on_unchecked_subtask: a
+                    .reopen_on_unchecked_subtask
+                    .unwrap_or(b.reopen_on_unchecked_subtask),
+                confirm_discard_new: a.confirm_discard_new.unwrap_or(b.confirm_discard_new),
+                card_viewer_initial_section: a
+                    .card_viewer_initial_section
+                    .unwrap_or(b.card_viewer_initial_section),
+                trash_retention_days: a.trash_retention_days.unwrap_or(b.trash_retention_days),
+                enforce_wip_limits: a.enforce_wip_limits.unwrap_or(b.enforce_wip_limits),
             }
         });
 
@@ -170,6 +839,36 @@ fn merge_config(user_config: ConfigFile, base_config: Config) -> Config {
         }
     });
 
+    let datetime = user_config.datetime.map(|a| {
+        let b = base_config.datetime.clone();
+        DateTimeConfig {
+            date_format: a.date_format.unwrap_or(b.date_format),
+            time_format: a.time_format.unwrap_or(b.time_format),
+        }
+    });
+
+    let keybinds = user_config
+        .keybinds
+        .map(|a| -> Result<KeybindsConfig<KeyBind>> {
+            let b = base_config.keybinds.clone();
+            Ok(KeybindsConfig {
+                quit: keybind_op("quit", a.quit, b.quit)?,
+                help: keybind_op("help", a.help, b.help)?,
+                command_palette: keybind_op(
+                    "command_palette",
+                    a.command_palette,
+                    b.command_palette,
+                )?,
+                up: keybind_op("up", a.up, b.up)?,
+                down: keybind_op("down", a.down, b.down)?,
+                left: keybind_op("left", a.left, b.left)?,
+                right: keybind_op("right", a.right, b.right)?,
+                leader: keybind_op("leader", a.leader, b.leader)?,
+                leader_timeout: a.leader_timeout.unwrap_or(b.leader_timeout),
+            })
+        })
+        .transpose()?;
+
     let profiles = user_config.profiles.map(|a| {
         a.iter()
             .map(|profile| ProfileConfig {
@@ -197,50 +896,170 @@ fn merge_config(user_config: ConfigFile, base_config: Config) -> Config {
             .collect()
     });
 
-    Config {
+    let mut config = Config {
         log_level: user_config.log_level.unwrap_or(base_config.log_level),
+        log_format: user_config.log_format.unwrap_or(base_config.log_format),
+        log_levels: user_config.log_levels.unwrap_or(base_config.log_levels),
         default_profile: user_config
             .default_profile
             .unwrap_or(base_config.default_profile),
+        mouse: user_config.mouse.unwrap_or(base_config.mouse),
+        monochrome: user_config.monochrome.unwrap_or(base_config.monochrome),
+        refresh_interval: user_config
+            .refresh_interval
+            .unwrap_or(base_config.refresh_interval),
         colors: colors.unwrap_or(base_config.colors),
+        datetime: datetime.unwrap_or(base_config.datetime),
+        keybinds: keybinds.unwrap_or(base_config.keybinds),
         modules: modules.unwrap_or(base_config.modules),
         profiles: profiles.unwrap_or(base_config.profiles),
+        unknown_keys: user_config.unknown_keys,
+    };
+    if config.monochrome {
+        config.set_monochrome();
     }
+    Ok(config)
 }
 
-/// Read, parse, and marge the configuration.
+/// Read, parse, and marge the configuration of a profile, or of the default
+/// profile when there is none.
+///
+/// ```
+/// # use pltx_config::init_config;
+/// let dir = std::env::temp_dir().join("pltx-init-config");
+/// std::fs::create_dir_all(&dir).unwrap();
+/// pltx_utils::dirs::set_config_dir(dir.clone());
+///
+/// std::fs::write(dir.join("config.toml"), "mouse = true\nmax_lsts = 3\n").unwrap();
+/// let (config, profile) = init_config(None).unwrap();
+/// assert_eq!(profile.name, "default");
+/// assert!(config.mouse);
+/// assert_eq!(config.unknown_keys, ["max_lsts"]);
+///
+/// std::fs::write(dir.join("config.toml"), "mouse = \n").unwrap();
+/// assert!(init_config(None).is_err());
+/// ```
 pub fn init_config(profile: Option<String>) -> Result<(Config, ProfileConfig)> {
-    let mut base_config = base_config();
-    let default_profile_name = base_config.default_profile;
-    let default_profile = base_config
+    let profile_name = profile.unwrap_or_else(|| base_config().default_profile.to_string());
+    let base_config = profile_base_config(Some(&profile_name));
+    let profile = base_config
         .profiles
-        .as_ref()
         .iter()
-        .find(|p| p.name == default_profile_name)
-        .expect("failed to get default profile")
-        .clone();
+        .find(|p| p.name == profile_name)
+        .ok_or_else(|| {
+            let names = base_config
+                .profiles
+                .iter()
+                .map(|p| p.name)
+                .collect::<Vec<&str>>();
+            eyre!(
+                "no profile \"{}\" in config.toml, the profiles are: {}",
+                profile_name,
+                names.join(", ")
+            )
+        })?
+        .to_owned();
+    let profile = resolve_profile(profile.into())?;
 
-    if profile.as_ref().is_some_and(|p| p.as_str() == "dev") {
+    let profile_config: Config = match read_config_file(&profile.config_file)? {
+        Some(user_config) => merge_config(user_config, base_config.into())?,
+        None => base_config.into(),
+    };
+    profile_config.validate()?;
+    Ok((profile_config, profile))
+}
+
+/// The base config of a profile, which is the same for every profile except
+/// the developer profile.
+fn profile_base_config(profile: Option<&str>) -> DefaultConfig {
+    let mut base_config = base_config();
+    if profile == Some("dev") {
         base_config.log_level = "debug";
         base_config.modules.home.dashboard_title = "DEVELOPER PROFILE ENABLED";
         base_config.modules.home.dashboard_message = "All data is separate from the main profile.";
     }
+    base_config
+}
 
-    if let Some(profile_name) = profile {
-        let profile = base_config
-            .profiles
-            .iter()
-            .find(|p| p.name == profile_name)
-            .unwrap_or_else(|| panic!("no profile \"{}\" in config.toml", profile_name))
-            .to_owned();
-
-        let profile_config_file = read_config_file(profile.config_file);
-        let profile_config = match profile_config_file? {
-            Some(user_config) => merge_config(user_config, base_config.into()),
-            None => base_config.into(),
-        };
-        Ok((profile_config, profile.into()))
-    } else {
-        Ok((base_config.into(), (default_profile).into()))
+/// Set values in the config file of a profile by their keys, such as
+/// `colors.primary`, and return the config merged from the new file. Nothing
+/// is written when the new config is invalid. The other values are kept, but
+/// the comments of the file aren't.
+///
+/// ```
+/// # use pltx_config::{write_config_values, ProfileConfig};
+/// # use ratatui::style::Color;
+/// let dir = std::env::temp_dir().join("pltx-write-config");
+/// std::fs::create_dir_all(&dir).unwrap();
+/// let config_file = dir.join("config.toml");
+/// std::fs::write(&config_file, "[colors]\nprimary = \"#ff0000\"\n").unwrap();
+/// let profile = ProfileConfig {
+///     name: String::from("default"),
+///     config_file: config_file.to_string_lossy().to_string(),
+///     db_file: String::from("data.db"),
+///     log_file: String::from("debug.log"),
+/// };
+///
+/// let config = write_config_values(
+///     &profile,
+///     vec![
+///         ("modules.project_management.due_soon_days", 5.into()),
+///         ("modules.home.dashboard_title", "Hello".into()),
+///     ],
+/// )
+/// .unwrap();
+/// assert_eq!(config.modules.project_management.due_soon_days, 5);
+/// assert_eq!(config.modules.home.dashboard_title, "Hello");
+/// assert_eq!(config.colors.primary, Color::Rgb(255, 0, 0));
+///
+/// // An invalid config isn't written.
+/// let invalid = vec![("modules.project_management.default_time", "25:00".into())];
+/// assert!(write_config_values(&profile, invalid).is_err());
+/// let contents = std::fs::read_to_string(&config_file).unwrap();
+/// assert!(contents.contains("due_soon_days = 5"));
+/// assert!(!contents.contains("25:00"));
+///
+/// let error = write_config_values(&profile, vec![("log_level", "verbose".into())])
+///     .err()
+///     .unwrap();
+/// assert_eq!(
+///     error.to_string(),
+///     "the log_level config \"verbose\" is not one of: debug, info, warn, error"
+/// );
+/// ```
+pub fn write_config_values(
+    profile: &ProfileConfig,
+    values: Vec<(&str, toml::Value)>,
+) -> Result<Config> {
+    let contents = fs::read_to_string(&profile.config_file).unwrap_or_default();
+    let mut table: toml::Table = toml::from_str(&contents)
+        .map_err(|e| eyre!("the config file {} is invalid: {}", profile.config_file, e))?;
+
+    for (key, value) in values {
+        let mut keys = key.split('.').collect::<Vec<&str>>();
+        let name = keys.pop().ok_or_else(|| eyre!("no config key"))?;
+        let mut parent = &mut table;
+        for key in keys {
+            parent = parent
+                .entry(key)
+                .or_insert_with(|| toml::Value::Table(toml::Table::new()))
+                .as_table_mut()
+                .ok_or_else(|| eyre!("the {} config is not a table", key))?;
+        }
+        parent.insert(name.to_string(), value);
     }
+
+    let mut user_config: ConfigFile = toml::Value::Table(table.clone()).try_into()?;
+    user_config.unknown_keys = unknown_config_keys(&table);
+    let config = merge_config(user_config, profile_base_config(Some(&profile.name)).into())?;
+    config.validate()?;
+    fs::write(&profile.config_file, toml::to_string(&table)?)?;
+
+    Ok(config)
+}
+
+/// Resolve the files of a profile in the platform directories.
+fn resolve_profile(profile: ProfileConfig) -> Result<ProfileConfig> {
+    let resolved = profile.resolve(&dirs::config_dir(), &dirs::data_dir(), &dirs::cache_dir())?;
+    Ok(resolved)
 }
@@ -0,0 +1,127 @@
+use color_eyre::{eyre::eyre, Result};
+
+use crate::Config;
+
+/// Colors and status characters that replace the base config when the preset
+/// is set. The colors and characters of the user config still override them.
+pub struct ColorPreset {
+    pub name: &'static str,
+    /// What the preset is for, which is shown where it's picked.
+    pub description: &'static str,
+    colors: &'static [(&'static str, &'static str)],
+    /// Whether the status characters of cards are replaced with characters
+    /// that can be told apart by their shape alone.
+    shape_chars: bool,
+}
+
+/// The presets that can be set with the `colors.preset` config.
+pub const COLOR_PRESETS: [ColorPreset; 4] = [
+    ColorPreset {
+        name: "default",
+        description: "The colors of the base config",
+        colors: &[],
+        shape_chars: false,
+    },
+    // Green and red look alike without green cones, so success is blue and
+    // danger is a vermilion that is darker than the yellow warnings.
+    ColorPreset {
+        name: "deuteranopia",
+        description: "Blue, yellow, and vermilion instead of green and red",
+        colors: &[
+            ("success", "#56b4e9"),
+            ("warning", "#f0e442"),
+            ("danger", "#d55e00"),
+            ("status_bar_normal_mode_bg", "#56b4e9"),
+            ("status_bar_delete_mode_bg", "#d55e00"),
+        ],
+        shape_chars: true,
+    },
+    // Reds look dark and dull without red cones, so danger is a bright orange
+    // and success is blue.
+    ColorPreset {
+        name: "protanopia",
+        description: "Blue, pale yellow, and bright orange instead of green and red",
+        colors: &[
+            ("success", "#56b4e9"),
+            ("warning", "#fff5a0"),
+            ("danger", "#ff8c00"),
+            ("status_bar_normal_mode_bg", "#56b4e9"),
+            ("status_bar_delete_mode_bg", "#ff8c00"),
+        ],
+        shape_chars: true,
+    },
+    // For low vision or bright screens. Text is white on black, the muted
+    // colors are lighter, and the status colors are fully saturated.
+    ColorPreset {
+        name: "high-contrast",
+        description: "White text on black with bright borders and status colors",
+        colors: &[
+            ("fg", "#ffffff"),
+            ("bg", "#000000"),
+            ("secondary_fg", "#d0d0d0"),
+            ("tertiary_fg", "#a0a0a0"),
+            ("success", "#00ff7f"),
+            ("warning", "#ffff00"),
+            ("danger", "#ff3030"),
+            ("border", "#c0c0c0"),
+            ("border_active", "#ffff00"),
+            ("popup_bg", "#000000"),
+            ("popup_border", "#ffffff"),
+            ("input_bg", "#1a1a1a"),
+            ("input_focus_bg", "#404040"),
+            ("status_bar_bg", "#000000"),
+            ("status_bar_fg", "#ffffff"),
+        ],
+        shape_chars: true,
+    },
+];
+
+/// Whether there is a preset with the name.
+pub fn is_color_preset(name: &str) -> bool {
+    COLOR_PRESETS.iter().any(|p| p.name == name)
+}
+
+/// Replace the colors and status characters of the config with the ones of the
+/// preset.
+///
+/// ```
+/// # use pltx_config::{apply_color_preset, base_config, Config};
+/// # use ratatui::style::Color;
+/// let mut config = Config::from(base_config());
+/// apply_color_preset(&mut config, "deuteranopia").unwrap();
+/// assert_eq!(config.colors.preset, "deuteranopia");
+/// assert_eq!(config.colors.success, Color::Rgb(0x56, 0xb4, 0xe9));
+/// assert_eq!(config.modules.project_management.completed_char, "✓");
+///
+/// let mut config = Config::from(base_config());
+/// let default = Config::from(base_config());
+/// apply_color_preset(&mut config, "default").unwrap();
+/// assert_eq!(config.colors.success, default.colors.success);
+/// assert!(apply_color_preset(&mut config, "sparkles").is_err());
+/// ```
+pub fn apply_color_preset(config: &mut Config, name: &str) -> Result<()> {
+    let preset = COLOR_PRESETS
+        .iter()
+        .find(|p| p.name == name)
+        .ok_or_else(|| eyre!("there is no color preset \"{}\"", name))?;
+
+    config.colors.preset = preset.name.to_string();
+    for (color_name, color) in preset.colors {
+        config.colors.set(color_name, color)?;
+    }
+
+    if preset.shape_chars {
+        let chars = &mut config.modules.project_management;
+        chars.completed_char = String::from("✓");
+        chars.overdue_char = String::from("✗");
+        chars.urgent_char = String::from("▲");
+        chars.due_soon_char = String::from("◔");
+        chars.in_progress_char = String::from("◑");
+        chars.important_char = String::from("★");
+        chars.medium_priority_char = String::from("◆");
+        chars.low_priority_char = String::from("◇");
+        chars.default_char = String::from(" ");
+    }
+
+    Ok(())
+}
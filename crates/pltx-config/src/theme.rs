@@ -0,0 +1,118 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use color_eyre::Result;
+use pltx_utils::dirs;
+use ratatui::style::Color;
+
+use crate::{
+    base_config, is_color_preset, write_config_values, ColorsConfig, Config, ProfileConfig,
+};
+
+/// The file that themes are exported to and imported from.
+pub fn theme_path() -> PathBuf {
+    dirs::config_dir().join("themes").join("theme.toml")
+}
+
+/// The result of importing a theme.
+pub struct ThemeImport {
+    /// The config with the colors of the theme.
+    pub config: Config,
+    /// The keys of the theme that aren't colors or have invalid colors, which
+    /// weren't imported.
+    pub invalid: Vec<String>,
+}
+
+/// Write the colors, including the preset, to a TOML file that only contains
+/// the theme.
+pub fn export_theme(colors: &ColorsConfig, path: &Path) -> Result<()> {
+    let mut theme = toml::Table::try_from(colors)?;
+    // Hex colors are written in lowercase, like the rest of the config.
+    for (_, value) in theme.iter_mut() {
+        if let toml::Value::String(color) = value {
+            if color.starts_with('#') {
+                *color = color.to_lowercase();
+            }
+        }
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, toml::to_string(&theme)?)?;
+    Ok(())
+}
+
+/// Read a theme from a TOML file and write its valid colors to the config file
+/// of the profile. The colors can also be in a `[colors]` table, as they are
+/// in the config file. The keys that aren't valid are returned rather than
+/// failing the import.
+///
+/// ```
+/// # use pltx_config::{base_config, export_theme, import_theme, Config, ProfileConfig};
+/// # use ratatui::style::Color;
+/// let dir = std::env::temp_dir().join("pltx-theme");
+/// std::fs::create_dir_all(&dir).unwrap();
+/// let profile = ProfileConfig {
+///     name: String::from("default"),
+///     config_file: dir.join("config.toml").to_string_lossy().to_string(),
+///     db_file: String::from("data.db"),
+///     log_file: String::from("debug.log"),
+/// };
+/// let _ = std::fs::remove_file(&profile.config_file);
+///
+/// let mut colors = Config::from(base_config()).colors;
+/// colors.primary = Color::Rgb(255, 0, 0);
+/// export_theme(&colors, &dir.join("red.toml")).unwrap();
+/// let theme = std::fs::read_to_string(dir.join("red.toml")).unwrap();
+/// assert!(theme.contains("preset = \"default\""));
+/// assert!(theme.contains("primary = \"#ff0000\""));
+///
+/// let import = import_theme(&dir.join("red.toml"), &profile).unwrap();
+/// assert_eq!(import.config.colors.primary, Color::Rgb(255, 0, 0));
+/// assert!(import.invalid.is_empty());
+///
+/// std::fs::write(
+///     dir.join("broken.toml"),
+///     "[colors]\nsuccess = \"#00ff00\"\ndanger = \"not a color\"\nsparkle = \"#ffffff\"\n",
+/// )
+/// .unwrap();
+/// let import = import_theme(&dir.join("broken.toml"), &profile).unwrap();
+/// assert_eq!(import.config.colors.success, Color::Rgb(0, 255, 0));
+/// assert_eq!(import.config.colors.primary, Color::Rgb(255, 0, 0));
+/// assert_eq!(import.invalid, ["danger", "sparkle"]);
+/// ```
+pub fn import_theme(path: &Path, profile: &ProfileConfig) -> Result<ThemeImport> {
+    let contents = fs::read_to_string(path)?;
+    let mut theme: toml::Table = toml::from_str(&contents)?;
+    if let Some(toml::Value::Table(colors)) = theme.remove("colors") {
+        theme = colors;
+    }
+
+    let names = serde_json::to_value(Config::from(base_config()).colors)?;
+    let mut colors = vec![];
+    let mut invalid = vec![];
+    for (key, value) in theme {
+        let valid = match (key.as_str(), value.as_str()) {
+            ("preset", Some(preset)) => is_color_preset(preset),
+            (name, Some(color)) => names.get(name).is_some() && Color::from_str(color).is_ok(),
+            _ => false,
+        };
+        if valid {
+            colors.push((format!("colors.{}", key), value));
+        } else {
+            invalid.push(key);
+        }
+    }
+
+    let values = colors
+        .iter()
+        .map(|(key, value)| (key.as_str(), value.clone()))
+        .collect();
+    let config = write_config_values(profile, values)?;
+
+    Ok(ThemeImport { config, invalid })
+}
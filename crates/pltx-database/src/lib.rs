@@ -2,55 +2,151 @@
 //! methods for convenience.
 
 use std::{
-    fs, thread,
+    fs, io,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
     time::{Duration, Instant},
 };
 
 use color_eyre::Result;
-use pltx_utils::{dirs, DateTime};
+use pltx_utils::DateTime;
 use r2d2::{Pool, PooledConnection};
 use r2d2_sqlite::SqliteConnectionManager;
 use rusqlite::ToSql;
 
 mod init_sql;
+mod migrations;
 
 pub use init_sql::*;
+pub use migrations::*;
 use tracing::info;
 
+/// The pragmas of every connection.
+///
+/// - `journal_mode = WAL` writes changes to a log that is merged into the
+///   database later, so reads don't wait for writes, such as the session that
+///   is synced every second, and a crash can't leave a write half done.
+/// - `synchronous = NORMAL` only syncs the log to the disk when it's merged,
+///   which is safe with WAL. A power loss can lose the last transactions, but
+///   can't corrupt the database.
+/// - `busy_timeout = 5000` waits up to 5 seconds for another connection of the
+///   pool to finish writing, rather than failing with "database is locked".
+///
+/// A database in memory keeps its journal in memory, whatever the journal mode
+/// is set to.
+const CONNECTION_PRAGMAS: &str = "PRAGMA journal_mode = WAL;
+PRAGMA synchronous = NORMAL;
+PRAGMA busy_timeout = 5000;";
+
+/// The number of prepared statements that each connection keeps, which is
+/// enough for the queries of reloading a project and its popups. The least
+/// recently used statement is dropped when it's full.
+const STATEMENT_CACHE_CAPACITY: usize = 64;
+
 pub struct Database {
     pool: Pool<SqliteConnectionManager>,
-    filename: String,
+    /// The absolute path of the database file.
+    path: String,
     session_started: bool,
+    /// Set to stop the thread that syncs the end of the session.
+    session_ended: Arc<AtomicBool>,
     pub session_id: Option<i32>,
     pub started: Option<DateTime>,
 }
 
 impl Database {
-    pub fn init(filename: String) -> Database {
-        let db_file = dirs::data_dir().join(&filename);
-        let manager = SqliteConnectionManager::file(db_file);
+    /// Open the database file at `path`, which is the resolved `db_file` of the
+    /// profile. Every connection is set up with the `CONNECTION_PRAGMAS`.
+    ///
+    /// ```
+    /// # use pltx_database::Database;
+    /// let path = std::env::temp_dir().join("pltx-database-init.db");
+    /// let db = Database::init(path.to_string_lossy().to_string());
+    /// let conn = db.conn();
+    /// let journal_mode: String = conn
+    ///     .pragma_query_value(None, "journal_mode", |r| r.get(0))
+    ///     .unwrap();
+    /// assert_eq!(journal_mode, "wal");
+    /// let synchronous: i32 = conn
+    ///     .pragma_query_value(None, "synchronous", |r| r.get(0))
+    ///     .unwrap();
+    /// // NORMAL
+    /// assert_eq!(synchronous, 1);
+    /// let busy_timeout: i32 = conn
+    ///     .pragma_query_value(None, "busy_timeout", |r| r.get(0))
+    ///     .unwrap();
+    /// assert_eq!(busy_timeout, 5000);
+    /// ```
+    pub fn init(path: String) -> Database {
+        let manager = SqliteConnectionManager::file(&path).with_init(|conn| {
+            conn.set_prepared_statement_cache_capacity(STATEMENT_CACHE_CAPACITY);
+            conn.execute_batch(CONNECTION_PRAGMAS)
+        });
         let pool = Pool::new(manager).expect("failed to create database pool");
 
         Database {
             pool,
-            filename,
+            path,
             session_id: None,
+            session_ended: Arc::new(AtomicBool::new(false)),
             session_started: false,
             started: None,
         }
     }
 
+    /// Open a database that is kept in memory until it's dropped, for tests and
+    /// previews. The connections of the pool share the same database.
+    pub fn in_memory() -> Database {
+        static COUNT: AtomicUsize = AtomicUsize::new(0);
+        let path = format!(
+            "file:pltx-memory-{}?mode=memory&cache=shared",
+            COUNT.fetch_add(1, Ordering::Relaxed)
+        );
+        Database::init(path)
+    }
+
     /// Access the pooled connection.
     pub fn conn(&self) -> PooledConnection<SqliteConnectionManager> {
         self.pool.get().expect("failed to get database pool")
     }
 
+    /// Execute a query with a statement from the cache of the connection, so a
+    /// query that is executed again isn't parsed again.
     pub fn execute<P: rusqlite::Params>(&self, query: &str, params: P) -> Result<usize> {
         let conn = self.conn();
-        let mut stmt = conn.prepare(query)?;
+        let mut stmt = conn.prepare_cached(query)?;
         Ok(stmt.execute(params)?)
     }
 
+    /// Execute an insert and return the id of the inserted row. The id is read
+    /// from the connection of the insert, so rows that are inserted at the same
+    /// time elsewhere don't affect it, unlike with
+    /// [`last_row_id`](Database::last_row_id).
+    ///
+    /// ```
+    /// # use pltx_database::Database;
+    /// let db = Database::in_memory();
+    /// db.execute("CREATE TABLE card (id INTEGER PRIMARY KEY, title TEXT)", ())
+    ///     .unwrap();
+    /// db.execute("INSERT INTO card VALUES (10, 'later')", ())
+    ///     .unwrap();
+    ///
+    /// let id = db
+    ///     .insert("INSERT INTO card (id, title) VALUES (?1, ?2)", (3, "first"))
+    ///     .unwrap();
+    /// assert_eq!(id, 3);
+    /// assert_eq!(db.last_row_id("card").unwrap(), 10);
+    /// ```
+    pub fn insert<P: rusqlite::Params>(&self, query: &str, params: P) -> Result<i32> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare_cached(query)?;
+        stmt.execute(params)?;
+        Ok(conn.last_insert_rowid() as i32)
+    }
+
     pub fn start_session(&mut self) -> Result<()> {
         let start = Instant::now();
         self.ensure_tables()?;
@@ -79,6 +175,13 @@ impl Database {
             )",
             (),
         )?;
+        self.execute(
+            "CREATE TABLE IF NOT EXISTS app_state (
+                key TEXT PRIMARY KEY,
+                value TEXT NOT NULL
+            )",
+            (),
+        )?;
 
         Ok(())
     }
@@ -86,8 +189,12 @@ impl Database {
     fn create_sync_session_thread(&self) -> Result<()> {
         let pool = self.pool.clone();
         let session_id = self.session_id;
+        let session_ended = self.session_ended.clone();
 
         thread::spawn(move || loop {
+            if session_ended.load(Ordering::Relaxed) {
+                break;
+            }
             let conn = pool.get().expect("failed to get database pool");
             conn.execute(
                 "UPDATE session SET ended = ?1 WHERE id = ?2",
@@ -100,15 +207,63 @@ impl Database {
         Ok(())
     }
 
+    /// Record the end of the session and stop syncing it, so the connections
+    /// are closed once the database is dropped.
+    pub fn end_session(&mut self) -> Result<()> {
+        if !self.session_started {
+            return Ok(());
+        }
+        self.session_ended.store(true, Ordering::Relaxed);
+        self.execute(
+            "UPDATE session SET ended = ?1 WHERE id = ?2",
+            (DateTime::now(), self.session_id),
+        )?;
+        self.session_started = false;
+        self.session_id = None;
+        self.started = None;
+        info!("ended session");
+        Ok(())
+    }
+
+    /// Delete the database file, along with the log and the shared memory file
+    /// of WAL mode.
     pub fn reset(&self) -> Result<()> {
-        fs::remove_file(dirs::data_dir().join(&self.filename))?;
+        fs::remove_file(&self.path)?;
+        for suffix in ["-wal", "-shm"] {
+            match fs::remove_file(format!("{}{}", self.path, suffix)) {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e.into()),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Get a value that the application keeps between sessions, such as the
+    /// last version that was run.
+    pub fn get_state(&self, key: &str) -> Result<Option<String>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare_cached("SELECT value FROM app_state WHERE key = ?1")?;
+        let mut rows = stmt.query([key])?;
+        Ok(match rows.next()? {
+            Some(row) => Some(row.get(0)?),
+            None => None,
+        })
+    }
+
+    /// Set a value that the application keeps between sessions.
+    pub fn set_state(&self, key: &str, value: &str) -> Result<()> {
+        self.execute(
+            "INSERT INTO app_state (key, value) VALUES (?1, ?2) ON CONFLICT(key) DO UPDATE SET \
+             value = excluded.value",
+            [key, value],
+        )?;
         Ok(())
     }
 
     pub fn get_position(&self, table: &str, id: i32) -> Result<i32> {
         let query = format!("SELECT position FROM {} WHERE id = ?1", table);
         let conn = self.conn();
-        let position: i32 = conn.prepare(&query)?.query_row([id], |r| r.get(0))?;
+        let position: i32 = conn.prepare_cached(&query)?.query_row([id], |r| r.get(0))?;
         Ok(position)
     }
 
@@ -118,12 +273,35 @@ impl Database {
         );
         let highest_position: i32 = self
             .conn()
-            .prepare(&query)?
+            .prepare_cached(&query)?
             .query_row([], |r| r.get(0))
             .unwrap_or(-1);
         Ok(highest_position)
     }
 
+    /// The highest position of the rows where `field` equals a value, or -1 if
+    /// there are none, so the next row is always at the highest position + 1.
+    ///
+    /// ```
+    /// # use pltx_database::Database;
+    /// let db = Database::in_memory();
+    /// db.execute(
+    ///     "CREATE TABLE card (id INTEGER, list_id INTEGER, position INTEGER)",
+    ///     (),
+    /// )
+    /// .unwrap();
+    /// db.execute("INSERT INTO card VALUES (1, 1, 0), (2, 1, 1)", ())
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     db.get_highest_position_where("card", "list_id", 1).unwrap(),
+    ///     1
+    /// );
+    /// assert_eq!(
+    ///     db.get_highest_position_where("card", "list_id", 2).unwrap(),
+    ///     -1
+    /// );
+    /// ```
     pub fn get_highest_position_where<T>(&self, table: &str, field: &str, equals: T) -> Result<i32>
     where
         T: ToSql,
@@ -134,7 +312,7 @@ impl Database {
              WHERE {} = ?1)",
             table, field, table, field
         );
-        let mut stmt = conn.prepare(&query)?;
+        let mut stmt = conn.prepare_cached(&query)?;
         let highest_position: i32 = stmt.query_row([equals], |r| r.get(0)).unwrap_or(-1);
         Ok(highest_position)
     }
@@ -148,6 +326,41 @@ impl Database {
         Ok(())
     }
 
+    /// Close the gap that a row at `old_position` left when it was removed
+    /// from the rows where `field` equals a value.
+    ///
+    /// ```
+    /// # use pltx_database::Database;
+    /// let db = Database::in_memory();
+    /// db.execute(
+    ///     "CREATE TABLE card (id INTEGER, list_id INTEGER, position INTEGER, updated_at TEXT)",
+    ///     (),
+    /// )
+    /// .unwrap();
+    /// db.execute(
+    ///     "INSERT INTO card (id, list_id, position) VALUES (1, 1, 0), (2, 1, 1), (3, 1, 2), (4, 2, \
+    ///      0), (5, 2, 1), (6, 2, 2)",
+    ///     (),
+    /// )
+    /// .unwrap();
+    /// let positions = |list_id: i32| {
+    ///     let conn = db.conn();
+    ///     let mut stmt = conn
+    ///         .prepare("SELECT id, position FROM card WHERE list_id = ?1 ORDER BY position")
+    ///         .unwrap();
+    ///     stmt.query_map([list_id], |r| Ok((r.get(0)?, r.get(1)?)))
+    ///         .unwrap()
+    ///         .map(|r| r.unwrap())
+    ///         .collect::<Vec<(i32, i32)>>()
+    /// };
+    ///
+    /// db.execute("DELETE FROM card WHERE id = 2", ()).unwrap();
+    /// db.decrement_positions_after_where("card", 1, "list_id", 1)
+    ///     .unwrap();
+    /// assert_eq!(positions(1), vec![(1, 0), (3, 1)]);
+    /// // The other lists are left as they are.
+    /// assert_eq!(positions(2), vec![(4, 0), (5, 1), (6, 2)]);
+    /// ```
     pub fn decrement_positions_after_where<T>(
         &self,
         table: &str,
@@ -167,34 +380,66 @@ impl Database {
         Ok(())
     }
 
+    /// Swap the row with the row after it, in one transaction.
+    ///
+    /// ```
+    /// # use pltx_database::Database;
+    /// let db = Database::in_memory();
+    /// db.execute(
+    ///     "CREATE TABLE list (id INTEGER, position INTEGER, updated_at TEXT)",
+    ///     (),
+    /// )
+    /// .unwrap();
+    /// db.execute(
+    ///     "INSERT INTO list (id, position) VALUES (1, 0), (2, 1), (3, 2)",
+    ///     (),
+    /// )
+    /// .unwrap();
+    ///
+    /// db.increment_position("list", 1, 2).unwrap();
+    /// assert_eq!(db.get_position("list", 1).unwrap(), 1);
+    /// assert_eq!(db.get_position("list", 2).unwrap(), 0);
+    ///
+    /// db.decrement_position("list", 3, 1).unwrap();
+    /// assert_eq!(db.get_position("list", 3).unwrap(), 1);
+    /// assert_eq!(db.get_position("list", 1).unwrap(), 2);
+    /// assert_eq!(db.get_highest_position("list").unwrap(), 2);
+    /// ```
     pub fn increment_position(&self, table: &str, id: i32, next_id: i32) -> Result<()> {
+        let mut conn = self.conn();
+        let tx = conn.transaction()?;
         let query = format!(
             "UPDATE {} SET position = position + 1, updated_at = ?1 where id = ?2",
             table,
         );
-        self.execute(&query, (DateTime::now(), id))?;
+        tx.execute(&query, (DateTime::now(), id))?;
 
         let query_2 = format!(
             "UPDATE {} SET position = position - 1, updated_at = ?1 where id = ?2",
             table
         );
-        self.execute(&query_2, (DateTime::now(), next_id))?;
+        tx.execute(&query_2, (DateTime::now(), next_id))?;
+        tx.commit()?;
 
         Ok(())
     }
 
+    /// Swap the row with the row before it, in one transaction.
     pub fn decrement_position(&self, table: &str, id: i32, prev_id: i32) -> Result<()> {
+        let mut conn = self.conn();
+        let tx = conn.transaction()?;
         let query = format!(
             "UPDATE {} SET position = position - 1, updated_at = ?1 where id = ?2",
             table,
         );
-        self.execute(&query, (DateTime::now(), id))?;
+        tx.execute(&query, (DateTime::now(), id))?;
 
         let query_2 = format!(
             "UPDATE {} SET position = position + 1, updated_at = ?1 where id = ?2",
             table
         );
-        self.execute(&query_2, (DateTime::now(), prev_id))?;
+        tx.execute(&query_2, (DateTime::now(), prev_id))?;
+        tx.commit()?;
 
         Ok(())
     }
@@ -205,7 +450,7 @@ impl Database {
             table, table
         );
         let conn = self.conn();
-        let mut stmt = conn.prepare(&query)?;
+        let mut stmt = conn.prepare_cached(&query)?;
         let recent_id: i32 = stmt.query_row((), |r| r.get(0))?;
         Ok(recent_id)
     }
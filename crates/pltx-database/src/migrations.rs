@@ -0,0 +1,145 @@
+use std::time::Instant;
+
+use color_eyre::Result;
+use rusqlite::{Connection, OptionalExtension};
+use tracing::info;
+
+use crate::Database;
+
+/// What a migration does to the database.
+pub enum MigrationStep {
+    /// A script of one or more statements.
+    Sql(&'static str),
+    /// A function for changes that can't be written as a script, such as
+    /// adding a column only if it doesn't exist.
+    Fn(fn(&Connection) -> rusqlite::Result<()>),
+}
+
+/// A change to the schema of a module. Each migration is applied once to a
+/// database, in the order of their versions. A migration should be
+/// idempotent, since the tables of a new database may already have been
+/// created with the change.
+pub struct Migration {
+    /// The schema version of the module after the migration.
+    pub version: i32,
+    pub description: &'static str,
+    pub step: MigrationStep,
+}
+
+/// Add a column to a table if it doesn't have it yet.
+pub fn add_column(
+    conn: &Connection,
+    table: &str,
+    column: &str,
+    definition: &str,
+) -> rusqlite::Result<()> {
+    let exists: bool = conn
+        .prepare("SELECT COUNT(*) > 0 FROM pragma_table_info(?1) WHERE name = ?2")?
+        .query_row([table, column], |r| r.get(0))?;
+    if !exists {
+        conn.execute(
+            &format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition),
+            (),
+        )?;
+    }
+    Ok(())
+}
+
+impl Database {
+    /// The schema version of a module, which is 0 before any of its migrations
+    /// have been applied.
+    pub fn schema_version(&self, module: &str) -> Result<i32> {
+        self.ensure_schema_version_table()?;
+        // Only a missing row means no version. Other errors, such as a locked
+        // database, would otherwise apply every migration again.
+        let version = self
+            .conn()
+            .query_row(
+                "SELECT version FROM schema_version WHERE module = ?1",
+                [module],
+                |r| r.get(0),
+            )
+            .optional()?
+            .unwrap_or(0);
+        Ok(version)
+    }
+
+    /// Apply the migrations of a module that are newer than its schema version.
+    /// Each migration is applied in a transaction with the new version, so a
+    /// failed migration leaves the database as it was. Returns the number of
+    /// migrations that were applied.
+    ///
+    /// ```
+    /// # use pltx_database::{Database, Migration, MigrationStep, add_column};
+    /// let db = Database::in_memory();
+    ///
+    /// let migrations = [
+    ///     Migration {
+    ///         version: 1,
+    ///         description: "create the note table",
+    ///         step: MigrationStep::Sql("CREATE TABLE IF NOT EXISTS note (id INTEGER PRIMARY KEY)"),
+    ///     },
+    ///     Migration {
+    ///         version: 2,
+    ///         description: "add the title of notes",
+    ///         step: MigrationStep::Fn(|conn| add_column(conn, "note", "title", "TEXT")),
+    ///     },
+    /// ];
+    /// assert_eq!(db.schema_version("notes").unwrap(), 0);
+    /// assert_eq!(db.apply_migrations("notes", &migrations[..1]).unwrap(), 1);
+    /// assert_eq!(db.apply_migrations("notes", &migrations).unwrap(), 1);
+    /// assert_eq!(db.apply_migrations("notes", &migrations).unwrap(), 0);
+    /// assert_eq!(db.schema_version("notes").unwrap(), 2);
+    /// ```
+    pub fn apply_migrations(&self, module: &str, migrations: &[Migration]) -> Result<usize> {
+        let start = Instant::now();
+
+        let version = self.schema_version(module)?;
+        let mut conn = self.conn();
+        let mut applied = 0;
+        for migration in migrations.iter().filter(|m| m.version > version) {
+            let tx = conn.transaction()?;
+            match migration.step {
+                MigrationStep::Sql(sql) => tx.execute_batch(sql)?,
+                MigrationStep::Fn(step) => step(&tx)?,
+            }
+            tx.execute(
+                "INSERT INTO schema_version (module, version) VALUES (?1, ?2) ON CONFLICT \
+                 (module) DO UPDATE SET version = ?2",
+                (module, migration.version),
+            )?;
+            tx.commit()?;
+            info!(
+                "applied {} migration {}: {}",
+                module, migration.version, migration.description
+            );
+            applied += 1;
+        }
+
+        if applied > 0 {
+            // SQLite prepares the statements of the other connections again
+            // when the schema changes, but the ones of this connection are
+            // dropped as they may be of tables that were changed.
+            conn.flush_prepared_statement_cache();
+            info!(
+                "applied {} {} migrations in {:?}",
+                applied,
+                module,
+                start.elapsed()
+            );
+        }
+
+        Ok(applied)
+    }
+
+    fn ensure_schema_version_table(&self) -> Result<()> {
+        self.execute(
+            "CREATE TABLE IF NOT EXISTS schema_version (
+                module TEXT PRIMARY KEY,
+                version INTEGER NOT NULL
+            )",
+            (),
+        )?;
+        Ok(())
+    }
+}
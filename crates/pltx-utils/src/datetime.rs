@@ -1,6 +1,10 @@
 use std::fmt;
 
-use chrono::{DateTime as ChronoDateTime, Duration, Local, Utc};
+use chrono::{
+    format::{Item, StrftimeItems},
+    DateTime as ChronoDateTime, Datelike, Days, Duration, Local, Months, NaiveDate, NaiveDateTime,
+    NaiveTime, TimeZone, Utc,
+};
 
 /// Custom struct around [`Chrono`](chrono) for managing datetime within the
 /// application. Provides convenience methods to reduce the need for repetitive
@@ -10,6 +14,7 @@ pub struct DateTime {
     pub datetime: ChronoDateTime<Utc>,
 }
 
+/// A duration shown as a clock, in hours, minutes, and seconds.
 pub struct DurationSince {
     pub hours: i64,
     pub minutes: i64,
@@ -40,6 +45,16 @@ impl fmt::Display for DurationSince {
     }
 }
 
+impl From<Duration> for DurationSince {
+    fn from(duration: Duration) -> Self {
+        Self {
+            hours: duration.num_hours(),
+            minutes: duration.num_minutes() % 60,
+            seconds: duration.num_seconds() % 60,
+        }
+    }
+}
+
 impl From<ChronoDateTime<Utc>> for DateTime {
     fn from(datetime: ChronoDateTime<Utc>) -> Self {
         Self { datetime }
@@ -72,24 +87,91 @@ impl DateTime {
         }
     }
 
-    /// Parse a datetime in the format "%Y-%m-%d %H:%M" and return in rfc3999.
-    /// format "%Y-%m-%dT%H:%M:%S+%z" as a [`Option`](Option).
+    /// Parse a datetime in the format "%Y-%m-%d %H:%M" in the local timezone,
+    /// and return it in UTC in rfc3999 format "%Y-%m-%dT%H:%M:%S+%z" as an
+    /// [`Option`](Option). Displaying the datetime converts it back to the
+    /// local timezone, so it shows the same value that was entered.
     /// ```
     /// # use pltx_utils::DateTime;
-    /// let datetime = DateTime::from_input(String::from("2000-01-01 00:00"));
-    /// assert_eq!(datetime, Some(String::from("2000-01-01T00:00:00+00:00")));
+    /// // In a timezone 2 hours ahead of UTC.
+    /// std::env::set_var("TZ", "Etc/GMT-2");
+    ///
+    /// let datetime = DateTime::from_input(String::from("2000-01-01 00:00")).unwrap();
+    /// assert_eq!(DateTime::from(datetime).display(), "2000-01-01 00:00");
+    /// assert_eq!(DateTime::from_input(String::from("2000-01-01")), None);
+    ///
+    /// let datetime = DateTime::from_input(String::from("2024-06-01 09:00")).unwrap();
+    /// assert_eq!(datetime, "2024-06-01T07:00:00+00:00");
+    /// assert_eq!(DateTime::from(datetime).display(), "2024-06-01 09:00");
     /// ```
     pub fn from_input(input: String) -> Option<String> {
-        let parsed_string_format = format!("{}:00+00:00", input.replacen(' ', "T", 1));
-        let input_result = match ChronoDateTime::parse_from_rfc3339(&parsed_string_format) {
-            Ok(datetime) => Ok(Self::from(datetime.to_utc())),
-            Err(err) => Err(err),
+        let naive = NaiveDateTime::parse_from_str(&input, "%Y-%m-%d %H:%M").ok()?;
+        // A local time can be ambiguous or skipped when the clocks change.
+        let local = Local.from_local_datetime(&naive).earliest()?;
+        Some(local.to_utc().to_rfc3339())
+    }
+
+    /// Parse a datetime input like [`from_input`](DateTime::from_input), or a
+    /// shorthand for a day from today: `today`, `tomorrow`, or a number of days
+    /// or weeks such as `+3d` or `+1w`. Shorthands are at `time`, in the
+    /// format "%H:%M".
+    /// ```
+    /// # use chrono::{Days, Local};
+    /// # use pltx_utils::DateTime;
+    /// let day = |days| {
+    ///     let date = Local::now().date_naive() + Days::new(days);
+    ///     DateTime::from_input(format!("{} 09:00", date.format("%Y-%m-%d")))
+    /// };
+    ///
+    /// assert_eq!(DateTime::from_input_or_shorthand("today", "09:00"), day(0));
+    /// assert_eq!(
+    ///     DateTime::from_input_or_shorthand("Tomorrow", "09:00"),
+    ///     day(1)
+    /// );
+    /// assert_eq!(DateTime::from_input_or_shorthand("+3d", "09:00"), day(3));
+    /// assert_eq!(DateTime::from_input_or_shorthand("+1w", "09:00"), day(7));
+    /// assert_eq!(
+    ///     DateTime::from_input_or_shorthand("2024-06-01 12:00", "09:00"),
+    ///     DateTime::from_input(String::from("2024-06-01 12:00"))
+    /// );
+    ///
+    /// for invalid in ["+3", "+d", "+3m", "-1d", "yesterday"] {
+    ///     assert_eq!(DateTime::from_input_or_shorthand(invalid, "09:00"), None);
+    /// }
+    /// assert_eq!(DateTime::from_input_or_shorthand("today", "9am"), None);
+    /// ```
+    pub fn from_input_or_shorthand(input: &str, time: &str) -> Option<String> {
+        let input = input.trim();
+        let days = match input.to_lowercase().as_str() {
+            "today" => 0,
+            "tomorrow" => 1,
+            shorthand => {
+                let Some(amount) = shorthand.strip_prefix('+') else {
+                    return Self::from_input(input.to_string());
+                };
+                if let Some(days) = amount.strip_suffix('d') {
+                    days.parse::<u64>().ok()?
+                } else if let Some(weeks) = amount.strip_suffix('w') {
+                    weeks.parse::<u64>().ok()?.checked_mul(7)?
+                } else {
+                    return None;
+                }
+            }
         };
+        let date = Local::now()
+            .date_naive()
+            .checked_add_days(Days::new(days))?;
+        Self::from_input(format!("{} {}", date.format("%Y-%m-%d"), time))
+    }
 
-        match input_result {
-            Ok(result) => Some(result.datetime.to_rfc3339()),
-            Err(_) => None,
-        }
+    /// Checks if a time is in the format "%H:%M".
+    /// ```
+    /// # use pltx_utils::DateTime;
+    /// assert!(DateTime::is_valid_time("09:00"));
+    /// assert!(!DateTime::is_valid_time("25:00"));
+    /// ```
+    pub fn is_valid_time(time: &str) -> bool {
+        NaiveTime::parse_from_str(time, "%H:%M").is_ok()
     }
 
     /// Get the current local datetime.
@@ -109,6 +191,30 @@ impl DateTime {
             .to_string()
     }
 
+    /// Convert and display the datetime in local format, using a strftime
+    /// format.
+    /// ```
+    /// # use pltx_utils::DateTime;
+    /// let datetime = DateTime::from("2024-05-25T12:00:00+00:00");
+    /// assert_eq!(datetime.display_format("%Y"), "2024");
+    /// ```
+    pub fn display_format(&self, format: &str) -> String {
+        ChronoDateTime::<Local>::from(self.datetime)
+            .format(format)
+            .to_string()
+    }
+
+    /// Checks if a strftime format only contains valid specifiers. Displaying
+    /// with an invalid format panics.
+    /// ```
+    /// # use pltx_utils::DateTime;
+    /// assert!(DateTime::is_valid_format("%d/%m/%Y %I:%M %p"));
+    /// assert!(!DateTime::is_valid_format("%Y-%m-%Q"));
+    /// ```
+    pub fn is_valid_format(format: &str) -> bool {
+        !StrftimeItems::new(format).any(|item| item == Item::Error)
+    }
+
     /// Convert and display the datetime in local format with seconds.
     pub fn display_with_seconds(&self) -> String {
         ChronoDateTime::<Local>::from(self.datetime)
@@ -148,41 +254,389 @@ impl DateTime {
         Utc::now().timestamp() > self.datetime.timestamp()
     }
 
-    /// Checks if the current datetime is past a datetime minus a specified
-    /// number of days, which means the number of days *before* the
-    /// datetime.
+    /// Checks if the datetime is less than a number of days from now, which
+    /// includes datetimes that have already passed. With 0 days, it's the same
+    /// as [`is_past`](DateTime::is_past), and negative days are treated as 0.
     /// ```
     /// # use pltx_utils::DateTime;
     /// let datetime = DateTime::from("2000-01-01T00:00:00+00:00");
     /// assert!(datetime.is_past_days(100));
     /// ```
     pub fn is_past_days(&self, days: i32) -> bool {
-        Utc::now().timestamp() > (self.datetime - Duration::days(days as i64)).timestamp()
+        self.is_past_days_at(days, &DateTime::new())
+    }
+
+    /// The same as [`is_past_days`](DateTime::is_past_days), from `now`
+    /// instead of the current datetime.
+    /// ```
+    /// # use pltx_utils::DateTime;
+    /// let now = DateTime::from("2024-06-01T12:00:00+00:00");
+    ///
+    /// // Exactly now hasn't passed, but it's within any number of days.
+    /// assert!(now.is_past_days_at(3, &now));
+    /// assert!(!now.is_past_days_at(0, &now));
+    ///
+    /// // The boundary itself isn't within the days.
+    /// assert!(DateTime::from("2024-06-04T11:59:59+00:00").is_past_days_at(3, &now));
+    /// assert!(!now.add_days(3).is_past_days_at(3, &now));
+    /// assert!(!now.add_days(4).is_past_days_at(3, &now));
+    ///
+    /// // Datetimes that already passed are always within the days.
+    /// assert!(now.add_days(-1).is_past_days_at(3, &now));
+    /// assert!(now.add_days(-1).is_past_days_at(0, &now));
+    /// assert!(!now.is_past_days_at(-2, &now));
+    /// ```
+    pub fn is_past_days_at(&self, days: i32, now: &DateTime) -> bool {
+        let days = Duration::days(days.max(0) as i64);
+        now.datetime.timestamp() > (self.datetime - days).timestamp()
+    }
+
+    /// The local date of the datetime, for days that start at `day_start_hour`
+    /// instead of midnight. A datetime before the start hour belongs to the
+    /// previous day.
+    /// ```
+    /// # use pltx_utils::DateTime;
+    /// let datetime = DateTime::from("2024-03-01T02:00:00+00:00");
+    /// let previous_evening = DateTime::from("2024-02-29T22:00:00+00:00");
+    /// assert_eq!(datetime.day(4), previous_evening.day(0));
+    /// ```
+    pub fn day(&self, day_start_hour: i32) -> NaiveDate {
+        let shifted = self.datetime - Duration::hours(day_start_hour.clamp(0, 23) as i64);
+        ChronoDateTime::<Local>::from(shifted).date_naive()
+    }
+
+    /// Checks if the datetime is on the current day, for days that start at
+    /// `day_start_hour`.
+    /// ```
+    /// # use pltx_utils::DateTime;
+    /// assert!(DateTime::new().is_today(0));
+    /// assert!(!DateTime::from("2000-01-01T00:00:00+00:00").is_today(4));
+    /// ```
+    pub fn is_today(&self, day_start_hour: i32) -> bool {
+        self.day(day_start_hour) == DateTime::new().day(day_start_hour)
     }
 
-    /// Calculates the duration since a past date.
+    /// Checks if the datetime hasn't passed, and is either less than a number
+    /// of days from now or on the current day. The current day ends at
+    /// `day_start_hour` of the next day, so a datetime after midnight can
+    /// still be due tonight. With 0 days, only the rest of the current day is
+    /// due soon.
     /// ```
     /// # use pltx_utils::DateTime;
+    /// assert!(!DateTime::from("2000-01-01T00:00:00+00:00").is_due_soon(3, 0));
+    /// assert!(!DateTime::from("3000-01-01T00:00:00+00:00").is_due_soon(3, 0));
+    /// ```
+    pub fn is_due_soon(&self, days: i32, day_start_hour: i32) -> bool {
+        !self.is_past() && (self.is_past_days(days) || self.is_today(day_start_hour))
+    }
+
+    /// Advance the datetime by a number of local calendar days, at the same
+    /// local time, so a day across a daylight saving change isn't 24 hours.
+    /// ```
+    /// # use pltx_utils::DateTime;
+    /// std::env::set_var("TZ", "UTC");
+    /// let datetime = DateTime::from("2024-02-28T09:30:00+00:00");
+    /// assert_eq!(datetime.add_days(2).into_db(), "2024-03-01T09:30:00+00:00");
+    /// ```
+    ///
+    /// The clocks in Berlin go forward on 2024-03-31, so the day is 23 hours.
+    /// ```
+    /// # use pltx_utils::DateTime;
+    /// std::env::set_var("TZ", "Europe/Berlin");
+    /// let datetime = DateTime::from(DateTime::from_input(String::from("2024-03-30 09:00")).unwrap());
+    /// assert_eq!(datetime.add_days(1).display(), "2024-03-31 09:00");
+    /// assert_eq!(datetime.add_days(1).into_db(), "2024-03-31T07:00:00+00:00");
+    /// ```
+    pub fn add_days(&self, days: i64) -> Self {
+        let local = self.local();
+        let date = local.date_naive() + Duration::days(days);
+        Self::from_local(date.and_time(local.time()))
+    }
+
+    /// Advance the datetime by a number of local calendar months. When the day
+    /// does not exist in the resulting month, the last day of that month is
+    /// used.
+    /// ```
+    /// # use pltx_utils::DateTime;
+    /// std::env::set_var("TZ", "UTC");
+    /// let datetime = DateTime::from("2024-01-31T09:30:00+00:00");
+    /// assert_eq!(
+    ///     datetime.add_months(1).into_db(),
+    ///     "2024-02-29T09:30:00+00:00"
+    /// );
+    ///
+    /// let datetime = DateTime::from("2023-01-31T09:30:00+00:00");
+    /// assert_eq!(
+    ///     datetime.add_months(1).into_db(),
+    ///     "2023-02-28T09:30:00+00:00"
+    /// );
+    ///
+    /// let datetime = DateTime::from("2023-12-31T09:30:00+00:00");
+    /// assert_eq!(
+    ///     datetime.add_months(1).into_db(),
+    ///     "2024-01-31T09:30:00+00:00"
+    /// );
+    /// ```
+    pub fn add_months(&self, months: u32) -> Self {
+        self.add_months_on_day(months, self.month_day())
+    }
+
+    /// Advance the datetime by a number of local calendar months, to a day of
+    /// the month rather than the day of the datetime. When the day does not
+    /// exist in the resulting month, the last day of that month is used, so a
+    /// date that was moved to the end of a short month can return to its day.
+    /// ```
+    /// # use pltx_utils::DateTime;
+    /// std::env::set_var("TZ", "UTC");
+    /// let mut datetime = DateTime::from("2024-01-31T09:30:00+00:00");
+    /// let day = datetime.month_day();
+    /// let mut dates = vec![];
+    /// for _ in 0..4 {
+    ///     datetime = datetime.add_months_on_day(1, day);
+    ///     dates.push(datetime.into_db());
+    /// }
+    /// assert_eq!(
+    ///     dates,
+    ///     [
+    ///         "2024-02-29T09:30:00+00:00",
+    ///         "2024-03-31T09:30:00+00:00",
+    ///         "2024-04-30T09:30:00+00:00",
+    ///         "2024-05-31T09:30:00+00:00",
+    ///     ]
+    /// );
+    ///
+    /// let datetime = DateTime::from("2024-02-29T09:30:00+00:00");
+    /// assert_eq!(
+    ///     datetime.add_months_on_day(1, 15).into_db(),
+    ///     "2024-03-15T09:30:00+00:00"
+    /// );
+    /// ```
+    ///
+    /// The days are the local days. 5 hours behind UTC, the 31st at 20:00 is
+    /// the 1st in UTC.
+    /// ```
+    /// # use pltx_utils::DateTime;
+    /// std::env::set_var("TZ", "Etc/GMT+5");
+    /// let mut datetime =
+    ///     DateTime::from(DateTime::from_input(String::from("2024-01-31 20:00")).unwrap());
+    /// assert_eq!(datetime.month_day(), 31);
+    /// let mut dates = vec![];
+    /// for _ in 0..3 {
+    ///     datetime = datetime.add_months_on_day(1, 31);
+    ///     dates.push(datetime.display());
+    /// }
+    /// assert_eq!(
+    ///     dates,
+    ///     ["2024-02-29 20:00", "2024-03-31 20:00", "2024-04-30 20:00"]
+    /// );
+    /// ```
+    pub fn add_months_on_day(&self, months: u32, day: u32) -> Self {
+        let local = self.local();
+        let first_day = local
+            .date_naive()
+            .with_day(1)
+            .expect("every month has a first day")
+            .checked_add_months(Months::new(months))
+            .expect("datetime out of range");
+        let date = (1..=day.max(1))
+            .rev()
+            .find_map(|day| first_day.with_day(day))
+            .unwrap_or(first_day);
+        Self::from_local(date.and_time(local.time()))
+    }
+
+    /// The day of the month of the local datetime.
+    pub fn month_day(&self) -> u32 {
+        self.local().day()
+    }
+
+    fn local(&self) -> ChronoDateTime<Local> {
+        self.datetime.with_timezone(&Local)
+    }
+
+    /// The datetime of a local date and time. A time that is skipped when the
+    /// clocks go forward is moved an hour later, past the change, and the
+    /// earlier of a time that happens twice is used.
+    fn from_local(datetime: NaiveDateTime) -> Self {
+        let local = Local
+            .from_local_datetime(&datetime)
+            .earliest()
+            .or_else(|| {
+                Local
+                    .from_local_datetime(&(datetime + Duration::hours(1)))
+                    .earliest()
+            })
+            .expect("datetime out of range");
+        Self::from(local.to_utc())
+    }
+
+    /// Describe the datetime relative to the current datetime, such as "in 3
+    /// days" or "2 weeks ago".
+    pub fn relative(&self) -> String {
+        self.relative_to(&DateTime::new())
+    }
+
+    /// Describe the datetime relative to another datetime. Times within a day
+    /// are described in minutes or hours, and longer times in days, weeks,
+    /// months, or years.
+    /// ```
+    /// # use pltx_utils::DateTime;
+    /// let now = DateTime::from("2024-05-25T12:00:00+00:00");
+    /// let relative = |datetime: &str| DateTime::from(datetime).relative_to(&now);
+    /// assert_eq!(relative("2024-05-25T12:00:30+00:00"), "just now");
+    /// assert_eq!(relative("2024-05-25T12:01:00+00:00"), "in 1 minute");
+    /// assert_eq!(relative("2024-05-25T07:00:00+00:00"), "5 hours ago");
+    /// assert_eq!(relative("2024-05-26T11:59:00+00:00"), "in 23 hours");
+    /// assert_eq!(relative("2024-05-26T12:00:00+00:00"), "tomorrow");
+    /// assert_eq!(relative("2024-05-24T12:00:00+00:00"), "yesterday");
+    /// assert_eq!(relative("2024-05-28T18:00:00+00:00"), "in 3 days");
+    /// assert_eq!(relative("2024-05-11T12:00:00+00:00"), "2 weeks ago");
+    /// assert_eq!(relative("2024-08-25T12:00:00+00:00"), "in 3 months");
+    /// assert_eq!(relative("2022-05-25T12:00:00+00:00"), "2 years ago");
+    /// ```
+    pub fn relative_to(&self, now: &DateTime) -> String {
+        let diff = self.datetime - now.datetime;
+        let future = diff > Duration::zero();
+        let diff = diff.abs();
+
+        let (count, unit) = if diff < Duration::minutes(1) {
+            return String::from("just now");
+        } else if diff < Duration::hours(1) {
+            (diff.num_minutes(), "minute")
+        } else if diff < Duration::days(1) {
+            (diff.num_hours(), "hour")
+        } else if diff < Duration::days(2) {
+            return String::from(if future { "tomorrow" } else { "yesterday" });
+        } else if diff < Duration::weeks(1) {
+            (diff.num_days(), "day")
+        } else if diff < Duration::days(30) {
+            (diff.num_weeks(), "week")
+        } else if diff < Duration::days(365) {
+            (diff.num_days() / 30, "month")
+        } else {
+            (diff.num_days() / 365, "year")
+        };
+
+        let plural = if count == 1 { "" } else { "s" };
+        if future {
+            format!("in {count} {unit}{plural}")
+        } else {
+            format!("{count} {unit}{plural} ago")
+        }
+    }
+
+    /// The weeks of the month that a date is in, from Monday to Sunday. The
+    /// first and last weeks include days of the adjacent months, so a month
+    /// takes four to six weeks.
+    /// ```
+    /// # use chrono::NaiveDate;
+    /// # use pltx_utils::DateTime;
+    /// let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+    ///
+    /// // June 2024 starts on a Saturday and ends on a Sunday.
+    /// let weeks = DateTime::month_weeks(date(2024, 6, 15));
+    /// assert_eq!(weeks.len(), 5);
+    /// assert_eq!(weeks[0][0], date(2024, 5, 27));
+    /// assert_eq!(weeks[0][5], date(2024, 6, 1));
+    /// assert_eq!(weeks[4][6], date(2024, 6, 30));
+    ///
+    /// // June 2025 starts on a Sunday and needs a sixth week for the 30th.
+    /// let weeks = DateTime::month_weeks(date(2025, 6, 1));
+    /// assert_eq!(weeks.len(), 6);
+    /// assert_eq!(weeks[0][6], date(2025, 6, 1));
+    /// assert_eq!(weeks[5][0], date(2025, 6, 30));
+    ///
+    /// // February 2021 starts on a Monday and has 28 days.
+    /// assert_eq!(DateTime::month_weeks(date(2021, 2, 10)).len(), 4);
+    /// ```
+    pub fn month_weeks(date: NaiveDate) -> Vec<[NaiveDate; 7]> {
+        let first_day = date.with_day(1).expect("every month has a first day");
+        let mut week_start =
+            first_day - Duration::days(first_day.weekday().num_days_from_monday() as i64);
+
+        let mut weeks = vec![];
+        while week_start < first_day || week_start.month() == first_day.month() {
+            weeks.push(std::array::from_fn(|i| {
+                week_start + Duration::days(i as i64)
+            }));
+            week_start += Duration::weeks(1);
+        }
+        weeks
+    }
+
+    /// The duration from an earlier datetime to the datetime, which is
+    /// negative when `earlier` is after it. The days are included, so the
+    /// duration can be shown as a clock with
+    /// [`DurationSince`](DurationSince).
+    /// ```
+    /// # use pltx_utils::{DateTime, DurationSince};
     /// let later_datetime = DateTime::from("2024-05-25T23:56:30+00:00");
     /// let past_datetime = DateTime::from("2024-05-25T20:59:54+00:00");
-    /// let duration = later_datetime.duration_since(&past_datetime);
+    /// let duration = DurationSince::from(later_datetime.duration_since(&past_datetime));
     /// assert_eq!(duration.hours, 2);
     /// assert_eq!(duration.minutes, 56);
     /// assert_eq!(duration.seconds, 36);
     /// assert_eq!(duration.to_string(), String::from("02:56:36"));
+    ///
+    /// let next_day = DateTime::from("2024-05-26T01:00:00+00:00");
+    /// let duration = DurationSince::from(next_day.duration_since(&past_datetime));
+    /// assert_eq!(duration.to_string(), String::from("04:00:06"));
     /// ```
-    pub fn duration_since(&self, past: &DateTime) -> DurationSince {
-        let diff = self.datetime.time() - past.datetime.time();
+    pub fn duration_since(&self, earlier: &DateTime) -> Duration {
+        self.datetime - earlier.datetime
+    }
 
-        let hours_overlap = diff.num_days() * 24;
-        let minutes_overlap = (diff.num_days() * 24 * 60) + (diff.num_hours() * 60);
-        let seconds_overlap = (diff.num_days() * 24 * 60 * 60) + (diff.num_minutes() * 60);
+    /// The duration from the datetime to a later datetime, which is negative
+    /// when `later` has already passed.
+    /// ```
+    /// # use chrono::Duration;
+    /// # use pltx_utils::DateTime;
+    /// let now = DateTime::from("2024-05-25T12:00:00+00:00");
+    /// let due = DateTime::from("2024-05-25T18:00:00+00:00");
+    /// assert_eq!(now.duration_until(&due), Duration::hours(6));
+    /// assert_eq!(due.duration_until(&now), Duration::hours(-6));
+    /// ```
+    pub fn duration_until(&self, later: &DateTime) -> Duration {
+        later.datetime - self.datetime
+    }
 
-        DurationSince {
-            hours: diff.num_hours() - hours_overlap,
-            minutes: diff.num_minutes() - minutes_overlap,
-            seconds: diff.num_seconds() - seconds_overlap,
-        }
+    /// Describe a duration with its two largest units, such as "2d 4h" or
+    /// "5m 30s". A unit of 0 is left out, and negative durations are
+    /// described the same as positive ones.
+    /// ```
+    /// # use chrono::Duration;
+    /// # use pltx_utils::DateTime;
+    /// let humanize = DateTime::humanize_duration;
+    /// assert_eq!(humanize(Duration::zero()), "0s");
+    /// assert_eq!(humanize(Duration::seconds(45)), "45s");
+    /// assert_eq!(humanize(Duration::seconds(330)), "5m 30s");
+    /// assert_eq!(humanize(Duration::minutes(12)), "12m");
+    /// assert_eq!(humanize(Duration::minutes(195)), "3h 15m");
+    /// assert_eq!(humanize(Duration::hours(6) + Duration::seconds(59)), "6h");
+    /// assert_eq!(humanize(Duration::hours(52)), "2d 4h");
+    /// assert_eq!(humanize(Duration::days(3)), "3d");
+    /// assert_eq!(humanize(Duration::days(-3)), "3d");
+    /// ```
+    pub fn humanize_duration(duration: Duration) -> String {
+        let duration = duration.abs();
+        let units = [
+            (duration.num_days(), "d"),
+            (duration.num_hours() % 24, "h"),
+            (duration.num_minutes() % 60, "m"),
+            (duration.num_seconds() % 60, "s"),
+        ];
+
+        let largest = units.iter().position(|(count, _)| *count > 0);
+        let Some(largest) = largest else {
+            return String::from("0s");
+        };
+        units[largest..]
+            .iter()
+            .take(2)
+            .filter(|(count, _)| *count > 0)
+            .map(|(count, unit)| format!("{count}{unit}"))
+            .collect::<Vec<String>>()
+            .join(" ")
     }
 }
 
@@ -1,6 +1,16 @@
-use std::{fs, path::PathBuf};
+use std::{fs, path::PathBuf, sync::OnceLock};
 
-/// Get the pltx config directory.
+/// The config directory that is used instead of the one of the platform.
+static CONFIG_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Use another config directory, such as one of a portable install or of a
+/// test. It can only be set once, before the config is read, and false is
+/// returned if it was already set.
+pub fn set_config_dir(dir: PathBuf) -> bool {
+    CONFIG_DIR.set(dir).is_ok()
+}
+
+/// Get the pltx config directory, unless it was set with [`set_config_dir`].
 /// ### Linux
 /// Value: `$XDG_CONFIG_HOME/.config/pltx`
 /// Default: `/home/user/.config/pltx`
@@ -11,9 +21,12 @@ use std::{fs, path::PathBuf};
 /// Value: `{FOLDERID_RoamingAppData}/pltx`
 /// Default: `C:\Users\User\AppData\Roaming\pltx`
 pub fn config_dir() -> PathBuf {
-    let config_dir = dirs::config_dir()
-        .expect("failed to get the config directory")
-        .join("pltx");
+    let config_dir = match CONFIG_DIR.get() {
+        Some(config_dir) => config_dir.to_owned(),
+        None => dirs::config_dir()
+            .expect("failed to get the config directory")
+            .join("pltx"),
+    };
     if !config_dir.exists() {
         fs::create_dir_all(&config_dir).expect("failed to create the config directory")
     }
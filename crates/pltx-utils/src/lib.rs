@@ -8,7 +8,7 @@ pub mod dirs;
 pub mod symbols;
 mod widget;
 
-pub use datetime::DateTime;
+pub use datetime::{DateTime, DurationSince};
 pub use widget::*;
 
 /// Center a rect by all sides within an area
@@ -77,10 +77,10 @@ impl<T> DefaultWidget for Buttons<T> {
                 ))),
             ])
             .style(if focused && i == self.focused_button {
-                Style::new()
+                app.config
+                    .focus_style(colors.active_bg)
                     .bold()
                     .fg(colors.active_fg)
-                    .bg(colors.active_bg)
             } else {
                 Style::new().fg(colors.secondary_fg)
             })
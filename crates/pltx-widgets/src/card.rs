@@ -4,7 +4,7 @@ use pltx_app::{App, DefaultWidget};
 use pltx_utils::{symbols, WidgetMargin};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
-    style::Stylize,
+    style::{Color, Style, Stylize},
     text::{Line, Span},
     widgets::Paragraph,
     Frame,
@@ -21,6 +21,7 @@ pub enum CardBorderType {
 pub struct Card {
     title: String,
     focused_title: bool,
+    title_color: Option<Color>,
     area: Rect,
     margin: WidgetMargin,
     child_margin: WidgetMargin,
@@ -33,6 +34,7 @@ impl Card {
         Self {
             title: title.to_string(),
             focused_title: false,
+            title_color: None,
             area,
             margin: WidgetMargin::default(),
             child_margin: WidgetMargin::default(),
@@ -45,6 +47,12 @@ impl Card {
         self
     }
 
+    /// The color of the title, instead of the foreground color.
+    pub fn title_color(mut self, color: Option<Color>) -> Self {
+        self.title_color = color;
+        self
+    }
+
     pub fn margin(mut self, margin: WidgetMargin) -> Self {
         self.margin = margin;
         self
@@ -91,10 +99,10 @@ impl Card {
 impl DefaultWidget for Card {
     fn render(&self, frame: &mut Frame, app: &App, area: Rect, focused: bool) {
         let colors = &app.config.colors;
-        let border_color = if focused {
-            colors.border_active
+        let border_style = if focused {
+            app.config.active_style(colors.border_active)
         } else {
-            colors.border
+            Style::new().fg(colors.border)
         };
 
         let [title_layout, block_bottom_layout] = Layout::default()
@@ -145,11 +153,13 @@ impl DefaultWidget for Card {
                         )
                     ));
                     if focused && self.focused_title {
-                        title_span = title_span.bold().bg(colors.input_focus_bg);
+                        title_span = title_span
+                            .bold()
+                            .patch_style(app.config.focus_style(colors.input_focus_bg));
                     }
                     title_span
                 }
-                .fg(colors.fg),
+                .fg(self.title_color.unwrap_or(colors.fg)),
                 Span::from(if self.border_type == CardBorderType::Bold {
                     symbols::bold::border::VERTICAL
                 } else {
@@ -177,7 +187,7 @@ impl DefaultWidget for Card {
                 }),
             ]),
         ])
-        .fg(border_color);
+        .style(border_style);
 
         if focused && self.focused_title {
             title_paragraph = title_paragraph.fg(colors.fg);
@@ -216,7 +226,7 @@ impl DefaultWidget for Card {
                 })
                 .collect::<Vec<Line>>(),
         )
-        .fg(border_color);
+        .style(border_style);
 
         let right_border = Paragraph::new(
             (0..center_layout.height as usize)
@@ -233,7 +243,7 @@ impl DefaultWidget for Card {
                 })
                 .collect::<Vec<Line>>(),
         )
-        .fg(border_color);
+        .style(border_style);
 
         frame.render_widget(left_border, left_border_layout);
         frame.render_widget(right_border, right_border_layout);
@@ -262,7 +272,7 @@ impl DefaultWidget for Card {
                 symbols::border::BOTTOM_RIGHT
             }),
         ]))
-        .fg(border_color);
+        .style(border_style);
 
         frame.render_widget(bottom_line, bottom_line_layout);
     }
@@ -0,0 +1,53 @@
+use pltx_config::ColorsConfig;
+use ratatui::style::Color;
+
+/// Colors that can be picked besides the theme colors.
+const PALETTE: [(&str, &str); 10] = [
+    ("Red", "#e06c75"),
+    ("Orange", "#ff9e64"),
+    ("Yellow", "#e0af68"),
+    ("Green", "#9ece6a"),
+    ("Teal", "#73daca"),
+    ("Cyan", "#7dcfff"),
+    ("Blue", "#7aa2f7"),
+    ("Purple", "#bb9af7"),
+    ("Pink", "#ff79c6"),
+    ("Gray", "#737aa2"),
+];
+
+/// The colors of the palette, starting with the theme colors, as hex colors
+/// with their names. The options are meant for a
+/// [`ListPicker`](crate::ListPicker).
+///
+/// ```
+/// # use pltx_config::{base_config, Config};
+/// # use pltx_widgets::color_palette;
+/// let colors = Config::from(base_config()).colors;
+/// let palette = color_palette(&colors);
+/// assert!(palette
+///     .iter()
+///     .any(|(hex, title)| hex == "#e06c75" && title.starts_with("Red")));
+/// assert!(palette[0].1.starts_with("Primary"));
+/// ```
+pub fn color_palette(colors: &ColorsConfig) -> Vec<(String, String)> {
+    let theme = [
+        ("Primary", colors.primary),
+        ("Success", colors.success),
+        ("Warning", colors.warning),
+        ("Danger", colors.danger),
+    ];
+    // Only colors set with a hex code can be stored, which all theme colors are
+    // unless they're named colors.
+    let theme = theme.into_iter().filter_map(|(name, color)| match color {
+        Color::Rgb(r, g, b) => Some((name, format!("#{:02x}{:02x}{:02x}", r, g, b))),
+        _ => None,
+    });
+    let palette = PALETTE
+        .into_iter()
+        .map(|(name, hex)| (name, hex.to_string()));
+
+    theme
+        .chain(palette)
+        .map(|(name, hex)| (hex.clone(), format!("{:<8} {}", name, hex)))
+        .collect()
+}
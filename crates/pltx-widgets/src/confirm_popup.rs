@@ -0,0 +1,148 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use pltx_app::{App, Popup};
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Paragraph, Wrap},
+    Frame,
+};
+
+use crate::{PopupSize, PopupWidget};
+
+const MIN_WIDTH: u16 = 40;
+const MAX_WIDTH: u16 = 70;
+
+/// Confirmation popup for destructive actions. It is opened with a message
+/// describing what will happen and the action to confirm, which is handed back
+/// to the parent once the user presses `y`. Pressing `n` or escape cancels it.
+///
+/// ```
+/// # use crossterm::event::{KeyCode, KeyEvent};
+/// # use pltx_app::Popup;
+/// # use pltx_widgets::ConfirmPopup;
+/// let mut confirm = ConfirmPopup::init();
+/// assert!(!confirm.is_open());
+///
+/// confirm.open("Delete card 'Write docs'?", 3);
+/// assert!(confirm.is_open());
+/// assert_eq!(confirm.handle_key(KeyEvent::from(KeyCode::Char('j'))), None);
+/// assert!(confirm.is_open());
+/// assert_eq!(
+///     confirm.handle_key(KeyEvent::from(KeyCode::Char('y'))),
+///     Some(3)
+/// );
+/// assert!(!confirm.is_open());
+///
+/// confirm.open("Delete card 'Write docs'?", 3);
+/// assert_eq!(confirm.handle_key(KeyEvent::from(KeyCode::Esc)), None);
+/// assert!(!confirm.is_open());
+/// ```
+pub struct ConfirmPopup<T> {
+    message: String,
+    action: Option<T>,
+}
+
+impl<T> ConfirmPopup<T> {
+    /// Open the popup for an action.
+    pub fn open(&mut self, message: impl Into<String>, action: T) {
+        self.message = message.into();
+        self.action = Some(action);
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.action.is_some()
+    }
+
+    /// Close the popup without confirming the action.
+    pub fn close(&mut self) {
+        self.message.clear();
+        self.action = None;
+    }
+
+    /// The same as the key event handler, which doesn't require the app.
+    /// Returns the action if it was confirmed.
+    pub fn handle_key(&mut self, key_event: KeyEvent) -> Option<T> {
+        match key_event.code {
+            KeyCode::Char('y') => {
+                self.message.clear();
+                self.action.take()
+            }
+            KeyCode::Char('n') | KeyCode::Esc => {
+                self.close();
+                None
+            }
+            _ => None,
+        }
+    }
+}
+
+impl<T> Popup<Option<T>> for ConfirmPopup<T> {
+    fn init() -> Self {
+        Self {
+            message: String::new(),
+            action: None,
+        }
+    }
+
+    fn key_event_handler(&mut self, _: &mut App, key_event: KeyEvent) -> Option<T> {
+        self.handle_key(key_event)
+    }
+
+    fn render(&self, app: &App, frame: &mut Frame, area: Rect) {
+        if !self.is_open() {
+            return;
+        }
+
+        let colors = &app.config.colors;
+
+        let border_width = 2;
+        let padding = 2;
+        let width = (self.message.chars().count() as u16 + border_width + padding * 2)
+            .clamp(MIN_WIDTH, MAX_WIDTH);
+        let message_lines = (self.message.chars().count() as u16)
+            .div_ceil(width - border_width - padding * 2)
+            .max(1);
+
+        let popup = PopupWidget::new(app, area)
+            .title_top("Confirm")
+            .size(
+                PopupSize::default()
+                    .width(width)
+                    .height(message_lines + border_width + 3),
+            )
+            .render(frame);
+
+        let message_area = Rect {
+            x: popup.sub_area.x + padding,
+            y: popup.sub_area.y + 1,
+            width: popup.sub_area.width.saturating_sub(padding * 2),
+            height: message_lines,
+        };
+        frame.render_widget(
+            Paragraph::new(self.message.as_str())
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true })
+                .bold()
+                .fg(colors.fg),
+            message_area,
+        );
+
+        let keybind = |key: &'static str| Span::styled(key, Style::new().fg(colors.keybind_key));
+        let hint = Paragraph::new(Line::from(vec![
+            keybind("y"),
+            Span::from(" yes  ").fg(colors.keybind_fg),
+            keybind("n"),
+            Span::from("/").fg(colors.keybind_fg),
+            keybind("esc"),
+            Span::from(" no").fg(colors.keybind_fg),
+        ]))
+        .alignment(Alignment::Center);
+        let hint_area = Rect {
+            y: message_area.y + message_lines + 1,
+            height: 1,
+            ..popup.sub_area
+        };
+        frame.render_widget(hint, hint_area);
+    }
+}
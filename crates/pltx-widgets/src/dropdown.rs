@@ -0,0 +1,274 @@
+use std::{cell::RefCell, cmp, rc::Rc};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use pltx_app::{App, CompositeWidget, DefaultWidget, KeyEventHandler};
+use ratatui::{
+    layout::Rect,
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::{FormInputState, FormWidget};
+
+type DropdownOptions<T> = Vec<(T, String)>;
+
+/// Single-choice dropdown widget. The chosen option is rendered inline, and
+/// pressing enter opens an overlay list of all options to choose from.
+///
+/// Within a [`Form`](crate::Form), the overlay opens as soon as the input is
+/// focused, and choosing an option takes the user back to the form.
+///
+/// ```
+/// # use crossterm::event::{KeyCode, KeyEvent};
+/// # use pltx_widgets::Dropdown;
+/// let mut dropdown = Dropdown::new(
+///     "Recurrence",
+///     vec![
+///         ("none", String::from("None")),
+///         ("daily", String::from("Daily")),
+///         ("weekly", String::from("Weekly")),
+///     ],
+/// );
+/// assert_eq!(dropdown.value(), Some(&"none"));
+///
+/// dropdown.handle_key(KeyEvent::from(KeyCode::Enter));
+/// assert!(dropdown.is_open());
+/// dropdown.handle_key(KeyEvent::from(KeyCode::Char('j')));
+/// dropdown.handle_key(KeyEvent::from(KeyCode::Char('j')));
+/// dropdown.handle_key(KeyEvent::from(KeyCode::Char('k')));
+/// // The value only changes once an option is chosen.
+/// assert_eq!(dropdown.value(), Some(&"none"));
+/// dropdown.handle_key(KeyEvent::from(KeyCode::Enter));
+/// assert!(!dropdown.is_open());
+/// assert_eq!(dropdown.value(), Some(&"daily"));
+///
+/// // Closing the overlay with escape keeps the chosen option.
+/// dropdown.handle_key(KeyEvent::from(KeyCode::Enter));
+/// dropdown.handle_key(KeyEvent::from(KeyCode::Char('G')));
+/// dropdown.handle_key(KeyEvent::from(KeyCode::Esc));
+/// assert_eq!(dropdown.value(), Some(&"daily"));
+///
+/// // A dropdown without options has no value.
+/// let empty = Dropdown::<&str>::new("Recurrence", vec![]);
+/// assert_eq!(empty.value(), None);
+/// ```
+pub struct Dropdown<T> {
+    pub options: DropdownOptions<T>,
+    pub focused_option: usize,
+    pub chosen: usize,
+    title: String,
+    open: bool,
+}
+
+impl<T> Dropdown<T> {
+    pub fn new(title: &str, options: DropdownOptions<T>) -> Self {
+        Self {
+            options,
+            focused_option: 0,
+            chosen: 0,
+            title: title.into(),
+            open: false,
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Open the overlay with the chosen option focused.
+    pub fn open(&mut self) {
+        self.focused_option = self.chosen;
+        self.open = true;
+    }
+
+    /// Close the overlay without choosing the focused option.
+    pub fn close(&mut self) {
+        self.open = false;
+    }
+
+    /// Choose the focused option and close the overlay.
+    pub fn choose(&mut self) {
+        self.chosen = self.focused_option;
+        self.open = false;
+    }
+
+    /// The value of the chosen option, or `None` if there are no options.
+    pub fn value(&self) -> Option<&T> {
+        self.options.get(self.chosen).map(|o| &o.0)
+    }
+
+    /// The same as the key event handler, which doesn't require the app.
+    pub fn handle_key(&mut self, key_event: KeyEvent) {
+        if self.open {
+            match key_event.code {
+                KeyCode::Char('j') => self.focus_next(),
+                KeyCode::Char('k') => self.focus_prev(),
+                KeyCode::Char('g') => self.focus_first(),
+                KeyCode::Char('G') => self.focus_last(),
+                KeyCode::Char(' ') | KeyCode::Enter => self.choose(),
+                KeyCode::Char('[') | KeyCode::Esc => self.close(),
+                _ => {}
+            }
+        } else if matches!(key_event.code, KeyCode::Char(' ') | KeyCode::Enter) {
+            self.open();
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.focused_option = 0;
+        self.chosen = 0;
+        self.open = false;
+    }
+}
+
+impl<T: PartialEq> Dropdown<T> {
+    /// Choose the option with the given value, if it exists.
+    pub fn set_value(&mut self, value: &T) {
+        if let Some(index) = self.options.iter().position(|o| &o.0 == value) {
+            self.chosen = index;
+            self.focused_option = index;
+        }
+    }
+}
+
+impl<T> KeyEventHandler for Dropdown<T> {
+    fn key_event_handler(&mut self, _: &mut App, key_event: KeyEvent) {
+        self.handle_key(key_event);
+    }
+}
+
+impl<T> FormWidget for Dropdown<T> {
+    fn form(self) -> Rc<RefCell<Self>>
+    where
+        Self: Sized,
+    {
+        Rc::new(RefCell::new(self))
+    }
+
+    fn state(&self) -> FormInputState {
+        let inline_height = 1;
+        let border_height = 2;
+        FormInputState {
+            title: self.title.clone(),
+            height: inline_height + border_height + self.options.len() as u16,
+            uses_insert_mode: false,
+            uses_interactive_mode: true,
+            hidden: self.options.is_empty(),
+            enter_back: true,
+            multiline: false,
+        }
+    }
+
+    fn focus(&mut self) {
+        self.open();
+    }
+
+    fn has_input(&self) -> bool {
+        self.chosen != 0
+    }
+
+    fn reset(&mut self) {
+        self.reset();
+    }
+}
+
+impl<T> CompositeWidget for Dropdown<T> {
+    fn focus_next(&mut self) {
+        if !self.is_focus_last() {
+            self.focused_option += 1;
+        }
+    }
+
+    fn focus_prev(&mut self) {
+        if !self.is_focus_first() {
+            self.focused_option -= 1;
+        }
+    }
+
+    fn is_focus_first(&self) -> bool {
+        self.focused_option == 0
+    }
+
+    fn is_focus_last(&self) -> bool {
+        self.focused_option == self.options.len().saturating_sub(1)
+    }
+
+    fn focus_first(&mut self) {
+        self.focused_option = 0;
+    }
+
+    fn focus_last(&mut self) {
+        self.focused_option = self.options.len().saturating_sub(1);
+    }
+}
+
+impl<T> DefaultWidget for Dropdown<T> {
+    fn render(&self, frame: &mut Frame, app: &App, area: Rect, focused: bool) {
+        let colors = &app.config.colors;
+
+        let chosen = self
+            .options
+            .get(self.chosen)
+            .map(|o| o.1.clone())
+            .unwrap_or_default();
+        let inline_style = if focused && !self.open {
+            app.config
+                .focus_style(colors.active_bg)
+                .bold()
+                .fg(colors.active_fg)
+        } else {
+            Style::new().fg(colors.fg)
+        };
+        let inline = Paragraph::new(Line::from(vec![
+            Span::from(format!("{}: ", self.title)),
+            Span::from(format!(" {} ▾ ", chosen)).style(inline_style),
+        ]));
+        frame.render_widget(inline, Rect { height: 1, ..area });
+
+        if !self.open {
+            return;
+        }
+
+        let border_height = 2;
+        let overlay_area = Rect {
+            y: area.y + 1,
+            height: cmp::min(
+                self.options.len() as u16 + border_height,
+                area.height.saturating_sub(1),
+            ),
+            ..area
+        };
+
+        let options = self
+            .options
+            .iter()
+            .enumerate()
+            .map(|(i, option)| {
+                let style = if self.focused_option == i {
+                    app.config
+                        .focus_style(colors.active_bg)
+                        .bold()
+                        .fg(colors.active_fg)
+                } else if self.chosen == i {
+                    Style::new().fg(colors.fg)
+                } else {
+                    Style::new().fg(colors.secondary_fg)
+                };
+                Line::from(format!(" {} ", option.1)).style(style)
+            })
+            .collect::<Vec<Line>>();
+
+        let overlay = Paragraph::new(options).block(
+            Block::new()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::new().fg(colors.popup_border))
+                .bg(colors.popup_bg),
+        );
+
+        frame.render_widget(Clear, overlay_area);
+        frame.render_widget(overlay, overlay_area);
+    }
+}
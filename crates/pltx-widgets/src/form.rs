@@ -18,11 +18,17 @@ pub struct FormInputState {
     pub title: String,
     pub height: u16,
     pub uses_insert_mode: bool,
+    /// Whether the input is navigated rather than typed in, such as a list of
+    /// options. The form enters interactive mode while the input is focused.
+    pub uses_interactive_mode: bool,
     pub hidden: bool,
     /// Determines whether pressing enter or ] should take the user
     /// back to the input selection. If the input is composite and has multiple
     /// screens, then this should be conditionally disabled.
     pub enter_back: bool,
+    /// Whether enter inserts a newline in insert mode instead of going back to
+    /// the input selection.
+    pub multiline: bool,
 }
 
 pub trait FormWidget: KeyEventHandler + DefaultWidget {
@@ -30,6 +36,17 @@ pub trait FormWidget: KeyEventHandler + DefaultWidget {
     where
         Self: Sized;
     fn state(&self) -> FormInputState;
+    /// Called when the form moves from the input selection to the input.
+    fn focus(&mut self) {}
+    /// Whether the input can be submit. The form can't be submit while any of
+    /// its inputs are invalid.
+    fn is_valid(&self) -> bool {
+        true
+    }
+    /// Whether the input has a value that would be lost by closing the form.
+    fn has_input(&self) -> bool {
+        false
+    }
     fn reset(&mut self);
 }
 
@@ -61,6 +78,9 @@ pub struct Form {
     default_size: PopupSize,
     size: PopupSize,
     show_close_prompt: bool,
+    /// Whether the form is editing an existing item, rather than creating one.
+    editing: bool,
+    confirm_discard_new: bool,
 }
 
 impl From<Vec<FormInput>> for Form {
@@ -80,6 +100,8 @@ impl From<Vec<FormInput>> for Form {
             default_size,
             size: default_size,
             show_close_prompt: false,
+            editing: false,
+            confirm_discard_new: true,
         }
     }
 }
@@ -105,6 +127,8 @@ where
             default_size,
             size: default_size,
             show_close_prompt: false,
+            editing: false,
+            confirm_discard_new: true,
         }
     }
 }
@@ -133,6 +157,25 @@ impl Form {
         self.title = Some(title.to_owned());
     }
 
+    /// Mark the form as editing an existing item. Closing it always asks for
+    /// confirmation, until the form is reset.
+    pub fn editing(&mut self) {
+        self.editing = true;
+    }
+
+    /// Whether closing a form for a new item asks for confirmation when any of
+    /// its inputs have a value.
+    pub fn confirm_discard_new(&mut self, confirm: bool) {
+        self.confirm_discard_new = confirm;
+    }
+
+    /// The index of the input that is open, among the inputs that aren't
+    /// hidden, or none while the inputs are being selected.
+    pub fn open_input(&self) -> Option<usize> {
+        (self.view == EditorView::Input && !self.show_close_prompt)
+            .then_some(self.selection.focused)
+    }
+
     pub fn reset(&mut self) {
         for input in self.inputs.iter_mut() {
             (*input).borrow_mut().reset();
@@ -142,6 +185,7 @@ impl Form {
         self.title.clone_from(&self.default_title);
         self.size = self.default_size;
         self.show_close_prompt = false;
+        self.editing = false;
     }
 }
 
@@ -194,7 +238,16 @@ impl KeyEventHandler<FormState> for Form {
                     }
                     _ => {}
                 }
-            } else if key_event.code == KeyCode::Char('q') {
+            } else if key_event.code == KeyCode::Char('q')
+                || (key_event.code == KeyCode::Char('[') && self.view == EditorView::Selection)
+            {
+                let confirm = self.editing || (self.confirm_discard_new && self.has_input());
+                if !confirm {
+                    self.reset();
+                    app.view.default();
+                    return FormState::Closed;
+                }
+
                 self.show_close_prompt = true;
                 self.title = None;
                 self.size = PopupSize::default().width(35).height(5);
@@ -214,14 +267,22 @@ impl KeyEventHandler<FormState> for Form {
                         .width(self.default_size.width)
                         .height(height);
                     self.view = EditorView::Input;
+                    self.current_input().focus();
                     if self.current_input_state().uses_insert_mode {
                         app.mode.insert();
+                    } else if self.current_input_state().uses_interactive_mode {
+                        app.mode.interactive();
                     }
                 }
                 KeyCode::Char('s') => {
-                    if self.view == EditorView::Selection {
+                    if self.is_valid() {
                         return FormState::Submit;
                     }
+                    // The invalid input is focused, so its error is shown.
+                    if let Some((index, title)) = self.first_invalid_input() {
+                        self.selection.focused = index;
+                        app.notify_warning(format!("Can't save until '{}' is valid", title));
+                    }
                 }
                 _ => {}
             }
@@ -229,12 +290,15 @@ impl KeyEventHandler<FormState> for Form {
             if self.current_input_state().enter_back {
                 match key_event.code {
                     KeyCode::Char('[') => {
-                        if app.mode.is_normal() {
+                        if app.mode.is_normal() || app.mode.is_interactive() {
                             self.view = EditorView::Selection;
+                            app.mode.normal();
                             self.size = self.default_size;
                         }
                     }
-                    KeyCode::Enter => {
+                    KeyCode::Enter
+                        if !(self.current_input_state().multiline && app.mode.is_insert()) =>
+                    {
                         self.view = EditorView::Selection;
                         app.mode.normal();
                         self.size = self.default_size;
@@ -281,7 +345,7 @@ impl DefaultWidget for Form {
                 .map(|(i, input)| {
                     if self.selection.focused == i {
                         Paragraph::new(format!(" {} ", (*input).borrow().state().title))
-                            .style(Style::new().bg(colors.input_focus_bg))
+                            .style(app.config.focus_style(colors.input_focus_bg))
                     } else {
                         Paragraph::new(format!(" {} ", (*input).borrow().state().title))
                             .style(Style::new().fg(colors.secondary_fg))
@@ -312,6 +376,28 @@ impl Form {
         (*widget).borrow_mut()
     }
 
+    /// Whether any input of the form has a value.
+    pub fn has_input(&self) -> bool {
+        self.inputs.iter().any(|i| (*i).borrow().has_input())
+    }
+
+    /// Whether all inputs of the form are valid.
+    pub fn is_valid(&self) -> bool {
+        self.inputs.iter().all(|i| (*i).borrow().is_valid())
+    }
+
+    /// The index in the input selection and the title of the first input that
+    /// is invalid.
+    fn first_invalid_input(&self) -> Option<(usize, String)> {
+        self.inputs
+            .iter()
+            .map(|i| (*i).borrow())
+            .filter(|i| !i.state().hidden)
+            .enumerate()
+            .find(|(_, i)| !i.is_valid())
+            .map(|(index, i)| (index, i.state().title))
+    }
+
     fn current_input_state(&self) -> FormInputState {
         let widget = self
             .inputs
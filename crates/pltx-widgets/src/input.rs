@@ -1,3 +1,4 @@
+pub mod clipboard;
 mod text_input;
 
 pub use text_input::*;
@@ -0,0 +1,30 @@
+//! The system clipboard, which is only available with the `clipboard` feature.
+//! Without it, nothing is copied and there is nothing to paste.
+
+/// Whether the app was built with the clipboard.
+pub fn is_available() -> bool {
+    cfg!(feature = "clipboard")
+}
+
+#[cfg(feature = "clipboard")]
+pub fn get_text() -> Option<String> {
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.get_text())
+        .map_err(|e| tracing::warn!("failed to read the clipboard: {e}"))
+        .ok()
+}
+
+#[cfg(feature = "clipboard")]
+pub fn set_text(text: String) {
+    if let Err(e) = arboard::Clipboard::new().and_then(|mut clipboard| clipboard.set_text(text)) {
+        tracing::warn!("failed to write to the clipboard: {e}");
+    }
+}
+
+#[cfg(not(feature = "clipboard"))]
+pub fn get_text() -> Option<String> {
+    None
+}
+
+#[cfg(not(feature = "clipboard"))]
+pub fn set_text(_: String) {}
@@ -3,7 +3,7 @@ use std::{
     rc::Rc,
 };
 
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use pltx_app::{state::View, App, DefaultWidget, FormWidgetOld, KeyEventHandler};
 use pltx_utils::{symbols, DateTime};
 use ratatui::{
@@ -14,10 +14,18 @@ use ratatui::{
     Frame,
 };
 
+use super::clipboard;
 use crate::{FormInputState, FormWidget};
 
 const WORD_SEPARATORS: [char; 1] = [' '];
 
+type Validator = Rc<dyn Fn(&str) -> Result<(), String>>;
+
+/// Whether a character is part of a word for the word motions in insert mode.
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
 // pub enum TextInputEvent {
 //     OnChange,
 //     None,
@@ -106,6 +114,10 @@ pub struct TextInput {
     prompt_lines: u16,
     keys: KeyManager,
     height: Cell<u16>,
+    validator: Option<Validator>,
+    numeric: bool,
+    bounds: Option<(i32, i32)>,
+    multiline: bool,
 }
 
 impl DefaultWidget for TextInput {
@@ -149,6 +161,20 @@ impl DefaultWidget for TextInput {
                     input_layout
                 },
             );
+
+            if let (true, Err(error)) = (focused, self.validate()) {
+                let error_area = Rect {
+                    y: input_layout.y + input_layout.height,
+                    height: 1,
+                    ..input_layout
+                };
+                if error_area.y < area.y + area.height {
+                    frame.render_widget(
+                        Paragraph::new(error).fg(app.config.colors.danger),
+                        error_area,
+                    );
+                }
+            }
         } else {
             let widget = self.render_block(app, area, focused);
             frame.render_widget(widget, area);
@@ -184,27 +210,46 @@ impl KeyEventHandler for TextInput {
     fn key_event_handler(&mut self, app: &mut App, key_event: KeyEvent) {
         // let mut event = TextInputEvent::None;
 
-        if app.view == self.view && app.mode.is_insert() {
+        if app.view == self.view
+            && (app.mode.is_insert() || app.mode.is_normal())
+            && key_event.modifiers == KeyModifiers::CONTROL
+        {
             match key_event.code {
-                KeyCode::Char(to_insert) => {
-                    self.enter_char(to_insert);
-                    // event = TextInputEvent::OnChange;
+                KeyCode::Char('v') => {
+                    if let Some(text) = clipboard::get_text() {
+                        if self.paste(&text) {
+                            app.notify_warning(format!(
+                                "The pasted text was cut off at {} characters",
+                                self.max.unwrap_or_default()
+                            ));
+                        }
+                    }
                 }
-                KeyCode::Backspace => {
-                    self.delete_char();
-                    // event = TextInputEvent::OnChange;
+                KeyCode::Char('c') => clipboard::set_text(self.current_line().to_string()),
+                KeyCode::Char('x') => {
+                    clipboard::set_text(self.current_line().to_string());
+                    self.input[self.cursor_position.y].clear();
+                    self.cursor_position.x = 0;
                 }
-                KeyCode::Left => self.move_cursor_left(),
-                KeyCode::Right => self.move_cursor_right(),
-                KeyCode::Esc => app.mode.normal(),
+                _ if app.mode.is_insert() => self.insert_key(key_event),
                 _ => {}
             }
+            return;
+        }
+
+        if app.view == self.view && app.mode.is_insert() {
+            match key_event.code {
+                KeyCode::Esc => app.mode.normal(),
+                _ => self.insert_key(key_event),
+            }
         }
 
         if app.view == self.view && app.mode.is_normal() {
             match key_event.code {
                 KeyCode::Char('h') | KeyCode::Left => self.move_cursor_left(),
                 KeyCode::Char('l') | KeyCode::Right => self.move_cursor_right(),
+                KeyCode::Char('k') | KeyCode::Up => self.move_cursor_up(),
+                KeyCode::Char('j') | KeyCode::Down => self.move_cursor_down(),
                 KeyCode::Char('w') => self.cursor_next_word(),
                 KeyCode::Char('b') => self.cursor_prev_word(),
                 KeyCode::Char('0') => self.cursor_start_line(),
@@ -246,16 +291,31 @@ impl FormWidget for TextInput {
         FormInputState {
             title: self.title.clone(),
             height: if self.style == InputStyle::Prompt {
-                self.prompt_lines + 1
+                let error_height = if self.validator.is_some() || self.bounds.is_some() {
+                    1
+                } else {
+                    0
+                };
+                self.prompt_lines + 1 + error_height
             } else {
                 self.height.get()
             },
             uses_insert_mode: true,
+            uses_interactive_mode: false,
             hidden: false,
             enter_back: true,
+            multiline: self.multiline,
         }
     }
 
+    fn is_valid(&self) -> bool {
+        self.is_valid()
+    }
+
+    fn has_input(&self) -> bool {
+        !self.is_empty()
+    }
+
     fn reset(&mut self) {
         self.reset();
     }
@@ -282,18 +342,22 @@ impl TextInput {
             prompt_lines: 1,
             keys: KeyManager::default(),
             height: Cell::new(0),
+            validator: None,
+            numeric: false,
+            bounds: None,
+            multiline: false,
         }
     }
 
     pub fn default_input(mut self, input: String) -> Self {
-        self.input = input.split('\n').map(|s| s.to_string()).collect();
-        self.cursor_end_line();
+        self.input(input);
         self
     }
 
     /// Set the input
     pub fn input(&mut self, input: String) {
         self.input = input.split('\n').map(|s| s.to_string()).collect();
+        self.cursor_position.y = self.input.len() - 1;
         self.cursor_end_line();
     }
 
@@ -315,12 +379,33 @@ impl TextInput {
         self.cursor_position.reset();
     }
 
+    /// An input for a datetime in the format "%Y-%m-%d %H:%M", or one of the
+    /// shorthands of [`DateTime::from_input_or_shorthand`]. It can be empty.
+    /// ```
+    /// # use pltx_widgets::TextInput;
+    /// let mut input = TextInput::new("Due Date").datetime_input();
+    /// assert!(input.is_valid());
+    /// for valid in ["2024-06-01 09:00", "today", "tomorrow", "+3d", "+1w"] {
+    ///     input.input(String::from(valid));
+    ///     assert!(input.is_valid());
+    /// }
+    /// input.input(String::from("+3x"));
+    /// assert!(!input.is_valid());
+    /// ```
     pub fn datetime_input(mut self) -> Self {
         self.input_type = TextInputType::Date;
         self.placeholder = Some(DateTime::display_now());
         self.min = Some(16);
         self.max = Some(16);
-        self
+        self.validator(|value| {
+            if value.is_empty() || DateTime::from_input_or_shorthand(value, "00:00").is_some() {
+                Ok(())
+            } else {
+                Err(String::from(
+                    "Enter YYYY-MM-DD HH:MM, today, tomorrow, +3d, or +1w",
+                ))
+            }
+        })
     }
 
     pub fn placeholder(mut self, placeholder: &str) -> Self {
@@ -356,6 +441,115 @@ impl TextInput {
         self
     }
 
+    /// Validate the input with a function that returns an error message when
+    /// the input is invalid. The error is shown below focused prompt inputs.
+    /// ```
+    /// # use pltx_widgets::TextInput;
+    /// let mut input = TextInput::new("Number").validator(|value| {
+    ///     value
+    ///         .parse::<i32>()
+    ///         .map(|_| ())
+    ///         .map_err(|_| String::from("Expected a number"))
+    /// });
+    /// input.input(String::from("abc"));
+    /// assert!(!input.is_valid());
+    /// assert_eq!(input.validate(), Err(String::from("Expected a number")));
+    /// input.input(String::from("123"));
+    /// assert!(input.is_valid());
+    /// ```
+    pub fn validator<F>(mut self, validator: F) -> Self
+    where
+        F: Fn(&str) -> Result<(), String> + 'static,
+    {
+        self.validator = Some(Rc::new(validator));
+        self
+    }
+
+    /// Only accept digits. Other characters are ignored as they're typed.
+    /// ```
+    /// # use crossterm::event::{KeyCode, KeyEvent};
+    /// # use pltx_widgets::TextInput;
+    /// let mut input = TextInput::new("Limit").numeric();
+    /// for char in ['1', 'a', '2', '-', ' ', '3'] {
+    ///     input.insert_key(KeyEvent::from(KeyCode::Char(char)));
+    /// }
+    /// assert_eq!(input.input_string(), "123");
+    /// input.insert_key(KeyEvent::from(KeyCode::Backspace));
+    /// assert_eq!(input.input_i32(), Some(12));
+    /// ```
+    pub fn numeric(mut self) -> Self {
+        self.numeric = true;
+        self
+    }
+
+    /// The lowest and highest value of a numeric input. Digits that would go
+    /// over the highest value are ignored, and values under the lowest value
+    /// are invalid.
+    /// ```
+    /// # use crossterm::event::{KeyCode, KeyEvent};
+    /// # use pltx_widgets::TextInput;
+    /// let mut input = TextInput::new("Hour").numeric().bounds(1, 23);
+    /// for char in ['2', '4'] {
+    ///     input.insert_key(KeyEvent::from(KeyCode::Char(char)));
+    /// }
+    /// assert_eq!(input.input_i32(), Some(2));
+    /// input.insert_key(KeyEvent::from(KeyCode::Backspace));
+    /// input.insert_key(KeyEvent::from(KeyCode::Char('0')));
+    /// assert!(!input.is_valid());
+    /// ```
+    pub fn bounds(mut self, min: i32, max: i32) -> Self {
+        self.bounds = Some((min, max));
+        self
+    }
+
+    /// Allow more than one line. Enter inserts a newline in insert mode, and
+    /// the maximum length applies to the whole input instead of each line.
+    /// ```
+    /// # use crossterm::event::{KeyCode, KeyEvent};
+    /// # use pltx_widgets::TextInput;
+    /// let mut input = TextInput::new("Description").multiline();
+    /// input.paste("First line");
+    /// input.insert_key(KeyEvent::from(KeyCode::Enter));
+    /// input.paste("Second line");
+    /// assert_eq!(input.input_string(), "First line\nSecond line");
+    ///
+    /// input.insert_key(KeyEvent::from(KeyCode::Up));
+    /// input.insert_key(KeyEvent::from(KeyCode::Char('!')));
+    /// assert_eq!(input.input_string(), "First line!\nSecond line");
+    ///
+    /// input.input(String::from("a\nb"));
+    /// input.insert_key(KeyEvent::from(KeyCode::Left));
+    /// input.insert_key(KeyEvent::from(KeyCode::Backspace));
+    /// assert_eq!(input.input_string(), "ab");
+    /// ```
+    pub fn multiline(mut self) -> Self {
+        self.multiline = true;
+        self
+    }
+
+    /// The input parsed as a number, or none if it's empty or not a number.
+    pub fn input_i32(&self) -> Option<i32> {
+        self.input_string().parse().ok()
+    }
+
+    /// Run the validator on the current input. Inputs without a validator are
+    /// always valid.
+    pub fn validate(&self) -> Result<(), String> {
+        if let (Some((min, max)), Some(value)) = (self.bounds, self.input_i32()) {
+            if value < min || value > max {
+                return Err(format!("Enter a number from {} to {}", min, max));
+            }
+        }
+        match &self.validator {
+            Some(validator) => validator(&self.input_string()),
+            None => Ok(()),
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.validate().is_ok()
+    }
+
     pub fn view(mut self, view: View) -> Self {
         self.view = view;
         self
@@ -413,18 +607,172 @@ impl TextInput {
         self.keys.clear();
     }
 
+    /// Insert text at the cursor. Newlines are replaced with spaces unless the
+    /// input is multiline, and text past the maximum length is dropped.
+    /// Returns whether any text was dropped.
+    /// ```
+    /// # use pltx_widgets::TextInput;
+    /// let mut input = TextInput::new("Title").max(12);
+    /// assert!(!input.paste("Write\ndocs"));
+    /// assert_eq!(input.input_string(), "Write docs");
+    /// assert!(input.paste(" today"));
+    /// assert_eq!(input.input_string(), "Write docs t");
+    /// ```
+    pub fn paste(&mut self, text: &str) -> bool {
+        let text = text.replace("\r\n", "\n").replace('\r', "\n");
+        for char in text.chars() {
+            if self.max.is_some_and(|max| self.len() >= max) {
+                return true;
+            }
+            match char {
+                '\n' if self.multiline => self.insert_newline(),
+                '\n' => self.enter_char(' '),
+                _ => self.enter_char(char),
+            }
+        }
+        false
+    }
+
+    fn current_line(&self) -> &str {
+        &self.input[self.cursor_position.y]
+    }
+
+    /// The length that is limited by the maximum, which is the whole input
+    /// when it's multiline.
+    fn len(&self) -> usize {
+        if self.multiline {
+            self.input_string().chars().count()
+        } else {
+            self.current_line().chars().count()
+        }
+    }
+
+    /// The same as the key event handler in insert mode, which doesn't require
+    /// the app. Words are split on whitespace and punctuation.
+    /// ```
+    /// # use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    /// # use pltx_widgets::TextInput;
+    /// let ctrl = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL);
+    /// let alt = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::ALT);
+    ///
+    /// let mut input = TextInput::new("Title");
+    /// input.input(String::from("fix the-parser now"));
+    /// input.insert_key(ctrl('w'));
+    /// assert_eq!(input.input_string(), "fix the-parser ");
+    /// input.insert_key(ctrl('w'));
+    /// assert_eq!(input.input_string(), "fix the-");
+    ///
+    /// input.input(String::from("fix the-parser now"));
+    /// input.insert_key(alt('b'));
+    /// input.insert_key(alt('b'));
+    /// input.insert_key(KeyEvent::from(KeyCode::Char('_')));
+    /// assert_eq!(input.input_string(), "fix the-_parser now");
+    /// input.insert_key(KeyEvent::new(KeyCode::Right, KeyModifiers::CONTROL));
+    /// input.insert_key(KeyEvent::from(KeyCode::Char('s')));
+    /// assert_eq!(input.input_string(), "fix the-_parsers now");
+    ///
+    /// input.insert_key(alt('f'));
+    /// input.insert_key(ctrl('u'));
+    /// assert_eq!(input.input_string(), "");
+    /// ```
+    pub fn insert_key(&mut self, key_event: KeyEvent) {
+        let ctrl = key_event.modifiers == KeyModifiers::CONTROL;
+        let alt = key_event.modifiers == KeyModifiers::ALT;
+        match key_event.code {
+            KeyCode::Char('w') if ctrl => self.delete_prev_word(),
+            KeyCode::Char('u') if ctrl => self.delete_to_line_start(),
+            KeyCode::Char('b') if alt => self.cursor_to_word_start(),
+            KeyCode::Char('f') if alt => self.cursor_to_word_end(),
+            KeyCode::Left if ctrl => self.cursor_to_word_start(),
+            KeyCode::Right if ctrl => self.cursor_to_word_end(),
+            KeyCode::Char(_) if ctrl || alt => {}
+            KeyCode::Char(to_insert) => {
+                self.enter_char(to_insert);
+                // event = TextInputEvent::OnChange;
+            }
+            KeyCode::Backspace => {
+                self.delete_char();
+                // event = TextInputEvent::OnChange;
+            }
+            KeyCode::Enter if self.multiline => self.insert_newline(),
+            KeyCode::Left => self.move_cursor_left(),
+            KeyCode::Right => self.move_cursor_right(),
+            KeyCode::Up => self.move_cursor_up(),
+            KeyCode::Down => self.move_cursor_down(),
+            _ => {}
+        }
+    }
+
     fn enter_char(&mut self, new_char: char) {
-        if let Some(max) = self.max {
-            if self.input[self.cursor_position.y].chars().count() == max {
+        if self.max.is_some_and(|max| self.len() >= max) {
+            return;
+        }
+        if self.numeric && !new_char.is_ascii_digit() {
+            return;
+        }
+
+        let mut line = self.input[self.cursor_position.y].clone();
+        let byte_index = line
+            .char_indices()
+            .nth(self.cursor_position.x)
+            .map_or(line.len(), |(i, _)| i);
+        line.insert(byte_index, new_char);
+        if self.numeric {
+            let over_max = match (self.bounds, line.parse::<i32>()) {
+                (Some((_, max)), Ok(value)) => value > max,
+                (_, value) => value.is_err(),
+            };
+            if over_max {
                 return;
             }
         }
-        self.input[self.cursor_position.y].insert(self.cursor_position.x, new_char);
+
+        self.input[self.cursor_position.y] = line;
         self.move_cursor_right();
     }
 
+    /// Split the line at the cursor and move the cursor to the start of the new
+    /// line.
+    fn insert_newline(&mut self) {
+        if self.max.is_some_and(|max| self.len() >= max) {
+            return;
+        }
+        let line = &mut self.input[self.cursor_position.y];
+        let byte_index = line
+            .char_indices()
+            .nth(self.cursor_position.x)
+            .map_or(line.len(), |(i, _)| i);
+        let new_line = line.split_off(byte_index);
+        self.input.insert(self.cursor_position.y + 1, new_line);
+        self.cursor_position.y += 1;
+        self.cursor_position.x = 0;
+        self.keys.clear();
+    }
+
+    fn move_cursor_up(&mut self) {
+        if self.cursor_position.y > 0 {
+            self.cursor_position.y -= 1;
+            self.cursor_position.x = self.clamp_cursor(self.cursor_position.x);
+        }
+        self.keys.clear();
+    }
+
+    fn move_cursor_down(&mut self) {
+        if self.cursor_position.y + 1 < self.input.len() {
+            self.cursor_position.y += 1;
+            self.cursor_position.x = self.clamp_cursor(self.cursor_position.x);
+        }
+        self.keys.clear();
+    }
+
     fn delete_char(&mut self) {
-        if !self.cursor_position.at_start() {
+        if self.cursor_position.x == 0 && self.cursor_position.y > 0 {
+            // Join the line onto the end of the previous line.
+            let line = self.input.remove(self.cursor_position.y);
+            self.cursor_position.y -= 1;
+            self.cursor_end_line();
+            self.input[self.cursor_position.y].push_str(&line);
+        } else if !self.cursor_position.at_start() {
             let before_char_to_delete = self.input[self.cursor_position.y]
                 .chars()
                 .take(self.cursor_position.x.saturating_sub(1));
@@ -450,7 +798,12 @@ impl TextInput {
 
     fn delete_line(&mut self) {
         if self.keys.key_is(KeyCode::Char('d')) {
-            self.input[self.cursor_position.y].clear();
+            if self.input.len() > 1 {
+                self.input.remove(self.cursor_position.y);
+                self.cursor_position.y = self.cursor_position.y.min(self.input.len() - 1);
+            } else {
+                self.input[self.cursor_position.y].clear();
+            }
             self.cursor_position.x = 0;
             self.keys.clear();
         } else {
@@ -491,6 +844,54 @@ impl TextInput {
         self.keys.clear();
     }
 
+    /// Move the cursor to the start of the word before it.
+    fn cursor_to_word_start(&mut self) {
+        let chars = self.current_line().chars().collect::<Vec<char>>();
+        let mut x = self.cursor_position.x.min(chars.len());
+        while x > 0 && !is_word_char(chars[x - 1]) {
+            x -= 1;
+        }
+        while x > 0 && is_word_char(chars[x - 1]) {
+            x -= 1;
+        }
+        self.cursor_position.x = x;
+        self.keys.clear();
+    }
+
+    /// Move the cursor to the end of the word after it.
+    fn cursor_to_word_end(&mut self) {
+        let chars = self.current_line().chars().collect::<Vec<char>>();
+        let mut x = self.cursor_position.x.min(chars.len());
+        while x < chars.len() && !is_word_char(chars[x]) {
+            x += 1;
+        }
+        while x < chars.len() && is_word_char(chars[x]) {
+            x += 1;
+        }
+        self.cursor_position.x = x;
+        self.keys.clear();
+    }
+
+    fn delete_prev_word(&mut self) {
+        let end = self.cursor_position.x;
+        self.cursor_to_word_start();
+        let start = self.cursor_position.x;
+        let line = &mut self.input[self.cursor_position.y];
+        *line = line
+            .chars()
+            .enumerate()
+            .filter(|(i, _)| *i < start || *i >= end)
+            .map(|(_, c)| c)
+            .collect();
+    }
+
+    fn delete_to_line_start(&mut self) {
+        let line = &mut self.input[self.cursor_position.y];
+        *line = line.chars().skip(self.cursor_position.x).collect();
+        self.cursor_position.x = 0;
+        self.keys.clear();
+    }
+
     fn cursor_start_line(&mut self) {
         self.cursor_position.x = 0;
         self.keys.clear();
@@ -503,11 +904,17 @@ impl TextInput {
 
     fn render_lines<'a>(&self, app: &App, area: Rect, focused: bool) -> Vec<Line<'a>> {
         let colors = &app.config.colors;
+        let cursor_style = if app.view == self.view && app.mode.is_insert() {
+            app.config
+                .focus_style(colors.input_cursor_insert_bg)
+                .fg(colors.input_cursor_insert_fg)
+        } else {
+            app.config
+                .focus_style(colors.input_cursor_bg)
+                .fg(colors.input_cursor_fg)
+        };
 
-        let input = if !self.input.is_empty()
-            && self.input[self.cursor_position.y].is_empty()
-            && self.cursor_position.at_start()
-        {
+        let input = if self.input.len() == 1 && self.input[0].is_empty() {
             if !focused {
                 if let Some(placeholder) = &self.placeholder {
                     vec![Line::from(vec![
@@ -521,29 +928,14 @@ impl TextInput {
                 vec![Line::from(vec![
                     Span::from(if self.inline { " " } else { "" }),
                     Span::from(" ").style(if app.view == self.view && app.mode.is_insert() {
-                        Style::new().fg(colors.bg).bg(colors.fg)
+                        app.config.focus_style(colors.fg).fg(colors.bg)
                     } else {
-                        Style::new().fg(colors.bg).bg(colors.secondary_fg)
+                        app.config.focus_style(colors.secondary_fg).fg(colors.bg)
                     }),
                 ])]
             }
         } else {
-            // TODO: Implement multiline by splitting \n
-            // TODO: Scrollable view (not with the widget) for when there are more lines to
-            // display than `height`
-
-            let border_width = 2;
-            let cursor_width = 1;
-            let side_space_width = if self.inline { 1 } else { 0 };
-            let form_width = if self.form_input { 4 } else { 0 };
-            let width = if self.use_size {
-                self.size.width
-            } else {
-                area.width - 2
-            };
-            let line_length = width.saturating_sub(border_width + cursor_width + side_space_width)
-                as usize
-                + form_width;
+            let line_length = self.line_length(area);
 
             type RenderCharType<'a> = ((usize, &'a String), (usize, &'a [char]), (usize, &'a char));
             let render_char =
@@ -561,13 +953,7 @@ impl TextInput {
 
                     let cursor_on_char = self.cursor_position.is(real_line_x_value, line_index);
                     if focused && cursor_on_char {
-                        if app.view == self.view && app.mode.is_insert() {
-                            style = style
-                                .fg(colors.input_cursor_insert_fg)
-                                .bg(colors.input_cursor_insert_bg)
-                        } else {
-                            style = style.fg(colors.input_cursor_fg).bg(colors.input_cursor_bg)
-                        }
+                        style = style.patch(cursor_style);
                     }
                     let mut span = vec![Span::from(character.to_string()).style(style)];
 
@@ -585,26 +971,28 @@ impl TextInput {
                         && !self.cursor_position.at_start()
                         && char_is_before_cursor
                     {
-                        span.push(Span::from(" ").style(
-                            if app.view == self.view && app.mode.is_insert() {
-                                Style::new()
-                                    .fg(colors.input_cursor_insert_fg)
-                                    .bg(colors.input_cursor_insert_bg)
-                            } else {
-                                Style::new()
-                                    .fg(colors.input_cursor_fg)
-                                    .bg(colors.input_cursor_bg)
-                            },
-                        ));
+                        span.push(Span::from(" ").style(cursor_style));
                     }
 
                     span
                 };
 
-            self.input
+            let lines = self
+                .input
                 .iter()
                 .enumerate()
                 .flat_map(|(line_index, line)| {
+                    if line.is_empty() {
+                        let mut spans = vec![];
+                        if focused && self.cursor_position.is(0, line_index) {
+                            spans.push(Span::from(" ").style(cursor_style));
+                        }
+                        if self.inline {
+                            spans.insert(0, Span::from(" "));
+                        }
+                        return vec![Line::from(spans)];
+                    }
+
                     line.chars()
                         .collect::<Vec<char>>()
                         .chunks(line_length)
@@ -624,13 +1012,47 @@ impl TextInput {
                             Line::from(line)
                         })
                         .collect::<Vec<Line>>()
-                })
-                .collect::<Vec<Line>>()
+                });
+
+            // Scroll to keep the line with the cursor in view.
+            let height = if self.style == InputStyle::Prompt {
+                area.height
+            } else {
+                area.height.saturating_sub(2)
+            } as usize;
+            let scroll = self
+                .cursor_row(line_length)
+                .saturating_sub(height.saturating_sub(1));
+            lines.skip(scroll).collect::<Vec<Line>>()
         };
 
         input
     }
 
+    /// The number of characters that fit on a line before it's wrapped.
+    fn line_length(&self, area: Rect) -> usize {
+        let border_width = 2;
+        let cursor_width = 1;
+        let side_space_width = if self.inline { 1 } else { 0 };
+        let form_width = if self.form_input { 4 } else { 0 };
+        let width = if self.use_size {
+            self.size.width
+        } else {
+            area.width - 2
+        };
+        width.saturating_sub(border_width + cursor_width + side_space_width) as usize + form_width
+    }
+
+    /// The rendered row of the cursor after lines are wrapped.
+    fn cursor_row(&self, line_length: usize) -> usize {
+        let line_length = line_length.max(1);
+        let rows_before = self.input[..self.cursor_position.y]
+            .iter()
+            .map(|line| line.chars().count().div_ceil(line_length).max(1))
+            .sum::<usize>();
+        rows_before + self.cursor_position.x / line_length
+    }
+
     fn render_text(&self, app: &App, area: Rect, focused: bool) -> impl Widget {
         let colors = &app.config.colors;
         let input_lines = self.render_lines(app, area, focused);
@@ -664,15 +1086,16 @@ impl TextInput {
                 }))
                 .borders(Borders::ALL)
                 .border_type(BorderType::Rounded)
-                .border_style(Style::new().fg(if focused {
-                    if app.view == self.view && app.mode.is_insert() {
-                        colors.status_bar_insert_mode_bg
-                    } else {
-                        colors.border_active
-                    }
+                .border_style(if focused {
+                    app.config
+                        .active_style(if app.view == self.view && app.mode.is_insert() {
+                            colors.status_bar_insert_mode_bg
+                        } else {
+                            colors.border_active
+                        })
                 } else {
-                    colors.border
-                })),
+                    Style::new().fg(colors.border)
+                }),
         )
     }
 }
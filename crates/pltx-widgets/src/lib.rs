@@ -5,8 +5,14 @@
 
 mod buttons;
 mod card;
+mod color_palette;
+mod confirm_popup;
+mod dropdown;
 mod form;
 mod input;
+mod list_picker;
+mod markdown;
+mod multi_select;
 mod popup;
 mod scrollable;
 mod selection;
@@ -15,8 +21,14 @@ mod tabs;
 
 pub use buttons::*;
 pub use card::*;
+pub use color_palette::*;
+pub use confirm_popup::*;
+pub use dropdown::*;
 pub use form::*;
 pub use input::*;
+pub use list_picker::*;
+pub use markdown::*;
+pub use multi_select::*;
 pub use popup::*;
 pub use scrollable::*;
 pub use selection::*;
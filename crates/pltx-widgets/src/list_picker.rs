@@ -0,0 +1,297 @@
+use std::collections::HashSet;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use nucleo::{
+    pattern::{Atom, AtomKind, CaseMatching, Normalization},
+    Matcher,
+};
+use pltx_app::{state::View, App, DefaultWidget, Popup};
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use crate::{PopupSize, PopupWidget, Scrollable, TextInput};
+
+/// An option with the index it has in the picker, so it can be matched by its
+/// title.
+struct Candidate<'a> {
+    index: usize,
+    title: &'a str,
+}
+
+impl AsRef<str> for Candidate<'_> {
+    fn as_ref(&self) -> &str {
+        self.title
+    }
+}
+
+/// Popup to pick from a list of options, which are fuzzy filtered by typing.
+/// `up`/`down` or `ctrl-p`/`ctrl-n` move the selection, `enter` picks the
+/// selected option, and `esc` closes the popup. In multi-select mode, `tab`
+/// checks options and `enter` picks the checked options, or the selected one
+/// when none are checked.
+///
+/// ```
+/// # use crossterm::event::{KeyCode, KeyEvent};
+/// # use pltx_app::Popup;
+/// # use pltx_widgets::ListPicker;
+/// let key = |code| KeyEvent::from(code);
+/// let projects = vec![
+///     (1, String::from("Website")),
+///     (2, String::from("Taxes")),
+///     (3, String::from("Weekly review")),
+/// ];
+///
+/// let mut picker = ListPicker::init().title("Open Project");
+/// picker.open(projects.clone());
+/// for char in "wk".chars() {
+///     picker.handle_key(key(KeyCode::Char(char)));
+/// }
+/// assert_eq!(picker.matches(), vec![&3]);
+/// assert_eq!(picker.handle_key(key(KeyCode::Enter)), Some(vec![3]));
+/// assert!(!picker.is_open());
+///
+/// let mut picker = ListPicker::init().multi_select();
+/// picker.open(projects);
+/// picker.handle_key(key(KeyCode::Tab));
+/// picker.handle_key(key(KeyCode::Down));
+/// picker.handle_key(key(KeyCode::Down));
+/// picker.handle_key(key(KeyCode::Tab));
+/// assert_eq!(picker.handle_key(key(KeyCode::Enter)), Some(vec![1, 3]));
+///
+/// picker.open(vec![(1, String::from("Website"))]);
+/// picker.handle_key(key(KeyCode::Char('x')));
+/// assert!(picker.matches().is_empty());
+/// assert_eq!(picker.handle_key(key(KeyCode::Enter)), None);
+/// assert_eq!(picker.handle_key(key(KeyCode::Esc)), None);
+/// assert!(!picker.is_open());
+/// ```
+pub struct ListPicker<T> {
+    title: Option<String>,
+    filter: TextInput,
+    options: Vec<(T, String)>,
+    /// The indexes of the options that match the filter, from best to worst.
+    matches: Vec<usize>,
+    checked: HashSet<usize>,
+    selection: Scrollable,
+    multi_select: bool,
+    open: bool,
+    matcher: Matcher,
+    size: PopupSize,
+}
+
+impl<T: Clone> ListPicker<T> {
+    pub fn title(mut self, title: &str) -> Self {
+        self.title = Some(title.to_string());
+        self
+    }
+
+    pub fn placeholder(mut self, placeholder: &str) -> Self {
+        self.filter = self.filter.placeholder(placeholder);
+        self
+    }
+
+    /// Allow more than one option to be picked.
+    pub fn multi_select(mut self) -> Self {
+        self.multi_select = true;
+        self
+    }
+
+    pub fn size(mut self, size: PopupSize) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Open the popup with the options to pick from, with their titles.
+    pub fn open(&mut self, options: Vec<(T, String)>) {
+        self.options = options;
+        self.checked.clear();
+        self.filter.reset();
+        self.selection.reset();
+        self.open = true;
+        self.update_matches();
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Close the popup without picking anything.
+    pub fn close(&mut self) {
+        self.open = false;
+        self.options.clear();
+        self.matches.clear();
+        self.checked.clear();
+    }
+
+    /// The options that match the filter, from best to worst.
+    pub fn matches(&self) -> Vec<&T> {
+        self.matches.iter().map(|i| &self.options[*i].0).collect()
+    }
+
+    /// The same as the key event handler, which doesn't require the app.
+    /// Returns the picked options, which is a single option unless the picker
+    /// is in multi-select mode.
+    pub fn handle_key(&mut self, key_event: KeyEvent) -> Option<Vec<T>> {
+        let ctrl = key_event.modifiers == KeyModifiers::CONTROL;
+        match key_event.code {
+            KeyCode::Esc => self.close(),
+            KeyCode::Enter => return self.pick(),
+            KeyCode::Down => self
+                .selection
+                .handle_key(KeyEvent::from(KeyCode::Char('j'))),
+            KeyCode::Char('n') if ctrl => self
+                .selection
+                .handle_key(KeyEvent::from(KeyCode::Char('j'))),
+            KeyCode::Up => self
+                .selection
+                .handle_key(KeyEvent::from(KeyCode::Char('k'))),
+            KeyCode::Char('p') if ctrl => self
+                .selection
+                .handle_key(KeyEvent::from(KeyCode::Char('k'))),
+            KeyCode::Tab if self.multi_select => {
+                if let Some(&index) = self.matches.get(self.selection.focused) {
+                    if !self.checked.remove(&index) {
+                        self.checked.insert(index);
+                    }
+                }
+            }
+            _ => {
+                let filter = self.filter.input_string();
+                self.filter.insert_key(key_event);
+                if self.filter.input_string() != filter {
+                    self.update_matches();
+                }
+            }
+        }
+        None
+    }
+
+    fn pick(&mut self) -> Option<Vec<T>> {
+        let picked = if self.multi_select && !self.checked.is_empty() {
+            let mut checked = self.checked.iter().copied().collect::<Vec<usize>>();
+            checked.sort();
+            checked
+        } else {
+            vec![*self.matches.get(self.selection.focused)?]
+        };
+        let options = std::mem::take(&mut self.options);
+        let picked = picked
+            .into_iter()
+            .map(|i| options[i].0.clone())
+            .collect::<Vec<T>>();
+        self.close();
+        Some(picked)
+    }
+
+    fn update_matches(&mut self) {
+        let filter = self.filter.input_string();
+        self.matches = if filter.is_empty() {
+            (0..self.options.len()).collect()
+        } else {
+            let candidates = self
+                .options
+                .iter()
+                .enumerate()
+                .map(|(index, option)| Candidate {
+                    index,
+                    title: &option.1,
+                })
+                .collect::<Vec<Candidate>>();
+            let pattern = Atom::new(
+                &filter,
+                CaseMatching::Smart,
+                Normalization::Smart,
+                AtomKind::Fuzzy,
+                false,
+            );
+            pattern
+                .match_list(candidates, &mut self.matcher)
+                .into_iter()
+                .map(|(candidate, _)| candidate.index)
+                .collect()
+        };
+        self.selection.clamp(self.matches.len());
+    }
+}
+
+impl<T: Clone> Popup<Option<Vec<T>>> for ListPicker<T> {
+    fn init() -> Self {
+        let size = PopupSize::default().width(60).height(20);
+        Self {
+            title: None,
+            filter: TextInput::new("Filter")
+                .view(View::Popup)
+                .size((size.width - 2, size.height - 2)),
+            options: vec![],
+            matches: vec![],
+            checked: HashSet::new(),
+            selection: Scrollable::default(),
+            multi_select: false,
+            open: false,
+            matcher: Matcher::default(),
+            size,
+        }
+    }
+
+    fn key_event_handler(&mut self, _: &mut App, key_event: KeyEvent) -> Option<Vec<T>> {
+        self.handle_key(key_event)
+    }
+
+    fn render(&self, app: &App, frame: &mut Frame, area: Rect) {
+        if !self.is_open() {
+            return;
+        }
+
+        let colors = &app.config.colors;
+
+        let mut popup = PopupWidget::new(app, area).size(self.size);
+        if let Some(title) = &self.title {
+            popup = popup.title_top(title);
+        }
+        let popup = popup.render(frame);
+
+        let [filter_layout, list_layout] = Layout::default()
+            .constraints([Constraint::Length(3), Constraint::Fill(1)])
+            .areas(popup.sub_area);
+
+        self.filter.render(frame, app, filter_layout, true);
+
+        if self.matches.is_empty() {
+            frame.render_widget(
+                Paragraph::new(" No matches.").fg(colors.secondary_fg),
+                list_layout,
+            );
+            return;
+        }
+
+        let table = self
+            .matches
+            .iter()
+            .enumerate()
+            .map(|(i, &option_index)| {
+                let mut spans = vec![];
+                if self.multi_select {
+                    let check = if self.checked.contains(&option_index) {
+                        " [x]"
+                    } else {
+                        " [ ]"
+                    };
+                    spans.push(Span::from(check).fg(colors.secondary_fg));
+                }
+                spans.push(Span::from(format!(" {}", self.options[option_index].1)).fg(colors.fg));
+                Paragraph::new(Line::from(spans)).style(if self.selection.focused == i {
+                    app.config.focus_style(colors.input_focus_bg)
+                } else {
+                    Style::new().bg(colors.popup_bg)
+                })
+            })
+            .collect::<Vec<Paragraph>>();
+
+        self.selection.render(frame, list_layout, table);
+    }
+}
@@ -0,0 +1,303 @@
+use pltx_config::ColorsConfig;
+use ratatui::{
+    style::{Modifier, Style, Stylize},
+    text::{Line, Span},
+};
+
+/// Render Markdown as lines that are wrapped to `width`, for text that is only
+/// read. Headings, bold and italic text, bullet and numbered lists, code spans
+/// and blocks, and links are styled. Anything that isn't closed, such as a
+/// single `**`, is shown as it's written.
+///
+/// ```
+/// # use pltx_config::{base_config, Config};
+/// # use pltx_widgets::markdown_lines;
+/// # use ratatui::style::Modifier;
+/// let colors = Config::from(base_config()).colors;
+/// let text = |lines: Vec<ratatui::text::Line>| {
+///     lines.iter().map(|l| l.to_string()).collect::<Vec<String>>()
+/// };
+///
+/// let lines = markdown_lines(
+///     "# Groceries\n- **Milk** and `eggs`\n  1. *Fresh* bread\nSee [the list](https://example.com)",
+///     &colors,
+///     80,
+/// );
+/// assert_eq!(
+///     text(lines.clone()),
+///     [
+///         "Groceries",
+///         "• Milk and eggs",
+///         "  1. Fresh bread",
+///         "See the list (https://example.com)",
+///     ]
+/// );
+/// assert!(lines[1].spans[1].style.add_modifier.contains(Modifier::BOLD));
+/// assert!(lines[2].spans[2].style.add_modifier.contains(Modifier::ITALIC));
+///
+/// // Unclosed markers are kept, and so is a snake_case word.
+/// let lines = markdown_lines("**not bold, `not code, snake_case_word", &colors, 80);
+/// assert_eq!(text(lines), ["**not bold, `not code, snake_case_word"]);
+///
+/// // Lines wrap at spaces, and list items are indented when they wrap.
+/// let lines = markdown_lines("one two three\n- four five", &colors, 9);
+/// assert_eq!(text(lines), ["one two", "three", "• four", "  five"]);
+/// ```
+pub fn markdown_lines(text: &str, colors: &ColorsConfig, width: u16) -> Vec<Line<'static>> {
+    let text_lines = text.lines().collect::<Vec<&str>>();
+
+    // A code block that is never closed isn't a code block.
+    let mut fences = text_lines
+        .iter()
+        .enumerate()
+        .filter(|(_, l)| l.trim_start().starts_with("```"))
+        .map(|(i, _)| i)
+        .collect::<Vec<usize>>();
+    if fences.len() % 2 == 1 {
+        fences.pop();
+    }
+
+    let text_style = Style::new().fg(colors.fg);
+    let code_style = Style::new().fg(colors.highlight_fg);
+    let marker_style = Style::new().fg(colors.secondary_fg);
+
+    let mut lines = vec![];
+    let mut in_code_block = false;
+    for (i, line) in text_lines.into_iter().enumerate() {
+        if fences.contains(&i) {
+            in_code_block = !in_code_block;
+            continue;
+        }
+
+        let trimmed = line.trim_start();
+        let indent = " ".repeat(line.len() - trimmed.len());
+
+        if in_code_block {
+            let spans = vec![Span::styled(line.to_string(), code_style)];
+            wrap(vec![], spans, width, &mut lines);
+        } else if let Some(heading) = heading(trimmed) {
+            let spans = inline(heading, colors, text_style.bold().fg(colors.primary));
+            wrap(vec![], spans, width, &mut lines);
+        } else if let Some((marker, item)) = list_item(trimmed) {
+            let mut prefix = vec![Span::styled(marker, marker_style)];
+            if !indent.is_empty() {
+                prefix.insert(0, Span::raw(indent));
+            }
+            wrap(prefix, inline(item, colors, text_style), width, &mut lines);
+        } else {
+            wrap(vec![], inline(line, colors, text_style), width, &mut lines);
+        }
+    }
+    lines
+}
+
+/// The text of a heading, which starts with one to six `#` and a space.
+fn heading(line: &str) -> Option<&str> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    if (1..=6).contains(&level) {
+        line[level..].strip_prefix(' ')
+    } else {
+        None
+    }
+}
+
+/// The marker to show for a bullet or numbered list item, and its text.
+fn list_item(line: &str) -> Option<(String, &str)> {
+    for bullet in ["- ", "* ", "+ "] {
+        if let Some(item) = line.strip_prefix(bullet) {
+            return Some((String::from("• "), item));
+        }
+    }
+    let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits > 0 {
+        if let Some(item) = line[digits..].strip_prefix(". ") {
+            return Some((format!("{}. ", &line[..digits]), item));
+        }
+    }
+    None
+}
+
+/// Style the bold and italic text, code spans, and links of a line.
+fn inline(text: &str, colors: &ColorsConfig, base: Style) -> Vec<Span<'static>> {
+    let chars = text.chars().collect::<Vec<char>>();
+    let mut spans = vec![];
+    let mut plain = String::new();
+    let mut bold = false;
+    let mut italic = false;
+
+    let style = |bold: bool, italic: bool| {
+        let mut style = base;
+        if bold {
+            style = style.add_modifier(Modifier::BOLD);
+        }
+        if italic {
+            style = style.add_modifier(Modifier::ITALIC);
+        }
+        style
+    };
+
+    let mut i = 0;
+    while i < chars.len() {
+        let char = chars[i];
+
+        if char == '`' {
+            if let Some(end) = (i + 1..chars.len()).find(|j| chars[*j] == '`') {
+                flush(&mut spans, &mut plain, style(bold, italic));
+                spans.push(Span::styled(
+                    chars[i + 1..end].iter().collect::<String>(),
+                    Style::new().fg(colors.highlight_fg),
+                ));
+                i = end + 1;
+                continue;
+            }
+        } else if char == '[' {
+            if let Some((title, url, end)) = link(&chars, i) {
+                flush(&mut spans, &mut plain, style(bold, italic));
+                spans.push(Span::styled(title, style(bold, italic).underlined()));
+                spans.push(Span::styled(
+                    format!(" ({})", url),
+                    base.fg(colors.secondary_fg),
+                ));
+                i = end + 1;
+                continue;
+            }
+        } else if char == '*' || char == '_' {
+            let len = if chars.get(i + 1) == Some(&char) {
+                2
+            } else {
+                1
+            };
+            let open = if len == 2 { bold } else { italic };
+            let toggles = if open {
+                closes(&chars, i, len)
+            } else {
+                opens(&chars, i, len)
+                    && (i + len..chars.len()).any(|j| chars[j] == char && closes(&chars, j, len))
+            };
+            if toggles {
+                flush(&mut spans, &mut plain, style(bold, italic));
+                if len == 2 {
+                    bold = !bold;
+                } else {
+                    italic = !italic;
+                }
+                i += len;
+                continue;
+            }
+        }
+
+        plain.push(char);
+        i += 1;
+    }
+
+    flush(&mut spans, &mut plain, style(bold, italic));
+    spans
+}
+
+fn flush(spans: &mut Vec<Span<'static>>, plain: &mut String, style: Style) {
+    if !plain.is_empty() {
+        spans.push(Span::styled(std::mem::take(plain), style));
+    }
+}
+
+/// Whether the marker of `len` characters at `i` can start bold or italic
+/// text. It has to be followed by text, and an underscore can't be within a
+/// word.
+fn opens(chars: &[char], i: usize, len: usize) -> bool {
+    let marker = chars[i];
+    let next = chars.get(i + len);
+    let within_word = marker == '_' && i > 0 && chars[i - 1].is_alphanumeric();
+    next.is_some_and(|c| !c.is_whitespace()) && !within_word
+}
+
+/// Whether there is a marker of `len` characters at `i` that can end bold or
+/// italic text, which has to follow text.
+fn closes(chars: &[char], i: usize, len: usize) -> bool {
+    let marker = chars[i];
+    if i == 0 || i + len > chars.len() || chars[i..i + len].iter().any(|c| *c != marker) {
+        return false;
+    }
+    let next = chars.get(i + len);
+    let within_word = marker == '_' && next.is_some_and(|c| c.is_alphanumeric());
+    // A marker right before or after means it's part of a longer one.
+    let previous = chars[i - 1];
+    !previous.is_whitespace() && previous != marker && next != Some(&marker) && !within_word
+}
+
+/// The title and url of a link like `[title](url)` at `i`, and the index of
+/// its closing parenthesis.
+fn link(chars: &[char], i: usize) -> Option<(String, String, usize)> {
+    let title_end = (i + 1..chars.len()).find(|j| chars[*j] == ']')?;
+    if title_end == i + 1 || chars.get(title_end + 1) != Some(&'(') {
+        return None;
+    }
+    let url_end = (title_end + 2..chars.len()).find(|j| chars[*j] == ')')?;
+    if url_end == title_end + 2 {
+        return None;
+    }
+    Some((
+        chars[i + 1..title_end].iter().collect(),
+        chars[title_end + 2..url_end].iter().collect(),
+        url_end,
+    ))
+}
+
+/// Wrap the spans of a line at spaces, after a prefix such as a list marker.
+/// The lines after the first are indented by the width of the prefix.
+fn wrap(
+    prefix: Vec<Span<'static>>,
+    spans: Vec<Span<'static>>,
+    width: u16,
+    lines: &mut Vec<Line<'static>>,
+) {
+    let indent = prefix
+        .iter()
+        .map(|s| s.content.chars().count())
+        .sum::<usize>();
+    let available = (width as usize).saturating_sub(indent).max(1);
+    let chars = spans
+        .iter()
+        .flat_map(|s| s.content.chars().map(|c| (c, s.style)))
+        .collect::<Vec<(char, Style)>>();
+
+    if chars.is_empty() {
+        lines.push(Line::from(prefix));
+        return;
+    }
+
+    let mut prefix = Some(prefix);
+    let mut start = 0;
+    while start < chars.len() {
+        let mut end = (start + available).min(chars.len());
+        if end < chars.len() {
+            // Break at the last space that fits, unless a word fills the line.
+            if let Some(space) = chars[start..=end].iter().rposition(|(c, _)| *c == ' ') {
+                if space > 0 {
+                    end = start + space;
+                }
+            }
+        }
+
+        let mut line = match prefix.take() {
+            Some(prefix) => prefix,
+            None if indent > 0 => vec![Span::raw(" ".repeat(indent))],
+            None => vec![],
+        };
+        let prefix_len = line.len();
+        for (char, style) in &chars[start..end] {
+            let has_content = line.len() > prefix_len;
+            match line.last_mut() {
+                Some(span) if has_content && span.style == *style => {
+                    span.content.to_mut().push(*char)
+                }
+                _ => line.push(Span::styled(char.to_string(), *style)),
+            }
+        }
+        lines.push(Line::from(line));
+
+        start = end;
+        while chars.get(start).is_some_and(|(c, _)| *c == ' ') {
+            start += 1;
+        }
+    }
+}
@@ -0,0 +1,227 @@
+use std::{cell::RefCell, collections::HashSet, hash::Hash, rc::Rc};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use pltx_app::{App, CompositeWidget, DefaultWidget, KeyEventHandler};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Stylize},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use crate::{FormInputState, FormWidget};
+
+const DEFAULT_HEIGHT: u16 = 12;
+
+type MultiSelectOptions<T> = Vec<(T, String, Color)>;
+
+/// Multi-select checklist widget. Each option has a value, a title, and the
+/// color of the swatch shown next to the title. The output is the set of
+/// checked values.
+///
+/// ```
+/// # use crossterm::event::{KeyCode, KeyEvent};
+/// # use pltx_widgets::MultiSelect;
+/// # use ratatui::style::Color;
+/// let mut labels = MultiSelect::new(
+///     "Labels",
+///     vec![
+///         (1, String::from("Bug"), Color::Red),
+///         (2, String::from("Feature"), Color::Green),
+///         (3, String::from("Docs"), Color::Blue),
+///     ],
+/// );
+///
+/// labels.handle_key(KeyEvent::from(KeyCode::Char(' ')));
+/// labels.handle_key(KeyEvent::from(KeyCode::Char('j')));
+/// labels.handle_key(KeyEvent::from(KeyCode::Char('j')));
+/// labels.handle_key(KeyEvent::from(KeyCode::Char(' ')));
+/// assert_eq!(labels.checked(), &[1, 3].into());
+///
+/// labels.handle_key(KeyEvent::from(KeyCode::Char(' ')));
+/// assert_eq!(labels.checked(), &[1].into());
+///
+/// labels.handle_key(KeyEvent::from(KeyCode::Char('a')));
+/// assert_eq!(labels.checked(), &[1, 2, 3].into());
+///
+/// labels.handle_key(KeyEvent::from(KeyCode::Char('c')));
+/// assert!(labels.checked().is_empty());
+/// ```
+pub struct MultiSelect<T> {
+    pub options: MultiSelectOptions<T>,
+    pub focused_option: usize,
+    checked: HashSet<T>,
+    title: String,
+    height: u16,
+}
+
+impl<T: Clone + Eq + Hash> MultiSelect<T> {
+    pub fn new(title: &str, options: MultiSelectOptions<T>) -> Self {
+        Self {
+            options,
+            focused_option: 0,
+            checked: HashSet::new(),
+            title: title.into(),
+            height: DEFAULT_HEIGHT,
+        }
+    }
+
+    pub fn default_height(mut self, height: u16) -> Self {
+        self.height = height;
+        self
+    }
+
+    /// Replace the options. Checked values that are no longer an option are
+    /// unchecked.
+    pub fn options(&mut self, options: MultiSelectOptions<T>) {
+        self.checked.retain(|v| options.iter().any(|o| &o.0 == v));
+        self.options = options;
+        if self.focused_option >= self.options.len() {
+            self.focused_option = self.options.len().saturating_sub(1);
+        }
+    }
+
+    /// The values of the checked options.
+    pub fn checked(&self) -> &HashSet<T> {
+        &self.checked
+    }
+
+    /// Check a value, if it is an option.
+    pub fn check(&mut self, value: T) {
+        if self.options.iter().any(|o| o.0 == value) {
+            self.checked.insert(value);
+        }
+    }
+
+    /// Toggle the focused option.
+    pub fn toggle(&mut self) {
+        if let Some(option) = self.options.get(self.focused_option) {
+            if !self.checked.remove(&option.0) {
+                self.checked.insert(option.0.clone());
+            }
+        }
+    }
+
+    pub fn select_all(&mut self) {
+        self.checked = self.options.iter().map(|o| o.0.clone()).collect();
+    }
+
+    pub fn clear_all(&mut self) {
+        self.checked.clear();
+    }
+
+    /// The same as the key event handler, which doesn't require the app.
+    pub fn handle_key(&mut self, key_event: KeyEvent) {
+        match key_event.code {
+            KeyCode::Char('j') => self.focus_next(),
+            KeyCode::Char('k') => self.focus_prev(),
+            KeyCode::Char('g') => self.focus_first(),
+            KeyCode::Char('G') => self.focus_last(),
+            KeyCode::Char(' ') => self.toggle(),
+            KeyCode::Char('a') => self.select_all(),
+            KeyCode::Char('c') => self.clear_all(),
+            _ => {}
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.focused_option = 0;
+        self.checked.clear();
+    }
+}
+
+impl<T: Clone + Eq + Hash> KeyEventHandler for MultiSelect<T> {
+    fn key_event_handler(&mut self, _: &mut App, key_event: KeyEvent) {
+        self.handle_key(key_event);
+    }
+}
+
+impl<T: Clone + Eq + Hash> FormWidget for MultiSelect<T> {
+    fn form(self) -> Rc<RefCell<Self>>
+    where
+        Self: Sized,
+    {
+        Rc::new(RefCell::new(self))
+    }
+
+    fn state(&self) -> FormInputState {
+        FormInputState {
+            title: self.title.clone(),
+            height: self.height,
+            uses_insert_mode: false,
+            uses_interactive_mode: true,
+            hidden: self.options.is_empty(),
+            enter_back: true,
+            multiline: false,
+        }
+    }
+
+    fn has_input(&self) -> bool {
+        !self.checked.is_empty()
+    }
+
+    fn reset(&mut self) {
+        self.reset();
+    }
+}
+
+impl<T> CompositeWidget for MultiSelect<T> {
+    fn focus_next(&mut self) {
+        if !self.is_focus_last() {
+            self.focused_option += 1;
+        }
+    }
+
+    fn focus_prev(&mut self) {
+        if !self.is_focus_first() {
+            self.focused_option -= 1;
+        }
+    }
+
+    fn is_focus_first(&self) -> bool {
+        self.focused_option == 0
+    }
+
+    fn is_focus_last(&self) -> bool {
+        self.focused_option == self.options.len().saturating_sub(1)
+    }
+
+    fn focus_first(&mut self) {
+        self.focused_option = 0;
+    }
+
+    fn focus_last(&mut self) {
+        self.focused_option = self.options.len().saturating_sub(1);
+    }
+}
+
+impl<T: Eq + Hash> DefaultWidget for MultiSelect<T> {
+    fn render(&self, frame: &mut Frame, app: &App, area: Rect, focused_widget: bool) {
+        let colors = &app.config.colors;
+
+        let text = self
+            .options
+            .iter()
+            .enumerate()
+            .map(|(i, option)| {
+                let focused = focused_widget && self.focused_option == i;
+                let checked = self.checked.contains(&option.0);
+                let title = Span::from(option.1.clone()).fg(colors.fg);
+
+                Line::from(vec![
+                    Span::from(if focused { "❯" } else { " " })
+                        .bold()
+                        .fg(colors.primary),
+                    Span::from("[").fg(colors.secondary_fg),
+                    Span::from(if checked { "x" } else { " " }).fg(colors.fg),
+                    Span::from("] ").fg(colors.secondary_fg),
+                    Span::from("⬤ ").fg(option.2),
+                    if focused { title.bold() } else { title },
+                ])
+            })
+            .collect::<Vec<Line>>();
+
+        frame.render_widget(Paragraph::new(text), area);
+    }
+}
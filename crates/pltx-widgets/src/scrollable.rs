@@ -1,4 +1,4 @@
-use std::cell::RefCell;
+use std::{cell::RefCell, cmp};
 
 use crossterm::event::{KeyCode, KeyEvent};
 use pltx_app::{App, KeyEventHandler};
@@ -12,6 +12,11 @@ pub struct Scrollable {
     row_height: u16,
     pub col_lengths: Option<Vec<u16>>,
     area_height: RefCell<u16>,
+    /// The height of each row when rendered with
+    /// [`render_rows`](Scrollable::render_rows).
+    row_heights: RefCell<Option<Vec<u16>>>,
+    /// Whether `g` was pressed, so that pressing it again jumps to the top.
+    pending_g: bool,
 }
 
 impl Default for Scrollable {
@@ -24,6 +29,8 @@ impl Default for Scrollable {
             row_height: 1,
             col_lengths: None,
             area_height: RefCell::new(0),
+            row_heights: RefCell::new(None),
+            pending_g: false,
         }
     }
 }
@@ -42,6 +49,24 @@ impl Scrollable {
 
 impl KeyEventHandler for Scrollable {
     fn key_event_handler(&mut self, _: &mut App, key_event: KeyEvent) {
+        self.handle_key(key_event);
+    }
+}
+
+impl Scrollable {
+    /// The same as the key event handler, which doesn't require the app.
+    pub fn handle_key(&mut self, key_event: KeyEvent) {
+        let pending_g = std::mem::take(&mut self.pending_g);
+        if key_event.code == KeyCode::Char('g') && !pending_g {
+            self.pending_g = true;
+            return;
+        }
+
+        if self.row_heights.borrow().is_some() {
+            self.variable_height_key_event_handler(key_event);
+            return;
+        }
+
         let header_height = if self.col_lengths.is_some() { 1 } else { 0 };
         let area_height = *self.area_height.borrow() as usize / self.row_height as usize;
 
@@ -75,7 +100,7 @@ impl KeyEventHandler for Scrollable {
                 self.from_top = self
                     .row_count
                     .borrow()
-                    .saturating_sub(area_height - header_height);
+                    .saturating_sub(area_height.saturating_sub(header_height));
                 self.focused_prev = 0;
                 self.focused = self.row_count.borrow().saturating_sub(1);
             }
@@ -84,6 +109,21 @@ impl KeyEventHandler for Scrollable {
     }
 }
 
+impl Scrollable {
+    /// Handle a key event `count` times, for motions with a count prefix such
+    /// as `5j`. The focus stops at the first or last row.
+    pub fn counted_key_event_handler(&mut self, app: &mut App, key_event: KeyEvent, count: usize) {
+        let count = match key_event.code {
+            KeyCode::Char('j') => cmp::min(count, self.row_count.borrow().saturating_sub(1)),
+            KeyCode::Char('k') => cmp::min(count, self.focused),
+            _ => 1,
+        };
+        for _ in 0..count {
+            self.key_event_handler(app, key_event);
+        }
+    }
+}
+
 // TODO: Allow a Row widget or something similar to be passed as rows, so the
 // user doesn't have to specify a row style for each cell.
 impl Scrollable {
@@ -152,6 +192,72 @@ impl Scrollable {
         }
     }
 
+    /// Render rows that each have their own height, rather than the fixed
+    /// [`row_height`](Scrollable::row_height).
+    pub fn render_rows<T>(&self, frame: &mut Frame, area: Rect, table: Vec<(T, u16)>)
+    where
+        T: Widget,
+    {
+        *self.area_height.borrow_mut() = area.height;
+        *self.row_count.borrow_mut() = table.len();
+        *self.row_heights.borrow_mut() = Some(table.iter().map(|(_, h)| *h).collect());
+
+        let mut y = area.y;
+        for (row, height) in table.into_iter().skip(self.from_top) {
+            if y + height > area.y + area.height {
+                break;
+            }
+            frame.render_widget(row, Rect::new(area.x, y, area.width, height));
+            y += height;
+        }
+    }
+
+    fn variable_height_key_event_handler(&mut self, key_event: KeyEvent) {
+        let row_count = *self.row_count.borrow();
+
+        match key_event.code {
+            KeyCode::Char('j') if self.focused != row_count.saturating_sub(1) => {
+                self.focused_prev = self.focused;
+                self.focused += 1;
+            }
+            KeyCode::Char('k') if self.focused != 0 => {
+                self.focused_prev = self.focused;
+                self.focused -= 1;
+            }
+            KeyCode::Char('g') => {
+                self.focused_prev = 0;
+                self.focused = 0;
+            }
+            KeyCode::Char('G') => {
+                self.focused_prev = 0;
+                self.focused = row_count.saturating_sub(1);
+            }
+            _ => {}
+        }
+
+        self.scroll_to_focused();
+    }
+
+    /// Scroll the least amount needed for the focused row to be in view.
+    fn scroll_to_focused(&mut self) {
+        if self.focused < self.from_top {
+            self.from_top = self.focused;
+            return;
+        }
+
+        if let Some(row_heights) = self.row_heights.borrow().as_ref() {
+            let area_height = *self.area_height.borrow();
+            let visible_height = |from_top: usize| -> u16 {
+                row_heights[from_top..=self.focused.min(row_heights.len().saturating_sub(1))]
+                    .iter()
+                    .sum()
+            };
+            while self.from_top < self.focused && visible_height(self.from_top) > area_height {
+                self.from_top += 1;
+            }
+        }
+    }
+
     fn row_rects(&self, area: Rect) -> Vec<Rect> {
         (0..area.height / self.row_height)
             .map(|i| {
@@ -165,9 +271,139 @@ impl Scrollable {
             .collect::<Vec<Rect>>()
     }
 
+    /// Set the number of rows before they're rendered, and keep the focus on
+    /// one of them.
+    pub fn clamp(&mut self, row_count: usize) {
+        *self.row_count.borrow_mut() = row_count;
+        self.focused = self.focused.min(row_count.saturating_sub(1));
+        self.from_top = self.from_top.min(self.focused);
+    }
+
+    /// Match the selections of several tables, such as the lists of a board,
+    /// to the tables after they're reloaded. The tables are matched by key, so
+    /// each table keeps its focus and scroll position when tables are added,
+    /// removed, or reordered. The selections are clamped to the number of rows
+    /// of their table, and the selections of new tables are created with `new`.
+    ///
+    /// ```
+    /// # use pltx_widgets::Scrollable;
+    /// let mut selections = vec![];
+    /// for _ in 0..3 {
+    ///     Scrollable::reload_all(
+    ///         &mut selections,
+    ///         &[1, 2],
+    ///         &[(1, 4), (2, 2)],
+    ///         Scrollable::default,
+    ///     );
+    ///     assert_eq!(selections.len(), 2);
+    /// }
+    ///
+    /// // The first list is moved after the second one.
+    /// selections[0].focus(3);
+    /// Scrollable::reload_all(
+    ///     &mut selections,
+    ///     &[1, 2],
+    ///     &[(2, 2), (1, 4)],
+    ///     Scrollable::default,
+    /// );
+    /// assert_eq!(selections[0].focused, 0);
+    /// assert_eq!(selections[1].focused, 3);
+    ///
+    /// // A card is removed from the moved list, and the other list is removed.
+    /// Scrollable::reload_all(
+    ///     &mut selections,
+    ///     &[2, 1],
+    ///     &[(1, 3), (3, 0)],
+    ///     Scrollable::default,
+    /// );
+    /// assert_eq!(selections.len(), 2);
+    /// assert_eq!(selections[0].focused, 2);
+    /// assert_eq!(selections[1].focused, 0);
+    /// ```
+    pub fn reload_all<K: PartialEq>(
+        selections: &mut Vec<Scrollable>,
+        previous_keys: &[K],
+        tables: &[(K, usize)],
+        new: impl Fn() -> Scrollable,
+    ) {
+        let mut previous = std::mem::take(selections)
+            .into_iter()
+            .map(Some)
+            .collect::<Vec<Option<Scrollable>>>();
+        *selections = tables
+            .iter()
+            .map(|(key, row_count)| {
+                let mut selection = previous_keys
+                    .iter()
+                    .position(|k| k == key)
+                    .and_then(|i| previous.get_mut(i))
+                    .and_then(Option::take)
+                    .unwrap_or_else(&new);
+                selection.clamp(*row_count);
+                selection
+            })
+            .collect();
+    }
+
+    /// The row that was rendered `offset` lines below the top of the area,
+    /// which is used to find the row under the mouse.
+    ///
+    /// ```
+    /// # use pltx_widgets::Scrollable;
+    /// let mut selection = Scrollable::default().row_height(2);
+    /// selection.clamp(3);
+    /// assert_eq!(selection.row_at(0), Some(0));
+    /// assert_eq!(selection.row_at(3), Some(1));
+    /// assert_eq!(selection.row_at(6), None);
+    /// ```
+    pub fn row_at(&self, offset: u16) -> Option<usize> {
+        let row_count = *self.row_count.borrow();
+        let row = match self.row_heights.borrow().as_ref() {
+            Some(row_heights) => {
+                let mut y = 0;
+                row_heights
+                    .iter()
+                    .enumerate()
+                    .skip(self.from_top)
+                    .find(|(_, height)| {
+                        y += **height;
+                        offset < y
+                    })
+                    .map(|(i, _)| i)?
+            }
+            None => self.from_top + (offset / self.row_height) as usize,
+        };
+        (row < row_count).then_some(row)
+    }
+
+    /// Whether the focused row is within `margin` rows of the last row, such as
+    /// to load more rows before the last one is reached.
+    ///
+    /// ```
+    /// # use pltx_widgets::Scrollable;
+    /// let mut selection = Scrollable::default();
+    /// selection.clamp(100);
+    /// assert!(!selection.is_near_end(10));
+    /// selection.focus(89);
+    /// assert!(!selection.is_near_end(10));
+    /// selection.focus(90);
+    /// assert!(selection.is_near_end(10));
+    /// ```
+    pub fn is_near_end(&self, margin: usize) -> bool {
+        self.focused + margin >= *self.row_count.borrow()
+    }
+
+    /// Focus a row, scrolling it into view.
+    pub fn focus(&mut self, index: usize) {
+        self.focused_prev = self.focused;
+        self.focused = index.min(self.row_count.borrow().saturating_sub(1));
+        self.scroll_to_focused();
+    }
+
     pub fn reset(&mut self) {
         self.focused = 0;
         self.focused_prev = 0;
         self.from_top = 0;
+        self.pending_g = false;
     }
 }
@@ -25,6 +25,7 @@ pub struct Selection<T> {
     title: String,
     height: u16,
     checklist: bool,
+    single: bool,
 }
 
 impl<T> Selection<T> {
@@ -36,6 +37,7 @@ impl<T> Selection<T> {
             title: title.into(),
             height: DEFAULT_HEIGHT,
             checklist: false,
+            single: false,
         }
     }
 
@@ -53,6 +55,13 @@ impl<T> Selection<T> {
         self
     }
 
+    /// Only allow one option to be selected, so selecting an option clears
+    /// the others.
+    pub fn single(mut self) -> Self {
+        self.single = true;
+        self
+    }
+
     pub fn options(&mut self, options: SelectionOptions<T>) {
         self.options = options;
     }
@@ -61,11 +70,17 @@ impl<T> Selection<T> {
         if self.selected.contains(&self.focused_option) {
             self.selected.remove(&self.focused_option);
         } else {
+            if self.single {
+                self.selected.clear();
+            }
             self.selected.insert(self.focused_option);
         }
     }
 
     pub fn toggle_all(&mut self) {
+        if self.single {
+            return;
+        }
         if self.selected.len() == self.options.len() {
             self.selected.clear();
         } else {
@@ -76,6 +91,9 @@ impl<T> Selection<T> {
     }
 
     pub fn invert_selection(&mut self) {
+        if self.single {
+            return;
+        }
         for (i, _) in self.options.iter().enumerate() {
             if self.selected.contains(&i) {
                 self.selected.remove(&i);
@@ -119,11 +137,17 @@ impl<T> FormWidget for Selection<T> {
             title: self.title.clone(),
             height: self.height,
             uses_insert_mode: false,
+            uses_interactive_mode: true,
             hidden: self.options.is_empty(),
             enter_back: true,
+            multiline: false,
         }
     }
 
+    fn has_input(&self) -> bool {
+        !self.selected.is_empty()
+    }
+
     fn reset(&mut self) {
         self.reset();
     }
@@ -34,10 +34,10 @@ impl DefaultWidget for Switch {
         frame.render_widget(Paragraph::new(format!("{}: ", self.title)), title_layout);
 
         let bracket_style = if focused {
-            Style::new()
+            app.config
+                .focus_style(colors.active_bg)
                 .bold()
                 .fg(colors.active_fg)
-                .bg(colors.active_bg)
         } else {
             Style::new().fg(colors.secondary_fg)
         };
@@ -45,10 +45,10 @@ impl DefaultWidget for Switch {
         let paragraph = Paragraph::new(Line::from(vec![
             Span::from("[").style(bracket_style),
             Span::from(if self.state { "x" } else { " " }).style(if focused {
-                Style::new()
+                app.config
+                    .focus_style(colors.active_bg)
                     .bold()
                     .fg(colors.active_fg)
-                    .bg(colors.active_bg)
             } else {
                 Style::new()
             }),
@@ -81,11 +81,17 @@ impl FormWidget for Switch {
             title: self.title.clone(),
             height: 1,
             uses_insert_mode: false,
+            uses_interactive_mode: true,
             hidden: false,
             enter_back: true,
+            multiline: false,
         }
     }
 
+    fn has_input(&self) -> bool {
+        self.state != self.original_state
+    }
+
     fn reset(&mut self) {
         self.state = self.original_state;
     }
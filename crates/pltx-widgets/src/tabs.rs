@@ -3,7 +3,7 @@ use pltx_app::{App, DefaultWidget, KeyEventHandler};
 use pltx_utils::symbols;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
-    style::Stylize,
+    style::{Style, Stylize},
     text::{Line, Span},
     widgets::Paragraph,
     Frame,
@@ -86,10 +86,10 @@ impl<T: Clone + PartialEq> DefaultWidget for Tabs<T> {
                 )),
                 Line::from(vec![
                     Span::from(format!("{} ", symbols::border::VERTICAL)),
-                    Span::from(t.1.clone()).fg(if self.active == t.0 {
-                        colors.tab_active_fg
+                    Span::from(t.1.clone()).style(if self.active == t.0 {
+                        app.config.active_style(colors.tab_active_fg)
                     } else {
-                        colors.tab_fg
+                        Style::new().fg(colors.tab_fg)
                     }),
                     Span::from(format!(" {}", symbols::border::VERTICAL)),
                 ]),
@@ -0,0 +1,354 @@
+use std::time::Instant;
+
+use chrono::{Datelike, Duration, Months, NaiveDate};
+use color_eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use pltx_app::{state::Navigation, App, KeyEventHandler, Screen};
+use pltx_utils::DateTime;
+use pltx_widgets::{PopupSize, PopupWidget, Scrollable};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph},
+    Frame,
+};
+use tracing::{info, info_span};
+
+const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+struct CalendarCard {
+    id: i32,
+    project_id: i32,
+    project_title: String,
+    title: String,
+    due_date: DateTime,
+    completed: bool,
+}
+
+#[derive(PartialEq)]
+enum CalendarView {
+    Month,
+    Week,
+}
+
+/// Shows the due dates of the cards of all projects on a month or week grid.
+pub struct Calendar {
+    view: CalendarView,
+    selected: NaiveDate,
+    cards: Vec<CalendarCard>,
+    /// Whether the cards of the selected day are listed in a popup.
+    show_day: bool,
+    day_selection: Scrollable,
+}
+
+impl Screen<Result<()>> for Calendar {
+    fn init(app: &App) -> Result<Self> {
+        let day_start_hour = app.config.modules.project_management.day_start_hour;
+
+        let mut calendar = Self {
+            view: CalendarView::Month,
+            selected: DateTime::new().day(day_start_hour),
+            cards: vec![],
+            show_day: false,
+            day_selection: Scrollable::default(),
+        };
+        calendar.db_get_cards(app)?;
+
+        Ok(calendar)
+    }
+
+    fn key_event_handler(&mut self, app: &mut App, key_event: KeyEvent) -> Result<()> {
+        if self.show_day {
+            self.day_selection.key_event_handler(app, key_event);
+            match key_event.code {
+                KeyCode::Enter => {
+                    if let Some(card) = self
+                        .day_cards(app)
+                        .get(self.day_selection.focused)
+                        .map(|c| (c.project_id, c.id))
+                    {
+                        self.close_day(app);
+                        app.navigation = Some(Navigation::OpenCard {
+                            project_id: card.0,
+                            card_id: card.1,
+                        });
+                    }
+                }
+                KeyCode::Char('q') | KeyCode::Esc => self.close_day(app),
+                _ => {}
+            }
+            return Ok(());
+        }
+
+        if !app.view.is_default() || !app.mode.is_normal() {
+            return Ok(());
+        }
+
+        match key_event.code {
+            KeyCode::Char('h') => self.selected -= Duration::days(1),
+            KeyCode::Char('l') => self.selected += Duration::days(1),
+            KeyCode::Char('k') => self.selected -= Duration::weeks(1),
+            KeyCode::Char('j') => self.selected += Duration::weeks(1),
+            KeyCode::Char('H') => {
+                self.selected = match self.view {
+                    CalendarView::Month => self.selected - Months::new(1),
+                    CalendarView::Week => self.selected - Duration::weeks(1),
+                }
+            }
+            KeyCode::Char('L') => {
+                self.selected = match self.view {
+                    CalendarView::Month => self.selected + Months::new(1),
+                    CalendarView::Week => self.selected + Duration::weeks(1),
+                }
+            }
+            KeyCode::Char('t') => {
+                let day_start_hour = app.config.modules.project_management.day_start_hour;
+                self.selected = DateTime::new().day(day_start_hour);
+            }
+            KeyCode::Char('w') => {
+                self.view = match self.view {
+                    CalendarView::Month => CalendarView::Week,
+                    CalendarView::Week => CalendarView::Month,
+                }
+            }
+            KeyCode::Enter if !self.day_cards(app).is_empty() => {
+                self.show_day = true;
+                self.day_selection.reset();
+                app.view.popup();
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn render(&self, app: &App, frame: &mut Frame, area: Rect) {
+        let colors = &app.config.colors;
+
+        let [title_area, weekdays_area, grid_area] = Layout::default()
+            .horizontal_margin(1)
+            .constraints([
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Fill(1),
+            ])
+            .areas(area);
+
+        let title = Paragraph::new(Line::from(vec![
+            Span::from(self.selected.format("%B %Y").to_string())
+                .bold()
+                .fg(colors.fg),
+            Span::from(match self.view {
+                CalendarView::Month => "",
+                CalendarView::Week => " (week)",
+            })
+            .fg(colors.secondary_fg),
+        ]));
+        frame.render_widget(title, title_area);
+
+        let weeks = match self.view {
+            CalendarView::Month => DateTime::month_weeks(self.selected),
+            CalendarView::Week => {
+                let monday = self.selected
+                    - Duration::days(self.selected.weekday().num_days_from_monday() as i64);
+                vec![std::array::from_fn(|i| monday + Duration::days(i as i64))]
+            }
+        };
+
+        let columns = |area: Rect| {
+            Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Fill(1); 7])
+                .split(area)
+        };
+
+        for (weekday, weekday_area) in WEEKDAYS.iter().zip(columns(weekdays_area).iter()) {
+            frame.render_widget(
+                Paragraph::new(*weekday).centered().fg(colors.secondary_fg),
+                *weekday_area,
+            );
+        }
+
+        let week_areas = Layout::default()
+            .constraints(vec![Constraint::Fill(1); weeks.len()])
+            .split(grid_area);
+        for (week, week_area) in weeks.iter().zip(week_areas.iter()) {
+            for (date, day_area) in week.iter().zip(columns(*week_area).iter()) {
+                self.render_day(app, frame, *day_area, *date);
+            }
+        }
+
+        if self.show_day {
+            self.render_day_cards(app, frame, area);
+        }
+    }
+}
+
+impl Calendar {
+    fn render_day(&self, app: &App, frame: &mut Frame, area: Rect, date: NaiveDate) {
+        let colors = &app.config.colors;
+        let day_start_hour = app.config.modules.project_management.day_start_hour;
+
+        let is_selected = date == self.selected;
+        let is_today = date == DateTime::new().day(day_start_hour);
+        let in_month = date.month() == self.selected.month();
+
+        let day_number = Span::from(format!(" {} ", date.day()));
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::new().fg(if is_selected {
+                colors.primary
+            } else {
+                colors.border
+            }))
+            .title(if is_today {
+                day_number.bold().fg(colors.primary)
+            } else if in_month {
+                day_number.fg(colors.fg)
+            } else {
+                day_number.fg(colors.tertiary_fg)
+            });
+        let content_area = block.inner(area);
+        frame.render_widget(block, area);
+
+        let cards = self.cards_on(app, date);
+        let max_lines = content_area.height as usize;
+        let shown = if cards.len() > max_lines {
+            max_lines.saturating_sub(1)
+        } else {
+            cards.len()
+        };
+
+        let mut lines = cards
+            .iter()
+            .take(shown)
+            .map(|card| {
+                let (char, color) = self.status(app, card);
+                Line::from(vec![
+                    Span::from(char).fg(color),
+                    Span::from(card.title.to_string()).fg(if card.completed {
+                        colors.secondary_fg
+                    } else {
+                        colors.fg
+                    }),
+                ])
+            })
+            .collect::<Vec<Line>>();
+        if shown < cards.len() {
+            lines
+                .push(Line::from(format!("+{} more", cards.len() - shown)).fg(colors.secondary_fg));
+        }
+
+        frame.render_widget(Paragraph::new(lines), content_area);
+    }
+
+    fn render_day_cards(&self, app: &App, frame: &mut Frame, area: Rect) {
+        let colors = &app.config.colors;
+
+        let title = self
+            .selected
+            .format(&app.config.datetime.date_format)
+            .to_string();
+        let popup = PopupWidget::new(app, area)
+            .title_top(&title)
+            .size(PopupSize::default().width(70).height(16))
+            .render(frame);
+
+        let project_width = 20;
+        let table = self
+            .day_cards(app)
+            .iter()
+            .enumerate()
+            .map(|(i, card)| {
+                let (char, color) = self.status(app, card);
+                let project_title = card
+                    .project_title
+                    .chars()
+                    .take(project_width)
+                    .collect::<String>();
+                Paragraph::new(Line::from(vec![
+                    Span::from(format!(" {}", char)).fg(color),
+                    Span::from(format!("{:<1$} ", project_title, project_width))
+                        .fg(colors.secondary_fg),
+                    Span::from(card.title.to_string()).fg(colors.fg),
+                ]))
+                .style(if self.day_selection.focused == i {
+                    app.config.focus_style(colors.input_focus_bg)
+                } else {
+                    Style::new().bg(colors.popup_bg)
+                })
+            })
+            .collect::<Vec<Paragraph>>();
+
+        self.day_selection.render(frame, popup.sub_area, table);
+    }
+
+    /// The status character of a card and its color.
+    fn status<'a>(&self, app: &'a App, card: &CalendarCard) -> (&'a str, Color) {
+        let colors = &app.config.colors;
+        let config = &app.config.modules.project_management;
+
+        if card.completed {
+            (&config.completed_char, colors.success)
+        } else if card.due_date.is_past() {
+            (&config.overdue_char, colors.danger)
+        } else if card
+            .due_date
+            .is_due_soon(config.due_soon_days, config.day_start_hour)
+        {
+            (&config.due_soon_char, colors.warning)
+        } else {
+            (&config.default_char, colors.fg)
+        }
+    }
+
+    fn cards_on(&self, app: &App, date: NaiveDate) -> Vec<&CalendarCard> {
+        let day_start_hour = app.config.modules.project_management.day_start_hour;
+        self.cards
+            .iter()
+            .filter(|c| c.due_date.day(day_start_hour) == date)
+            .collect()
+    }
+
+    fn day_cards(&self, app: &App) -> Vec<&CalendarCard> {
+        self.cards_on(app, self.selected)
+    }
+
+    fn close_day(&mut self, app: &mut App) {
+        self.show_day = false;
+        self.day_selection.reset();
+        app.view.default();
+    }
+
+    pub fn db_get_cards(&mut self, app: &App) -> Result<()> {
+        let _span = info_span!("home", screen = "calendar").entered();
+        let start = Instant::now();
+
+        let query = "SELECT c.id, c.project_id, p.title, c.title, c.due_date, c.completed FROM \
+                     project_card c JOIN project p ON p.id = c.project_id WHERE c.due_date IS NOT \
+                     NULL AND c.deleted_at IS NULL ORDER BY c.due_date, p.position, c.id";
+        let conn = app.db.conn();
+        let mut stmt = conn.prepare(query)?;
+        let card_iter = stmt.query_map([], |row| {
+            Ok(CalendarCard {
+                id: row.get(0)?,
+                project_id: row.get(1)?,
+                project_title: row.get(2)?,
+                title: row.get(3)?,
+                due_date: DateTime::from_db(row.get(4)?),
+                completed: row.get(5)?,
+            })
+        })?;
+
+        self.cards.clear();
+        for card in card_iter {
+            self.cards.push(card?);
+        }
+
+        info!("get calendar cards query executed in {:?}", start.elapsed());
+
+        Ok(())
+    }
+}
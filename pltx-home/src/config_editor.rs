@@ -0,0 +1,723 @@
+use std::{cell::RefCell, rc::Rc, str::FromStr};
+
+use color_eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use pltx_app::{App, DefaultWidget, KeyEventHandler, Popup, Screen};
+use pltx_config::{
+    apply_color_preset, export_theme, import_theme, is_color_preset, theme_path,
+    write_config_values, Config, COLOR_PRESETS,
+};
+use pltx_utils::DateTime;
+use pltx_widgets::{
+    color_palette, Buttons, Card, Form, FormInput, FormWidget, ListPicker, PopupSize, Scrollable,
+    Tabs, TextInput,
+};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph},
+    Frame,
+};
+use tracing::{error, info};
+
+const DASHBOARD_TITLE_MAX_LENGTH: usize = 50;
+const DASHBOARD_MESSAGE_MAX_LENGTH: usize = 160;
+/// The forms with more values than fit scroll.
+const FORM_MAX_HEIGHT: u16 = 20;
+const PREVIEW_WIDTH: u16 = 40;
+
+/// A color as it's written in the config, which is a hex color unless it's a
+/// named color.
+fn color_hex(color: Color) -> String {
+    match color {
+        Color::Rgb(r, g, b) => format!("#{:02x}{:02x}{:02x}", r, g, b),
+        color => color.to_string(),
+    }
+}
+
+fn validate_color(color: &str) -> Result<(), String> {
+    Color::from_str(color)
+        .map(|_| ())
+        .map_err(|_| String::from("Enter a hex color, such as #61a4ff"))
+}
+
+fn validate_preset(preset: &str) -> Result<(), String> {
+    if is_color_preset(preset) {
+        Ok(())
+    } else {
+        Err(String::from(
+            "Enter the name of a preset, or p in normal mode for the list",
+        ))
+    }
+}
+
+/// The color presets with their descriptions, for the preset picker.
+fn preset_options() -> Vec<(String, String)> {
+    COLOR_PRESETS
+        .iter()
+        .map(|p| {
+            (
+                p.name.to_string(),
+                format!("{:<14} {}", p.name, p.description),
+            )
+        })
+        .collect()
+}
+
+fn validate_time(time: &str) -> Result<(), String> {
+    if DateTime::is_valid_time(time) {
+        Ok(())
+    } else {
+        Err(String::from("Enter a time in the format HH:MM"))
+    }
+}
+
+fn validate_not_empty(text: &str) -> Result<(), String> {
+    if text.trim().is_empty() {
+        Err(String::from("Enter a value"))
+    } else {
+        Ok(())
+    }
+}
+
+/// Export the colors of the config to the theme file.
+pub fn export_theme_file(app: &mut App) {
+    let path = theme_path();
+    match export_theme(&app.config.colors, &path) {
+        Ok(()) => app.notify_info(format!("Exported the theme to {}", path.display())),
+        Err(e) => {
+            error!("failed to export theme: {e}");
+            app.notify_error("Failed to export the theme");
+        }
+    }
+}
+
+/// Import the colors of the theme file into the config of the profile.
+pub fn import_theme_file(app: &mut App) {
+    let path = theme_path();
+    match import_theme(&path, &app.profile) {
+        Ok(import) => {
+            app.set_config(import.config);
+            if import.invalid.is_empty() {
+                app.notify_info(format!("Imported the theme from {}", path.display()));
+            } else {
+                app.notify_warning(format!(
+                    "Imported the theme, except the invalid keys: {}",
+                    import.invalid.join(", ")
+                ));
+            }
+        }
+        Err(e) => {
+            error!("failed to import theme: {e}");
+            app.notify_error(format!("Failed to import the theme: {e}"));
+        }
+    }
+}
+
+enum FieldKind {
+    Color,
+    /// The name of a color preset.
+    Preset,
+    /// A number from the lowest to the highest value.
+    Number(i32, i32),
+    Time,
+    /// Text of up to a length.
+    Text(usize),
+}
+
+/// A config value that can be edited, with its full key in the config file.
+struct ConfigField {
+    key: &'static str,
+    kind: FieldKind,
+    value: fn(&Config) -> String,
+}
+
+impl ConfigField {
+    fn new(key: &'static str, kind: FieldKind, value: fn(&Config) -> String) -> Self {
+        Self { key, kind, value }
+    }
+
+    /// The name of the value, without its table.
+    fn name(&self) -> &'static str {
+        self.key.rsplit('.').next().unwrap_or(self.key)
+    }
+
+    fn input(&self) -> TextInput {
+        let input = TextInput::new(self.name());
+        match self.kind {
+            FieldKind::Color => input
+                .max(7)
+                .placeholder("Hex color, or p in normal mode for the palette")
+                .validator(validate_color),
+            FieldKind::Number(min, max) => input
+                .numeric()
+                .bounds(min, max)
+                .validator(validate_not_empty),
+            FieldKind::Preset => input
+                .placeholder("Preset, or p in normal mode for the list")
+                .validator(validate_preset),
+            FieldKind::Time => input.max(5).validator(validate_time),
+            FieldKind::Text(max) => input.max(max).validator(validate_not_empty),
+        }
+    }
+
+    /// The value of the input as it's written to the config file.
+    fn toml_value(&self, input: &TextInput) -> toml::Value {
+        match self.kind {
+            FieldKind::Number(..) => toml::Value::Integer(input.input_i32().unwrap_or(0) as i64),
+            _ => toml::Value::String(input.input_string()),
+        }
+    }
+}
+
+/// Config values that are edited together in a form.
+struct ConfigGroup {
+    title: &'static str,
+    fields: Vec<ConfigField>,
+    inputs: Vec<Rc<RefCell<TextInput>>>,
+    form: Form,
+}
+
+impl ConfigGroup {
+    fn new(title: &'static str, fields: Vec<ConfigField>) -> Self {
+        let inputs = fields
+            .iter()
+            .map(|f| f.input().form())
+            .collect::<Vec<Rc<RefCell<TextInput>>>>();
+        let form = Form::from(
+            inputs
+                .iter()
+                .map(|i| FormInput(Rc::clone(i) as Rc<RefCell<dyn FormWidget>>))
+                .collect::<Vec<FormInput>>(),
+        )
+        .default_title(title)
+        .default_size(
+            PopupSize::default()
+                .width(60)
+                .height((fields.len() as u16 + 2).min(FORM_MAX_HEIGHT)),
+        );
+
+        Self {
+            title,
+            fields,
+            inputs,
+            form,
+        }
+    }
+}
+
+/// Edit the most used values of the config of the active profile. A group of
+/// values is edited in a form, and saving writes the values to the config file
+/// of the profile and reloads the config. While colors are edited, a preview
+/// of the widgets is shown with the colors of the form.
+pub struct ConfigEditor {
+    groups: Vec<ConfigGroup>,
+    selection: Scrollable,
+    /// The group whose form is open.
+    editing: Option<usize>,
+    color_picker: ListPicker<String>,
+    preset_picker: ListPicker<String>,
+    /// The app that the preview is rendered with, which has the colors of the
+    /// open form.
+    preview: App,
+    preview_tabs: Tabs<usize>,
+    preview_input: TextInput,
+    preview_buttons: Buttons<usize>,
+}
+
+impl Screen<Result<()>> for ConfigEditor {
+    fn init(app: &App) -> Result<Self> {
+        let groups = vec![
+            ConfigGroup::new(
+                "Colors",
+                vec![
+                    ConfigField::new("colors.preset", FieldKind::Preset, |c| {
+                        c.colors.preset.to_string()
+                    }),
+                    ConfigField::new("colors.fg", FieldKind::Color, |c| color_hex(c.colors.fg)),
+                    ConfigField::new("colors.bg", FieldKind::Color, |c| color_hex(c.colors.bg)),
+                    ConfigField::new("colors.secondary_fg", FieldKind::Color, |c| {
+                        color_hex(c.colors.secondary_fg)
+                    }),
+                    ConfigField::new("colors.tertiary_fg", FieldKind::Color, |c| {
+                        color_hex(c.colors.tertiary_fg)
+                    }),
+                    ConfigField::new("colors.highlight_fg", FieldKind::Color, |c| {
+                        color_hex(c.colors.highlight_fg)
+                    }),
+                    ConfigField::new("colors.primary", FieldKind::Color, |c| {
+                        color_hex(c.colors.primary)
+                    }),
+                    ConfigField::new("colors.success", FieldKind::Color, |c| {
+                        color_hex(c.colors.success)
+                    }),
+                    ConfigField::new("colors.warning", FieldKind::Color, |c| {
+                        color_hex(c.colors.warning)
+                    }),
+                    ConfigField::new("colors.danger", FieldKind::Color, |c| {
+                        color_hex(c.colors.danger)
+                    }),
+                    ConfigField::new("colors.border", FieldKind::Color, |c| {
+                        color_hex(c.colors.border)
+                    }),
+                    ConfigField::new("colors.border_active", FieldKind::Color, |c| {
+                        color_hex(c.colors.border_active)
+                    }),
+                    ConfigField::new("colors.popup_bg", FieldKind::Color, |c| {
+                        color_hex(c.colors.popup_bg)
+                    }),
+                    ConfigField::new("colors.input_focus_bg", FieldKind::Color, |c| {
+                        color_hex(c.colors.input_focus_bg)
+                    }),
+                    ConfigField::new("colors.date_fg", FieldKind::Color, |c| {
+                        color_hex(c.colors.date_fg)
+                    }),
+                    ConfigField::new("colors.time_fg", FieldKind::Color, |c| {
+                        color_hex(c.colors.time_fg)
+                    }),
+                    ConfigField::new("colors.tab_active_fg", FieldKind::Color, |c| {
+                        color_hex(c.colors.tab_active_fg)
+                    }),
+                    ConfigField::new("colors.popup_border", FieldKind::Color, |c| {
+                        color_hex(c.colors.popup_border)
+                    }),
+                    ConfigField::new("colors.status_bar_fg", FieldKind::Color, |c| {
+                        color_hex(c.colors.status_bar_fg)
+                    }),
+                    ConfigField::new("colors.status_bar_bg", FieldKind::Color, |c| {
+                        color_hex(c.colors.status_bar_bg)
+                    }),
+                    ConfigField::new("colors.status_bar_normal_mode_fg", FieldKind::Color, |c| {
+                        color_hex(c.colors.status_bar_normal_mode_fg)
+                    }),
+                    ConfigField::new("colors.status_bar_normal_mode_bg", FieldKind::Color, |c| {
+                        color_hex(c.colors.status_bar_normal_mode_bg)
+                    }),
+                ],
+            ),
+            ConfigGroup::new(
+                "Project Management",
+                vec![
+                    ConfigField::new(
+                        "modules.project_management.max_lists",
+                        FieldKind::Number(1, 50),
+                        |c| c.modules.project_management.max_lists.to_string(),
+                    ),
+                    ConfigField::new(
+                        "modules.project_management.due_soon_days",
+                        FieldKind::Number(0, 365),
+                        |c| c.modules.project_management.due_soon_days.to_string(),
+                    ),
+                    ConfigField::new(
+                        "modules.project_management.day_start_hour",
+                        FieldKind::Number(0, 23),
+                        |c| c.modules.project_management.day_start_hour.to_string(),
+                    ),
+                    ConfigField::new(
+                        "modules.project_management.default_time",
+                        FieldKind::Time,
+                        |c| c.modules.project_management.default_time.to_string(),
+                    ),
+                    ConfigField::new(
+                        "modules.project_management.min_list_width",
+                        FieldKind::Number(0, 200),
+                        |c| c.modules.project_management.min_list_width.to_string(),
+                    ),
+                    ConfigField::new(
+                        "modules.project_management.max_single_list_width",
+                        FieldKind::Number(0, 500),
+                        |c| {
+                            c.modules
+                                .project_management
+                                .max_single_list_width
+                                .to_string()
+                        },
+                    ),
+                    ConfigField::new(
+                        "modules.project_management.trash_retention_days",
+                        FieldKind::Number(0, 3650),
+                        |c| {
+                            c.modules
+                                .project_management
+                                .trash_retention_days
+                                .to_string()
+                        },
+                    ),
+                ],
+            ),
+            ConfigGroup::new(
+                "Dashboard",
+                vec![
+                    ConfigField::new(
+                        "modules.home.dashboard_title",
+                        FieldKind::Text(DASHBOARD_TITLE_MAX_LENGTH),
+                        |c| c.modules.home.dashboard_title.to_string(),
+                    ),
+                    ConfigField::new(
+                        "modules.home.dashboard_message",
+                        FieldKind::Text(DASHBOARD_MESSAGE_MAX_LENGTH),
+                        |c| c.modules.home.dashboard_message.to_string(),
+                    ),
+                ],
+            ),
+        ];
+
+        let mut selection = Scrollable::default().cols([20, 30, 20]);
+        selection.clamp(groups.iter().map(|g| g.fields.len()).sum());
+
+        Ok(Self {
+            groups,
+            selection,
+            editing: None,
+            color_picker: ListPicker::init()
+                .title("Color")
+                .placeholder("Filter colors")
+                .size(PopupSize::default().width(40).height(20)),
+            preset_picker: ListPicker::init()
+                .title("Color Preset")
+                .placeholder("Filter presets")
+                .size(PopupSize::default().width(80).height(10)),
+            preview: app.preview(),
+            preview_tabs: Tabs::from([(0, "Dashboard"), (1, "Projects")]),
+            preview_input: TextInput::new("Title").default_input(String::from("Plan the trip")),
+            preview_buttons: Buttons::from([(0, "Save"), (1, "Cancel")]),
+        })
+    }
+
+    fn key_event_handler(&mut self, app: &mut App, key_event: KeyEvent) -> Result<()> {
+        let picker = if self.color_picker.is_open() {
+            Some(&mut self.color_picker)
+        } else if self.preset_picker.is_open() {
+            Some(&mut self.preset_picker)
+        } else {
+            None
+        };
+        if let Some(picker) = picker {
+            let picked = picker
+                .handle_key(key_event)
+                .and_then(|picked| picked.into_iter().next());
+            let is_open = picker.is_open();
+            if let (Some(value), Some(input)) = (picked, self.open_input()) {
+                input.borrow_mut().input(value);
+            }
+            if !is_open {
+                app.mode.normal();
+            }
+            self.update_preview(app);
+            return Ok(());
+        }
+
+        if let Some(group_index) = self.editing {
+            self.form_key_event_handler(app, key_event, group_index)?;
+            self.update_preview(app);
+            return Ok(());
+        }
+
+        if !app.view.is_default() || !app.mode.is_normal() {
+            return Ok(());
+        }
+
+        self.selection.key_event_handler(app, key_event);
+
+        match key_event.code {
+            KeyCode::Enter | KeyCode::Char('e') => {
+                if let Some((group_index, _)) = self.rows().get(self.selection.focused) {
+                    self.open_form(app, *group_index);
+                }
+            }
+            KeyCode::Char('x') => export_theme_file(app),
+            KeyCode::Char('i') => import_theme_file(app),
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn render(&self, app: &App, frame: &mut Frame, area: Rect) {
+        let colors = &app.config.colors;
+
+        let header = ["Group", "Setting", "Value"]
+            .into_iter()
+            .map(|h| Paragraph::new(h).fg(colors.secondary_fg))
+            .collect::<Vec<Paragraph>>();
+
+        let table = self
+            .rows()
+            .into_iter()
+            .enumerate()
+            .map(|(i, (group_index, field_index))| {
+                let group = &self.groups[group_index];
+                let field = &group.fields[field_index];
+                let style = if self.selection.focused == i {
+                    app.config.focus_style(colors.input_focus_bg).fg(colors.fg)
+                } else {
+                    Style::new().fg(colors.fg)
+                };
+                let value = (field.value)(&app.config);
+                let value = match field.kind {
+                    FieldKind::Color => {
+                        let color = Color::from_str(&value).unwrap_or(colors.fg);
+                        Line::from(vec![Span::from("■ ").fg(color), Span::from(value)])
+                    }
+                    _ => Line::from(value),
+                };
+                vec![
+                    Paragraph::new(group.title)
+                        .style(style)
+                        .fg(colors.secondary_fg),
+                    Paragraph::new(field.name()).style(style),
+                    Paragraph::new(value).style(style),
+                ]
+            })
+            .collect::<Vec<Vec<Paragraph>>>();
+
+        self.selection.render_with_cols(frame, area, header, table);
+    }
+}
+
+impl ConfigEditor {
+    /// Whether a form is open, which is shown over the whole screen.
+    pub fn is_editing(&self) -> bool {
+        self.editing.is_some()
+    }
+
+    /// The form and the color picker are rendered over the whole screen.
+    pub fn render_popups(&self, app: &App, frame: &mut Frame, area: Rect) {
+        if let Some(group_index) = self.editing {
+            let form = &self.groups[group_index].form;
+            if self.is_editing_colors() {
+                let [form_layout, preview_layout] = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints([Constraint::Fill(1), Constraint::Length(PREVIEW_WIDTH)])
+                    .areas(area);
+                form.render(frame, app, form_layout, true);
+                self.render_preview(frame, preview_layout);
+            } else {
+                form.render(frame, app, area, true);
+            }
+        }
+        self.color_picker.render(app, frame, area);
+        self.preset_picker.render(app, frame, area);
+    }
+
+    fn is_editing_colors(&self) -> bool {
+        self.editing.is_some_and(|g| {
+            self.groups[g]
+                .fields
+                .iter()
+                .any(|f| matches!(f.kind, FieldKind::Color))
+        })
+    }
+
+    /// Use the colors of the open form for the preview. Only the values that
+    /// were changed are used, like when the form is saved, so a new preset
+    /// isn't hidden by the colors that the form started with. Invalid values
+    /// are skipped.
+    fn update_preview(&mut self, app: &App) {
+        let mut config = app.config.clone();
+        if let Some(group_index) = self.editing {
+            let group = &self.groups[group_index];
+            for (field, input) in group.fields.iter().zip(&group.inputs) {
+                let input = input.borrow();
+                let value = input.input_string();
+                if !input.is_valid() || value == (field.value)(&app.config) {
+                    continue;
+                }
+                // The values are valid, so these can't fail.
+                let _ = match field.kind {
+                    FieldKind::Preset => apply_color_preset(&mut config, &value),
+                    FieldKind::Color => config.colors.set(field.name(), &value),
+                    _ => Ok(()),
+                };
+            }
+        }
+        // The colors are shown as the terminal would show them once saved.
+        app.color_support.apply(&mut config);
+        self.preview.config.colors = config.colors;
+    }
+
+    /// A small mock of the interface, which renders widgets with the colors of
+    /// the preview app.
+    fn render_preview(&self, frame: &mut Frame, area: Rect) {
+        let preview = &self.preview;
+        let colors = &preview.config.colors;
+
+        frame.render_widget(
+            Block::new()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .title(" Preview ")
+                .fg(colors.popup_border)
+                .bg(colors.bg),
+            area,
+        );
+        let [tabs_layout, card_layout, input_layout, buttons_layout, _, status_bar_layout] =
+            Layout::default()
+                .margin(1)
+                .constraints([
+                    Constraint::Length(3),
+                    Constraint::Length(9),
+                    Constraint::Length(3),
+                    Constraint::Length(4),
+                    Constraint::Fill(1),
+                    Constraint::Length(1),
+                ])
+                .areas(area);
+
+        self.preview_tabs.render(frame, preview, tabs_layout, true);
+
+        let card = Card::new("Sample Card", card_layout).focused_title(true);
+        card.render(frame, preview, card_layout, true);
+        let card_text = Paragraph::new(vec![
+            Line::from("Book the flights").fg(colors.fg),
+            Line::from("Compare the hotels").fg(colors.secondary_fg),
+            Line::from(vec![
+                Span::from("Due ").fg(colors.tertiary_fg),
+                Span::from("2024-06-01").fg(colors.date_fg),
+                Span::from(" 09:00").fg(colors.time_fg),
+            ]),
+            Line::from(vec![
+                Span::from("Done ").fg(colors.success),
+                Span::from("Soon ").fg(colors.warning),
+                Span::from("Overdue ").fg(colors.danger),
+                Span::from("Note").fg(colors.highlight_fg),
+            ]),
+        ]);
+        frame.render_widget(card_text, card.child_layout());
+
+        self.preview_input
+            .render(frame, preview, input_layout, true);
+        self.preview_buttons
+            .render(frame, preview, buttons_layout, true);
+
+        let status_bar = Paragraph::new(Line::from(vec![
+            Span::from(" NORMAL ")
+                .style(preview.config.focus_style(colors.status_bar_normal_mode_bg))
+                .bold()
+                .fg(colors.status_bar_normal_mode_fg),
+            Span::from(" Status bar"),
+        ]))
+        .fg(colors.status_bar_fg)
+        .bg(colors.status_bar_bg);
+        frame.render_widget(status_bar, status_bar_layout);
+    }
+
+    /// The group and field of each row.
+    fn rows(&self) -> Vec<(usize, usize)> {
+        self.groups
+            .iter()
+            .enumerate()
+            .flat_map(|(g, group)| (0..group.fields.len()).map(move |f| (g, f)))
+            .collect()
+    }
+
+    /// The input of the open form that is being edited.
+    fn open_input(&self) -> Option<Rc<RefCell<TextInput>>> {
+        let group = &self.groups[self.editing?];
+        group.form.open_input().map(|i| Rc::clone(&group.inputs[i]))
+    }
+
+    fn open_form(&mut self, app: &mut App, group_index: usize) {
+        let group = &mut self.groups[group_index];
+        for (field, input) in group.fields.iter().zip(&group.inputs) {
+            input.borrow_mut().input((field.value)(&app.config));
+        }
+        group.form.editing();
+        self.editing = Some(group_index);
+        self.update_preview(app);
+        app.view.popup();
+    }
+
+    fn close_form(&mut self, app: &mut App, group_index: usize) {
+        self.groups[group_index].form.reset();
+        self.editing = None;
+        app.view.default();
+        app.mode.normal();
+    }
+
+    fn form_key_event_handler(
+        &mut self,
+        app: &mut App,
+        key_event: KeyEvent,
+        group_index: usize,
+    ) -> Result<()> {
+        let group = &mut self.groups[group_index];
+
+        if app.mode.is_normal() {
+            match key_event.code {
+                KeyCode::Char('p') => {
+                    let kind = group.form.open_input().map(|i| &group.fields[i].kind);
+                    let picker = match kind {
+                        Some(FieldKind::Color) => {
+                            self.color_picker.open(color_palette(&app.config.colors));
+                            true
+                        }
+                        Some(FieldKind::Preset) => {
+                            self.preset_picker.open(preset_options());
+                            true
+                        }
+                        _ => false,
+                    };
+                    if picker {
+                        // Typed keys go to the filter of the picker.
+                        app.mode.insert();
+                        return Ok(());
+                    }
+                }
+                // The form isn't submit while it's invalid, so say why.
+                KeyCode::Char('s')
+                    if group.form.open_input().is_none() && !group.form.is_valid() =>
+                {
+                    app.notify_warning("Fix the invalid settings before saving");
+                    return Ok(());
+                }
+                _ => {}
+            }
+        }
+
+        let state = group.form.key_event_handler(app, key_event);
+        if state.is_submit() {
+            self.save(app, group_index);
+        } else if state.is_closed() {
+            self.editing = None;
+            app.mode.normal();
+        }
+
+        Ok(())
+    }
+
+    /// Write the changed values of the group to the config file and reload the
+    /// config. The form stays open if the config can't be saved.
+    fn save(&mut self, app: &mut App, group_index: usize) {
+        let group = &self.groups[group_index];
+        let values = group
+            .fields
+            .iter()
+            .zip(&group.inputs)
+            .filter(|(field, input)| (field.value)(&app.config) != input.borrow().input_string())
+            .map(|(field, input)| (field.key, field.toml_value(&input.borrow())))
+            .collect::<Vec<(&str, toml::Value)>>();
+
+        if values.is_empty() {
+            self.close_form(app, group_index);
+            return;
+        }
+
+        match write_config_values(&app.profile, values) {
+            Ok(config) => {
+                info!(
+                    "saved the {} config to {}",
+                    group.title, app.profile.config_file
+                );
+                app.set_config(config);
+                self.close_form(app, group_index);
+                app.notify_info("Saved the config");
+            }
+            Err(e) => {
+                error!("failed to save config: {e}");
+                app.notify_error(format!("Failed to save the config: {e}"));
+            }
+        }
+    }
+}
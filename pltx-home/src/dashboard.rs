@@ -1,21 +1,23 @@
-use std::vec;
+use std::{time::Instant, vec};
 
 use color_eyre::Result;
 use crossterm::event::{KeyCode, KeyEvent};
-use pltx_app::{App, DefaultWidget, KeyEventHandler, Screen};
-use pltx_utils::{symbols, DateTime, WidgetMargin};
+use pltx_app::{state::Navigation, App, DefaultWidget, KeyEventHandler, Screen};
+use pltx_utils::{symbols, DateTime, DurationSince, WidgetMargin};
 use pltx_widgets::{CardCell, CardLayout, CardRow, Scrollable};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
-    style::Stylize,
+    style::{Style, Stylize},
     text::{Line, Span},
     widgets::{Block, Padding, Paragraph, Widget},
     Frame,
 };
+use tracing::{info, info_span};
 
 const CARDS_MAX_WIDTH: u16 = 200;
 const SMALL_HEIGHT: u16 = 35;
 const MEDIUM_HEIGHT: u16 = 45;
+const AGENDA_PROJECT_MAX_WIDTH: usize = 16;
 
 struct Session {
     id: i32,
@@ -24,6 +26,21 @@ struct Session {
     is_current: bool,
 }
 
+/// An uncompleted card that is overdue or due soon.
+struct AgendaCard {
+    id: i32,
+    project_id: i32,
+    project_title: String,
+    title: String,
+    due_date: DateTime,
+}
+
+impl AgendaCard {
+    fn overdue(&self) -> bool {
+        self.due_date.is_past()
+    }
+}
+
 #[derive(PartialEq, Clone)]
 enum Pane {
     Sessions,
@@ -35,24 +52,52 @@ pub struct Dashboard {
     pane: Pane,
     sessions: Vec<Session>,
     scrollable_sessions: Scrollable,
+    agenda: Vec<AgendaCard>,
+    scrollable_agenda: Scrollable,
+    /// The ids and titles of the tags that the agenda can be filtered by.
+    tags: Vec<(i32, String)>,
+    /// The tag that the cards of the agenda must have, if any.
+    tag_filter: Option<i32>,
 }
 
-impl Screen for Dashboard {
+impl Screen<Result<()>> for Dashboard {
     fn init(app: &App) -> Result<Self> {
         let sessions = Dashboard::db_get_sessions(app)?;
+        let agenda = Dashboard::db_get_agenda(app, None)?;
 
         Ok(Self {
             pane: Pane::Sessions,
             sessions,
             scrollable_sessions: Scrollable::default().cols([5, 10, 21, 21]),
+            agenda,
+            scrollable_agenda: Scrollable::default(),
+            tags: Dashboard::db_get_tags(app)?,
+            tag_filter: None,
         })
     }
 
-    fn key_event_handler(&mut self, app: &mut App, key_event: KeyEvent) {
+    fn key_event_handler(&mut self, app: &mut App, key_event: KeyEvent) -> Result<()> {
         if self.pane == Pane::Sessions {
             self.scrollable_sessions.key_event_handler(app, key_event);
         }
 
+        if self.pane == Pane::Tasks {
+            self.scrollable_agenda.key_event_handler(app, key_event);
+
+            if app.view.is_default() && key_event.code == KeyCode::Enter {
+                if let Some(card) = self.agenda.get(self.scrollable_agenda.focused) {
+                    app.navigation = Some(Navigation::OpenCard {
+                        project_id: card.project_id,
+                        card_id: card.id,
+                    });
+                }
+            }
+
+            if app.view.is_default() && key_event.code == KeyCode::Char('t') {
+                self.next_tag_filter(app)?;
+            }
+        }
+
         if app.view.is_default() {
             match key_event.code {
                 KeyCode::Tab => {
@@ -72,6 +117,8 @@ impl Screen for Dashboard {
                 _ => {}
             }
         }
+
+        Ok(())
     }
 
     fn render(&self, app: &App, frame: &mut Frame, area: Rect) {
@@ -93,6 +140,57 @@ impl Screen for Dashboard {
 }
 
 impl Dashboard {
+    /// Reload the agenda, since cards are added to it as they become due soon.
+    pub fn refresh(&mut self, app: &App) -> Result<()> {
+        self.tags = Dashboard::db_get_tags(app)?;
+        if self
+            .tag_filter
+            .is_some_and(|id| !self.tags.iter().any(|t| t.0 == id))
+        {
+            self.tag_filter = None;
+        }
+        self.agenda = Dashboard::db_get_agenda(app, self.tag_filter)?;
+        self.scrollable_agenda.clamp(self.agenda.len());
+        Ok(())
+    }
+
+    /// Filter the agenda by the next tag, or show all cards again after the
+    /// last tag.
+    fn next_tag_filter(&mut self, app: &mut App) -> Result<()> {
+        self.tags = Dashboard::db_get_tags(app)?;
+        if self.tags.is_empty() {
+            self.tag_filter = None;
+            app.notify_warning("Create tags in the settings to filter the agenda by tag");
+        } else {
+            let next_index = match self.tag_filter {
+                Some(id) => self.tags.iter().position(|t| t.0 == id).map(|i| i + 1),
+                None => Some(0),
+            };
+            self.tag_filter = next_index.and_then(|i| self.tags.get(i)).map(|t| t.0);
+        }
+        self.agenda = Dashboard::db_get_agenda(app, self.tag_filter)?;
+        self.scrollable_agenda.reset();
+        self.scrollable_agenda.clamp(self.agenda.len());
+        Ok(())
+    }
+
+    /// The title of the tag that the agenda is filtered by.
+    fn tag_filter_title(&self) -> Option<&str> {
+        let id = self.tag_filter?;
+        self.tags.iter().find(|t| t.0 == id).map(|t| t.1.as_str())
+    }
+
+    fn db_get_tags(app: &App) -> Result<Vec<(i32, String)>> {
+        let conn = app.db.conn();
+        let mut stmt = conn.prepare("SELECT id, title FROM tag ORDER BY position")?;
+        let tag_iter = stmt.query_map([], |r| Ok((r.get(0)?, r.get(1)?)))?;
+        let mut tags = vec![];
+        for tag in tag_iter {
+            tags.push(tag?);
+        }
+        Ok(tags)
+    }
+
     fn db_get_sessions(app: &App) -> Result<Vec<Session>> {
         let query = "SELECT id, started, ended FROM session ORDER BY started DESC LIMIT 20";
         let conn = app.db.conn();
@@ -119,6 +217,49 @@ impl Dashboard {
         Ok(sessions)
     }
 
+    /// Get the uncompleted cards of all projects that are overdue or due within
+    /// `due_soon_days`, grouped by project and sorted by due date. Only the
+    /// cards with the tag are included when there is one.
+    fn db_get_agenda(app: &App, tag_id: Option<i32>) -> Result<Vec<AgendaCard>> {
+        let _span = info_span!("home", screen = "dashboard").entered();
+        let start = Instant::now();
+
+        let config = &app.config.modules.project_management;
+
+        let query = "SELECT c.id, c.project_id, p.title, c.title, c.due_date FROM project_card c \
+                     JOIN project p ON p.id = c.project_id WHERE c.completed = 0 AND c.due_date \
+                     IS NOT NULL AND c.deleted_at IS NULL AND (?1 IS NULL OR c.id IN (SELECT \
+                     card_id FROM card_tag WHERE tag_id = ?1)) ORDER BY p.position, c.due_date, \
+                     c.id";
+        let conn = app.db.conn();
+        let mut stmt = conn.prepare(query)?;
+        let card_iter = stmt.query_map([tag_id], |row| {
+            Ok(AgendaCard {
+                id: row.get(0)?,
+                project_id: row.get(1)?,
+                project_title: row.get(2)?,
+                title: row.get(3)?,
+                due_date: DateTime::from_db(row.get(4)?),
+            })
+        })?;
+
+        let mut agenda = Vec::new();
+        for c in card_iter {
+            let card = c?;
+            if card.overdue()
+                || card
+                    .due_date
+                    .is_due_soon(config.due_soon_days, config.day_start_hour)
+            {
+                agenda.push(card);
+            }
+        }
+
+        info!("get agenda query executed in {:?}", start.elapsed());
+
+        Ok(agenda)
+    }
+
     fn render_welcome(&self, app: &App, frame: &mut Frame, area: Rect, parent_area: Rect) {
         let vertical_spacing = if parent_area.height < SMALL_HEIGHT {
             1
@@ -222,7 +363,7 @@ impl Dashboard {
         let [row_1_layouts, row_2_layouts] = card_layout.layouts(area);
 
         self.render_sessions(frame, app, row_1_layouts[0]);
-        frame.render_widget(self.render_tasks(app), row_1_layouts[1]);
+        self.render_agenda(frame, app, row_1_layouts[1]);
         frame.render_widget(self.render_calendar(app), row_1_layouts[2]);
         frame.render_widget(self.render_screentime(app), row_2_layouts[0]);
         frame.render_widget(self.render_app_info(app), row_2_layouts[1]);
@@ -230,6 +371,7 @@ impl Dashboard {
 
     fn render_sessions(&self, frame: &mut Frame, app: &App, area: Rect) {
         let colors = &app.config.colors;
+        let date_format = &app.config.datetime.date_format;
 
         let header = [
             Paragraph::new(" ID").bold(),
@@ -243,82 +385,67 @@ impl Dashboard {
             .iter()
             .enumerate()
             .map(|(i, s)| {
+                let row_style =
+                    if self.pane == Pane::Sessions && self.scrollable_sessions.focused == i {
+                        app.config.focus_style(colors.input_focus_bg)
+                    } else {
+                        Style::new().bg(colors.bg)
+                    };
                 vec![
-                    Paragraph::new(format!(" {}", s.id)).fg(colors.fg).bg(
-                        if self.pane == Pane::Sessions && self.scrollable_sessions.focused == i {
-                            colors.input_focus_bg
-                        } else {
-                            colors.bg
-                        },
-                    ),
+                    Paragraph::new(format!(" {}", s.id))
+                        .style(row_style)
+                        .fg(colors.fg),
                     Paragraph::new(if let Some(started) = &s.started {
                         if s.is_current {
-                            DateTime::new().duration_since(started).to_string()
+                            DurationSince::from(DateTime::new().duration_since(started)).to_string()
                         } else if let Some(ended) = &s.ended {
-                            ended.duration_since(started).to_string()
+                            DurationSince::from(ended.duration_since(started)).to_string()
                         } else {
                             "<unknown>".to_string()
                         }
                     } else {
                         "<pending>".to_string()
                     })
-                    .fg(colors.success)
-                    .bg(
-                        if self.pane == Pane::Sessions && self.scrollable_sessions.focused == i {
-                            colors.input_focus_bg
-                        } else {
-                            colors.bg
-                        },
-                    ),
+                    .style(row_style)
+                    .fg(colors.success),
                     Paragraph::new(if let Some(started) = &s.started {
                         Line::from(vec![
-                            Span::from(started.display_date()).fg(colors.date_fg),
+                            Span::from(started.display_format(date_format)).fg(colors.date_fg),
                             Span::from(" "),
                             Span::from(started.display_time_with_seconds()).fg(colors.time_fg),
                         ])
                     } else {
                         Line::from("<pending>".to_string())
                     })
+                    .style(row_style)
                     .fg(if s.started.is_some() {
                         colors.date_fg
                     } else {
                         colors.secondary_fg
-                    })
-                    .bg(
-                        if self.pane == Pane::Sessions && self.scrollable_sessions.focused == i {
-                            colors.input_focus_bg
-                        } else {
-                            colors.bg
-                        },
-                    ),
+                    }),
                     Paragraph::new(if s.is_current {
                         Line::from(vec![
-                            Span::from(DateTime::new().display_date()).fg(colors.date_fg),
+                            Span::from(DateTime::new().display_format(date_format))
+                                .fg(colors.date_fg),
                             Span::from(" "),
                             Span::from(DateTime::new().display_time_with_seconds())
                                 .fg(colors.time_fg),
                         ])
                     } else if let Some(ended) = &s.ended {
                         Line::from(vec![
-                            Span::from(ended.display_date()).fg(colors.date_fg),
+                            Span::from(ended.display_format(date_format)).fg(colors.date_fg),
                             Span::from(" "),
                             Span::from(ended.display_time_with_seconds()).fg(colors.time_fg),
                         ])
                     } else {
                         Line::from("<empty>".to_string())
                     })
+                    .style(row_style)
                     .fg(if s.ended.is_some() {
                         colors.time_fg
                     } else {
                         colors.secondary_fg
-                    })
-                    .bg(
-                        if self.pane == Pane::Sessions && self.scrollable_sessions.focused == i {
-                            colors.input_focus_bg
-                        } else {
-                            colors.bg
-                        },
-                    ),
+                    }),
                 ]
             })
             .collect::<Vec<Vec<Paragraph>>>();
@@ -327,11 +454,90 @@ impl Dashboard {
             .render_with_cols(frame, area, header.into(), table);
     }
 
-    fn render_tasks(&self, app: &App) -> impl Widget {
+    fn render_agenda(&self, frame: &mut Frame, app: &App, area: Rect) {
         let colors = &app.config.colors;
+        let config = &app.config.modules.project_management;
+
+        let tag_filter = self.tag_filter_title();
+        let area = if let Some(title) = tag_filter {
+            let [filter_layout, agenda_layout] = Layout::default()
+                .constraints([Constraint::Length(1), Constraint::Fill(1)])
+                .areas(area);
+            frame.render_widget(
+                Paragraph::new(Line::from(vec![
+                    Span::from(" Tagged ").fg(colors.secondary_fg),
+                    Span::from(format!("#{}", title)).fg(colors.primary),
+                ])),
+                filter_layout,
+            );
+            agenda_layout
+        } else {
+            area
+        };
 
-        let tasks = vec![Line::from("You don't have any planned tasks.").fg(colors.secondary_fg)];
-        Paragraph::new(tasks)
+        if self.agenda.is_empty() {
+            let message = match tag_filter {
+                Some(title) => format!("You don't have any planned tasks tagged #{}.", title),
+                None => String::from("You don't have any planned tasks."),
+            };
+            frame.render_widget(Paragraph::new(message).fg(colors.secondary_fg), area);
+            return;
+        }
+
+        let project_width = self
+            .agenda
+            .iter()
+            .map(|c| c.project_title.chars().count())
+            .max()
+            .unwrap_or(0)
+            .min(AGENDA_PROJECT_MAX_WIDTH);
+
+        let table = self
+            .agenda
+            .iter()
+            .enumerate()
+            .map(|(i, card)| {
+                let focused = self.pane == Pane::Tasks && self.scrollable_agenda.focused == i;
+
+                // Only the first card of each project shows the project title.
+                let project_title = if i == 0 || self.agenda[i - 1].project_id != card.project_id {
+                    let mut title = card.project_title.chars().collect::<Vec<char>>();
+                    if title.len() > project_width {
+                        title.truncate(project_width.saturating_sub(1));
+                        title.push('…');
+                    }
+                    title.into_iter().collect::<String>()
+                } else {
+                    String::new()
+                };
+
+                let (status_char, due_color) = if card.overdue() {
+                    (&config.overdue_char, colors.danger)
+                } else {
+                    (&config.due_soon_char, colors.warning)
+                };
+
+                Paragraph::new(Line::from(vec![
+                    Span::from(format!(" {} ", status_char)),
+                    Span::from(format!(
+                        "{} ",
+                        card.due_date
+                            .display_format(&app.config.datetime.date_format)
+                    ))
+                    .fg(due_color),
+                    Span::from(format!("{:<1$} ", project_title, project_width))
+                        .fg(colors.secondary_fg),
+                    Span::from(card.title.to_string()).fg(colors.fg),
+                ]))
+                .style(if focused {
+                    app.config.focus_style(colors.input_focus_bg)
+                } else {
+                    Style::new().bg(colors.bg)
+                })
+            })
+            .collect::<Vec<Paragraph>>();
+
+        self.scrollable_agenda.render(frame, area, table);
     }
 
     fn render_calendar(&self, app: &App) -> impl Widget {
@@ -353,6 +559,7 @@ impl Dashboard {
 
         Paragraph::new(vec![
             Line::from(format!("Version: {}", env!("CARGO_PKG_VERSION"))),
+            Line::from(format!("Terminal colors: {}", app.color_support)),
             Line::from(vec![
                 Span::from("Up to date: "),
                 Span::from(symbols::CHECK).fg(colors.success),
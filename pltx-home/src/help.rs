@@ -274,11 +274,11 @@ impl Help {
             .iter()
             .enumerate()
             .map(|(i, d)| {
-                Paragraph::new(format!(" {} ", d.frontmatter.title)).bg(
+                Paragraph::new(format!(" {} ", d.frontmatter.title)).style(
                     if self.selected_page == i {
-                        colors.input_focus_bg
+                        app.config.focus_style(colors.input_focus_bg)
                     } else {
-                        colors.bg
+                        Style::new().bg(colors.bg)
                     },
                 )
             })
@@ -1,30 +1,46 @@
-//! The Home Module - Contains the dashboard, settings, and help pages.
+//! The Home Module - Contains the dashboard, calendar, settings, help, and log
+//! pages.
 use color_eyre::Result;
 use crossterm::event::KeyEvent;
-use pltx_app::{App, DefaultWidget, KeyEventHandler, Module, Screen};
+use pltx_app::{
+    state::Navigation,
+    App, CommandRegistry, DefaultWidget, KeyEventHandler, KeybindSection, Module, Screen,
+};
 use pltx_widgets::Tabs;
 use ratatui::{
     layout::{Constraint, Layout, Rect},
     Frame,
 };
 
+mod calendar;
+mod config_editor;
 mod dashboard;
 mod generated_docs;
 mod help;
+mod logs;
+mod settings;
 
+use calendar::Calendar;
 use dashboard::Dashboard;
 use help::Help;
+use logs::Logs;
+use settings::Settings;
 
 #[derive(Clone, PartialEq)]
 enum Tab {
     Dashboard,
+    Calendar,
     Settings,
     Help,
+    Logs,
 }
 
 pub struct Screens {
     dashboard: Dashboard,
+    calendar: Calendar,
+    settings: Settings,
     help: Help,
+    logs: Logs,
 }
 
 pub struct Home {
@@ -37,16 +53,113 @@ impl Home {
         self.tabs.active = Tab::Dashboard;
     }
 
-    pub fn settings(&mut self) {
+    pub fn calendar(&mut self, app: &App) -> Result<()> {
+        self.tabs.active = Tab::Calendar;
+        self.screens.calendar.db_get_cards(app)
+    }
+
+    /// Reload the agenda of the dashboard.
+    pub fn refresh(&mut self, app: &App) -> Result<()> {
+        self.screens.dashboard.refresh(app)
+    }
+
+    pub fn settings(&mut self, app: &App) -> Result<()> {
         self.tabs.active = Tab::Settings;
+        self.screens.settings.refresh(&app.db)
     }
 
     pub fn help(&mut self) {
         self.tabs.active = Tab::Help;
     }
+
+    pub fn logs(&mut self, app: &App) {
+        self.tabs.active = Tab::Logs;
+        self.screens.logs.tick(app);
+    }
+
+    /// Read the new lines of the log file while the logs are open.
+    pub fn tick(&mut self, app: &App) {
+        if self.tabs.active == Tab::Logs {
+            self.screens.logs.tick(app);
+        }
+    }
+
+    /// The keybinds of the active tab, for the help popup.
+    pub fn keybinds(&self) -> Vec<KeybindSection> {
+        match self.tabs.active {
+            Tab::Dashboard => vec![KeybindSection::new("Dashboard")
+                .keybind("tab S-tab", "Next or previous pane")
+                .keybind("enter", "Open the card of the agenda")
+                .keybind("t", "Filter the agenda by the next tag")],
+            Tab::Calendar => vec![KeybindSection::new("Calendar")
+                .keybind("h l", "Previous or next day")
+                .keybind("k j", "Previous or next week")
+                .keybind("H L", "Previous or next month or week")
+                .keybind("t", "Go to today")
+                .keybind("w", "Toggle the week view")
+                .keybind("enter", "Show the cards of the day")],
+            Tab::Settings => vec![
+                KeybindSection::new("Settings").keybind("tab S-tab", "Config or tags pane"),
+                KeybindSection::new("Config")
+                    .keybind("enter e", "Edit the group of the setting")
+                    .keybind("x", "Export the colors to the theme file")
+                    .keybind("i", "Import the colors of the theme file")
+                    .keybind("s", "Save the form, when every value is valid")
+                    .keybind("p", "Pick a color from the palette, in normal mode"),
+                KeybindSection::new("Tags")
+                .keybind("n", "New tag")
+                .keybind("e", "Edit the tag")
+                .keybind("d", "Delete the tag")
+                    .keybind("J K", "Move the tag down or up"),
+            ],
+            Tab::Help => vec![KeybindSection::new("Help Pages")
+                .keybind("enter l", "Open the page")
+                .keybind("[", "Back to the pages")],
+            Tab::Logs => vec![KeybindSection::new("Logs")
+                .keybind("f", "Pause or resume following new lines")
+                .keybind("j k g", "Scroll, which pauses following")
+                .keybind("G", "Follow the last line")],
+        }
+    }
+
+    /// Register the commands of the home module.
+    pub fn register_commands(commands: &mut CommandRegistry) {
+        commands.register("dashboard", "Open the dashboard", |app| {
+            app.navigation = Some(Navigation::Dashboard)
+        });
+        commands.register("home", "Open the home module", |app| {
+            app.navigation = Some(Navigation::Dashboard)
+        });
+        commands.register("calendar", "Open the calendar of due dates", |app| {
+            app.navigation = Some(Navigation::Calendar)
+        });
+        commands.register("settings", "Open the settings", |app| {
+            app.navigation = Some(Navigation::Settings)
+        });
+        commands.register(
+            "export theme",
+            "Export the colors to the theme file",
+            config_editor::export_theme_file,
+        );
+        commands.register(
+            "import theme",
+            "Import the colors of the theme file",
+            config_editor::import_theme_file,
+        );
+        commands.register("help", "Open the help page", |app| {
+            app.navigation = Some(Navigation::Help)
+        });
+        commands.register("logs", "Follow the log file of the profile", |app| {
+            app.navigation = Some(Navigation::Logs)
+        });
+
+        commands.leader('d', "dashboard");
+        commands.leader('c', "calendar");
+        commands.leader('h', "help");
+    }
 }
 
-impl Module for Home {
+impl Module<Result<()>> for Home {
     fn init(app: &App) -> Result<Self>
     where
         Self: Sized,
@@ -54,24 +167,47 @@ impl Module for Home {
         Ok(Self {
             tabs: Tabs::from([
                 (Tab::Dashboard, "Dashboard"),
+                (Tab::Calendar, "Calendar"),
                 (Tab::Settings, "Settings"),
                 (Tab::Help, "Help"),
+                (Tab::Logs, "Logs"),
             ]),
             screens: Screens {
                 dashboard: Dashboard::init(app)?,
+                calendar: Calendar::init(app)?,
+                settings: Settings::init(app)?,
                 help: Help::init(app)?,
+                logs: Logs::init(app)?,
             },
         })
     }
 
-    fn key_event_handler(&mut self, app: &mut App, key_event: KeyEvent) {
-        self.tabs.key_event_handler(app, key_event);
+    fn key_event_handler(&mut self, app: &mut App, key_event: KeyEvent) -> Result<()> {
+        let previous_tab = self.tabs.active.clone();
+        // Tabs aren't switched while a tag or the config is edited in the
+        // settings.
+        if app.view.is_default() && app.mode.is_normal() {
+            self.tabs.key_event_handler(app, key_event);
+        }
+        if self.tabs.active != previous_tab {
+            match self.tabs.active {
+                Tab::Calendar => self.screens.calendar.db_get_cards(app)?,
+                Tab::Settings => self.screens.settings.refresh(&app.db)?,
+                Tab::Logs => self.screens.logs.tick(app),
+                _ => {}
+            }
+            return Ok(());
+        }
 
         match self.tabs.active {
-            Tab::Dashboard => self.screens.dashboard.key_event_handler(app, key_event),
-            Tab::Settings => {}
+            Tab::Dashboard => self.screens.dashboard.key_event_handler(app, key_event)?,
+            Tab::Calendar => self.screens.calendar.key_event_handler(app, key_event)?,
+            Tab::Settings => self.screens.settings.key_event_handler(app, key_event)?,
             Tab::Help => self.screens.help.key_event_handler(app, key_event),
+            Tab::Logs => self.screens.logs.key_event_handler(app, key_event)?,
         }
+
+        Ok(())
     }
 
     fn render(&self, app: &App, frame: &mut Frame, area: Rect) {
@@ -83,8 +219,10 @@ impl Module for Home {
 
         match self.tabs.active {
             Tab::Dashboard => self.screens.dashboard.render(app, frame, screen_layout),
-            Tab::Settings => {}
+            Tab::Calendar => self.screens.calendar.render(app, frame, screen_layout),
+            Tab::Settings => self.screens.settings.render(app, frame, screen_layout),
             Tab::Help => self.screens.help.render(app, frame, screen_layout),
+            Tab::Logs => self.screens.logs.render(app, frame, screen_layout),
         }
     }
 }
@@ -0,0 +1,257 @@
+use std::{
+    fs::File,
+    io::{self, Read, Seek, SeekFrom},
+    str::FromStr,
+};
+
+use color_eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use pltx_app::{App, KeyEventHandler, Screen};
+use pltx_utils::WidgetMargin;
+use pltx_widgets::Scrollable;
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+use tracing::Level;
+
+/// The number of lines that are kept, so a long session doesn't use more and
+/// more memory.
+const MAX_LINES: usize = 1000;
+
+struct LogLine {
+    text: String,
+    /// The level of the event, which lines that continue an event, such as the
+    /// lines of an error report, have too.
+    level: Option<Level>,
+}
+
+/// The level of a line of the log file, in the text or the JSON format.
+fn line_level(line: &str) -> Option<Level> {
+    if line.starts_with('{') {
+        let (_, level) = line.split_once("\"level\":\"")?;
+        let (level, _) = level.split_once('"')?;
+        return Level::from_str(level).ok();
+    }
+    // The level follows the timestamp in the text format.
+    line.split_whitespace()
+        .take(2)
+        .find_map(|word| Level::from_str(word).ok())
+}
+
+/// Tails the log file of the profile. New lines are read every tick, and the
+/// last line is followed until following is paused.
+pub struct Logs {
+    /// The log file that is read, which changes when the profile is switched.
+    path: String,
+    lines: Vec<LogLine>,
+    /// The number of bytes of the log file that were read.
+    read: u64,
+    /// The end of the file that isn't a whole line yet.
+    partial: String,
+    /// Whether the log file exists.
+    exists: bool,
+    error: Option<String>,
+    following: bool,
+    selection: Scrollable,
+}
+
+impl Screen<Result<()>> for Logs {
+    fn init(app: &App) -> Result<Self>
+    where
+        Self: Sized,
+    {
+        Ok(Self {
+            path: app.profile.log_file.to_string(),
+            lines: vec![],
+            read: 0,
+            partial: String::new(),
+            exists: false,
+            error: None,
+            following: true,
+            selection: Scrollable::default(),
+        })
+    }
+
+    fn key_event_handler(&mut self, app: &mut App, key_event: KeyEvent) -> Result<()> {
+        match key_event.code {
+            KeyCode::Char('f') => {
+                self.following = !self.following;
+                self.follow();
+            }
+            KeyCode::Char('G') => {
+                self.following = true;
+                self.follow();
+            }
+            KeyCode::Char('j') | KeyCode::Char('k') | KeyCode::Char('g') => {
+                self.following = false;
+                self.selection.key_event_handler(app, key_event);
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn render(&self, app: &App, frame: &mut Frame, area: Rect) {
+        let colors = &app.config.colors;
+
+        let area = WidgetMargin::uniform(1).apply(area);
+        let [title_layout, lines_layout, hint_layout] = Layout::default()
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Fill(1),
+                Constraint::Length(1),
+            ])
+            .areas(area);
+
+        let status = if self.following {
+            Span::from("Following").fg(colors.success)
+        } else {
+            Span::from("Paused").fg(colors.warning)
+        };
+        frame.render_widget(
+            Paragraph::new(vec![
+                Line::from(vec![Span::from("Logs  ").bold().fg(colors.primary), status]),
+                Line::from(self.path.to_string()).fg(colors.secondary_fg),
+            ]),
+            title_layout,
+        );
+
+        let message = if let Some(error) = &self.error {
+            Some(format!("Failed to read the log file: {}", error))
+        } else if !self.exists {
+            Some(String::from("There is no log file yet."))
+        } else if self.lines.is_empty() {
+            Some(String::from("The log file is empty."))
+        } else {
+            None
+        };
+        if let Some(message) = message {
+            frame.render_widget(
+                Paragraph::new(message).fg(colors.secondary_fg),
+                lines_layout,
+            );
+        } else {
+            let table = self
+                .lines
+                .iter()
+                .enumerate()
+                .map(|(i, line)| {
+                    let fg = match line.level {
+                        Some(Level::ERROR) => colors.danger,
+                        Some(Level::WARN) => colors.warning,
+                        Some(Level::DEBUG) | Some(Level::TRACE) => colors.secondary_fg,
+                        _ => colors.fg,
+                    };
+                    let style = if !self.following && self.selection.focused == i {
+                        app.config.focus_style(colors.input_focus_bg)
+                    } else {
+                        Style::new()
+                    };
+                    Paragraph::new(line.text.to_string()).style(style).fg(fg)
+                })
+                .collect::<Vec<Paragraph>>();
+            self.selection.render(frame, lines_layout, table);
+        }
+
+        let keybind = |key: &'static str| Span::from(key).fg(colors.keybind_key);
+        let hint = Paragraph::new(Line::from(vec![
+            keybind("f"),
+            Span::from(if self.following {
+                " pause following  "
+            } else {
+                " follow  "
+            })
+            .fg(colors.keybind_fg),
+            keybind("j k"),
+            Span::from(" scroll  ").fg(colors.keybind_fg),
+            keybind("G"),
+            Span::from(" follow the last line").fg(colors.keybind_fg),
+        ]));
+        frame.render_widget(hint, hint_layout);
+    }
+}
+
+impl Logs {
+    /// Read the lines that were written since the last tick.
+    pub fn tick(&mut self, app: &App) {
+        if self.path != app.profile.log_file {
+            self.path = app.profile.log_file.to_string();
+            self.clear();
+        }
+
+        match self.read_new_lines() {
+            Ok(()) => self.error = None,
+            Err(e) => self.error = Some(e.to_string()),
+        }
+        self.selection.clamp(self.lines.len());
+        self.follow();
+    }
+
+    fn clear(&mut self) {
+        self.lines.clear();
+        self.read = 0;
+        self.partial.clear();
+        self.selection.reset();
+    }
+
+    fn read_new_lines(&mut self) -> io::Result<()> {
+        let mut file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                self.exists = false;
+                self.clear();
+                return Ok(());
+            }
+            Err(e) => return Err(e),
+        };
+        self.exists = true;
+
+        // The log file is created again when the app is started, so it may be
+        // shorter than what was read.
+        let len = file.metadata()?.len();
+        if len < self.read {
+            self.clear();
+        }
+        if len == self.read {
+            return Ok(());
+        }
+
+        file.seek(SeekFrom::Start(self.read))?;
+        let mut bytes = vec![];
+        self.read += file.read_to_end(&mut bytes)? as u64;
+        self.partial.push_str(&String::from_utf8_lossy(&bytes));
+
+        let Some((complete, partial)) = self.partial.rsplit_once('\n') else {
+            return Ok(());
+        };
+        let complete = complete.to_string();
+        self.partial = partial.to_string();
+
+        for text in complete.split('\n') {
+            let level = line_level(text).or_else(|| self.lines.last().and_then(|l| l.level));
+            self.lines.push(LogLine {
+                text: text.to_string(),
+                level,
+            });
+        }
+        if self.lines.len() > MAX_LINES {
+            let overflow = self.lines.len() - MAX_LINES;
+            self.lines.drain(..overflow);
+            self.selection.focused = self.selection.focused.saturating_sub(overflow);
+        }
+
+        Ok(())
+    }
+
+    /// Focus the last line while following.
+    fn follow(&mut self) {
+        if self.following {
+            self.selection.focus(self.lines.len().saturating_sub(1));
+        }
+    }
+}
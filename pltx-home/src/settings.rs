@@ -0,0 +1,513 @@
+use std::{str::FromStr, time::Instant};
+
+use color_eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use pltx_app::{state::View, App, DefaultWidget, KeyEventHandler, Popup, Screen};
+use pltx_database::Database;
+use pltx_utils::{DateTime, WidgetMargin};
+use pltx_widgets::{ConfirmPopup, PopupSize, PopupWidget, Scrollable, TextInput};
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+use tracing::{error, info, info_span};
+
+use crate::config_editor::ConfigEditor;
+
+const TAG_TITLE_MAX_LENGTH: usize = 15;
+const TAG_COLOR_REQUIRED_LENGTH: usize = 7;
+
+fn validate_tag_color(color: &str) -> Result<(), String> {
+    Color::from_str(color)
+        .map(|_| ())
+        .map_err(|_| String::from("Enter a hex color, such as #61a4ff"))
+}
+
+/// A tag that can be added to the cards of any project.
+struct Tag {
+    id: i32,
+    title: String,
+    color: String,
+    /// The number of cards with the tag, not counting deleted cards.
+    cards: i32,
+}
+
+#[derive(PartialEq)]
+enum Pane {
+    Config,
+    Tags,
+}
+
+#[derive(PartialEq)]
+enum FocusedInput {
+    Title,
+    Color,
+}
+
+/// The settings, which edit the config of the profile and manage the tags
+/// that are shared by all projects.
+pub struct Settings {
+    pane: Pane,
+    config_editor: ConfigEditor,
+    tags: Vec<Tag>,
+    selection: Scrollable,
+    /// The tag that is being edited, or `Some(None)` for a new tag, while the
+    /// editor is open.
+    editing: Option<Option<i32>>,
+    title_input: TextInput,
+    color_input: TextInput,
+    focused_input: FocusedInput,
+    size: PopupSize,
+    confirm_delete: ConfirmPopup<i32>,
+}
+
+impl Screen<Result<()>> for Settings {
+    fn init(app: &App) -> Result<Self> {
+        let size = PopupSize::default().width(60).height(12);
+        let mut settings = Self {
+            pane: Pane::Config,
+            config_editor: ConfigEditor::init(app)?,
+            tags: vec![],
+            selection: Scrollable::default().cols([20, 10, 7]),
+            editing: None,
+            title_input: TextInput::new("Title")
+                .view(View::Popup)
+                .max(TAG_TITLE_MAX_LENGTH)
+                .size((size.width - 2, size.height - 2))
+                .prompt(),
+            color_input: TextInput::new("Color")
+                .view(View::Popup)
+                .max(TAG_COLOR_REQUIRED_LENGTH)
+                .placeholder("Hex color, such as #61a4ff")
+                .validator(validate_tag_color)
+                .size((size.width - 2, size.height - 2))
+                .prompt(),
+            focused_input: FocusedInput::Title,
+            size,
+            confirm_delete: ConfirmPopup::init(),
+        };
+        settings.db_get_tags(&app.db)?;
+        Ok(settings)
+    }
+
+    fn key_event_handler(&mut self, app: &mut App, key_event: KeyEvent) -> Result<()> {
+        if app.mode.is_delete() {
+            if let Some(tag_id) = self.confirm_delete.handle_key(key_event) {
+                self.delete_tag(app, tag_id)?;
+            }
+            if !self.confirm_delete.is_open() {
+                app.mode.normal();
+            }
+            return Ok(());
+        }
+
+        if self.editing.is_some() {
+            return self.editor_key_event_handler(app, key_event);
+        }
+
+        if self.config_editor.is_editing() {
+            return self.config_editor.key_event_handler(app, key_event);
+        }
+
+        if !app.view.is_default() || !app.mode.is_normal() {
+            return Ok(());
+        }
+
+        if let KeyCode::Tab | KeyCode::BackTab = key_event.code {
+            self.pane = match self.pane {
+                Pane::Config => Pane::Tags,
+                Pane::Tags => Pane::Config,
+            };
+            return Ok(());
+        }
+
+        if self.pane == Pane::Config {
+            return self.config_editor.key_event_handler(app, key_event);
+        }
+
+        self.selection.key_event_handler(app, key_event);
+
+        match key_event.code {
+            KeyCode::Char('n') => self.open_editor(app, None),
+            KeyCode::Char('e') => {
+                if let Some(tag) = self.tags.get(self.selection.focused) {
+                    self.title_input.input(tag.title.to_string());
+                    self.color_input.input(tag.color.to_string());
+                    self.open_editor(app, Some(tag.id));
+                }
+            }
+            KeyCode::Char('d') => {
+                if let Some(tag) = self.tags.get(self.selection.focused) {
+                    if tag.cards == 0 {
+                        self.delete_tag(app, tag.id)?;
+                    } else {
+                        let message = format!(
+                            "'{}' is used by {} {}. Delete it?",
+                            tag.title,
+                            tag.cards,
+                            if tag.cards == 1 { "card" } else { "cards" }
+                        );
+                        self.confirm_delete.open(message, tag.id);
+                        app.mode.delete();
+                    }
+                }
+            }
+            KeyCode::Char('J') => self.move_tag(app, true)?,
+            KeyCode::Char('K') => self.move_tag(app, false)?,
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn render(&self, app: &App, frame: &mut Frame, area: Rect) {
+        let colors = &app.config.colors;
+
+        let area = WidgetMargin::uniform(1).apply(area);
+        let [config_title_layout, config_layout, title_layout, tags_layout, hint_layout] =
+            Layout::default()
+                .constraints([
+                    Constraint::Length(3),
+                    Constraint::Fill(1),
+                    Constraint::Length(3),
+                    Constraint::Fill(1),
+                    Constraint::Length(1),
+                ])
+                .areas(area);
+
+        let heading = |title: &'static str, pane: Pane| {
+            Line::from(title).bold().fg(if self.pane == pane {
+                colors.primary
+            } else {
+                colors.fg
+            })
+        };
+
+        frame.render_widget(
+            Paragraph::new(vec![
+                heading("Config", Pane::Config),
+                Line::from(format!(
+                    "Changes are saved to {} and applied right away.",
+                    app.profile.config_file
+                ))
+                .fg(colors.secondary_fg),
+            ]),
+            config_title_layout,
+        );
+        self.config_editor.render(app, frame, config_layout);
+
+        frame.render_widget(
+            Paragraph::new(vec![
+                heading("Tags", Pane::Tags),
+                Line::from(
+                    "Tags can be added to the cards of every project, unlike the labels of a \
+                     project.",
+                )
+                .fg(colors.secondary_fg),
+            ]),
+            title_layout,
+        );
+
+        if self.tags.is_empty() {
+            frame.render_widget(
+                Paragraph::new("Press n to create a tag.").fg(colors.secondary_fg),
+                tags_layout,
+            );
+        } else {
+            let header = ["Title", "Color", "Cards"]
+                .into_iter()
+                .map(|h| Paragraph::new(h).fg(colors.secondary_fg))
+                .collect::<Vec<Paragraph>>();
+
+            let table = self
+                .tags
+                .iter()
+                .enumerate()
+                .map(|(i, tag)| {
+                    let style = if self.selection.focused == i {
+                        app.config.focus_style(colors.input_focus_bg).fg(colors.fg)
+                    } else {
+                        Style::new().fg(colors.fg)
+                    };
+                    let color = Color::from_str(&tag.color).unwrap_or(colors.fg);
+                    vec![
+                        Paragraph::new(format!("#{}", tag.title))
+                            .style(style)
+                            .fg(color),
+                        Paragraph::new(tag.color.to_string()).style(style),
+                        Paragraph::new(tag.cards.to_string()).style(style),
+                    ]
+                })
+                .collect::<Vec<Vec<Paragraph>>>();
+
+            self.selection
+                .render_with_cols(frame, tags_layout, header, table);
+        }
+
+        let keybind = |key: &'static str| Span::from(key).fg(colors.keybind_key);
+        let mut hint = vec![keybind("tab"), Span::from(" pane  ").fg(colors.keybind_fg)];
+        hint.extend(match self.pane {
+            Pane::Config => vec![
+                keybind("e"),
+                Span::from(" edit the group  ").fg(colors.keybind_fg),
+                keybind("s"),
+                Span::from(" save in the form  ").fg(colors.keybind_fg),
+                keybind("x i"),
+                Span::from(" export or import the theme").fg(colors.keybind_fg),
+            ],
+            Pane::Tags => vec![
+                keybind("n"),
+                Span::from(" new  ").fg(colors.keybind_fg),
+                keybind("e"),
+                Span::from(" edit  ").fg(colors.keybind_fg),
+                keybind("d"),
+                Span::from(" delete  ").fg(colors.keybind_fg),
+                keybind("J K"),
+                Span::from(" move").fg(colors.keybind_fg),
+            ],
+        });
+        let hint = Paragraph::new(Line::from(hint));
+        frame.render_widget(hint, hint_layout);
+
+        if self.editing.is_some() {
+            self.render_editor(app, frame, area);
+        }
+        self.config_editor.render_popups(app, frame, area);
+        self.confirm_delete.render(app, frame, area);
+    }
+}
+
+impl Settings {
+    /// Reload the tags, which may have been created by importing projects.
+    pub fn refresh(&mut self, db: &Database) -> Result<()> {
+        self.db_get_tags(db)
+    }
+
+    fn open_editor(&mut self, app: &mut App, tag_id: Option<i32>) {
+        self.editing = Some(tag_id);
+        self.focused_input = FocusedInput::Title;
+        app.view.popup();
+        app.mode.insert();
+    }
+
+    fn close_editor(&mut self, app: &mut App) {
+        self.editing = None;
+        self.title_input.reset();
+        self.color_input.reset();
+        self.focused_input = FocusedInput::Title;
+        app.view.default();
+        app.mode.normal();
+    }
+
+    fn editor_key_event_handler(&mut self, app: &mut App, key_event: KeyEvent) -> Result<()> {
+        match self.focused_input {
+            FocusedInput::Title => self.title_input.key_event_handler(app, key_event),
+            FocusedInput::Color => self.color_input.key_event_handler(app, key_event),
+        }
+
+        match key_event.code {
+            KeyCode::Char('q') if app.mode.is_normal() => self.close_editor(app),
+            KeyCode::Tab => {
+                self.focused_input = match self.focused_input {
+                    FocusedInput::Title => FocusedInput::Color,
+                    FocusedInput::Color => FocusedInput::Title,
+                }
+            }
+            KeyCode::Enter if self.focused_input == FocusedInput::Title => {
+                self.focused_input = FocusedInput::Color;
+            }
+            KeyCode::Enter => {
+                let title = self.title_input.input_string();
+                if title.trim().is_empty() {
+                    app.notify_warning("Enter a title for the tag");
+                } else if self.color_input.is_valid() {
+                    let tag_id = self.editing.flatten();
+                    match self.db_save_tag(&app.db, tag_id, title.trim()) {
+                        Ok(()) => {
+                            self.close_editor(app);
+                            self.db_get_tags(&app.db)?;
+                        }
+                        Err(e) => {
+                            error!("failed to save tag: {e}");
+                            app.notify_error("Failed to save the tag");
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn render_editor(&self, app: &App, frame: &mut Frame, area: Rect) {
+        let colors = &app.config.colors;
+
+        let popup = PopupWidget::new(app, area)
+            .title_top(if self.editing.flatten().is_some() {
+                "Edit Tag"
+            } else {
+                "New Tag"
+            })
+            .size(self.size)
+            .render(frame);
+
+        let [preview_layout, title_layout, color_layout] = Layout::default()
+            .margin(2)
+            .constraints([
+                Constraint::Length(2),
+                Constraint::Length(3),
+                Constraint::Length(3),
+            ])
+            .areas(popup.popup_area);
+
+        let color = Color::from_str(&self.color_input.input_string()).unwrap_or(colors.fg);
+        frame.render_widget(
+            Paragraph::new(Line::from(vec![
+                Span::from("Preview: ").fg(colors.secondary_fg),
+                Span::from(format!("#{}", self.title_input.input_string())).fg(color),
+            ])),
+            preview_layout,
+        );
+
+        self.title_input.render(
+            frame,
+            app,
+            title_layout,
+            self.focused_input == FocusedInput::Title,
+        );
+        self.color_input.render(
+            frame,
+            app,
+            color_layout,
+            self.focused_input == FocusedInput::Color,
+        );
+    }
+
+    /// Swap the focused tag with the next or previous tag, keeping it focused.
+    fn move_tag(&mut self, app: &App, down: bool) -> Result<()> {
+        let index = self.selection.focused;
+        let other_index = if down {
+            index + 1
+        } else {
+            match index.checked_sub(1) {
+                Some(i) => i,
+                None => return Ok(()),
+            }
+        };
+        if other_index >= self.tags.len() {
+            return Ok(());
+        }
+
+        self.tags.swap(index, other_index);
+        self.selection.focus(other_index);
+        self.db_save_positions(&app.db)
+    }
+
+    fn delete_tag(&mut self, app: &mut App, tag_id: i32) -> Result<()> {
+        let title = self
+            .tags
+            .iter()
+            .find(|t| t.id == tag_id)
+            .map(|t| t.title.to_string())
+            .unwrap_or_default();
+        self.db_delete_tag(&app.db, tag_id)?;
+        self.db_get_tags(&app.db)?;
+        app.notify_info(format!("Deleted the tag '{}'", title));
+        Ok(())
+    }
+
+    fn db_get_tags(&mut self, db: &Database) -> Result<()> {
+        let _span = info_span!("home", screen = "settings").entered();
+        let start = Instant::now();
+
+        let conn = db.conn();
+        let query = "SELECT t.id, t.title, t.color, (SELECT COUNT(*) FROM card_tag ct JOIN \
+                     project_card c ON c.id = ct.card_id WHERE ct.tag_id = t.id AND c.deleted_at \
+                     IS NULL) FROM tag t ORDER BY t.position";
+        let mut stmt = conn.prepare(query)?;
+        let tag_iter = stmt.query_map([], |r| {
+            Ok(Tag {
+                id: r.get(0)?,
+                title: r.get(1)?,
+                color: r.get(2)?,
+                cards: r.get(3)?,
+            })
+        })?;
+
+        self.tags.clear();
+        for tag in tag_iter {
+            self.tags.push(tag?);
+        }
+        self.selection.clamp(self.tags.len());
+
+        info!("get tags query executed in {:?}", start.elapsed());
+
+        Ok(())
+    }
+
+    /// Save the tag of the editor, or create it after the others when there's
+    /// no id.
+    fn db_save_tag(&self, db: &Database, tag_id: Option<i32>, title: &str) -> Result<()> {
+        let start = Instant::now();
+
+        let color = self.color_input.input_string();
+        match tag_id {
+            Some(id) => {
+                db.execute(
+                    "UPDATE tag SET title = ?1, color = ?2, updated_at = ?3 WHERE id = ?4",
+                    (title, color, DateTime::now(), id),
+                )?;
+            }
+            None => {
+                let position = db.get_highest_position("tag")? + 1;
+                db.execute(
+                    "INSERT INTO tag (title, color, position, created_at, updated_at) VALUES (?1, \
+                     ?2, ?3, ?4, ?5)",
+                    (title, color, position, DateTime::now(), DateTime::now()),
+                )?;
+            }
+        }
+
+        info!("save tag query executed in {:?}", start.elapsed());
+
+        Ok(())
+    }
+
+    /// Save the positions of the tags from their order.
+    fn db_save_positions(&self, db: &Database) -> Result<()> {
+        let start = Instant::now();
+
+        let mut conn = db.conn();
+        let tx = conn.transaction()?;
+        for (position, tag) in self.tags.iter().enumerate() {
+            tx.execute(
+                "UPDATE tag SET position = ?1 WHERE id = ?2",
+                (position as i32, tag.id),
+            )?;
+        }
+        tx.commit()?;
+
+        info!("save tag positions query executed in {:?}", start.elapsed());
+
+        Ok(())
+    }
+
+    /// Delete a tag and remove it from the cards.
+    fn db_delete_tag(&self, db: &Database, tag_id: i32) -> Result<()> {
+        let start = Instant::now();
+
+        let mut conn = db.conn();
+        let tx = conn.transaction()?;
+        tx.execute("DELETE FROM card_tag WHERE tag_id = ?1", [tag_id])?;
+        tx.execute("DELETE FROM tag WHERE id = ?1", [tag_id])?;
+        tx.commit()?;
+
+        info!("delete tag query executed in {:?}", start.elapsed());
+
+        Ok(())
+    }
+}
@@ -38,6 +38,8 @@ CREATE TABLE IF NOT EXISTS project_list (
     title TEXT NOT NULL,
     position INTEGER NOT NULL,
     archived BOOLEAN CHECK (archived IN (0, 1)) DEFAULT 0,
+    wip_limit INTEGER,
+    deleted_at DATETIME,
     created_at DATETIME NOT NULL,
     updated_at DATETIME NOT NULL,
     FOREIGN KEY (project_id)
@@ -60,9 +62,14 @@ CREATE TABLE IF NOT EXISTS project_card (
     start_date DATETIME,
     due_date DATETIME,
     reminder INTEGER,
+    recurrence TEXT,
+    recurrence_day INTEGER,
+    priority INTEGER NOT NULL DEFAULT 0,
     completed BOOLEAN CHECK (archived IN (0, 1)) DEFAULT 0,
+    completed_at DATETIME,
     position INTEGER NOT NULL,
     archived BOOLEAN CHECK (archived IN (0, 1)) DEFAULT 0,
+    deleted_at DATETIME,
     created_at DATETIME NOT NULL,
     updated_at DATETIME NOT NULL,
     FOREIGN KEY (list_id)
@@ -103,6 +110,36 @@ CREATE TABLE IF NOT EXISTS card_label (
 CREATE INDEX IF NOT EXISTS idx_card_label_project_id ON card_label (project_id);
 CREATE INDEX IF NOT EXISTS idx_card_label_card_id ON card_label (card_id);
 
+CREATE TABLE IF NOT EXISTS tag (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    title TEXT NOT NULL,
+    color TEXT NOT NULL,
+    position INTEGER NOT NULL,
+    created_at DATETIME NOT NULL,
+    updated_at DATETIME NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_tag_position ON tag (position);
+
+CREATE TABLE IF NOT EXISTS card_tag (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    card_id INTEGER NOT NULL,
+    tag_id INTEGER NOT NULL,
+    created_at DATETIME NOT NULL,
+    updated_at DATETIME NOT NULL,
+    FOREIGN KEY (card_id)
+        REFERENCES project_card (id)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE,
+    FOREIGN KEY (tag_id)
+        REFERENCES tag (id)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_card_tag_card_id ON card_tag (card_id);
+CREATE INDEX IF NOT EXISTS idx_card_tag_tag_id ON card_tag (tag_id);
+
 CREATE TABLE IF NOT EXISTS card_subtask (
     id INTEGER PRIMARY KEY AUTOINCREMENT,
     project_id INTEGER NOT NULL,
@@ -127,4 +164,86 @@ CREATE INDEX IF NOT EXISTS idx_card_subtask_position ON card_subtask (position);
 CREATE INDEX IF NOT EXISTS idx_card_subtask_project_id ON card_subtask (project_id);
 CREATE INDEX IF NOT EXISTS idx_card_subtask_card_id ON card_subtask (card_id);
 
+CREATE TABLE IF NOT EXISTS card_template (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    project_id INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    title TEXT NOT NULL,
+    description TEXT,
+    position INTEGER NOT NULL,
+    created_at DATETIME NOT NULL,
+    updated_at DATETIME NOT NULL,
+    FOREIGN KEY (project_id)
+        REFERENCES project (id)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_card_template_position ON card_template (position);
+CREATE INDEX IF NOT EXISTS idx_card_template_project_id ON card_template (project_id);
+
+CREATE TABLE IF NOT EXISTS card_template_label (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    template_id INTEGER NOT NULL,
+    label_id INTEGER NOT NULL,
+    created_at DATETIME NOT NULL,
+    updated_at DATETIME NOT NULL,
+    FOREIGN KEY (template_id)
+        REFERENCES card_template (id)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE,
+    FOREIGN KEY (label_id)
+        REFERENCES project_label (id)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_card_template_label_template_id ON card_template_label (template_id);
+
+CREATE TABLE IF NOT EXISTS card_template_subtask (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    template_id INTEGER NOT NULL,
+    value TEXT NOT NULL,
+    position INTEGER NOT NULL,
+    created_at DATETIME NOT NULL,
+    updated_at DATETIME NOT NULL,
+    FOREIGN KEY (template_id)
+        REFERENCES card_template (id)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_card_template_subtask_template_id ON card_template_subtask (template_id);
+
+CREATE TABLE IF NOT EXISTS subtask_template (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    project_id INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    position INTEGER NOT NULL,
+    created_at DATETIME NOT NULL,
+    updated_at DATETIME NOT NULL,
+    FOREIGN KEY (project_id)
+        REFERENCES project (id)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_subtask_template_position ON subtask_template (position);
+CREATE INDEX IF NOT EXISTS idx_subtask_template_project_id ON subtask_template (project_id);
+
+CREATE TABLE IF NOT EXISTS subtask_template_item (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    template_id INTEGER NOT NULL,
+    value TEXT NOT NULL,
+    position INTEGER NOT NULL,
+    created_at DATETIME NOT NULL,
+    updated_at DATETIME NOT NULL,
+    FOREIGN KEY (template_id)
+        REFERENCES subtask_template (id)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_subtask_template_item_template_id ON subtask_template_item (template_id);
+
 COMMIT;
@@ -0,0 +1,723 @@
+//! Export and import of all project management data as a JSON document, used
+//! for backups and for moving data between machines, cloning of projects, and
+//! import of cards from CSV files.
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    time::Instant,
+};
+
+use color_eyre::{eyre::eyre, Result};
+use pltx_database::Database;
+use pltx_utils::{dirs, DateTime};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::priority::Priority;
+
+/// The version of the JSON document format. It must be incremented whenever
+/// the format changes in a way that older versions can't import.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// The color of the tags that are created on import.
+const IMPORTED_TAG_COLOR: &str = "#737aa2";
+
+#[derive(Serialize, Deserialize)]
+pub struct JsonExport {
+    pub schema_version: u32,
+    pub exported_at: String,
+    pub projects: Vec<JsonProject>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct JsonProject {
+    pub title: String,
+    pub description: Option<String>,
+    pub position: i32,
+    pub archived: bool,
+    pub created_at: String,
+    pub updated_at: String,
+    pub labels: Vec<JsonLabel>,
+    pub lists: Vec<JsonList>,
+}
+
+/// A project label. The id is only used to reference the label from cards
+/// within the same document, and is replaced on import.
+#[derive(Serialize, Deserialize)]
+pub struct JsonLabel {
+    pub id: i32,
+    pub title: String,
+    pub color: String,
+    pub position: i32,
+    pub archived: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct JsonList {
+    pub title: String,
+    /// Missing from documents exported before lists had WIP limits.
+    #[serde(default)]
+    pub wip_limit: Option<i32>,
+    pub position: i32,
+    pub archived: bool,
+    pub created_at: String,
+    pub updated_at: String,
+    pub cards: Vec<JsonCard>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct JsonCard {
+    pub title: String,
+    pub description: Option<String>,
+    pub important: bool,
+    /// Missing from documents exported before cards had priorities.
+    #[serde(default)]
+    pub priority: Option<i32>,
+    pub start_date: Option<String>,
+    pub due_date: Option<String>,
+    pub reminder: Option<i32>,
+    pub recurrence: Option<String>,
+    /// The day of the month that a monthly card recurs on. Missing from
+    /// documents exported before it was kept.
+    #[serde(default)]
+    pub recurrence_day: Option<u32>,
+    pub completed: bool,
+    pub completed_at: Option<String>,
+    pub position: i32,
+    pub archived: bool,
+    pub created_at: String,
+    pub updated_at: String,
+    /// The ids of the labels of the project that are added to the card.
+    pub labels: Vec<i32>,
+    /// The titles of the tags of the card, which are shared by all projects.
+    /// Missing from documents exported before there were tags.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub subtasks: Vec<JsonSubtask>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct JsonSubtask {
+    pub value: String,
+    pub completed: bool,
+    pub position: i32,
+    pub archived: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// The file that data is exported to and imported from.
+pub fn json_path() -> PathBuf {
+    dirs::config_dir().join("exports").join("pltx.json")
+}
+
+/// Serialize all projects, along with their labels, lists, cards, and subtasks.
+pub fn export_json(db: &Database) -> Result<String> {
+    let start = Instant::now();
+
+    let conn = db.conn();
+    let mut projects = vec![];
+
+    let mut project_stmt = conn.prepare(
+        "SELECT id, title, description, position, archived, created_at, updated_at FROM project \
+         ORDER BY position",
+    )?;
+    let project_iter = project_stmt.query_map([], |r| {
+        Ok((
+            r.get::<_, i32>(0)?,
+            JsonProject {
+                title: r.get(1)?,
+                description: r.get(2)?,
+                position: r.get(3)?,
+                archived: r.get(4)?,
+                created_at: r.get(5)?,
+                updated_at: r.get(6)?,
+                labels: vec![],
+                lists: vec![],
+            },
+        ))
+    })?;
+
+    for project in project_iter {
+        let (project_id, mut project) = project?;
+        project.labels = get_labels(&conn, project_id)?;
+        project.lists = get_lists(&conn, project_id)?;
+        projects.push(project);
+    }
+
+    let json = serde_json::to_string_pretty(&JsonExport {
+        schema_version: SCHEMA_VERSION,
+        exported_at: DateTime::now(),
+        projects,
+    })?;
+
+    info!("exported json in {:?}", start.elapsed());
+
+    Ok(json)
+}
+
+fn get_labels(conn: &Connection, project_id: i32) -> Result<Vec<JsonLabel>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, title, color, position, archived, created_at, updated_at FROM project_label \
+         WHERE project_id = ?1 ORDER BY position",
+    )?;
+    let label_iter = stmt.query_map([project_id], |r| {
+        Ok(JsonLabel {
+            id: r.get(0)?,
+            title: r.get(1)?,
+            color: r.get(2)?,
+            position: r.get(3)?,
+            archived: r.get(4)?,
+            created_at: r.get(5)?,
+            updated_at: r.get(6)?,
+        })
+    })?;
+
+    let mut labels = vec![];
+    for label in label_iter {
+        labels.push(label?);
+    }
+    Ok(labels)
+}
+
+fn get_lists(conn: &Connection, project_id: i32) -> Result<Vec<JsonList>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, title, wip_limit, position, archived, created_at, updated_at FROM \
+         project_list WHERE project_id = ?1 AND deleted_at IS NULL ORDER BY position",
+    )?;
+    let list_iter = stmt.query_map([project_id], |r| {
+        Ok((
+            r.get::<_, i32>(0)?,
+            JsonList {
+                title: r.get(1)?,
+                wip_limit: r.get(2)?,
+                position: r.get(3)?,
+                archived: r.get(4)?,
+                created_at: r.get(5)?,
+                updated_at: r.get(6)?,
+                cards: vec![],
+            },
+        ))
+    })?;
+
+    let mut lists = vec![];
+    for list in list_iter {
+        let (list_id, mut list) = list?;
+        list.cards = get_cards(conn, list_id)?;
+        lists.push(list);
+    }
+    Ok(lists)
+}
+
+fn get_cards(conn: &Connection, list_id: i32) -> Result<Vec<JsonCard>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, title, description, important, priority, start_date, due_date, reminder, \
+         recurrence, completed, completed_at, position, archived, created_at, updated_at, \
+         recurrence_day FROM project_card WHERE list_id = ?1 AND deleted_at IS NULL ORDER BY \
+         position",
+    )?;
+    let card_iter = stmt.query_map([list_id], |r| {
+        Ok((
+            r.get::<_, i32>(0)?,
+            JsonCard {
+                title: r.get(1)?,
+                description: r.get(2)?,
+                important: r.get(3)?,
+                priority: r.get(4)?,
+                start_date: r.get(5)?,
+                due_date: r.get(6)?,
+                reminder: r.get(7)?,
+                recurrence: r.get(8)?,
+                recurrence_day: r.get(15)?,
+                completed: r.get(9)?,
+                completed_at: r.get(10)?,
+                position: r.get(11)?,
+                archived: r.get(12)?,
+                created_at: r.get(13)?,
+                updated_at: r.get(14)?,
+                labels: vec![],
+                tags: vec![],
+                subtasks: vec![],
+            },
+        ))
+    })?;
+
+    let mut label_stmt = conn.prepare("SELECT label_id FROM card_label WHERE card_id = ?1")?;
+    let mut tag_stmt = conn.prepare(
+        "SELECT t.title FROM card_tag ct JOIN tag t ON t.id = ct.tag_id WHERE ct.card_id = ?1 \
+         ORDER BY t.position",
+    )?;
+    let mut subtask_stmt = conn.prepare(
+        "SELECT value, completed, position, archived, created_at, updated_at FROM card_subtask \
+         WHERE card_id = ?1 ORDER BY position",
+    )?;
+
+    let mut cards = vec![];
+    for card in card_iter {
+        let (card_id, mut card) = card?;
+
+        for label_id in label_stmt.query_map([card_id], |r| r.get(0))? {
+            card.labels.push(label_id?);
+        }
+        for tag in tag_stmt.query_map([card_id], |r| r.get(0))? {
+            card.tags.push(tag?);
+        }
+
+        let subtask_iter = subtask_stmt.query_map([card_id], |r| {
+            Ok(JsonSubtask {
+                value: r.get(0)?,
+                completed: r.get(1)?,
+                position: r.get(2)?,
+                archived: r.get(3)?,
+                created_at: r.get(4)?,
+                updated_at: r.get(5)?,
+            })
+        })?;
+        for subtask in subtask_iter {
+            card.subtasks.push(subtask?);
+        }
+
+        cards.push(card);
+    }
+    Ok(cards)
+}
+
+/// Import the projects of a JSON document with new ids, after the existing
+/// projects. Everything is imported in a single transaction, so nothing is
+/// imported if any part of it fails. Returns the number of imported projects.
+pub fn import_json(db: &Database, json: &str) -> Result<usize> {
+    let start = Instant::now();
+
+    let data: JsonExport = serde_json::from_str(json)?;
+    if data.schema_version > SCHEMA_VERSION {
+        return Err(eyre!(
+            "unsupported schema version {} (the latest supported version is {})",
+            data.schema_version,
+            SCHEMA_VERSION
+        ));
+    }
+
+    let mut conn = db.conn();
+    let tx = conn.transaction()?;
+
+    let position_offset: i32 = tx.query_row(
+        "SELECT COALESCE(MAX(position) + 1, 0) FROM project",
+        [],
+        |r| r.get(0),
+    )?;
+
+    for (i, project) in data.projects.iter().enumerate() {
+        insert_project(&tx, project, position_offset + i as i32)?;
+    }
+
+    tx.commit()?;
+
+    info!(
+        "imported {} projects from json in {:?}",
+        data.projects.len(),
+        start.elapsed()
+    );
+
+    Ok(data.projects.len())
+}
+
+/// Insert a project with new ids at a position, along with everything in it.
+/// The labels of cards are matched to the new ids of the project labels, and
+/// their tags to the tags with the same title.
+fn insert_project(tx: &Connection, project: &JsonProject, position: i32) -> Result<()> {
+    tx.execute(
+        "INSERT INTO project (title, description, position, archived, created_at, updated_at) \
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            project.title,
+            project.description,
+            position,
+            project.archived,
+            project.created_at,
+            project.updated_at,
+        ],
+    )?;
+    let project_id = tx.last_insert_rowid();
+
+    let mut label_ids = HashMap::new();
+    for label in &project.labels {
+        tx.execute(
+            "INSERT INTO project_label (project_id, title, color, position, archived, created_at, \
+             updated_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                project_id,
+                label.title,
+                label.color,
+                label.position,
+                label.archived,
+                label.created_at,
+                label.updated_at,
+            ],
+        )?;
+        label_ids.insert(label.id, tx.last_insert_rowid());
+    }
+
+    for list in &project.lists {
+        tx.execute(
+            "INSERT INTO project_list (project_id, title, wip_limit, position, archived, \
+             created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                project_id,
+                list.title,
+                list.wip_limit,
+                list.position,
+                list.archived,
+                list.created_at,
+                list.updated_at,
+            ],
+        )?;
+        let list_id = tx.last_insert_rowid();
+
+        for card in &list.cards {
+            let priority = card
+                .priority
+                .map(Priority::from_db)
+                .unwrap_or(if card.important {
+                    Priority::High
+                } else {
+                    Priority::None
+                });
+            tx.execute(
+                "INSERT INTO project_card (project_id, list_id, title, description, important, \
+                 priority, start_date, due_date, reminder, recurrence, recurrence_day, completed, \
+                 completed_at, position, archived, created_at, updated_at) VALUES (?1, ?2, ?3, \
+                 ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17)",
+                params![
+                    project_id,
+                    list_id,
+                    card.title,
+                    card.description,
+                    priority.is_important(),
+                    priority.into_db(),
+                    card.start_date,
+                    card.due_date,
+                    card.reminder,
+                    card.recurrence,
+                    card.recurrence_day,
+                    card.completed,
+                    card.completed_at,
+                    card.position,
+                    card.archived,
+                    card.created_at,
+                    card.updated_at,
+                ],
+            )?;
+            let card_id = tx.last_insert_rowid();
+
+            for label_id in &card.labels {
+                let label_id = label_ids.get(label_id).ok_or_else(|| {
+                    eyre!(
+                        "card '{}' references label {} which is not in project '{}'",
+                        card.title,
+                        label_id,
+                        project.title
+                    )
+                })?;
+                tx.execute(
+                    "INSERT INTO card_label (project_id, card_id, label_id, created_at, \
+                     updated_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+                    params![
+                        project_id,
+                        card_id,
+                        label_id,
+                        card.created_at,
+                        card.updated_at,
+                    ],
+                )?;
+            }
+
+            for title in &card.tags {
+                tx.execute(
+                    "INSERT INTO card_tag (card_id, tag_id, created_at, updated_at) VALUES (?1, \
+                     ?2, ?3, ?4)",
+                    params![
+                        card_id,
+                        tag_id(tx, title)?,
+                        card.created_at,
+                        card.updated_at
+                    ],
+                )?;
+            }
+
+            for subtask in &card.subtasks {
+                tx.execute(
+                    "INSERT INTO card_subtask (project_id, card_id, value, completed, position, \
+                     archived, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                    params![
+                        project_id,
+                        card_id,
+                        subtask.value,
+                        subtask.completed,
+                        subtask.position,
+                        subtask.archived,
+                        subtask.created_at,
+                        subtask.updated_at,
+                    ],
+                )?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// The id of the tag with a title, which is created if there isn't one.
+fn tag_id(tx: &Connection, title: &str) -> Result<i64> {
+    let existing = tx
+        .query_row("SELECT id FROM tag WHERE title = ?1", [title], |r| r.get(0))
+        .optional()?;
+    if let Some(id) = existing {
+        return Ok(id);
+    }
+
+    tx.execute(
+        "INSERT INTO tag (title, color, position, created_at, updated_at) VALUES (?1, ?2, (SELECT \
+         COALESCE(MAX(position) + 1, 0) FROM tag), ?3, ?3)",
+        params![title, IMPORTED_TAG_COLOR, DateTime::now()],
+    )?;
+    Ok(tx.last_insert_rowid())
+}
+
+/// Clone a project into a new project after the others, titled "<title>
+/// (copy)". Cards are only cloned when `include_cards` is set, so a project
+/// can be used as a template for its lists and labels. Everything is cloned in
+/// a single transaction. Returns the title of the clone.
+///
+/// ```
+/// # use pltx_database::Database;
+/// # use pltx_project_management::{data, ProjectManagement};
+/// let db = Database::in_memory();
+/// ProjectManagement::init_db(&db).unwrap();
+/// let now = "2024-05-25T12:00:00+00:00";
+/// for query in [
+///     "INSERT INTO project VALUES (1, 'Week', NULL, 0, 0, ?1, ?1)",
+///     "INSERT INTO project_label (id, project_id, title, color, position, created_at, \
+///      updated_at) VALUES (1, 1, 'Home', '#9ece6a', 0, ?1, ?1)",
+///     "INSERT INTO project_list (id, project_id, title, position, wip_limit, created_at, \
+///      updated_at) VALUES (1, 1, 'Doing', 0, 3, ?1, ?1)",
+///     "INSERT INTO project_card (id, project_id, list_id, title, important, position, \
+///      created_at, updated_at) VALUES (1, 1, 1, 'Laundry', 0, 0, ?1, ?1)",
+///     "INSERT INTO card_label (project_id, card_id, label_id, created_at, updated_at) VALUES \
+///      (1, 1, 1, ?1, ?1)",
+///     "INSERT INTO tag (id, title, color, position, created_at, updated_at) VALUES (1, \
+///      'blocked', '#e06c75', 0, ?1, ?1)",
+///     "INSERT INTO card_tag (card_id, tag_id, created_at, updated_at) VALUES (1, 1, ?1, ?1)",
+/// ] {
+///     db.execute(query, [now]).unwrap();
+/// }
+///
+/// assert_eq!(data::clone_project(&db, 1, true).unwrap(), "Week (copy)");
+/// assert_eq!(data::clone_project(&db, 1, false).unwrap(), "Week (copy)");
+///
+/// let conn = db.conn();
+/// let count = |query: &str| conn.query_row(query, [], |r| r.get::<_, i32>(0)).unwrap();
+/// assert_eq!(
+///     count("SELECT COUNT(*) FROM project_list WHERE wip_limit = 3"),
+///     3
+/// );
+/// assert_eq!(count("SELECT COUNT(*) FROM project_card"), 2);
+/// // The label of the cloned card is the label of the clone.
+/// assert_eq!(
+///     count(
+///         "SELECT COUNT(*) FROM card_label cl JOIN project_label l ON l.id = cl.label_id WHERE \
+///          cl.card_id = 2 AND l.project_id = 2"
+///     ),
+///     1
+/// );
+/// // Tags are shared, so the cloned card has the same tag.
+/// assert_eq!(
+///     count("SELECT COUNT(*) FROM card_tag WHERE card_id = 2 AND tag_id = 1"),
+///     1
+/// );
+/// assert_eq!(count("SELECT COUNT(*) FROM tag"), 1);
+/// ```
+pub fn clone_project(db: &Database, project_id: i32, include_cards: bool) -> Result<String> {
+    let start = Instant::now();
+
+    let mut conn = db.conn();
+    let tx = conn.transaction()?;
+
+    let mut project = tx.query_row(
+        "SELECT title, description FROM project WHERE id = ?1",
+        [project_id],
+        |r| {
+            Ok(JsonProject {
+                title: r.get(0)?,
+                description: r.get(1)?,
+                position: 0,
+                // An archived project can be cloned to start over.
+                archived: false,
+                created_at: DateTime::now(),
+                updated_at: DateTime::now(),
+                labels: vec![],
+                lists: vec![],
+            })
+        },
+    )?;
+    project.title = format!("{} (copy)", project.title);
+    project.labels = get_labels(&tx, project_id)?;
+    project.lists = get_lists(&tx, project_id)?;
+    if !include_cards {
+        for list in &mut project.lists {
+            list.cards.clear();
+        }
+    }
+
+    let position: i32 = tx.query_row(
+        "SELECT COALESCE(MAX(position) + 1, 0) FROM project",
+        [],
+        |r| r.get(0),
+    )?;
+    insert_project(&tx, &project, position)?;
+
+    tx.commit()?;
+
+    info!("cloned project {} in {:?}", project_id, start.elapsed());
+
+    Ok(project.title)
+}
+
+/// Export all data to the JSON file.
+pub fn export_json_file(db: &Database) -> Result<PathBuf> {
+    let json = export_json(db)?;
+    let path = json_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, json)?;
+    Ok(path)
+}
+
+/// Import all data from the JSON file. Returns the number of imported projects.
+pub fn import_json_file(db: &Database) -> Result<usize> {
+    let json = fs::read_to_string(json_path())?;
+    import_json(db, &json)
+}
+
+/// The result of a CSV import.
+pub struct CsvImport {
+    pub imported: usize,
+    pub skipped: usize,
+}
+
+/// The file that cards are imported from.
+pub fn cards_csv_path() -> PathBuf {
+    dirs::config_dir().join("imports").join("cards.csv")
+}
+
+/// Import the rows of a CSV file as cards at the end of a list. The header row
+/// names the columns, of which `title` is required and `description`,
+/// `due_date`, and `important` are optional. Rows without a title or with an
+/// invalid due date are skipped. Everything is imported in a single
+/// transaction, so nothing is imported if any part of it fails.
+pub fn import_cards_csv(db: &Database, list_id: i32, path: &Path) -> Result<CsvImport> {
+    let start = Instant::now();
+
+    let mut reader = csv::Reader::from_path(path)?;
+    let headers = reader.headers()?.clone();
+    let column = |name: &str| {
+        headers
+            .iter()
+            .position(|h| h.trim().eq_ignore_ascii_case(name))
+    };
+    let title_column = column("title").ok_or_else(|| eyre!("the csv file has no title column"))?;
+    let description_column = column("description");
+    let due_date_column = column("due_date");
+    let important_column = column("important");
+
+    let mut conn = db.conn();
+    let tx = conn.transaction()?;
+
+    let project_id: i32 = tx.query_row(
+        "SELECT project_id FROM project_list WHERE id = ?1",
+        [list_id],
+        |r| r.get(0),
+    )?;
+    let mut position: i32 = tx.query_row(
+        "SELECT COALESCE(MAX(position) + 1, 0) FROM project_card WHERE list_id = ?1 AND \
+         deleted_at IS NULL",
+        [list_id],
+        |r| r.get(0),
+    )?;
+
+    let mut result = CsvImport {
+        imported: 0,
+        skipped: 0,
+    };
+
+    for (i, record) in reader.records().enumerate() {
+        let record = record?;
+        // The header is the first line.
+        let line = i + 2;
+        let field = |column: Option<usize>| {
+            column
+                .and_then(|c| record.get(c))
+                .map(|f| f.trim())
+                .filter(|f| !f.is_empty())
+        };
+
+        let Some(title) = field(Some(title_column)) else {
+            warn!("skipped csv line {line}: the title is empty");
+            result.skipped += 1;
+            continue;
+        };
+        let due_date = match field(due_date_column) {
+            Some(input) => match DateTime::from_input(input.to_string()) {
+                Some(due_date) => Some(due_date),
+                None => {
+                    warn!("skipped csv line {line}: invalid due date '{input}'");
+                    result.skipped += 1;
+                    continue;
+                }
+            },
+            None => None,
+        };
+        let important = field(important_column)
+            .is_some_and(|f| matches!(f.to_lowercase().as_str(), "1" | "true" | "yes" | "y" | "x"));
+        let priority = if important {
+            Priority::High
+        } else {
+            Priority::None
+        };
+
+        tx.execute(
+            "INSERT INTO project_card (project_id, list_id, title, description, important, \
+             priority, due_date, position, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5, \
+             ?6, ?7, ?8, ?9, ?10)",
+            params![
+                project_id,
+                list_id,
+                title,
+                field(description_column),
+                priority.is_important(),
+                priority.into_db(),
+                due_date,
+                position,
+                DateTime::now(),
+                DateTime::now(),
+            ],
+        )?;
+        position += 1;
+        result.imported += 1;
+    }
+
+    tx.commit()?;
+
+    info!(
+        "imported {} cards from csv in {:?}",
+        result.imported,
+        start.elapsed()
+    );
+
+    Ok(result)
+}
@@ -1,20 +1,33 @@
 //! The Project Management Modules - Similar to Trello or GitHub Projects.
 
 use color_eyre::Result;
-use crossterm::event::KeyEvent;
-use pltx_app::{App, DefaultWidget, KeyEventHandler, Module, Screen};
+use crossterm::event::{KeyEvent, MouseEvent};
+use pltx_app::{
+    state::{AppModule, Navigation},
+    App, CommandRegistry, DefaultWidget, KeyEventHandler, KeybindSection, Module, Screen,
+};
+use pltx_database::Database;
 use pltx_widgets::Tabs;
 use ratatui::{
     layout::{Constraint, Layout, Rect},
     Frame,
 };
+use tracing::error;
 
+pub mod data;
 mod list_projects;
+mod migrations;
 mod open_project;
 pub mod popups;
+pub mod positions;
+mod priority;
 mod projects;
+mod recurrence;
+mod trash;
 
+use priority::Priority;
 use projects::Projects;
+use trash::Trash;
 
 include!("generated_sql.rs");
 
@@ -24,10 +37,12 @@ pub enum Tab {
     Projects,
     Planned,
     Important,
+    Trash,
 }
 
 struct Screens {
     projects: Projects,
+    trash: Trash,
 }
 
 pub struct ProjectManagement {
@@ -37,27 +52,45 @@ pub struct ProjectManagement {
 
 impl Module<Result<()>> for ProjectManagement {
     fn init(app: &App) -> Result<Self> {
-        app.db.conn().execute_batch(SQL)?;
+        Self::init_db(&app.db)?;
+        Trash::db_purge(
+            &app.db,
+            app.config.modules.project_management.trash_retention_days,
+        )?;
 
         Ok(Self {
             tabs: Tabs::from([
                 (Tab::Projects, "Projects"),
                 (Tab::Planned, "Planned"),
                 (Tab::Important, "Important"),
+                (Tab::Trash, "Trash"),
             ]),
             screens: Screens {
                 projects: Projects::init(app)?,
+                trash: Trash::init(app)?,
             },
         })
     }
 
     fn key_event_handler(&mut self, app: &mut App, key_event: KeyEvent) -> Result<()> {
-        self.tabs.key_event_handler(app, key_event);
+        let previous_tab = self.tabs.active.clone();
+        if app.mode.is_normal() {
+            self.tabs.key_event_handler(app, key_event);
+        }
+        if self.tabs.active != previous_tab {
+            match self.tabs.active {
+                Tab::Projects => self.screens.projects.reload(app)?,
+                Tab::Trash => self.screens.trash.db_get_items(&app.db)?,
+                _ => {}
+            }
+            return Ok(());
+        }
 
         match self.tabs.active {
             Tab::Planned => {}
             Tab::Projects => self.screens.projects.key_event_handler(app, key_event)?,
             Tab::Important => {}
+            Tab::Trash => self.screens.trash.key_event_handler(app, key_event)?,
         }
 
         Ok(())
@@ -74,6 +107,158 @@ impl Module<Result<()>> for ProjectManagement {
             Tab::Planned => {}
             Tab::Projects => self.screens.projects.render(app, frame, content_layout),
             Tab::Important => {}
+            Tab::Trash => self.screens.trash.render(app, frame, content_layout),
+        }
+    }
+}
+
+impl ProjectManagement {
+    /// Create the tables of the module and apply its migrations. This is all
+    /// that a database needs for the queries of the module, so it can be used
+    /// with [`Database::in_memory`] in tests.
+    ///
+    /// ```
+    /// # use pltx_database::Database;
+    /// # use pltx_project_management::ProjectManagement;
+    /// let db = Database::in_memory();
+    /// ProjectManagement::init_db(&db).unwrap();
+    /// assert_eq!(db.schema_version("project_management").unwrap(), 8);
+    ///
+    /// // Applying them again leaves the database as it is.
+    /// ProjectManagement::init_db(&db).unwrap();
+    /// assert_eq!(db.schema_version("project_management").unwrap(), 8);
+    /// assert_eq!(db.get_highest_position("project_card").unwrap(), -1);
+    /// ```
+    pub fn init_db(db: &Database) -> Result<()> {
+        db.conn().execute_batch(SQL)?;
+        db.apply_migrations("project_management", migrations::MIGRATIONS)?;
+        Ok(())
+    }
+
+    /// Register the commands of the project management module.
+    pub fn register_commands(commands: &mut CommandRegistry) {
+        commands.register(
+            "project management",
+            "Open the project management module",
+            |app| app.module = AppModule::ProjectManagement,
+        );
+        commands.register(
+            "debug positions",
+            "Show the stored card positions of the open project",
+            |app| app.navigation = Some(Navigation::DebugPositions),
+        );
+        commands.register(
+            "export markdown",
+            "Export the open project to a Markdown file",
+            |app| app.navigation = Some(Navigation::ExportProject),
+        );
+        commands.register(
+            "new card",
+            "Add a card to the selected list of the open project",
+            |app| app.navigation = Some(Navigation::NewCard),
+        );
+        commands.register("new list", "Add a list to the open project", |app| {
+            app.navigation = Some(Navigation::NewList)
+        });
+        commands.register("export json", "Export all projects to a JSON file", |app| {
+            match data::export_json_file(&app.db) {
+                Ok(path) => app.notify_info(format!("Exported all projects to {}", path.display())),
+                Err(e) => {
+                    error!("failed to export json: {e}");
+                    app.notify_error("Failed to export the projects");
+                }
+            }
+        });
+        commands.register(
+            "import json",
+            "Import the projects of the JSON export file",
+            |app| app.navigation = Some(Navigation::ImportJson),
+        );
+
+        commands.leader('n', "new card");
+        commands.leader('l', "new list");
+        commands.leader('e', "export markdown");
+        commands.leader('p', "project management");
+    }
+
+    /// The keybinds of the active tab, for the help popup.
+    pub fn keybinds(&self, app: &App) -> Vec<KeybindSection> {
+        match self.tabs.active {
+            Tab::Projects => self.screens.projects.keybinds(app),
+            Tab::Trash => vec![KeybindSection::new("Trash")
+                .keybind("r", "Restore the item")
+                .keybind("d", "Delete the item permanently")
+                .keybind("D", "Empty the trash")],
+            Tab::Planned | Tab::Important => vec![],
         }
     }
+
+    pub fn mouse_event_handler(&mut self, mouse_event: MouseEvent) {
+        if self.tabs.active == Tab::Projects {
+            self.screens.projects.mouse_event_handler(mouse_event);
+        }
+    }
+
+    /// Open the card positions of the open project, if there is one.
+    pub fn debug_positions(&mut self, app: &mut App) -> Result<()> {
+        self.tabs.active = Tab::Projects;
+        self.screens.projects.debug_positions(app)
+    }
+
+    /// Open the editor of a new card in the open project, if there is one.
+    pub fn new_card(&mut self, app: &mut App) {
+        self.tabs.active = Tab::Projects;
+        self.screens.projects.new_card(app);
+    }
+
+    /// Open the editor of a new list in the open project, if there is one.
+    pub fn new_list(&mut self, app: &mut App) {
+        self.tabs.active = Tab::Projects;
+        self.screens.projects.new_list(app);
+    }
+
+    /// Export the open project to Markdown, if there is one.
+    pub fn export_project(&mut self, app: &mut App) {
+        self.tabs.active = Tab::Projects;
+        self.screens.projects.export_project(app);
+    }
+
+    /// Import the projects of the JSON export file, leaving the existing
+    /// projects as they are.
+    pub fn import_json(&mut self, app: &mut App) -> Result<()> {
+        match data::import_json_file(&app.db) {
+            Ok(count) => {
+                app.notify_info(format!(
+                    "Imported {} projects from {}",
+                    count,
+                    data::json_path().display()
+                ));
+                self.tabs.active = Tab::Projects;
+                self.screens.projects.reload(app)?;
+            }
+            Err(e) => {
+                error!("failed to import json: {e}");
+                app.notify_error(format!("Failed to import the projects: {e}"));
+            }
+        }
+        Ok(())
+    }
+
+    /// Reload the projects and the open project, which count and show the
+    /// cards that are due soon or overdue.
+    pub fn refresh(&mut self, app: &mut App) -> Result<()> {
+        self.screens.projects.reload(app)
+    }
+
+    /// Open a project.
+    pub fn open_project(&mut self, app: &mut App, project_id: i32) -> Result<()> {
+        self.tabs.active = Tab::Projects;
+        self.screens.projects.open_project(app, project_id)
+    }
+
+    /// Open a project with one of its cards focused.
+    pub fn open_card(&mut self, app: &mut App, project_id: i32, card_id: i32) -> Result<()> {
+        self.tabs.active = Tab::Projects;
+        self.screens.projects.open_card(app, project_id, card_id)
+    }
 }
@@ -1,19 +1,25 @@
-use std::time::Instant;
+use std::{cmp::Reverse, collections::HashMap, str::FromStr, time::Instant};
 
 use color_eyre::Result;
 use crossterm::event::{KeyCode, KeyEvent};
-use pltx_app::{App, KeyEventHandler, Screen};
+use pltx_app::{App, KeyEventHandler, Popup, Screen};
 use pltx_database::Database;
 use pltx_utils::{centered_rect, DateTime};
-use pltx_widgets::Scrollable;
+use pltx_widgets::{ConfirmPopup, ListPicker, PopupSize, Scrollable};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Style, Stylize},
+    style::{Color, Style, Stylize},
     text::{Line, Span, Text},
     widgets::{Block, BorderType, Borders, Padding, Paragraph},
     Frame,
 };
-use tracing::{info, info_span};
+use rusqlite::TransactionBehavior;
+use tracing::{error, info, info_span};
+
+use crate::data;
+
+/// The number of label swatches that fit in the labels column.
+const LABEL_SWATCHES: usize = 6;
 
 #[derive(Clone)]
 pub struct Project {
@@ -30,18 +36,65 @@ pub struct Project {
     cards_due_soon: i32,
     cards_overdue: i32,
     important_cards: i32,
+    label_colors: Vec<Color>,
+    archived: bool,
+}
+
+/// The order projects are listed in. Only the position is stored, the other
+/// orders are only for display.
+#[derive(Clone, Copy, PartialEq)]
+enum ProjectSort {
+    Position,
+    Title,
+    Created,
+    Cards,
+}
+
+impl ProjectSort {
+    fn next(self) -> Self {
+        match self {
+            Self::Position => Self::Title,
+            Self::Title => Self::Created,
+            Self::Created => Self::Cards,
+            Self::Cards => Self::Position,
+        }
+    }
+
+    fn title(self) -> &'static str {
+        match self {
+            Self::Position => "position",
+            Self::Title => "title",
+            Self::Created => "newest",
+            Self::Cards => "most cards",
+        }
+    }
 }
 
 pub struct ListProjects {
     pub selection: Scrollable,
     pub projects: Vec<Project>,
+    /// Whether archived projects are listed along with the active ones.
+    show_archived: bool,
+    /// The number of archived projects that are hidden.
+    hidden_archived: i32,
+    sort: ProjectSort,
+    confirm_delete: ConfirmPopup<i32>,
+    /// Picks whether the focused project is cloned with its cards.
+    clone_picker: ListPicker<bool>,
 }
 
 impl Screen<Result<bool>> for ListProjects {
     fn init(app: &App) -> Result<ListProjects> {
         let mut list_projects = ListProjects {
             projects: vec![],
-            selection: Scrollable::default().cols([5, 50, 7, 13, 10, 9, 9, 8]),
+            show_archived: false,
+            hidden_archived: 0,
+            sort: ProjectSort::Position,
+            selection: Scrollable::default().cols([5, 40, 7, 7, 13, 10, 9, 11, 12]),
+            confirm_delete: ConfirmPopup::init(),
+            clone_picker: ListPicker::init()
+                .title("Clone Project")
+                .size(PopupSize::default().width(40).height(8)),
         };
 
         list_projects.db_get_projects(app)?;
@@ -50,11 +103,66 @@ impl Screen<Result<bool>> for ListProjects {
     }
 
     fn key_event_handler(&mut self, app: &mut App, key_event: KeyEvent) -> Result<bool> {
+        if self.clone_picker.is_open() {
+            if let Some(include_cards) = self
+                .clone_picker
+                .handle_key(key_event)
+                .and_then(|picked| picked.into_iter().next())
+            {
+                self.clone_project(app, include_cards)?;
+            }
+            if !self.clone_picker.is_open() {
+                app.mode.normal();
+            }
+            return Ok(false);
+        }
+
         self.selection.key_event_handler(app, key_event);
 
         if app.mode.is_normal() {
             match key_event.code {
-                KeyCode::Char('d') => app.mode.delete(),
+                KeyCode::Char('d') if !self.projects.is_empty() => {
+                    let project = &self.projects[self.selection.focused];
+                    let message = format!(
+                        "Delete project '{}' and its {} {}?",
+                        project.title,
+                        project.total_cards,
+                        if project.total_cards == 1 {
+                            "card"
+                        } else {
+                            "cards"
+                        }
+                    );
+                    self.confirm_delete.open(message, project.id);
+                    app.mode.delete();
+                }
+                KeyCode::Char('D') if !self.projects.is_empty() => {
+                    self.clone_picker.open(vec![
+                        (false, String::from("Lists and labels")),
+                        (true, String::from("Lists, labels, and cards")),
+                    ]);
+                    // Typed keys go to the filter of the picker.
+                    app.mode.insert();
+                }
+                KeyCode::Char('a') if !self.projects.is_empty() => self.db_toggle_archived(app)?,
+                KeyCode::Char('A') => {
+                    self.show_archived = !self.show_archived;
+                    self.db_get_projects(app)?;
+                    self.selection.clamp(self.projects.len());
+                }
+                KeyCode::Char('s') => {
+                    self.sort = self.sort.next();
+                    let focused_id = self.get_id();
+                    self.sort_projects();
+                    if let Some(index) = self.projects.iter().position(|p| Some(p.id) == focused_id)
+                    {
+                        self.selection.focus(index);
+                    }
+                    app.notify_info(format!("Sorted projects by {}", self.sort.title()));
+                }
+                KeyCode::Char('J') | KeyCode::Char('K') if self.sort != ProjectSort::Position => {
+                    app.notify_warning("Sort projects by position to move them");
+                }
                 KeyCode::Char('J') => self.increment_project_position(app)?,
                 KeyCode::Char('K') => self.decrement_project_position(app)?,
                 _ => {}
@@ -62,14 +170,12 @@ impl Screen<Result<bool>> for ListProjects {
         }
 
         if app.mode.is_delete() {
-            match key_event.code {
-                KeyCode::Char('y') => {
-                    self.db_delete_project(&app.db)?;
-                    self.db_get_projects(app)?;
-                    app.mode.normal();
-                }
-                KeyCode::Char('n') => app.mode.normal(),
-                _ => {}
+            if let Some(project_id) = self.confirm_delete.key_event_handler(app, key_event) {
+                self.db_delete_project(&app.db, project_id)?;
+                self.db_get_projects(app)?;
+            }
+            if !self.confirm_delete.is_open() {
+                app.mode.normal();
             }
         }
         Ok(false)
@@ -103,11 +209,19 @@ impl Screen<Result<bool>> for ListProjects {
             .areas(centered_list_layout);
 
         if self.projects.is_empty() {
-            let content = Paragraph::new(Text::from(vec![Line::from(vec![
+            let mut lines = vec![Line::from(vec![
                 Span::from("You have no projects. Press "),
                 Span::styled("n", Style::new().bold().fg(colors.keybind_key)),
                 Span::from(" to create a new project."),
-            ])]));
+            ])];
+            if self.hidden_archived > 0 {
+                lines.push(Line::from(vec![
+                    Span::from(format!("{} archived hidden. Press ", self.hidden_archived)),
+                    Span::styled("A", Style::new().bold().fg(colors.keybind_key)),
+                    Span::from(" to show them."),
+                ]));
+            }
+            let content = Paragraph::new(Text::from(lines));
 
             frame.render_widget(content, list_layout);
             frame.render_widget(Block::new(), info_layout);
@@ -115,11 +229,13 @@ impl Screen<Result<bool>> for ListProjects {
             let header = [
                 Paragraph::new(" "),
                 Paragraph::new("Title"),
+                Paragraph::new("Lists"),
                 Paragraph::new("Cards"),
                 Paragraph::new("In Progress"),
                 Paragraph::new("Due Soon"),
                 Paragraph::new("Overdue"),
                 Paragraph::new("Important"),
+                Paragraph::new("Labels"),
             ]
             .into_iter()
             .map(|p| p.fg(colors.secondary_fg))
@@ -133,6 +249,16 @@ impl Screen<Result<bool>> for ListProjects {
                     vec![
                         Paragraph::new(format!(" {}", p.position)).fg(colors.secondary_fg),
                         Paragraph::new(p.title.to_string()),
+                        Paragraph::new(if p.lists > 0 {
+                            p.lists.to_string()
+                        } else {
+                            String::from("-")
+                        })
+                        .fg(if p.lists > 0 {
+                            colors.secondary_fg
+                        } else {
+                            colors.tertiary_fg
+                        }),
                         Paragraph::new(if p.total_cards > 0 {
                             p.total_cards.to_string()
                         } else {
@@ -183,16 +309,26 @@ impl Screen<Result<bool>> for ListProjects {
                         } else {
                             colors.tertiary_fg
                         }),
+                        // A swatch per label, as many as fit in the column.
+                        Paragraph::new(Line::from(
+                            p.label_colors
+                                .iter()
+                                .take(LABEL_SWATCHES)
+                                .map(|color| Span::from("■ ").fg(*color))
+                                .collect::<Vec<Span>>(),
+                        )),
                     ]
                     .into_iter()
                     .map(|widget| {
                         if self.selection.focused == i {
                             widget.style(
-                                Style::new()
+                                app.config
+                                    .focus_style(colors.active_bg)
                                     .bold()
-                                    .fg(colors.active_fg)
-                                    .bg(colors.active_bg),
+                                    .fg(colors.active_fg),
                             )
+                        } else if p.archived {
+                            widget.fg(colors.tertiary_fg)
                         } else {
                             widget
                         }
@@ -204,7 +340,19 @@ impl Screen<Result<bool>> for ListProjects {
             self.selection
                 .render_with_cols(frame, list_layout, header, table);
 
+            if self.sort != ProjectSort::Position {
+                let sort_area = Rect {
+                    y: list_layout.bottom(),
+                    height: 1,
+                    ..list_layout
+                };
+                let sort = Paragraph::new(format!("Sorted by {}", self.sort.title()))
+                    .fg(colors.secondary_fg);
+                frame.render_widget(sort, sort_area);
+            }
+
             let project = &self.projects[self.selection.focused];
+            let datetime_format = app.config.datetime.datetime_format();
 
             let info_1 = vec![
                 Line::from(vec![
@@ -218,7 +366,9 @@ impl Screen<Result<bool>> for ListProjects {
             ];
             let line_length = info_layout.width as usize - 6;
             let mut first_line_length = line_length - "Description: ".chars().count();
-            let description = if let Some(desc) = &project.description {
+            // Descriptions can have newlines, which are wrapped here instead.
+            let description = project.description.as_ref().map(|d| d.replace('\n', " "));
+            let description = if let Some(desc) = &description {
                 if desc.chars().count() <= first_line_length {
                     first_line_length = 0;
                 }
@@ -252,6 +402,14 @@ impl Screen<Result<bool>> for ListProjects {
                     Span::styled("Position: ", Style::new().fg(colors.secondary_fg)),
                     Span::from(project.position.to_string()),
                 ]),
+                Line::from(vec![
+                    Span::styled("Status: ", Style::new().fg(colors.secondary_fg)),
+                    Span::from(if project.archived {
+                        "Archived"
+                    } else {
+                        "Active"
+                    }),
+                ]),
                 Line::from(vec![
                     Span::styled("Labels: ", Style::new().fg(colors.secondary_fg)),
                     Span::from(project.labels.to_string()),
@@ -282,11 +440,11 @@ impl Screen<Result<bool>> for ListProjects {
                 ]),
                 Line::from(vec![
                     Span::styled("Created At: ", Style::new().fg(colors.secondary_fg)),
-                    Span::from(project.created_at.display()),
+                    Span::from(project.created_at.display_format(&datetime_format)),
                 ]),
                 Line::from(vec![
                     Span::styled("Updated At: ", Style::new().fg(colors.secondary_fg)),
-                    Span::from(project.updated_at.display()),
+                    Span::from(project.updated_at.display_format(&datetime_format)),
                 ]),
             ];
             let info_text = Text::from([info_1, description, info_2].concat());
@@ -301,10 +459,49 @@ impl Screen<Result<bool>> for ListProjects {
             );
             frame.render_widget(info_content, info_layout);
         }
+
+        if app.mode.is_delete() {
+            self.confirm_delete.render(app, frame, area);
+        }
+        self.clone_picker.render(app, frame, area);
     }
 }
 
 impl ListProjects {
+    /// Clone the focused project, and focus the clone.
+    fn clone_project(&mut self, app: &mut App, include_cards: bool) -> Result<()> {
+        let Some(project_id) = self.get_id() else {
+            return Ok(());
+        };
+        match data::clone_project(&app.db, project_id, include_cards) {
+            Ok(title) => {
+                app.notify_info(format!("Cloned the project as '{}'", title));
+                self.db_get_projects(app)?;
+                // The clone has the newest id, wherever it's sorted.
+                if let Some(index) = (0..self.projects.len()).max_by_key(|i| self.projects[*i].id) {
+                    self.selection.focus(index);
+                }
+            }
+            Err(e) => {
+                error!("failed to clone project: {e}");
+                app.notify_error("Failed to clone the project");
+            }
+        }
+        Ok(())
+    }
+
+    /// Sort the projects by the sort order. They're loaded by position.
+    fn sort_projects(&mut self) {
+        match self.sort {
+            ProjectSort::Position => self.projects.sort_by_key(|p| p.position),
+            ProjectSort::Title => self.projects.sort_by_key(|p| p.title.to_lowercase()),
+            ProjectSort::Created => self
+                .projects
+                .sort_by_key(|p| Reverse(p.created_at.datetime)),
+            ProjectSort::Cards => self.projects.sort_by_key(|p| Reverse(p.total_cards)),
+        }
+    }
+
     pub fn get_id(&self) -> Option<i32> {
         if self.projects.is_empty() {
             return None;
@@ -319,10 +516,10 @@ impl ListProjects {
         let start = Instant::now();
 
         let conn = app.db.conn();
-        let project_query = "SELECT id, title, description, position, created_at, updated_at FROM \
-                             project ORDER BY position";
-        let mut project_stmt = conn.prepare(project_query)?;
-        let project_iter = project_stmt.query_map([], |row| {
+        let project_query = "SELECT id, title, description, position, created_at, updated_at, \
+                             archived FROM project WHERE archived = 0 OR ?1 ORDER BY position";
+        let mut project_stmt = conn.prepare_cached(project_query)?;
+        let project_iter = project_stmt.query_map([self.show_archived], |row| {
             Ok(Project {
                 id: row.get(0)?,
                 title: row.get(1)?,
@@ -337,12 +534,21 @@ impl ListProjects {
                 cards_due_soon: 0,
                 cards_overdue: 0,
                 important_cards: 0,
+                label_colors: vec![],
+                archived: row.get(6)?,
             })
         })?;
         let mut projects = Vec::new();
         for p in project_iter {
             projects.push(p?)
         }
+        self.hidden_archived = if self.show_archived {
+            0
+        } else {
+            conn.query_row("SELECT COUNT(*) FROM project WHERE archived = 1", [], |r| {
+                r.get(0)
+            })?
+        };
         info!("get projects query executed in {:?}", start.elapsed());
 
         projects = self.db_get_labels(&app.db, &mut projects)?;
@@ -350,6 +556,7 @@ impl ListProjects {
         projects = self.db_get_cards(app, &mut projects)?;
 
         self.projects = projects;
+        self.sort_projects();
 
         info!(
             "get projects query durations totaled at {:?}",
@@ -359,19 +566,32 @@ impl ListProjects {
         Ok(())
     }
 
+    /// The index of each project by its id. Projects that aren't listed, such
+    /// as hidden archived projects, aren't counted.
+    fn project_indexes(projects: &[Project]) -> HashMap<i32, usize> {
+        projects
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (p.id, i))
+            .collect()
+    }
+
     fn db_get_labels(&self, db: &Database, projects: &mut [Project]) -> Result<Vec<Project>> {
         let start = Instant::now();
+        let indexes = Self::project_indexes(projects);
         let conn = db.conn();
-        let query = "SELECT project_id FROM project_label ORDER BY position";
-        let mut stmt = conn.prepare(query)?;
-        let label_iter = stmt.query_map([], |row| row.get::<usize, i32>(0))?;
-        for label_id in label_iter {
-            let id = label_id?;
-            let index = projects
-                .iter()
-                .position(|p| p.id == id)
-                .expect("failed to get project index");
-            projects[index].labels += 1;
+        let query = "SELECT project_id, color FROM project_label ORDER BY position";
+        let mut stmt = conn.prepare_cached(query)?;
+        let label_iter =
+            stmt.query_map([], |r| Ok((r.get::<_, i32>(0)?, r.get::<_, String>(1)?)))?;
+        for label in label_iter {
+            let (project_id, color) = label?;
+            if let Some(&index) = indexes.get(&project_id) {
+                projects[index].labels += 1;
+                if let Ok(color) = Color::from_str(&color) {
+                    projects[index].label_colors.push(color);
+                }
+            }
         }
         info!("get project labels query executed in {:?}", start.elapsed());
         Ok(projects.to_vec())
@@ -379,17 +599,17 @@ impl ListProjects {
 
     fn db_get_lists(&self, db: &Database, projects: &mut [Project]) -> Result<Vec<Project>> {
         let start = Instant::now();
+        let indexes = Self::project_indexes(projects);
         let conn = db.conn();
-        let query = "SELECT project_id FROM project_list";
-        let mut stmt = conn.prepare(query)?;
-        let id_iter = stmt.query_map([], |r| r.get::<usize, i32>(0))?;
-        for list_id in id_iter {
-            let id = list_id?;
-            let index = projects
-                .iter()
-                .position(|p| p.id == id)
-                .expect("failed to get project index");
-            projects[index].lists += 1;
+        let query = "SELECT project_id, COUNT(*) FROM project_list WHERE deleted_at IS NULL GROUP \
+                     BY project_id";
+        let mut stmt = conn.prepare_cached(query)?;
+        let count_iter = stmt.query_map([], |r| Ok((r.get::<_, i32>(0)?, r.get::<_, i32>(1)?)))?;
+        for count in count_iter {
+            let (project_id, lists) = count?;
+            if let Some(&index) = indexes.get(&project_id) {
+                projects[index].lists = lists;
+            }
         }
         info!("get project lists query executed in {:?}", start.elapsed());
         Ok(projects.to_vec())
@@ -397,33 +617,51 @@ impl ListProjects {
 
     fn db_get_cards(&self, app: &App, projects: &mut [Project]) -> Result<Vec<Project>> {
         let start = Instant::now();
+        let indexes = Self::project_indexes(projects);
+        let conn = app.db.conn();
+
+        let count_query = "SELECT project_id, COUNT(*), SUM(important) FROM project_card WHERE \
+                           deleted_at IS NULL GROUP BY project_id";
+        let mut count_stmt = conn.prepare_cached(count_query)?;
+        let count_iter = count_stmt.query_map([], |r| {
+            Ok((
+                r.get::<_, i32>(0)?,
+                r.get::<_, i32>(1)?,
+                r.get::<_, i32>(2)?,
+            ))
+        })?;
+        for count in count_iter {
+            let (project_id, total, important) = count?;
+            if let Some(&index) = indexes.get(&project_id) {
+                projects[index].total_cards = total;
+                projects[index].important_cards = important;
+            }
+        }
+
+        // Whether a card is in progress, due soon, or overdue depends on the
+        // time and the config, so only the cards with dates are compared.
         struct ListProjectCard {
             project_id: i32,
             start_date: Option<DateTime>,
             due_date: Option<DateTime>,
-            important: bool,
         }
 
-        let conn = app.db.conn();
-        let query = "SELECT project_id, start_date, due_date, important FROM project_card ORDER \
-                     BY position";
-        let mut stmt = conn.prepare(query)?;
+        let query = "SELECT project_id, start_date, due_date FROM project_card WHERE deleted_at \
+                     IS NULL AND (start_date IS NOT NULL OR due_date IS NOT NULL)";
+        let mut stmt = conn.prepare_cached(query)?;
         let card_iter = stmt.query_map([], |row| {
             Ok(ListProjectCard {
                 project_id: row.get(0)?,
                 start_date: DateTime::from_db_option(row.get(1)?),
                 due_date: DateTime::from_db_option(row.get(2)?),
-                important: row.get(3)?,
             })
         })?;
 
         for c in card_iter {
             let card = c?;
-            let index = projects
-                .iter()
-                .position(|p| p.id == card.project_id)
-                .expect("failed to get project index");
-            projects[index].total_cards += 1;
+            let Some(&index) = indexes.get(&card.project_id) else {
+                continue;
+            };
 
             if card.start_date.as_ref().is_some_and(|d| d.is_past())
                 && !card.due_date.as_ref().is_some_and(|d| d.is_past())
@@ -432,9 +670,8 @@ impl ListProjects {
             }
 
             if let Some(due_date) = card.due_date {
-                if due_date.is_past_days(app.config.modules.project_management.due_soon_days)
-                    && !due_date.is_past()
-                {
+                let config = &app.config.modules.project_management;
+                if due_date.is_due_soon(config.due_soon_days, config.day_start_hour) {
                     projects[index].cards_due_soon += 1;
                 }
 
@@ -442,10 +679,6 @@ impl ListProjects {
                     projects[index].cards_overdue += 1;
                 }
             }
-
-            if card.important {
-                projects[index].important_cards += 1;
-            }
         }
 
         info!("get project cards query executed in {:?}", start.elapsed());
@@ -453,45 +686,74 @@ impl ListProjects {
         Ok(projects.to_vec())
     }
 
-    fn db_delete_project(&mut self, db: &Database) -> Result<()> {
-        if let Some(id) = self.get_id() {
-            let start = Instant::now();
-            struct Select {
-                position: i32,
-            }
-            let conn = db.conn();
-            let select_query = "SELECT position FROM project WHERE id = ?1";
-            let mut select_stmt = conn.prepare(select_query)?;
-            let select = select_stmt.query_row([id], |r| {
-                Ok(Select {
-                    position: r.get(0)?,
-                })
-            })?;
-
-            db.execute("DELETE FROM project WHERE id = ?1", [id])?;
+    fn db_delete_project(&mut self, db: &Database, id: i32) -> Result<()> {
+        let start = Instant::now();
+        struct Select {
+            position: i32,
+        }
+        let mut conn = db.conn();
+        let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+        let select_query = "SELECT position FROM project WHERE id = ?1";
+        let select = tx.query_row(select_query, [id], |r| {
+            Ok(Select {
+                position: r.get(0)?,
+            })
+        })?;
 
-            let update_position_query =
-                "UPDATE project SET position = position - 1, updated_at = ?1 WHERE position > ?2";
-            db.execute(update_position_query, (DateTime::now(), select.position))?;
+        tx.execute("DELETE FROM project WHERE id = ?1", [id])?;
 
-            if self.selection.focused == self.selection.row_count.borrow().saturating_sub(1)
-                && self.selection.focused != 0
-            {
-                self.selection.focused -= 1;
-            }
+        let update_position_query =
+            "UPDATE project SET position = position - 1, updated_at = ?1 WHERE position > ?2";
+        tx.execute(update_position_query, (DateTime::now(), select.position))?;
+        tx.commit()?;
 
-            info!("delete project query executed in {:?}", start.elapsed());
+        if self.selection.focused == self.selection.row_count.borrow().saturating_sub(1)
+            && self.selection.focused != 0
+        {
+            self.selection.focused -= 1;
         }
 
+        info!("delete project query executed in {:?}", start.elapsed());
+
+        Ok(())
+    }
+
+    /// Swap the positions of two listed projects. Hidden archived projects can
+    /// be between them, so the positions aren't always next to each other.
+    fn db_swap_positions(&self, db: &Database, index: usize, other_index: usize) -> Result<()> {
+        let (project, other) = (&self.projects[index], &self.projects[other_index]);
+        let mut conn = db.conn();
+        let tx = conn.transaction()?;
+        let query = "UPDATE project SET position = ?1, updated_at = ?2 WHERE id = ?3";
+        tx.execute(query, (other.position, DateTime::now(), project.id))?;
+        tx.execute(query, (project.position, DateTime::now(), other.id))?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Archive the focused project, or make it active again if it's archived.
+    fn db_toggle_archived(&mut self, app: &mut App) -> Result<()> {
+        let project = &self.projects[self.selection.focused];
+        let archived = !project.archived;
+        app.db.execute(
+            "UPDATE project SET archived = ?1, updated_at = ?2 WHERE id = ?3",
+            (archived, DateTime::now(), project.id),
+        )?;
+        app.notify_info(format!(
+            "{} '{}'",
+            if archived { "Archived" } else { "Restored" },
+            project.title
+        ));
+        self.db_get_projects(app)?;
+        self.selection.clamp(self.projects.len());
         Ok(())
     }
 
     fn increment_project_position(&mut self, app: &App) -> Result<()> {
         let start = Instant::now();
         if !self.projects.is_empty() && self.selection.focused + 1 != self.projects.len() {
-            let id = self.projects[self.selection.focused].id;
-            let next_id = self.projects[self.selection.focused + 1].id;
-            app.db.increment_position("project", id, next_id)?;
+            let focused = self.selection.focused;
+            self.db_swap_positions(&app.db, focused, focused + 1)?;
             self.selection.focused += 1;
             info!(
                 "increment project position query executed in {:?}",
@@ -505,9 +767,8 @@ impl ListProjects {
     fn decrement_project_position(&mut self, app: &App) -> Result<()> {
         let start = Instant::now();
         if !self.projects.is_empty() && self.selection.focused != 0 {
-            let id = self.projects[self.selection.focused].id;
-            let prev_id = self.projects[self.selection.focused - 1].id;
-            app.db.decrement_position("project", id, prev_id)?;
+            let focused = self.selection.focused;
+            self.db_swap_positions(&app.db, focused, focused - 1)?;
             self.selection.focused -= 1;
             info!(
                 "decrement project position query executed in {:?}",
@@ -0,0 +1,69 @@
+use pltx_database::{add_column, Migration, MigrationStep};
+
+use crate::Priority;
+
+/// The changes to the tables of `init.sql` since they were first created. New
+/// databases are created with the latest tables, so each migration must leave
+/// them as they are.
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "add the recurrence of cards",
+        step: MigrationStep::Fn(|conn| add_column(conn, "project_card", "recurrence", "TEXT")),
+    },
+    Migration {
+        version: 2,
+        description: "add the completion date of cards",
+        step: MigrationStep::Fn(|conn| {
+            add_column(conn, "project_card", "completed_at", "DATETIME")
+        }),
+    },
+    Migration {
+        version: 3,
+        description: "add the deletion date of lists",
+        step: MigrationStep::Fn(|conn| add_column(conn, "project_list", "deleted_at", "DATETIME")),
+    },
+    Migration {
+        version: 4,
+        description: "add the priority of cards",
+        step: MigrationStep::Fn(|conn| {
+            add_column(
+                conn,
+                "project_card",
+                "priority",
+                "INTEGER NOT NULL DEFAULT 0",
+            )?;
+            // Cards from before priorities were added are only marked important.
+            conn.execute(
+                "UPDATE project_card SET priority = ?1 WHERE important = 1 AND priority = 0",
+                [Priority::High.into_db()],
+            )?;
+            Ok(())
+        }),
+    },
+    Migration {
+        version: 5,
+        description: "add the deletion date of cards",
+        step: MigrationStep::Fn(|conn| add_column(conn, "project_card", "deleted_at", "DATETIME")),
+    },
+    Migration {
+        version: 6,
+        description: "add the WIP limit of lists",
+        step: MigrationStep::Fn(|conn| add_column(conn, "project_list", "wip_limit", "INTEGER")),
+    },
+    Migration {
+        version: 7,
+        description: "add the tags that are shared by all projects",
+        // The tables are created by `init.sql`, which runs before the
+        // migrations, so only the version is changed. The migration is kept
+        // so that the versions of the later migrations don't shift.
+        step: MigrationStep::Sql(""),
+    },
+    Migration {
+        version: 8,
+        description: "add the day of the month that cards recur on",
+        step: MigrationStep::Fn(|conn| {
+            add_column(conn, "project_card", "recurrence_day", "INTEGER")
+        }),
+    },
+];
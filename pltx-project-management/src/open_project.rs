@@ -1,11 +1,21 @@
-use std::{collections::HashSet, str::FromStr, time::Instant};
+use std::{
+    cell::RefCell,
+    cmp,
+    collections::{HashMap, HashSet},
+    fs,
+    ops::Range,
+    str::FromStr,
+    time::Instant,
+};
 
-use color_eyre::Result;
-use crossterm::event::{KeyCode, KeyEvent};
-use pltx_app::{state::AppPopup, App, DefaultWidget, KeyEventHandler, Popup, Screen};
+use color_eyre::{eyre::eyre, Result};
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use pltx_app::{
+    state::AppPopup, App, DefaultWidget, KeyEventHandler, KeybindSection, Popup, Screen,
+};
 use pltx_database::Database;
-use pltx_utils::{DateTime, WidgetMargin};
-use pltx_widgets::{Card, CardBorderType, Scrollable};
+use pltx_utils::{dirs, DateTime, WidgetMargin};
+use pltx_widgets::{Card, CardBorderType, ConfirmPopup, Scrollable, TextInput};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style, Stylize},
@@ -13,9 +23,36 @@ use ratatui::{
     widgets::{Block, BorderType, Borders, Padding, Paragraph, Widget},
     Frame,
 };
-use tracing::{info, info_span};
+use rusqlite::{Transaction, TransactionBehavior};
+use tracing::{error, info, info_span, warn};
+
+use crate::{
+    data,
+    popups::{
+        card_editor::{CardEditor, CARD_TITLE_MAX_LENGTH},
+        card_templates::CardTemplates,
+        card_viewer::CardViewer,
+        debug_positions::DebugPositions,
+        list_editor::ListEditor,
+    },
+    positions,
+    priority::Priority,
+    recurrence::Recurrence,
+};
 
-use crate::popups::{card_editor::CardEditor, card_viewer::CardViewer, list_editor::ListEditor};
+/// The width of a collapsed list, including its margin.
+const COLLAPSED_LIST_WIDTH: u16 = 6;
+const MIN_PROGRESS_BAR_WIDTH: usize = 4;
+const MAX_PROGRESS_BAR_WIDTH: usize = 10;
+/// The number of cards of a project above which the cards of each list are
+/// loaded a page at a time, rather than all at once.
+const LAZY_LOAD_THRESHOLD: usize = 1000;
+/// The number of cards of a list that are loaded at a time when they're loaded
+/// lazily.
+const CARD_PAGE_SIZE: usize = 100;
+/// How close the focus gets to the last loaded card of a list before the next
+/// page is loaded.
+const LOAD_MORE_MARGIN: usize = 10;
 
 #[derive(Clone)]
 pub struct ProjectLabel {
@@ -24,6 +61,15 @@ pub struct ProjectLabel {
     pub color: String,
 }
 
+/// A tag that can be added to the cards of any project, unlike the labels of
+/// a project.
+#[derive(Clone)]
+pub struct Tag {
+    pub id: i32,
+    pub title: String,
+    pub color: String,
+}
+
 #[derive(Clone)]
 struct ProjectCardLabel {
     card_id: i32,
@@ -33,6 +79,7 @@ struct ProjectCardLabel {
 #[derive(Clone)]
 struct ProjectCardSubtask {
     card_id: i32,
+    value: String,
     completed: bool,
 }
 
@@ -42,11 +89,10 @@ struct OpenProjectCard {
     list_id: i32,
     title: String,
     description: Option<String>,
-    important: bool,
+    priority: Priority,
     start_date: Option<DateTime>,
     due_date: Option<DateTime>,
     completed: bool,
-    position: i32,
     labels: HashSet<i32>,
     subtasks: Vec<ProjectCardSubtask>,
 }
@@ -56,8 +102,31 @@ impl OpenProjectCard {
         self.start_date.as_ref().is_some_and(|d| d.is_past()) && !self.overdue()
     }
 
-    fn due_soon(&self, days: i32) -> bool {
-        self.due_date.as_ref().is_some_and(|d| d.is_past_days(days)) && !self.overdue()
+    fn due_soon(&self, days: i32, day_start_hour: i32) -> bool {
+        self.due_date
+            .as_ref()
+            .is_some_and(|d| d.is_due_soon(days, day_start_hour))
+    }
+
+    fn overdue(&self) -> bool {
+        self.due_date.as_ref().is_some_and(|d| d.is_past())
+    }
+}
+
+/// The status of a card that is kept for every card of a list, including the
+/// cards that aren't loaded, so the counts of the board are always complete.
+#[derive(Clone)]
+struct CardSummary {
+    id: i32,
+    completed: bool,
+    due_date: Option<DateTime>,
+}
+
+impl CardSummary {
+    fn due_soon(&self, days: i32, day_start_hour: i32) -> bool {
+        self.due_date
+            .as_ref()
+            .is_some_and(|d| d.is_due_soon(days, day_start_hour))
     }
 
     fn overdue(&self) -> bool {
@@ -69,7 +138,38 @@ impl OpenProjectCard {
 struct ProjectList {
     id: i32,
     title: String,
+    wip_limit: Option<i32>,
+    /// The loaded cards, which are the first cards of the list when the cards
+    /// are loaded lazily.
     cards: Vec<OpenProjectCard>,
+    /// Every card of the list, whether or not it's loaded.
+    summaries: Vec<CardSummary>,
+}
+
+impl ProjectList {
+    /// The number of cards, with the WIP limit when the list has one.
+    fn card_count(&self) -> String {
+        match self.wip_limit {
+            Some(limit) => format!("{}/{}", self.summaries.len(), limit),
+            None => self.summaries.len().to_string(),
+        }
+    }
+
+    /// The color of the list title when the list is at or over its WIP limit.
+    fn wip_limit_color(&self, app: &App) -> Option<Color> {
+        let colors = &app.config.colors;
+        let count = self.summaries.len() as i32;
+        match self.wip_limit {
+            Some(limit) if count > limit => Some(colors.danger),
+            Some(limit) if count == limit => Some(colors.warning),
+            _ => None,
+        }
+    }
+
+    fn at_wip_limit(&self) -> bool {
+        self.wip_limit
+            .is_some_and(|limit| self.summaries.len() as i32 >= limit)
+    }
 }
 
 #[derive(Default, Clone)]
@@ -79,6 +179,71 @@ struct ProjectData {
     lists: Vec<ProjectList>,
 }
 
+impl ProjectData {
+    /// The project as a Markdown document, with a section per list and a
+    /// checklist item per card.
+    fn to_markdown(&self) -> String {
+        let mut markdown = format!("# {}\n", self.title);
+        for list in &self.lists {
+            markdown.push_str(&format!("\n## {}\n\n", list.title));
+            for card in &list.cards {
+                let subtasks = card
+                    .subtasks
+                    .iter()
+                    .map(|s| (s.completed, s.value.as_str()))
+                    .collect::<Vec<(bool, &str)>>();
+                markdown.push_str(&card_markdown(
+                    &card.title,
+                    card.completed,
+                    &self.card_label_titles(&card.labels),
+                    card.description.as_deref(),
+                    &subtasks,
+                ));
+            }
+        }
+
+        markdown
+    }
+
+    fn card_label_titles(&self, label_ids: &HashSet<i32>) -> Vec<&str> {
+        self.labels
+            .iter()
+            .filter(|l| label_ids.contains(&l.id))
+            .map(|l| l.title.as_str())
+            .collect()
+    }
+}
+
+/// A card as a Markdown checklist item, with its labels after the title and
+/// its description and subtasks indented below it.
+pub fn card_markdown(
+    title: &str,
+    completed: bool,
+    labels: &[&str],
+    description: Option<&str>,
+    subtasks: &[(bool, &str)],
+) -> String {
+    let checkbox = |completed: bool| if completed { "- [x]" } else { "- [ ]" };
+
+    let mut markdown = format!("{} {}", checkbox(completed), title);
+    if !labels.is_empty() {
+        markdown.push_str(&format!(" ({})", labels.join(", ")));
+    }
+    markdown.push('\n');
+
+    if let Some(description) = description {
+        for line in description.lines() {
+            markdown.push_str(format!("  {}", line).trim_end());
+            markdown.push('\n');
+        }
+    }
+    for (completed, value) in subtasks {
+        markdown.push_str(&format!("  {} {}\n", checkbox(*completed), value));
+    }
+
+    markdown
+}
+
 #[derive(PartialEq)]
 enum OpenProjectPopup {
     NewList,
@@ -86,6 +251,8 @@ enum OpenProjectPopup {
     ViewCard,
     NewCard,
     EditCard,
+    CardTemplates,
+    DebugPositions,
     None,
 }
 
@@ -95,13 +262,17 @@ struct Popups {
     view_card: CardViewer,
     new_card: CardEditor,
     edit_card: CardEditor,
+    card_templates: CardTemplates,
+    debug_positions: DebugPositions,
 }
 
+/// An action on the board that has to be confirmed first.
 #[derive(PartialEq)]
-enum DeleteSelection {
-    List,
-    Card,
-    None,
+enum ConfirmAction {
+    DeleteList,
+    DeleteCard,
+    /// Set whether every card in the selected list is completed.
+    CompleteList(bool),
 }
 
 #[derive(PartialEq)]
@@ -110,15 +281,57 @@ enum Focus {
     Card,
 }
 
+/// A card that is being moved in grab mode, with where every card of the
+/// project was when it was grabbed, so that the move can be cancelled.
+struct GrabbedCard {
+    id: i32,
+    /// The list id and position of each card, by card id.
+    positions: HashMap<i32, (i32, i32)>,
+}
+
+/// Where a list was last rendered, so the list or card under the mouse can be
+/// found.
+struct ListArea {
+    list_index: usize,
+    area: Rect,
+    cards_area: Rect,
+}
+
 pub struct OpenProject {
     project_id: Option<i32>,
     selected_list_index: usize,
     data: ProjectData,
     popup: OpenProjectPopup,
     popups: Popups,
-    delete_selection: DeleteSelection,
+    confirm: ConfirmPopup<ConfirmAction>,
     list_selections: Vec<Scrollable>,
     focus: Focus,
+    collapsed_lists: HashSet<i32>,
+    pending_keys: String,
+    /// The count typed before a motion, such as the `5` in `5j`. It is 0 when
+    /// no count has been typed.
+    pending_count: usize,
+    /// Whether the `wrap_card_titles` config has been toggled on this board.
+    wrap_titles_toggled: bool,
+    /// Whether only the selected list is shown. Unlike collapsing, the other
+    /// lists are hidden entirely and the selected list takes the full width.
+    zoomed: bool,
+    /// The index of the first list in view when the lists don't fit on the
+    /// board. It is updated when rendering, since it depends on the width.
+    list_offset: RefCell<usize>,
+    list_areas: RefCell<Vec<ListArea>>,
+    /// The card that moves with the motion keys, until it's dropped with
+    /// `enter` or put back with `esc`.
+    grabbed: Option<GrabbedCard>,
+    /// The input at the bottom of the selected list that adds a card with each
+    /// title that's entered, while it's open.
+    quick_add: Option<TextInput>,
+    /// Whether the project has too many cards to load them all, so the cards
+    /// of each list are loaded a page at a time as the list is scrolled.
+    lazy: bool,
+    /// The number of cards that are loaded of each list, by list id, while the
+    /// cards are loaded lazily. Lists that aren't in it have one page loaded.
+    card_limits: HashMap<i32, usize>,
 }
 
 impl Screen<Result<bool>> for OpenProject {
@@ -134,10 +347,23 @@ impl Screen<Result<bool>> for OpenProject {
                 view_card: CardViewer::init(),
                 new_card: CardEditor::init(),
                 edit_card: CardEditor::init(),
+                card_templates: CardTemplates::init(),
+                debug_positions: DebugPositions::init(),
             },
-            delete_selection: DeleteSelection::None,
+            confirm: ConfirmPopup::init(),
             list_selections: vec![],
             focus: Focus::Card,
+            collapsed_lists: HashSet::new(),
+            pending_keys: String::new(),
+            pending_count: 0,
+            wrap_titles_toggled: false,
+            zoomed: false,
+            list_offset: RefCell::new(0),
+            list_areas: RefCell::new(vec![]),
+            grabbed: None,
+            quick_add: None,
+            lazy: false,
+            card_limits: HashMap::new(),
         })
     }
 
@@ -164,9 +390,10 @@ impl Screen<Result<bool>> for OpenProject {
                 OpenProjectPopup::NewCard => {
                     if self.popups.new_card.key_event_handler(app, key_event)? {
                         self.db_get_project(app)?;
-                        let last_card_index =
-                            self.data.lists[self.selected_list_index].cards.len() - 1;
-                        self.list_selections[self.selected_list_index].focused = last_card_index;
+                        if let Some(card_id) = self.popups.new_card.take_new_card_id() {
+                            self.db_load_card(app, card_id)?;
+                            self.focus_card(card_id);
+                        }
                     }
                 }
                 OpenProjectPopup::EditCard => {
@@ -174,22 +401,58 @@ impl Screen<Result<bool>> for OpenProject {
                         self.db_get_project(app)?
                     }
                 }
+                OpenProjectPopup::CardTemplates => {
+                    if let Some(template_id) = self
+                        .popups
+                        .card_templates
+                        .key_event_handler(app, key_event)?
+                    {
+                        self.popups.new_card.set_template(&app.db, template_id)?;
+                        self.popup = OpenProjectPopup::NewCard;
+                    }
+                }
+                OpenProjectPopup::DebugPositions => {
+                    self.popups
+                        .debug_positions
+                        .key_event_handler(app, key_event)?;
+                }
                 OpenProjectPopup::None => {}
             };
         }
 
+        if app.view.is_default() && self.quick_add.is_some() {
+            self.quick_add_key_event_handler(app, key_event)?;
+            return Ok(false);
+        }
+
+        if app.view.is_default() && app.mode.is_normal() && self.grabbed.is_some() {
+            self.grab_key_event_handler(app, key_event)?;
+            return Ok(false);
+        }
+
+        if app.view.is_default() && app.mode.is_normal() && self.count_handler(key_event) {
+            return Ok(false);
+        }
+        let count = cmp::max(std::mem::take(&mut self.pending_count), 1);
+
+        if app.view.is_default()
+            && app.mode.is_normal()
+            && self.key_sequence_handler(app, key_event)
+        {
+            return Ok(false);
+        }
+
         if app.view.is_default() && app.mode.is_normal() {
             match key_event.code {
                 KeyCode::Char('[') => return Ok(true),
                 KeyCode::Char('h') => {
-                    if self.selected_list_index != 0 {
-                        self.selected_list_index -= 1;
-                    }
+                    self.selected_list_index = self.selected_list_index.saturating_sub(count);
                 }
                 KeyCode::Char('l') => {
-                    if self.selected_list_index != self.data.lists.len().saturating_sub(1) {
-                        self.selected_list_index += 1;
-                    }
+                    self.selected_list_index = cmp::min(
+                        self.selected_list_index.saturating_add(count),
+                        self.data.lists.len().saturating_sub(1),
+                    );
                 }
                 _ => {}
             }
@@ -201,26 +464,56 @@ impl Screen<Result<bool>> for OpenProject {
                     KeyCode::Char('j') => {
                         self.focus = Focus::Card;
                     }
+                    KeyCode::Char('I') => self.import_cards_csv(app)?,
+                    KeyCode::Char('o') => self.open_quick_add(app),
+                    KeyCode::Char('C') => {
+                        if let Some(list) = self.data.lists.get(self.selected_list_index) {
+                            if !list.summaries.is_empty() {
+                                // Mixed lists are completed rather than toggled card by card.
+                                let completed = !list.summaries.iter().all(|c| c.completed);
+                                let message = format!(
+                                    "{} all {} cards in '{}'?",
+                                    if completed { "Complete" } else { "Reopen" },
+                                    list.summaries.len(),
+                                    list.title
+                                );
+                                self.confirm
+                                    .open(message, ConfirmAction::CompleteList(completed));
+                                app.mode.delete();
+                            }
+                        }
+                    }
                     KeyCode::Char('d') => {
                         if self.project_id.is_some() && !self.data.lists.is_empty() {
-                            self.delete_selection = DeleteSelection::List;
+                            let list = &self.data.lists[self.selected_list_index];
+                            let message = format!(
+                                "Move list '{}' and its {} {} to the trash?",
+                                list.title,
+                                list.summaries.len(),
+                                if list.summaries.len() == 1 {
+                                    "card"
+                                } else {
+                                    "cards"
+                                }
+                            );
+                            self.confirm.open(message, ConfirmAction::DeleteList);
                             app.mode.delete();
                         }
                     }
                     KeyCode::Char('e') => {
                         if !self.data.lists.is_empty() {
                             let list_id = self.data.lists[self.selected_list_index].id;
+                            if let Err(e) = self.popups.edit_list.set(&app.db, list_id) {
+                                error!("failed to get list: {e}");
+                                app.notify_error("Failed to open the list");
+                                return Ok(false);
+                            }
                             self.popup = OpenProjectPopup::EditList;
-                            self.popups.edit_list.set(&app.db, list_id)?;
                             app.view.popup();
                             app.mode.insert();
                         }
                     }
-                    KeyCode::Char('n') => {
-                        self.popup = OpenProjectPopup::NewList;
-                        app.view.popup();
-                        app.mode.insert();
-                    }
+                    KeyCode::Char('n') => self.new_list(app),
                     _ => {}
                 }
             } else if self.focus == Focus::Card
@@ -230,6 +523,11 @@ impl Screen<Result<bool>> for OpenProject {
                 self.popup = OpenProjectPopup::NewList;
                 app.view.popup();
                 app.mode.insert();
+            } else if self.focus == Focus::Card && self.is_list_collapsed(self.selected_list_index)
+            {
+                if key_event.code == KeyCode::Char('k') {
+                    self.focus = Focus::List;
+                }
             } else if self.focus == Focus::Card && !self.data.lists.is_empty() {
                 if self.list_selections[self.selected_list_index].focused == 0
                     && key_event.code == KeyCode::Char('k')
@@ -237,7 +535,7 @@ impl Screen<Result<bool>> for OpenProject {
                     self.focus = Focus::List;
                 } else {
                     self.list_selections[self.selected_list_index]
-                        .key_event_handler(app, key_event);
+                        .counted_key_event_handler(app, key_event, count);
                 }
 
                 match key_event.code {
@@ -245,6 +543,8 @@ impl Screen<Result<bool>> for OpenProject {
                     KeyCode::Char('K') => self.decrement_card_position(app)?,
                     KeyCode::Char('H') => self.move_card_left(app)?,
                     KeyCode::Char('L') => self.move_card_right(app)?,
+                    KeyCode::Char(' ') => self.db_grab_card(app)?,
+                    KeyCode::Char('o') => self.open_quick_add(app),
                     KeyCode::Enter => {
                         if !self.data.lists.is_empty()
                             && !self.data.lists[self.selected_list_index].cards.is_empty()
@@ -255,19 +555,11 @@ impl Screen<Result<bool>> for OpenProject {
                                 self.data.lists[self.selected_list_index].cards[card_index].id;
                             self.popups.view_card.id(card_id);
                             self.popups.view_card.set_data(&app.db, card_id)?;
+                            self.popups.view_card.focus_initial_section(app);
                             app.view.popup();
                         }
                     }
-                    KeyCode::Char('n') => {
-                        if let Some(project_id) = self.project_id {
-                            if !self.data.lists.is_empty() {
-                                let list_id = self.data.lists[self.selected_list_index].id;
-                                self.popups.new_card.ids(project_id, list_id);
-                                self.popup = OpenProjectPopup::NewCard;
-                                app.view.popup();
-                            }
-                        }
-                    }
+                    KeyCode::Char('n') => self.new_card(app),
                     KeyCode::Char('e') => {
                         if let Some(project_id) = self.project_id {
                             let selected_list_has_cards =
@@ -285,13 +577,50 @@ impl Screen<Result<bool>> for OpenProject {
                             }
                         }
                     }
+                    KeyCode::Char('t') => {
+                        if let Some(card) = self.get_card() {
+                            self.popups
+                                .card_templates
+                                .db_new_template(&app.db, card.id)?;
+                        }
+                    }
+                    KeyCode::Char('T') => {
+                        if let Some(project_id) = self.project_id {
+                            if self.allow_card_into(app, self.selected_list_index) {
+                                let list_id = self.data.lists[self.selected_list_index].id;
+                                self.popups.new_card.ids(project_id, list_id);
+                                self.popups.card_templates.db_get_templates(&app.db)?;
+                                self.popup = OpenProjectPopup::CardTemplates;
+                                app.view.popup();
+                            }
+                        }
+                    }
                     KeyCode::Char('c') => self.db_toggle_card_completed(app)?,
                     KeyCode::Char('i') => self.db_toggle_card_important(app)?,
+                    KeyCode::Char('x') => self.db_clear_card_dates(app, false)?,
+                    KeyCode::Char('X') => self.db_clear_card_dates(app, true)?,
+                    KeyCode::Char('w') => self.wrap_titles_toggled = !self.wrap_titles_toggled,
+                    KeyCode::Char('E') => self.export_markdown(app),
+                    KeyCode::Char(char)
+                        if app.config.modules.project_management.next_status_key
+                            == char.to_string() =>
+                    {
+                        self.step_card_status(app, true)?
+                    }
+                    KeyCode::Char(char)
+                        if app.config.modules.project_management.prev_status_key
+                            == char.to_string() =>
+                    {
+                        self.step_card_status(app, false)?
+                    }
                     KeyCode::Char('d') => {
                         if !self.data.lists.is_empty()
                             && !self.data.lists[self.selected_list_index].cards.is_empty()
                         {
-                            self.delete_selection = DeleteSelection::Card;
+                            let card_index = self.list_selections[self.selected_list_index].focused;
+                            let card = &self.data.lists[self.selected_list_index].cards[card_index];
+                            let message = format!("Move card '{}' to the trash?", card.title);
+                            self.confirm.open(message, ConfirmAction::DeleteCard);
                             app.mode.delete();
                         }
                     }
@@ -300,26 +629,26 @@ impl Screen<Result<bool>> for OpenProject {
             }
         }
 
-        if app.mode.is_delete() {
-            match key_event.code {
-                KeyCode::Char('y') => {
-                    if self.delete_selection == DeleteSelection::List {
-                        if !self.data.lists.is_empty() {
-                            self.db_delete_list(&app.db)?;
-                            self.db_get_project(app)?;
-                            app.mode.normal();
-                        }
-                    } else if self.delete_selection == DeleteSelection::Card {
-                        self.db_delete_card(&app.db)?;
-                        self.db_get_project(app)?;
-                        app.mode.normal();
-                    }
-                    self.delete_selection = DeleteSelection::None;
+        // The popups have their own confirmations in delete mode.
+        if self.confirm.is_open() {
+            match self.confirm.key_event_handler(app, key_event) {
+                Some(ConfirmAction::DeleteList) if !self.data.lists.is_empty() => {
+                    self.db_delete_list(&app.db)?;
+                    self.db_get_project(app)?;
+                }
+                Some(ConfirmAction::DeleteCard) => {
+                    self.db_delete_card(&app.db)?;
+                    self.db_get_project(app)?;
+                }
+                Some(ConfirmAction::CompleteList(completed)) if !self.data.lists.is_empty() => {
+                    self.db_set_list_completed(&app.db, completed)?;
+                    self.db_get_project(app)?;
                 }
-                KeyCode::Char('n') => app.mode.normal(),
-
                 _ => {}
             }
+            if !self.confirm.is_open() {
+                app.mode.normal();
+            }
         }
         Ok(false)
     }
@@ -332,11 +661,13 @@ impl Screen<Result<bool>> for OpenProject {
             .constraints([Constraint::Length(3), Constraint::Fill(1)])
             .areas(area);
 
-        let title = Paragraph::new(Line::from(vec![
+        let mut title_spans = vec![
             Span::from("Project: ").fg(colors.secondary_fg),
             Span::from(self.data.title.to_string()),
-        ]))
-        .block(
+        ];
+        title_spans.extend(self.title_counts(app));
+
+        let title = Paragraph::new(Line::from(title_spans)).block(
             Block::new()
                 .padding(Padding::horizontal(1))
                 .borders(Borders::ALL)
@@ -346,6 +677,7 @@ impl Screen<Result<bool>> for OpenProject {
 
         frame.render_widget(title, title_area);
 
+        self.list_areas.borrow_mut().clear();
         if self.data.lists.is_empty() {
             let content = Paragraph::new(Text::from(vec![Line::from(vec![
                 Span::from("You have no lists in your project. Press "),
@@ -356,29 +688,149 @@ impl Screen<Result<bool>> for OpenProject {
 
             frame.render_widget(content, list_areas)
         } else {
+            let max_single_list_width = app.config.modules.project_management.max_single_list_width;
+            let list_areas = if self.data.lists.len() == 1
+                && max_single_list_width > 0
+                && (max_single_list_width as u16) < list_areas.width
+            {
+                let [_, centered_area, _] = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints([
+                        Constraint::Fill(1),
+                        Constraint::Length(max_single_list_width as u16),
+                        Constraint::Fill(1),
+                    ])
+                    .areas(list_areas);
+                centered_area
+            } else {
+                list_areas
+            };
+
+            let visible_lists = if self.zoomed {
+                vec![self.selected_list_index]
+            } else {
+                self.visible_list_range(app, list_areas.width)
+                    .collect::<Vec<usize>>()
+            };
+
+            // Arrows in the outer columns show that there are lists out of view.
+            let list_areas = if visible_lists.len() < self.data.lists.len() && !self.zoomed {
+                let [left_arrow_area, list_areas, right_arrow_area] = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints([
+                        Constraint::Length(1),
+                        Constraint::Fill(1),
+                        Constraint::Length(1),
+                    ])
+                    .areas(list_areas);
+                let mut arrow = |arrow: &'static str, area: Rect, show: bool| {
+                    if show {
+                        let arrow_area = Rect {
+                            y: area.y + area.height / 2,
+                            height: 1,
+                            ..area
+                        };
+                        frame.render_widget(
+                            Paragraph::new(arrow).fg(colors.secondary_fg),
+                            arrow_area,
+                        );
+                    }
+                };
+                arrow("◀", left_arrow_area, visible_lists[0] != 0);
+                arrow(
+                    "▶",
+                    right_arrow_area,
+                    visible_lists
+                        .last()
+                        .is_some_and(|i| *i + 1 < self.data.lists.len()),
+                );
+                list_areas
+            } else {
+                list_areas
+            };
+
             let project_layout = Layout::default()
                 .direction(Direction::Horizontal)
                 .constraints(
-                    self.data
-                        .lists
+                    visible_lists
                         .iter()
-                        .map(|_| Constraint::Fill(1))
+                        .map(|list_index| {
+                            if self.is_list_collapsed(*list_index) {
+                                Constraint::Length(COLLAPSED_LIST_WIDTH)
+                            } else {
+                                Constraint::Fill(1)
+                            }
+                        })
                         .collect::<Vec<Constraint>>(),
                 )
                 .split(list_areas);
 
-            for (list_index, list_layout) in project_layout.iter().enumerate() {
+            for (i, list_layout) in project_layout.iter().enumerate() {
+                let list_index = visible_lists[i];
                 let list_width = list_areas.width as usize - 2;
                 let list = &self.data.lists[list_index];
+                let margin = if i == 0 {
+                    WidgetMargin::zero()
+                } else {
+                    WidgetMargin::left(1)
+                };
+
+                // The divider is drawn in the margin of the list, so it doesn't take
+                // any width away from the cards.
+                if i != 0 && app.config.modules.project_management.list_dividers {
+                    let divider = Paragraph::new(
+                        (0..list_layout.height)
+                            .map(|_| Line::from("│"))
+                            .collect::<Vec<Line>>(),
+                    )
+                    .fg(colors.tab_border);
+                    frame.render_widget(
+                        divider,
+                        Rect {
+                            width: 1,
+                            ..*list_layout
+                        },
+                    );
+                }
 
-                let list_card = Card::new(&format!(" {} ", list.title), *list_layout)
+                if self.is_list_collapsed(list_index) {
+                    let list_card = Card::new(&list.card_count(), *list_layout)
+                        .focused_title(self.focus == Focus::List)
+                        .title_color(list.wip_limit_color(app))
+                        .border_type(CardBorderType::Rounded)
+                        .margin(margin);
+                    self.save_list_area(list_index, *list_layout, list_card.child_layout());
+
+                    list_card.render(
+                        frame,
+                        app,
+                        *list_layout,
+                        self.selected_list_index == list_index,
+                    );
+
+                    let vertical_title = list
+                        .title
+                        .chars()
+                        .map(|c| Line::from(c.to_string()).centered())
+                        .collect::<Vec<Line>>();
+                    frame.render_widget(
+                        Paragraph::new(vertical_title).fg(colors.secondary_fg),
+                        list_card.child_layout(),
+                    );
+
+                    continue;
+                }
+
+                let list_title = if self.zoomed {
+                    format!(" {} ({}) (zoomed) ", list.title, list.card_count())
+                } else {
+                    format!(" {} ({}) ", list.title, list.card_count())
+                };
+                let list_card = Card::new(&list_title, *list_layout)
                     .focused_title(self.focus == Focus::List)
+                    .title_color(list.wip_limit_color(app))
                     .border_type(CardBorderType::Rounded)
-                    .margin(if list_index == 0 {
-                        WidgetMargin::zero()
-                    } else {
-                        WidgetMargin::left(1)
-                    });
+                    .margin(margin);
 
                 list_card.render(
                     frame,
@@ -387,6 +839,18 @@ impl Screen<Result<bool>> for OpenProject {
                     self.selected_list_index == list_index,
                 );
 
+                let cards_area = match &self.quick_add {
+                    Some(quick_add) if self.selected_list_index == list_index => {
+                        let [cards_area, quick_add_area] = Layout::default()
+                            .constraints([Constraint::Fill(1), Constraint::Length(3)])
+                            .areas(list_card.child_layout());
+                        quick_add.render(frame, app, quick_add_area, true);
+                        cards_area
+                    }
+                    _ => list_card.child_layout(),
+                };
+                self.save_list_area(list_index, *list_layout, cards_area);
+
                 if list.cards.is_empty() {
                     frame.render_widget(
                         Line::from(vec![
@@ -394,17 +858,25 @@ impl Screen<Result<bool>> for OpenProject {
                             Span::from("n").bold().fg(colors.keybind_key),
                             Span::from(" to create a new task."),
                         ]),
-                        list_card.child_layout(),
+                        cards_area,
                     );
                 } else {
                     let mut table = vec![];
+                    let content_width = cards_area.width as usize;
 
                     for (card_index, card) in list.cards.iter().enumerate() {
-                        let card = self.render_card(app, card, list_index, card_index, list_width);
+                        let card = self.render_card(
+                            app,
+                            card,
+                            list_index,
+                            card_index,
+                            list_width,
+                            content_width,
+                        );
                         table.push(card);
                     }
 
-                    self.list_selections[list_index].render(frame, list_card.child_layout(), table);
+                    self.list_selections[list_index].render_rows(frame, cards_area, table);
                 }
             }
         }
@@ -416,9 +888,19 @@ impl Screen<Result<bool>> for OpenProject {
                 OpenProjectPopup::ViewCard => self.popups.view_card.render(app, frame, list_areas),
                 OpenProjectPopup::NewCard => self.popups.new_card.render(app, frame, list_areas),
                 OpenProjectPopup::EditCard => self.popups.edit_card.render(app, frame, list_areas),
+                OpenProjectPopup::CardTemplates => {
+                    self.popups.card_templates.render(app, frame, list_areas)
+                }
+                OpenProjectPopup::DebugPositions => {
+                    self.popups.debug_positions.render(app, frame, list_areas)
+                }
                 OpenProjectPopup::None => {}
             }
         }
+
+        if app.mode.is_delete() {
+            self.confirm.render(app, frame, list_areas);
+        }
     }
 }
 
@@ -430,7 +912,8 @@ impl OpenProject {
         list_index: usize,
         card_index: usize,
         list_width: usize,
-    ) -> impl Widget {
+        content_width: usize,
+    ) -> (impl Widget, u16) {
         let colors = &app.config.colors;
 
         let selected = self.selected_list_index == list_index
@@ -443,43 +926,86 @@ impl OpenProject {
             &config.completed_char
         } else if card.overdue() {
             &config.overdue_char
-        } else if card.due_soon(app.config.modules.project_management.due_soon_days) {
+        } else if card.priority == Priority::Urgent {
+            &config.urgent_char
+        } else if card.due_soon(config.due_soon_days, config.day_start_hour) {
             &config.due_soon_char
         } else if card.in_progress() {
             &config.in_progress_char
-        } else if card.important {
+        } else if card.priority == Priority::High {
             &config.important_char
+        } else if card.priority == Priority::Medium {
+            &config.medium_priority_char
+        } else if card.priority == Priority::Low {
+            &config.low_priority_char
         } else {
             &config.default_char
         };
 
-        let line_style =
-            if self.selected_list_index == list_index && selected && self.focus == Focus::Card {
-                Style::new().bold().fg(colors.fg).bg(colors.input_focus_bg)
-            } else if unfocused_selected {
-                Style::new().bold().fg(colors.fg)
-            } else {
-                Style::new().fg(colors.secondary_fg)
-            };
+        let grabbed = self.grabbed.as_ref().is_some_and(|g| g.id == card.id);
+        let line_style = if grabbed {
+            app.config
+                .focus_style(colors.active_bg)
+                .bold()
+                .fg(colors.active_fg)
+        } else if self.selected_list_index == list_index && selected && self.focus == Focus::Card {
+            app.config
+                .focus_style(colors.input_focus_bg)
+                .bold()
+                .fg(colors.fg)
+        } else if unfocused_selected {
+            Style::new().bold().fg(colors.fg)
+        } else {
+            Style::new().fg(colors.secondary_fg)
+        };
 
-        let title = Line::from(vec![
-            Span::from(format!(" [{}] ", status_char)).fg(
-                if self.selected_list_index == list_index && selected {
-                    colors.fg
-                } else {
-                    colors.secondary_fg
-                },
-            ),
+        let status = Span::from(format!(" [{}] ", status_char));
+        let status_width = status.width();
+
+        // Only titles that don't fit are wrapped, so short titles keep their single
+        // line even when wrapping is enabled.
+        let (first_line, second_line) = if self.wrap_titles(app) {
+            split_at_width(&card.title, content_width.saturating_sub(status_width))
+        } else {
+            (card.title.to_string(), String::new())
+        };
+
+        let title_span = |text: String| {
             if card.completed {
-                Span::from(card.title.to_string())
+                Span::from(text)
                     .fg(colors.secondary_fg)
                     .add_modifier(Modifier::CROSSED_OUT)
+            } else if grabbed {
+                Span::from(text).fg(colors.active_fg)
             } else {
-                Span::from(card.title.to_string()).fg(colors.fg)
-            },
-            Span::from(" ".repeat(list_width.saturating_sub(card.title.chars().count() + 2))),
+                Span::from(text).fg(colors.fg)
+            }
+        };
+
+        let first_line_padding = list_width.saturating_sub(first_line.chars().count() + 2);
+        let mut lines = vec![Line::from(vec![
+            status.fg(if grabbed {
+                colors.active_fg
+            } else if self.selected_list_index == list_index && selected {
+                colors.fg
+            } else {
+                colors.secondary_fg
+            }),
+            title_span(first_line),
+            Span::from(" ".repeat(first_line_padding)),
         ])
-        .style(line_style);
+        .style(line_style)];
+
+        if !second_line.is_empty() {
+            lines.push(
+                Line::from(vec![
+                    Span::from(" ".repeat(status_width)),
+                    title_span(second_line),
+                    Span::from(" ".repeat(list_width)),
+                ])
+                .style(line_style),
+            );
+        }
 
         let mut details = vec![Span::from(" ".repeat(5)).fg(colors.tertiary_fg)];
 
@@ -488,114 +1014,801 @@ impl OpenProject {
         }
 
         if !card.subtasks.is_empty() {
-            details.push(
-                Span::from(format!(
-                    " {}",
-                    card.subtasks.iter().filter(|st| st.completed).count(),
-                ))
-                .fg(colors.success),
+            let completed_subtasks = card.subtasks.iter().filter(|st| st.completed).count();
+
+            let label_width = card.labels.len() * 2;
+            let description_width = if card.description.is_some() { 2 } else { 0 };
+            let bar_width = cmp::min(
+                MAX_PROGRESS_BAR_WIDTH,
+                content_width.saturating_sub(5 + description_width + label_width + 2),
             );
-            details.push(Span::from("/").fg(colors.secondary_fg));
-            details.push(Span::from(card.subtasks.len().to_string()));
-            details.push(Span::from(" "));
-        }
 
-        for label in self.data.labels.iter() {
-            if card.labels.contains(&label.id) {
+            if config.show_progress_bar && bar_width >= MIN_PROGRESS_BAR_WIDTH {
+                let (filled, unfilled) =
+                    progress_bar(completed_subtasks, card.subtasks.len(), bar_width);
+                details.push(Span::from(" "));
                 details.push(
-                    Span::from(" ⬤")
-                        .fg(Color::from_str(&label.color).expect("failed to parse label color")),
+                    Span::from(filled).fg(if completed_subtasks == card.subtasks.len() {
+                        colors.success
+                    } else {
+                        colors.primary
+                    }),
                 );
+                details.push(Span::from(unfilled).fg(colors.tertiary_fg));
+            } else {
+                details.push(Span::from(format!(" {}", completed_subtasks)).fg(colors.success));
+                details.push(Span::from("/").fg(colors.secondary_fg));
+                details.push(Span::from(card.subtasks.len().to_string()));
             }
+            details.push(Span::from(" "));
+        }
+
+        let label_colors = self
+            .data
+            .labels
+            .iter()
+            .filter(|l| card.labels.contains(&l.id))
+            .map(|l| Color::from_str(&l.color).expect("failed to parse label color"))
+            .collect::<Vec<Color>>();
+        let details_width = details.iter().map(|s| s.width()).sum::<usize>();
+        let (shown_labels, overflow) = label_overflow(
+            label_colors.len(),
+            content_width.saturating_sub(details_width),
+            config.label_overflow_total,
+        );
+
+        for color in label_colors.into_iter().take(shown_labels) {
+            details.push(Span::from(" ⬤").fg(color));
+        }
+        if let Some(overflow) = overflow {
+            details.push(Span::from(overflow).fg(colors.label_overflow_fg));
         }
 
-        details.push(Span::from(" ".repeat(list_width.saturating_sub(
-            card.labels.len() + if card.labels.is_empty() { 1 } else { 2 },
-        ))));
+        let details_width = details.iter().map(|s| s.width()).sum::<usize>();
+        details.push(Span::from(
+            " ".repeat(list_width.saturating_sub(details_width)),
+        ));
 
-        let details_line = Line::from(details).style(line_style);
+        lines.push(Line::from(details).style(line_style));
 
-        Paragraph::new(vec![title, details_line])
+        let height = lines.len() as u16;
+        (Paragraph::new(lines), height)
     }
 
-    pub fn set_project_id(&mut self, project_id: i32) {
-        self.project_id = Some(project_id);
-        self.popups.new_list.project_id(project_id);
-        self.popups.edit_list.project_id(project_id);
+    /// The card counts of the board that are shown after the project title. The
+    /// statuses take the same precedence as the status characters of cards, so
+    /// completed cards aren't counted as overdue.
+    fn title_counts(&self, app: &App) -> Vec<Span<'static>> {
+        let colors = &app.config.colors;
+        let config = &app.config.modules.project_management;
+
+        let cards = self.data.lists.iter().flat_map(|l| l.summaries.iter());
+        let total = cards.clone().count();
+        let completed = cards.clone().filter(|c| c.completed).count();
+        let overdue = cards
+            .clone()
+            .filter(|c| !c.completed && c.overdue())
+            .count();
+        let due_soon = cards
+            .filter(|c| !c.completed && c.due_soon(config.due_soon_days, config.day_start_hour))
+            .count();
+
+        let mut spans = vec![
+            Span::from("  "),
+            Span::from(total.to_string()).fg(colors.fg),
+            Span::from(if total == 1 { " card" } else { " cards" }).fg(colors.secondary_fg),
+            Span::from(" · ").fg(colors.tertiary_fg),
+            Span::from(completed.to_string()).fg(colors.success),
+            Span::from(" completed").fg(colors.secondary_fg),
+        ];
+
+        if overdue > 0 {
+            spans.push(Span::from(" · ").fg(colors.tertiary_fg));
+            spans.push(Span::from(overdue.to_string()).fg(colors.danger));
+            spans.push(Span::from(" overdue").fg(colors.secondary_fg));
+        }
+
+        if due_soon > 0 {
+            spans.push(Span::from(" · ").fg(colors.tertiary_fg));
+            spans.push(Span::from(due_soon.to_string()).fg(colors.warning));
+            spans.push(Span::from(" due soon").fg(colors.secondary_fg));
+        }
+
+        spans
     }
 
-    pub fn reset(&mut self, app: &mut App) {
-        self.project_id = None;
-        self.selected_list_index = 0;
-        self.list_selections = vec![];
-        self.data = ProjectData::default();
-        self.popup = OpenProjectPopup::None;
-        self.popups.new_list.reset(app);
-        self.popups.edit_list.reset(app);
-        self.popups.view_card.reset();
-        self.popups.new_card.reset();
-        self.popups.edit_card.reset();
-        self.delete_selection = DeleteSelection::None;
+    fn wrap_titles(&self, app: &App) -> bool {
+        app.config.modules.project_management.wrap_card_titles != self.wrap_titles_toggled
     }
-}
 
-impl OpenProject {
-    pub fn db_get_project(&mut self, app: &App) -> Result<()> {
-        let start = Instant::now();
-        let _span = info_span!("project management", screen = "open project").entered();
+    /// Whether a list is collapsed. A zoomed list is always expanded.
+    fn is_list_collapsed(&self, list_index: usize) -> bool {
+        !self.zoomed
+            && self
+                .data
+                .lists
+                .get(list_index)
+                .is_some_and(|l| self.collapsed_lists.contains(&l.id))
+    }
 
-        let conn = app.db.conn();
-        let query = "SELECT title, description, position, created_at, updated_at FROM project \
-                     WHERE id = ?1 ORDER BY position";
-        let mut stmt = conn.prepare(query)?;
+    /// The lists that fit on a board of the given width at the `min_list_width`
+    /// config, scrolled so that the selected list is in view.
+    fn visible_list_range(&self, app: &App, width: u16) -> Range<usize> {
+        let list_count = self.data.lists.len();
+        let min_list_width = app.config.modules.project_management.min_list_width;
+        let list_width = |list_index: usize| {
+            if self.is_list_collapsed(list_index) {
+                COLLAPSED_LIST_WIDTH
+            } else {
+                min_list_width as u16
+            }
+        };
+        let total_width = |range: Range<usize>| range.map(list_width).sum::<u16>();
 
-        if let Some(project_id) = self.project_id {
-            let mut project = stmt.query_row([project_id], |r| {
-                Ok(ProjectData {
-                    title: r.get(0)?,
-                    labels: vec![],
-                    lists: vec![],
-                })
-            })?;
+        if min_list_width <= 0 || total_width(0..list_count) <= width {
+            *self.list_offset.borrow_mut() = 0;
+            return 0..list_count;
+        }
 
-            info!("get project query executed in {:?}", start.elapsed());
+        // The outer columns are taken by the arrows.
+        let width = width.saturating_sub(2);
+        let selected = cmp::min(self.selected_list_index, list_count.saturating_sub(1));
+        let mut offset = cmp::min(*self.list_offset.borrow(), selected);
+        while offset < selected && total_width(offset..selected + 1) > width {
+            offset += 1;
+        }
+        let mut end = selected + 1;
+        while end < list_count && total_width(offset..end + 1) <= width {
+            end += 1;
+        }
+        // Fill the board when scrolled to the last list.
+        while offset > 0 && total_width(offset - 1..end) <= width {
+            offset -= 1;
+        }
 
-            project.labels = self.db_get_labels(app)?;
-            project.lists = self.db_get_lists(&app.db, project_id)?;
-            project = self.db_get_cards(&app.db, &mut project, project_id)?;
-            project = self.db_get_card_labels(&app.db, &mut project, project_id)?;
-            project = self.db_get_card_subtasks(&app.db, &mut project, project_id)?;
+        *self.list_offset.borrow_mut() = offset;
+        offset..end
+    }
 
-            if !project.lists.is_empty() {
-                let list_id = project.lists[self.selected_list_index].id;
+    /// Add a digit to the count of the next motion. Returns true if the key was
+    /// part of a count. A leading `0` is not a count, so it is left free to be
+    /// used as a motion.
+    fn count_handler(&mut self, key_event: KeyEvent) -> bool {
+        let KeyCode::Char(char) = key_event.code else {
+            return false;
+        };
+        match char.to_digit(10) {
+            Some(digit)
+                if self.pending_keys.is_empty() && (digit != 0 || self.pending_count != 0) =>
+            {
+                self.pending_count = self
+                    .pending_count
+                    .saturating_mul(10)
+                    .saturating_add(digit as usize);
+                true
+            }
+            _ => false,
+        }
+    }
 
-                self.popups.edit_list.set(&app.db, list_id)?;
+    /// Handles the multi-key sequences of the board, such as `zM`. Returns
+    /// whether the key event was consumed by a sequence.
+    fn key_sequence_handler(&mut self, app: &App, key_event: KeyEvent) -> bool {
+        let KeyCode::Char(char) = key_event.code else {
+            self.pending_keys.clear();
+            return false;
+        };
 
-                if let Some(project_id) = self.project_id {
-                    self.popups.new_card.ids(project_id, list_id);
-                    self.popups.edit_card.ids(project_id, list_id);
-                }
+        let config = &app.config.modules.project_management;
+        let sequences = [
+            &config.toggle_list_collapse_key,
+            &config.collapse_all_lists_key,
+            &config.expand_all_lists_key,
+            &config.toggle_list_zoom_key,
+        ];
+
+        self.pending_keys.push(char);
+        if !sequences.iter().any(|s| s.starts_with(&self.pending_keys)) {
+            // Start over from this key in case it begins another sequence.
+            self.pending_keys = char.to_string();
+            if !sequences.iter().any(|s| s.starts_with(&self.pending_keys)) {
+                self.pending_keys.clear();
+                return false;
             }
+        }
 
-            self.data = project;
+        if &self.pending_keys == sequences[0] {
+            if let Some(list) = self.data.lists.get(self.selected_list_index) {
+                if !self.collapsed_lists.remove(&list.id) {
+                    self.collapsed_lists.insert(list.id);
+                }
+            }
+        } else if &self.pending_keys == sequences[1] {
+            self.collapsed_lists = self.data.lists.iter().map(|l| l.id).collect();
+        } else if &self.pending_keys == sequences[2] {
+            self.collapsed_lists.clear();
+        } else if &self.pending_keys == sequences[3] {
+            self.zoomed = !self.zoomed;
+        } else {
+            return true;
         }
 
-        info!(
-            "get project query durations totaled at {:?}",
-            start.elapsed()
-        );
+        self.pending_keys.clear();
+        true
+    }
 
+    /// Open a popup with the stored positions of the cards of the project.
+    pub fn debug_positions(&mut self, app: &mut App) -> Result<()> {
+        if let Some(project_id) = self.project_id {
+            self.popups
+                .debug_positions
+                .db_get_cards(&app.db, project_id)?;
+            self.popup = OpenProjectPopup::DebugPositions;
+            app.view.popup();
+        }
         Ok(())
     }
 
-    fn db_get_labels(&mut self, app: &App) -> Result<Vec<ProjectLabel>> {
-        let start = Instant::now();
-        let mut labels = vec![];
+    /// Write the open project to a Markdown file in the exports directory.
+    pub fn export_markdown(&self, app: &mut App) {
+        if self.project_id.is_none() {
+            return;
+        }
+
+        // Titles are free text, so anything that can't be part of a file name
+        // is replaced.
+        let file_name = self
+            .data
+            .title
+            .trim()
+            .chars()
+            .map(|c| match c {
+                '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+                c if c.is_control() => '_',
+                c => c,
+            })
+            .collect::<String>();
+        let file_name = if file_name.is_empty() || file_name.chars().all(|c| c == '.') {
+            String::from("project")
+        } else {
+            file_name
+        };
+
+        let markdown = if self.lazy {
+            match self.db_get_all_cards(&app.db) {
+                Ok(project) => project.to_markdown(),
+                Err(e) => {
+                    error!("failed to get the cards of the project to export: {e}");
+                    app.notify_error("Failed to export the project");
+                    return;
+                }
+            }
+        } else {
+            self.data.to_markdown()
+        };
+
+        let exports_dir = dirs::config_dir().join("exports");
+        let path = exports_dir.join(format!("{}.md", file_name));
+        let result = fs::create_dir_all(&exports_dir).and_then(|_| fs::write(&path, markdown));
+
+        match result {
+            Ok(_) => {
+                info!("exported project to {}", path.display());
+                app.notify_info(format!("Exported the project to {}", path.display()));
+            }
+            Err(e) => {
+                error!("failed to export project to {}: {e}", path.display());
+                app.notify_error("Failed to export the project");
+            }
+        }
+    }
+
+    /// Import the cards of the CSV import file into the selected list.
+    fn import_cards_csv(&mut self, app: &mut App) -> Result<()> {
+        let Some(list) = self.data.lists.get(self.selected_list_index) else {
+            return Ok(());
+        };
+
+        let path = data::cards_csv_path();
+        match data::import_cards_csv(&app.db, list.id, &path) {
+            Ok(result) if result.skipped > 0 => {
+                app.notify_warning(format!(
+                    "Imported {} cards, skipped {} invalid rows",
+                    result.imported, result.skipped
+                ));
+            }
+            Ok(result) => app.notify_info(format!("Imported {} cards", result.imported)),
+            Err(e) => {
+                error!("failed to import cards from {}: {e}", path.display());
+                app.notify_error(format!("Failed to import the cards: {e}"));
+                return Ok(());
+            }
+        }
+
+        self.db_get_project(app)
+    }
+
+    /// The keybinds of the board, for the help popup. The card viewer and
+    /// editor are included since they're opened from the board.
+    pub fn keybinds(&self, app: &App) -> Vec<KeybindSection> {
+        let config = &app.config.modules.project_management;
+        vec![
+            KeybindSection::new("Project Board")
+                .keybind("5j 3l", "Move by a count")
+                .keybind("enter", "View the card")
+                .keybind("n", "New card, or new list when the list is focused")
+                .keybind("e", "Edit the card or list")
+                .keybind("d", "Delete the card or list")
+                .keybind("o", "Quick add cards to the list")
+                .keybind("H J K L", "Move the card or list")
+                .keybind("space", "Grab the card to move it")
+                .keybind("c", "Toggle the card completed")
+                .keybind("C", "Complete or reopen every card of the list")
+                .keybind("i", "Toggle the card important")
+                .keybind(
+                    format!("{} {}", config.prev_status_key, config.next_status_key),
+                    "Move the card to the previous or next list",
+                )
+                .keybind("x X", "Clear the due date, or both dates")
+                .keybind("t", "Save the card as a template")
+                .keybind("T", "New card from a template")
+                .keybind("I", "Import cards from CSV into the list")
+                .keybind(&config.toggle_list_collapse_key, "Collapse the list")
+                .keybind(&config.collapse_all_lists_key, "Collapse all lists")
+                .keybind(&config.expand_all_lists_key, "Expand all lists")
+                .keybind(&config.toggle_list_zoom_key, "Zoom into the list")
+                .keybind("w", "Toggle wrapping card titles")
+                .keybind("E", "Export the project to Markdown")
+                .keybind("[", "Back to the projects"),
+            KeybindSection::new("Card Viewer")
+                .keybind("j k g G", "Scroll the description")
+                .keybind("tab", "Switch between the description and subtasks")
+                .keybind("y t", "Copy the title")
+                .keybind("y d", "Copy the description")
+                .keybind("y a", "Copy the card as Markdown")
+                .keybind("A", "Toggle absolute created and updated times")
+                .keybind("t", "Save the subtasks as a template")
+                .keybind("T", "Add subtasks from a template")
+                .keybind("q", "Close the viewer"),
+            KeybindSection::new("Card Editor")
+                .keybind("enter l", "Edit the field")
+                .keybind("enter [", "Back to the fields")
+                .keybind("s", "Save the card")
+                .keybind("q", "Close the editor"),
+        ]
+    }
+
+    /// Open the editor of a new list, unless the project has the maximum number
+    /// of lists.
+    pub fn new_list(&mut self, app: &mut App) {
+        let max_lists = app.config.modules.project_management.max_lists;
+        if self.data.lists.len() as i32 >= max_lists {
+            app.notify_warning(format!(
+                "A project can't have more than {} lists",
+                max_lists
+            ));
+            return;
+        }
+        self.popup = OpenProjectPopup::NewList;
+        app.view.popup();
+        app.mode.insert();
+    }
+
+    /// Open the editor of a new card in the selected list.
+    pub fn new_card(&mut self, app: &mut App) {
+        if let Some(project_id) = self.project_id {
+            if !self.data.lists.is_empty() && self.allow_card_into(app, self.selected_list_index) {
+                let list_id = self.data.lists[self.selected_list_index].id;
+                self.popups.new_card.ids(project_id, list_id);
+                self.popup = OpenProjectPopup::NewCard;
+                app.view.popup();
+            }
+        }
+    }
+
+    /// Select the list of a card and focus the card, if it exists.
+    pub fn focus_card(&mut self, card_id: i32) {
+        for (list_index, list) in self.data.lists.iter().enumerate() {
+            if let Some(card_index) = list.cards.iter().position(|c| c.id == card_id) {
+                self.selected_list_index = list_index;
+                self.list_selections[list_index].focused = card_index;
+                self.focus = Focus::Card;
+                return;
+            }
+        }
+    }
+
+    pub fn set_project_id(&mut self, project_id: i32) {
+        self.project_id = Some(project_id);
+        self.popups.new_list.project_id(project_id);
+        self.popups.edit_list.project_id(project_id);
+        self.popups.card_templates.project_id(project_id);
+    }
+
+    /// Open the quick-add input at the bottom of the selected list, expanding
+    /// the list if it's collapsed.
+    fn open_quick_add(&mut self, app: &mut App) {
+        let Some(list) = self.data.lists.get(self.selected_list_index) else {
+            return;
+        };
+        self.collapsed_lists.remove(&list.id);
+        self.quick_add = Some(
+            TextInput::new("New Card")
+                .placeholder("Title")
+                .max(CARD_TITLE_MAX_LENGTH),
+        );
+        app.mode.insert();
+    }
+
+    /// Each title that's entered is added as a card and the input is cleared
+    /// for the next one, until `esc` closes it.
+    fn quick_add_key_event_handler(&mut self, app: &mut App, key_event: KeyEvent) -> Result<()> {
+        let Some(quick_add) = self.quick_add.as_mut() else {
+            return Ok(());
+        };
+
+        match key_event.code {
+            KeyCode::Esc => {
+                self.quick_add = None;
+                app.mode.normal();
+            }
+            KeyCode::Enter => {
+                let title = quick_add.input_string().trim().to_string();
+                if !title.is_empty() && self.allow_card_into(app, self.selected_list_index) {
+                    let card_id = self.db_quick_add_card(&app.db, &title)?;
+                    self.db_get_project(app)?;
+                    if let Some(card_id) = card_id {
+                        self.db_load_card(app, card_id)?;
+                        self.focus_card(card_id);
+                    }
+                    if let Some(quick_add) = self.quick_add.as_mut() {
+                        quick_add.reset();
+                    }
+                }
+            }
+            _ => quick_add.key_event_handler(app, key_event),
+        }
+
+        Ok(())
+    }
+
+    /// Add a card with only a title to the end of the selected list, and
+    /// return its id.
+    fn db_quick_add_card(&self, db: &Database, title: &str) -> Result<Option<i32>> {
+        let (Some(project_id), Some(list)) = (
+            self.project_id,
+            self.data.lists.get(self.selected_list_index),
+        ) else {
+            return Ok(None);
+        };
+        let start = Instant::now();
+
+        let position = db.get_highest_position_where("project_card", "list_id", list.id)? + 1;
+        let card_id = db.insert(
+            "INSERT INTO project_card (project_id, list_id, title, important, position, \
+             created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            (
+                project_id,
+                list.id,
+                title,
+                false,
+                position,
+                DateTime::now(),
+                DateTime::now(),
+            ),
+        )?;
+
+        info!("quick add card query executed in {:?}", start.elapsed());
+
+        Ok(Some(card_id))
+    }
+
+    /// Grab the focused card, so it can be moved until it's dropped. The
+    /// positions are read from the database, as not every card is loaded when
+    /// the cards are loaded lazily.
+    fn db_grab_card(&mut self, app: &App) -> Result<()> {
+        let Some(id) = self.get_card().map(|c| c.id) else {
+            return Ok(());
+        };
+        let conn = app.db.conn();
+        let mut stmt = conn.prepare_cached(
+            "SELECT id, list_id, position FROM project_card WHERE project_id = ?1 AND deleted_at \
+             IS NULL",
+        )?;
+        let positions = stmt
+            .query_map([self.project_id], |r| {
+                Ok((r.get(0)?, (r.get(1)?, r.get(2)?)))
+            })?
+            .collect::<rusqlite::Result<HashMap<i32, (i32, i32)>>>()?;
+        self.grabbed = Some(GrabbedCard { id, positions });
+        Ok(())
+    }
+
+    /// Only the motions and dropping the card are handled while a card is
+    /// grabbed, so nothing else happens to the board in the middle of a move.
+    fn grab_key_event_handler(&mut self, app: &mut App, key_event: KeyEvent) -> Result<()> {
+        match key_event.code {
+            KeyCode::Char('j' | 'J') | KeyCode::Down => self.increment_card_position(app)?,
+            KeyCode::Char('k' | 'K') | KeyCode::Up => self.decrement_card_position(app)?,
+            KeyCode::Char('h' | 'H') | KeyCode::Left => self.move_card_left(app)?,
+            KeyCode::Char('l' | 'L') | KeyCode::Right => self.move_card_right(app)?,
+            KeyCode::Enter | KeyCode::Char(' ') => self.grabbed = None,
+            KeyCode::Esc => self.db_revert_grab(app)?,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Put every card that was moved while a card was grabbed back where it
+    /// was.
+    fn db_revert_grab(&mut self, app: &App) -> Result<()> {
+        let Some(grabbed) = self.grabbed.take() else {
+            return Ok(());
+        };
+        let _span = info_span!("project management", screen = "open project").entered();
+        let start = Instant::now();
+
+        let mut conn = app.db.conn();
+        let tx = conn.transaction()?;
+        for (card_id, (list_id, position)) in &grabbed.positions {
+            tx.execute(
+                "UPDATE project_card SET list_id = ?1, position = ?2 WHERE id = ?3 AND (list_id \
+                 != ?1 OR position != ?2)",
+                [list_id, position, card_id],
+            )?;
+        }
+        tx.commit()?;
+        drop(conn);
+
+        info!(
+            "revert grabbed card query executed in {:?}",
+            start.elapsed()
+        );
+        self.db_get_project(app)?;
+        self.db_load_card(app, grabbed.id)?;
+        self.focus_card(grabbed.id);
+        Ok(())
+    }
+
+    /// Focus the list or card that was clicked, and move the selection of the
+    /// list under the mouse when scrolling.
+    pub fn mouse_event_handler(&mut self, mouse_event: MouseEvent) {
+        if self.grabbed.is_some() {
+            return;
+        }
+
+        let (column, row) = (mouse_event.column, mouse_event.row);
+        let Some((list_index, cards_area)) = self
+            .list_areas
+            .borrow()
+            .iter()
+            .find(|l| area_contains(l.area, column, row))
+            .map(|l| (l.list_index, l.cards_area))
+        else {
+            return;
+        };
+
+        match mouse_event.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                self.selected_list_index = list_index;
+                self.pending_keys.clear();
+                self.pending_count = 0;
+                if self.is_list_collapsed(list_index) || row < cards_area.y {
+                    self.focus = Focus::List;
+                } else if let Some(card_index) = area_contains(cards_area, column, row)
+                    .then(|| self.list_selections[list_index].row_at(row - cards_area.y))
+                    .flatten()
+                {
+                    self.list_selections[list_index].focus(card_index);
+                    self.focus = Focus::Card;
+                }
+            }
+            MouseEventKind::ScrollDown => {
+                self.selected_list_index = list_index;
+                self.list_selections[list_index].handle_key(KeyEvent::from(KeyCode::Char('j')));
+            }
+            MouseEventKind::ScrollUp => {
+                self.selected_list_index = list_index;
+                self.list_selections[list_index].handle_key(KeyEvent::from(KeyCode::Char('k')));
+            }
+            _ => {}
+        }
+    }
+
+    fn save_list_area(&self, list_index: usize, area: Rect, cards_area: Rect) {
+        self.list_areas.borrow_mut().push(ListArea {
+            list_index,
+            area,
+            cards_area,
+        });
+    }
+
+    pub fn reset(&mut self, app: &mut App) {
+        self.project_id = None;
+        self.selected_list_index = 0;
+        self.list_selections = vec![];
+        self.data = ProjectData::default();
+        self.popup = OpenProjectPopup::None;
+        self.popups.new_list.reset(app);
+        self.popups.edit_list.reset(app);
+        self.popups.view_card.reset();
+        self.popups.new_card.reset();
+        self.popups.edit_card.reset();
+        self.popups.card_templates.reset();
+        self.popups.debug_positions.reset();
+        self.confirm.close();
+        self.collapsed_lists.clear();
+        self.pending_keys.clear();
+        self.pending_count = 0;
+        *self.list_offset.borrow_mut() = 0;
+        self.list_areas.borrow_mut().clear();
+        self.grabbed = None;
+        self.quick_add = None;
+        self.wrap_titles_toggled = false;
+        self.zoomed = false;
+        self.lazy = false;
+        self.card_limits.clear();
+    }
+}
+
+fn area_contains(area: Rect, column: u16, row: u16) -> bool {
+    column >= area.x && column < area.right() && row >= area.y && row < area.bottom()
+}
+
+/// Render the filled and unfilled parts of a progress bar. The filled part is
+/// drawn in eighths of a cell, so small amounts of progress are still visible.
+fn progress_bar(completed: usize, total: usize, width: usize) -> (String, String) {
+    const PARTIAL_BLOCKS: [char; 8] = [' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+
+    let eighths = completed * width * 8 / cmp::max(total, 1);
+    let mut filled = "█".repeat(eighths / 8);
+    let partial = eighths % 8;
+    if partial > 0 {
+        filled.push(PARTIAL_BLOCKS[partial]);
+    }
+    let unfilled = "░".repeat(width - filled.chars().count());
+
+    (filled, unfilled)
+}
+
+/// Split text into two lines at the given display width. The second line is
+/// truncated with an ellipsis when it doesn't fit within the width either.
+fn split_at_width(text: &str, width: usize) -> (String, String) {
+    let (first, rest) = take_width(text, width);
+    let rest = rest.trim_start();
+
+    if Span::from(rest).width() <= width {
+        return (first, rest.to_string());
+    }
+
+    let (mut second, _) = take_width(rest, width.saturating_sub(1));
+    second.push('…');
+    (first, second)
+}
+
+/// Take as many characters from the start of the text as fit within the display
+/// width, and return them along with the remaining text.
+fn take_width(text: &str, width: usize) -> (String, &str) {
+    let mut taken_width = 0;
+
+    for (i, char) in text.char_indices() {
+        taken_width += Span::from(char.to_string()).width();
+        if taken_width > width {
+            return (text[..i].to_string(), &text[i..]);
+        }
+    }
+
+    (text.to_string(), "")
+}
+
+/// The number of label dots that fit in the width, and the `+N` indicator
+/// for the labels that don't. Each dot takes a width of 2, and the indicator
+/// takes the place of as many dots as needed for it to fit.
+fn label_overflow(label_count: usize, width: usize, show_total: bool) -> (usize, Option<String>) {
+    let dot_width = 2;
+    if label_count * dot_width <= width {
+        return (label_count, None);
+    }
+
+    let mut shown = label_count;
+    loop {
+        shown -= 1;
+        let indicator = format!(
+            " +{}",
+            if show_total {
+                label_count
+            } else {
+                label_count - shown
+            }
+        );
+        if shown == 0 || shown * dot_width + indicator.chars().count() <= width {
+            return (shown, Some(indicator));
+        }
+    }
+}
+
+impl OpenProject {
+    pub fn db_get_project(&mut self, app: &App) -> Result<()> {
+        let start = Instant::now();
+        let _span = info_span!("project management", screen = "open project").entered();
+
+        let conn = app.db.conn();
+        let query = "SELECT title, description, position, created_at, updated_at FROM project \
+                     WHERE id = ?1 ORDER BY position";
+        let mut stmt = conn.prepare_cached(query)?;
+
+        if let Some(project_id) = self.project_id {
+            let mut project = stmt.query_row([project_id], |r| {
+                Ok(ProjectData {
+                    title: r.get(0)?,
+                    labels: vec![],
+                    lists: vec![],
+                })
+            })?;
+
+            info!("get project query executed in {:?}", start.elapsed());
+
+            project.labels = self.db_get_labels(app)?;
+            self.db_get_tags(app)?;
+            project.lists = self.db_get_lists(&app.db, project_id)?;
+            self.db_get_card_summaries(&app.db, &mut project, project_id)?;
+            let card_total = project
+                .lists
+                .iter()
+                .map(|l| l.summaries.len())
+                .sum::<usize>();
+            self.lazy = card_total > LAZY_LOAD_THRESHOLD;
+            let limits = self.lazy.then_some(&self.card_limits);
+            self.db_get_cards(&app.db, &mut project, project_id, limits)?;
+            let card_indexes = Self::card_indexes(&project);
+            self.db_get_card_labels(&app.db, &mut project, &card_indexes, project_id)?;
+            self.db_get_card_subtasks(&app.db, &mut project, &card_indexes, project_id)?;
+
+            // The lists keep their focus and scroll position when they're
+            // reloaded, even if lists were added, removed, or moved.
+            let previous_list_ids = self.data.lists.iter().map(|l| l.id).collect::<Vec<i32>>();
+            let card_counts = project
+                .lists
+                .iter()
+                .map(|l| (l.id, l.cards.len()))
+                .collect::<Vec<(i32, usize)>>();
+            Scrollable::reload_all(
+                &mut self.list_selections,
+                &previous_list_ids,
+                &card_counts,
+                || Scrollable::default().row_height(2),
+            );
+
+            if !project.lists.is_empty() {
+                let list_id = project.lists[self.selected_list_index].id;
+
+                self.popups.edit_list.set(&app.db, list_id)?;
+
+                if let Some(project_id) = self.project_id {
+                    self.popups.new_card.ids(project_id, list_id);
+                    self.popups.edit_card.ids(project_id, list_id);
+                }
+            }
+
+            self.data = project;
+        }
+
+        info!(
+            "get project query durations totaled at {:?}",
+            start.elapsed()
+        );
+
+        Ok(())
+    }
+
+    fn db_get_labels(&mut self, app: &App) -> Result<Vec<ProjectLabel>> {
+        let start = Instant::now();
+        let mut labels = vec![];
 
         let conn = app.db.conn();
         let project_label_query = "SELECT id, title, color, position, created_at, updated_at FROM \
                                    project_label WHERE project_id = ?1 ORDER BY position";
-        let mut project_label_stmt = conn.prepare(project_label_query)?;
+        let mut project_label_stmt = conn.prepare_cached(project_label_query)?;
 
         let project_label_iter = project_label_stmt.query_map([&self.project_id], |r| {
             Ok(ProjectLabel {
@@ -622,25 +1835,54 @@ impl OpenProject {
         Ok(labels)
     }
 
-    fn db_get_lists(&mut self, db: &Database, project_id: i32) -> Result<Vec<ProjectList>> {
+    /// Get the tags of all projects, which are shown in the card viewer and
+    /// editor along with the labels.
+    fn db_get_tags(&mut self, app: &App) -> Result<()> {
+        let start = Instant::now();
+        let mut tags = vec![];
+
+        let conn = app.db.conn();
+        let query = "SELECT id, title, color FROM tag ORDER BY position";
+        let mut stmt = conn.prepare_cached(query)?;
+        let tag_iter = stmt.query_map([], |r| {
+            Ok(Tag {
+                id: r.get(0)?,
+                title: r.get(1)?,
+                color: r.get(2)?,
+            })
+        })?;
+        for tag in tag_iter {
+            tags.push(tag?);
+        }
+
+        self.popups.view_card.tags(tags.clone());
+        self.popups.new_card.tags(&app.config.colors, tags.clone());
+        self.popups.edit_card.tags(&app.config.colors, tags);
+
+        info!("get tags query executed in {:?}", start.elapsed());
+
+        Ok(())
+    }
+
+    fn db_get_lists(&self, db: &Database, project_id: i32) -> Result<Vec<ProjectList>> {
         let start = Instant::now();
         let mut lists = vec![];
 
         let conn = db.conn();
-        let query =
-            "SELECT id, title, position FROM project_list WHERE project_id = ?1 ORDER BY position";
-        let mut stmt = conn.prepare(query)?;
+        let query = "SELECT id, title, wip_limit FROM project_list WHERE project_id = ?1 AND \
+                     deleted_at IS NULL ORDER BY position";
+        let mut stmt = conn.prepare_cached(query)?;
         let project_list_iter = stmt.query_map([project_id], |r| {
             Ok(ProjectList {
                 id: r.get(0)?,
                 title: r.get(1)?,
+                wip_limit: r.get(2)?,
                 cards: vec![],
+                summaries: vec![],
             })
         })?;
         for list in project_list_iter {
             lists.push(list?);
-            self.list_selections
-                .push(Scrollable::default().row_height(2));
         }
 
         info!("get project lists query executed in {:?}", start.elapsed());
@@ -648,59 +1890,213 @@ impl OpenProject {
         Ok(lists)
     }
 
+    /// The completion and due date of every card of the project, which are
+    /// read even when the cards are loaded lazily.
+    fn db_get_card_summaries(
+        &self,
+        db: &Database,
+        project: &mut ProjectData,
+        project_id: i32,
+    ) -> Result<()> {
+        let start = Instant::now();
+
+        let list_indexes = Self::list_indexes(project);
+        let conn = db.conn();
+        let query = "SELECT list_id, id, completed, due_date FROM project_card WHERE project_id = \
+                     ?1 AND deleted_at IS NULL";
+        let mut stmt = conn.prepare_cached(query)?;
+        let summary_iter = stmt.query_map([project_id], |r| {
+            Ok((
+                r.get::<_, i32>(0)?,
+                CardSummary {
+                    id: r.get(1)?,
+                    completed: r.get(2)?,
+                    due_date: DateTime::from_db_option(r.get(3)?),
+                },
+            ))
+        })?;
+        for summary in summary_iter {
+            let (list_id, summary) = summary?;
+            if let Some(&index) = list_indexes.get(&list_id) {
+                project.lists[index].summaries.push(summary);
+            }
+        }
+
+        info!("get card summaries query executed in {:?}", start.elapsed());
+
+        Ok(())
+    }
+
+    /// Load the cards of every list, or only up to the limit of each list when
+    /// `limits` are given.
     fn db_get_cards(
         &self,
         db: &Database,
         project: &mut ProjectData,
         project_id: i32,
-    ) -> Result<ProjectData> {
+        limits: Option<&HashMap<i32, usize>>,
+    ) -> Result<()> {
         let start = Instant::now();
+
+        let list_indexes = Self::list_indexes(project);
+
         let conn = db.conn();
-        let project_card_query = "SELECT id, list_id, title, description, important, start_date, \
-                                  due_date, completed, position FROM project_card WHERE \
-                                  project_id = ?1 ORDER BY position";
-        let mut project_card_stmt = conn.prepare(project_card_query)?;
-        let project_card_iter = project_card_stmt.query_map([project_id], |r| {
+        let card = |r: &rusqlite::Row| {
             Ok(OpenProjectCard {
                 id: r.get(0)?,
                 list_id: r.get(1)?,
                 title: r.get(2)?,
                 description: r.get(3)?,
-                important: r.get(4)?,
+                priority: Priority::from_db(r.get(4)?),
                 start_date: DateTime::from_db_option(r.get(5)?),
                 due_date: DateTime::from_db_option(r.get(6)?),
                 completed: r.get(7)?,
-                position: r.get(8)?,
                 labels: HashSet::new(),
                 subtasks: vec![],
             })
-        })?;
+        };
+
+        if let Some(limits) = limits {
+            let list_card_query = "SELECT id, list_id, title, description, priority, start_date, \
+                                   due_date, completed FROM project_card WHERE list_id = ?1 AND \
+                                   deleted_at IS NULL ORDER BY position LIMIT ?2";
+            let mut list_card_stmt = conn.prepare_cached(list_card_query)?;
+            for list in project.lists.iter_mut() {
+                let limit = limits.get(&list.id).copied().unwrap_or(CARD_PAGE_SIZE);
+                for card in list_card_stmt.query_map((list.id, limit), card)? {
+                    list.cards.push(card?);
+                }
+            }
+            info!(
+                "get project cards query of each list executed in {:?}",
+                start.elapsed()
+            );
+            return Ok(());
+        }
+
+        let project_card_query = "SELECT id, list_id, title, description, priority, start_date, \
+                                  due_date, completed FROM project_card WHERE project_id = ?1 AND \
+                                  deleted_at IS NULL ORDER BY position";
+        let mut project_card_stmt = conn.prepare_cached(project_card_query)?;
+        let project_card_iter = project_card_stmt.query_map([project_id], card)?;
         for card in project_card_iter {
-            let c = card?;
-            let index = project
-                .lists
-                .iter()
-                .position(|l| l.id == c.list_id)
-                .expect("failed to get project list index");
-            project.lists[index].cards.push(c);
+            let card = card?;
+            match list_indexes.get(&card.list_id) {
+                Some(&index) => project.lists[index].cards.push(card),
+                None => warn!(
+                    "card {} is in list {} which isn't loaded",
+                    card.id, card.list_id
+                ),
+            }
         }
 
         info!("get project cards query executed in {:?}", start.elapsed());
 
-        Ok(project.clone())
+        Ok(())
+    }
+
+    /// The project with every card of its lists, for exporting it while the
+    /// cards are loaded lazily.
+    fn db_get_all_cards(&self, db: &Database) -> Result<ProjectData> {
+        let mut project = self.data.clone();
+        let project_id = self.project_id.ok_or_else(|| eyre!("no project is open"))?;
+        for list in project.lists.iter_mut() {
+            list.cards.clear();
+        }
+        self.db_get_cards(db, &mut project, project_id, None)?;
+        let card_indexes = Self::card_indexes(&project);
+        self.db_get_card_labels(db, &mut project, &card_indexes, project_id)?;
+        self.db_get_card_subtasks(db, &mut project, &card_indexes, project_id)?;
+        Ok(project)
+    }
+
+    /// The index of every list of the project, by list id.
+    fn list_indexes(project: &ProjectData) -> HashMap<i32, usize> {
+        project
+            .lists
+            .iter()
+            .enumerate()
+            .map(|(i, l)| (l.id, i))
+            .collect()
+    }
+
+    /// Load the cards of the list of a card up to the card, if it isn't loaded
+    /// because the cards are loaded lazily, such as a card that was moved to
+    /// the end of a long list.
+    pub fn db_load_card(&mut self, app: &App, card_id: i32) -> Result<()> {
+        if !self.lazy {
+            return Ok(());
+        }
+        // The index of the card in its list is the number of cards before it,
+        // as the positions can have gaps.
+        let (list_id, index): (i32, usize) = app.db.conn().query_row(
+            "SELECT list_id, (SELECT COUNT(*) FROM project_card b WHERE b.list_id = c.list_id AND \
+             b.position < c.position AND b.deleted_at IS NULL) FROM project_card c WHERE c.id = ?1",
+            [card_id],
+            |r| Ok((r.get(0)?, r.get(1)?)),
+        )?;
+        let limit = self.card_limits.entry(list_id).or_insert(CARD_PAGE_SIZE);
+        if index < *limit {
+            return Ok(());
+        }
+        *limit = (index / CARD_PAGE_SIZE + 1) * CARD_PAGE_SIZE;
+        self.db_get_project(app)
+    }
+
+    /// Load the next page of the cards of the selected list when the focus is
+    /// near its last loaded card, while the cards are loaded lazily.
+    pub fn db_load_more_cards(&mut self, app: &App) -> Result<()> {
+        if !self.lazy || self.focus != Focus::Card {
+            return Ok(());
+        }
+        let (Some(list), Some(selection)) = (
+            self.data.lists.get(self.selected_list_index),
+            self.list_selections.get(self.selected_list_index),
+        ) else {
+            return Ok(());
+        };
+        if list.cards.len() >= list.summaries.len() || !selection.is_near_end(LOAD_MORE_MARGIN) {
+            return Ok(());
+        }
+
+        self.card_limits
+            .insert(list.id, list.cards.len() + CARD_PAGE_SIZE);
+        info!(
+            "loading the next {} cards of list {}",
+            CARD_PAGE_SIZE, list.id
+        );
+        self.db_get_project(app)
+    }
+
+    /// The list index and card index of every card of the project, by card id,
+    /// so the labels and subtasks can be added to their cards without searching
+    /// the lists for each row.
+    fn card_indexes(project: &ProjectData) -> HashMap<i32, (usize, usize)> {
+        project
+            .lists
+            .iter()
+            .enumerate()
+            .flat_map(|(list_index, list)| {
+                list.cards
+                    .iter()
+                    .enumerate()
+                    .map(move |(card_index, card)| (card.id, (list_index, card_index)))
+            })
+            .collect()
     }
 
     fn db_get_card_labels(
         &self,
         db: &Database,
         project: &mut ProjectData,
+        card_indexes: &HashMap<i32, (usize, usize)>,
         project_id: i32,
-    ) -> Result<ProjectData> {
+    ) -> Result<()> {
         let start = Instant::now();
 
         let conn = db.conn();
         let card_label_query = "SELECT card_id, label_id FROM card_label WHERE project_id = ?1";
-        let mut card_label_stmt = conn.prepare(card_label_query)?;
+        let mut card_label_stmt = conn.prepare_cached(card_label_query)?;
         let card_label_iter = card_label_stmt.query_map([project_id], |r| {
             Ok(ProjectCardLabel {
                 card_id: r.get(0)?,
@@ -708,68 +2104,55 @@ impl OpenProject {
             })
         })?;
 
+        // The labels of deleted cards are skipped, since the cards aren't
+        // loaded.
         for card_label in card_label_iter {
             let label = card_label?;
-
-            let list_index = project
-                .lists
-                .iter()
-                .position(|l| l.cards.iter().any(|c| c.id == label.card_id))
-                .expect("failed to get project list index");
-            let card_index = project.lists[list_index]
-                .cards
-                .iter()
-                .position(|c| c.id == label.card_id)
-                .expect("failed to get project card index");
-            project.lists[list_index].cards[card_index]
-                .labels
-                .insert(label.label_id);
+            if let Some(&(list_index, card_index)) = card_indexes.get(&label.card_id) {
+                project.lists[list_index].cards[card_index]
+                    .labels
+                    .insert(label.label_id);
+            }
         }
 
         info!("get card labels query executed in {:?}", start.elapsed());
 
-        Ok(project.clone())
+        Ok(())
     }
 
     fn db_get_card_subtasks(
         &self,
         db: &Database,
         project: &mut ProjectData,
+        card_indexes: &HashMap<i32, (usize, usize)>,
         project_id: i32,
-    ) -> Result<ProjectData> {
+    ) -> Result<()> {
         let start = Instant::now();
 
         let conn = db.conn();
-        let card_subtask_query =
-            "SELECT card_id, completed FROM card_subtask WHERE project_id = ?1";
-        let mut card_subtask_stmt = conn.prepare(card_subtask_query)?;
+        let card_subtask_query = "SELECT card_id, value, completed FROM card_subtask WHERE \
+                                  project_id = ?1 ORDER BY position";
+        let mut card_subtask_stmt = conn.prepare_cached(card_subtask_query)?;
         let card_subtask_iter = card_subtask_stmt.query_map([project_id], |r| {
             Ok(ProjectCardSubtask {
                 card_id: r.get(0)?,
-                completed: r.get(1)?,
+                value: r.get(1)?,
+                completed: r.get(2)?,
             })
         })?;
 
         for card_subtask in card_subtask_iter {
             let subtask = card_subtask?;
-            let list_index = project
-                .lists
-                .iter()
-                .position(|l| l.cards.iter().any(|c| c.id == subtask.card_id))
-                .expect("failed to get project list index");
-            let card_index = project.lists[list_index]
-                .cards
-                .iter()
-                .position(|c| c.id == subtask.card_id)
-                .expect("failed to get project card index");
-            project.lists[list_index].cards[card_index]
-                .subtasks
-                .push(subtask);
+            if let Some(&(list_index, card_index)) = card_indexes.get(&subtask.card_id) {
+                project.lists[list_index].cards[card_index]
+                    .subtasks
+                    .push(subtask);
+            }
         }
 
         info!("get card subtasks query executed in {:?}", start.elapsed());
 
-        Ok(project.clone())
+        Ok(())
     }
 
     fn db_delete_list(&mut self, db: &Database) -> Result<()> {
@@ -777,11 +2160,25 @@ impl OpenProject {
 
         let list_id = self.data.lists[self.selected_list_index].id;
         let original_position = db.get_position("project_list", list_id)?;
+        let project_id = self.project_id;
 
-        let query = "DELETE FROM project_list WHERE id = ?1";
-        db.execute(query, [list_id])?;
-
-        db.decrement_positions_after("project_list", original_position)?;
+        let mut conn = db.conn();
+        let tx = conn.transaction()?;
+        // Deleted lists and cards are moved to the trash. They're taken out of
+        // the positions of their project or list, and put at the end when
+        // they're restored.
+        let query =
+            "UPDATE project_list SET deleted_at = ?1, position = -1, updated_at = ?1 WHERE id = ?2";
+        tx.execute(query, (DateTime::now(), list_id))?;
+        // The cards of the list are restored along with it, so they share its
+        // deletion time.
+        let query = "UPDATE project_card SET deleted_at = (SELECT deleted_at FROM project_list \
+                     WHERE id = ?1), updated_at = ?2 WHERE list_id = ?1 AND deleted_at IS NULL";
+        tx.execute(query, (list_id, DateTime::now()))?;
+        let query = "UPDATE project_list SET position = position - 1, updated_at = ?1 WHERE \
+                     position > ?2 AND project_id = ?3";
+        tx.execute(query, (DateTime::now(), original_position, project_id))?;
+        tx.commit()?;
 
         if self.selected_list_index != 0 {
             self.selected_list_index -= 1;
@@ -801,12 +2198,7 @@ impl OpenProject {
         let card_index = self.list_selections[self.selected_list_index].focused;
         let card = self.data.lists[self.selected_list_index].cards[card_index].clone();
 
-        let original_position = db.get_position("project_card", card.id)?;
-
-        let query = "DELETE FROM project_card WHERE id = ?1";
-        db.execute(query, [card.id])?;
-
-        db.decrement_positions_after("project_card", original_position)?;
+        positions::delete_card(db, card.id)?;
 
         let list = &self.data.lists[self.selected_list_index];
 
@@ -829,11 +2221,37 @@ impl OpenProject {
         let start = Instant::now();
 
         if let Some(card) = self.get_card() {
-            let query = "UPDATE project_card SET completed = ?1, updated_at = ?2 WHERE id = ?3";
-            let params = (!card.completed, DateTime::now(), card.id);
-            app.db.execute(query, params)?;
-
-            self.db_get_project(app)?;
+            let query = "UPDATE project_card SET completed = ?1, completed_at = ?2, updated_at = \
+                         ?3 WHERE id = ?4";
+            let completed_at = if card.completed {
+                None
+            } else {
+                Some(DateTime::now())
+            };
+            let params = (!card.completed, completed_at, DateTime::now(), card.id);
+            let (card_id, completed) = (card.id, !card.completed);
+            let mut conn = app.db.conn();
+            let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+            tx.execute(query, params)?;
+            let recurred = completed && Self::db_new_recurring_card(&tx, card_id)?;
+            tx.commit()?;
+            // The connection goes back to the pool before the project reloads.
+            drop(conn);
+
+            // The card is updated in place, unless completing it created the
+            // next occurrence of a recurring card, which has to be loaded.
+            if recurred {
+                self.db_get_project(app)?;
+            } else {
+                if let Some(card) = self.get_card_mut() {
+                    card.completed = completed;
+                }
+                // The counts of the board are read from the summaries.
+                let list = &mut self.data.lists[self.selected_list_index];
+                if let Some(summary) = list.summaries.iter_mut().find(|s| s.id == card_id) {
+                    summary.completed = completed;
+                }
+            }
 
             info!(
                 "toggle project card completed query executed in {:?}",
@@ -844,15 +2262,168 @@ impl OpenProject {
         Ok(())
     }
 
+    /// Create the next occurrence of a recurring card that was completed. The
+    /// copy keeps the labels, tags, and subtasks of the card, with its dates
+    /// advanced by the recurrence interval. Cards without a due date don't
+    /// recur. Returns whether a card was created. The copy is made in the
+    /// transaction that completes the card, so a card is never completed
+    /// without its next occurrence, or copied in part.
+    pub(crate) fn db_new_recurring_card(tx: &Transaction, card_id: i32) -> Result<bool> {
+        let start = Instant::now();
+
+        let query = "SELECT list_id, recurrence, recurrence_day, start_date, due_date FROM \
+                     project_card WHERE id = ?1";
+        let (list_id, recurrence, recurrence_day, start_date, due_date) =
+            tx.prepare_cached(query)?.query_row([card_id], |r| {
+                Ok((
+                    r.get::<usize, i32>(0)?,
+                    Recurrence::from_db(r.get(1)?),
+                    r.get::<usize, Option<u32>>(2)?,
+                    DateTime::from_db_option(r.get(3)?),
+                    DateTime::from_db_option(r.get(4)?),
+                ))
+            })?;
+
+        let Some(due_date) = due_date else {
+            return Ok(false);
+        };
+        if recurrence == Recurrence::None {
+            return Ok(false);
+        }
+
+        let position: i32 = tx.query_row(
+            "SELECT COALESCE(MAX(position), -1) + 1 FROM project_card WHERE list_id = ?1",
+            [list_id],
+            |r| r.get(0),
+        )?;
+
+        // The day of the first due date is kept, and the start date is moved
+        // by as much as the due date, so it stays as far before it.
+        let recurrence_day = recurrence_day.unwrap_or(due_date.month_day());
+        let new_due_date = recurrence.advance(&due_date, recurrence_day);
+        let new_start_date =
+            start_date.map(|d| DateTime::from(d.datetime + new_due_date.duration_since(&due_date)));
+
+        let card_query = "INSERT INTO project_card (project_id, list_id, title, description, \
+                          important, priority, start_date, due_date, recurrence, recurrence_day, \
+                          reminder, position, created_at, updated_at) SELECT project_id, list_id, \
+                          title, description, important, priority, ?1, ?2, recurrence, ?3, \
+                          reminder, ?4, ?5, ?6 FROM project_card WHERE id = ?7";
+        let params = (
+            new_start_date.map(|d| d.into_db()),
+            new_due_date.into_db(),
+            recurrence_day,
+            position,
+            DateTime::now(),
+            DateTime::now(),
+            card_id,
+        );
+        tx.prepare_cached(card_query)?.execute(params)?;
+        let new_card_id = tx.last_insert_rowid() as i32;
+
+        // The recurrence moves to the new card so that completing the original
+        // card again doesn't create another copy.
+        tx.execute(
+            "UPDATE project_card SET recurrence = NULL WHERE id = ?1",
+            [card_id],
+        )?;
+
+        let label_query = "INSERT INTO card_label (project_id, card_id, label_id, created_at, \
+                           updated_at) SELECT project_id, ?1, label_id, ?2, ?3 FROM card_label \
+                           WHERE card_id = ?4";
+        tx.execute(
+            label_query,
+            (new_card_id, DateTime::now(), DateTime::now(), card_id),
+        )?;
+
+        let subtask_query = "INSERT INTO card_subtask (project_id, card_id, value, completed, \
+                             position, created_at, updated_at) SELECT project_id, ?1, value, 0, \
+                             position, ?2, ?3 FROM card_subtask WHERE card_id = ?4";
+        tx.execute(
+            subtask_query,
+            (new_card_id, DateTime::now(), DateTime::now(), card_id),
+        )?;
+
+        let tag_query = "INSERT INTO card_tag (card_id, tag_id, created_at, updated_at) SELECT \
+                         ?1, tag_id, ?2, ?3 FROM card_tag WHERE card_id = ?4";
+        tx.execute(
+            tag_query,
+            (new_card_id, DateTime::now(), DateTime::now(), card_id),
+        )?;
+
+        info!(
+            "new recurring project card query executed in {:?}",
+            start.elapsed()
+        );
+
+        Ok(true)
+    }
+
+    /// Set whether every card in the selected list is completed. Recurring
+    /// cards that are completed are copied like when they're completed one by
+    /// one.
+    fn db_set_list_completed(&mut self, db: &Database, completed: bool) -> Result<()> {
+        let start = Instant::now();
+
+        // The cards are read from the database, since not every card is loaded
+        // when the cards are loaded lazily.
+        let list_id = self.data.lists[self.selected_list_index].id;
+        let mut conn = db.conn();
+        let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+        let changed_cards = tx
+            .prepare_cached(
+                "SELECT id FROM project_card WHERE list_id = ?1 AND deleted_at IS NULL AND \
+                 completed != ?2",
+            )?
+            .query_map((list_id, completed), |r| r.get(0))?
+            .collect::<rusqlite::Result<Vec<i32>>>()?;
+
+        for card_id in &changed_cards {
+            let now = DateTime::now();
+            let completed_at = completed.then(|| now.clone());
+            tx.execute(
+                "UPDATE project_card SET completed = ?1, completed_at = ?2, updated_at = ?3 WHERE \
+                 id = ?4",
+                (completed, completed_at, now, card_id),
+            )?;
+            if completed {
+                Self::db_new_recurring_card(&tx, *card_id)?;
+            }
+        }
+        tx.commit()?;
+
+        info!(
+            "set project list completed query executed in {:?}",
+            start.elapsed()
+        );
+
+        Ok(())
+    }
+
     fn db_toggle_card_important(&mut self, app: &App) -> Result<()> {
         let start = Instant::now();
 
         if let Some(card) = self.get_card() {
-            let query = "UPDATE project_card SET important = ?1, updated_at = ?2 WHERE id = ?3";
-            let params = (!card.important, DateTime::now(), card.id);
+            // Toggles between high and no priority, like the important flag that
+            // priorities replaced.
+            let priority = if card.priority.is_important() {
+                Priority::None
+            } else {
+                Priority::High
+            };
+            let query = "UPDATE project_card SET important = ?1, priority = ?2, updated_at = ?3 \
+                         WHERE id = ?4";
+            let params = (
+                priority.is_important(),
+                priority.into_db(),
+                DateTime::now(),
+                card.id,
+            );
             app.db.execute(query, params)?;
 
-            self.db_get_project(app)?;
+            if let Some(card) = self.get_card_mut() {
+                card.priority = priority;
+            }
 
             info!(
                 "toggle project card important query executed in {:?}",
@@ -863,6 +2434,43 @@ impl OpenProject {
         Ok(())
     }
 
+    /// Remove the due date of the focused card, and the start date as well if
+    /// `start_date` is true.
+    fn db_clear_card_dates(&mut self, app: &mut App, start_date: bool) -> Result<()> {
+        let start = Instant::now();
+
+        let Some(card) = self.get_card() else {
+            return Ok(());
+        };
+        if card.due_date.is_none() && (!start_date || card.start_date.is_none()) {
+            app.notify_info("The card has no dates to clear");
+            return Ok(());
+        }
+
+        let query = if start_date {
+            "UPDATE project_card SET start_date = NULL, due_date = NULL, updated_at = ?1 WHERE id \
+             = ?2"
+        } else {
+            "UPDATE project_card SET due_date = NULL, updated_at = ?1 WHERE id = ?2"
+        };
+        app.db.execute(query, (DateTime::now(), card.id))?;
+
+        app.notify_info(if start_date {
+            "Cleared the start and due dates"
+        } else {
+            "Cleared the due date"
+        });
+
+        self.db_get_project(app)?;
+
+        info!(
+            "clear project card dates query executed in {:?}",
+            start.elapsed()
+        );
+
+        Ok(())
+    }
+
     fn get_card(&self) -> Option<&OpenProjectCard> {
         self.list_selections
             .get(self.selected_list_index)
@@ -873,6 +2481,13 @@ impl OpenProject {
             })
     }
 
+    fn get_card_mut(&mut self) -> Option<&mut OpenProjectCard> {
+        let focused = self.list_selections.get(self.selected_list_index)?.focused;
+        self.data.lists[self.selected_list_index]
+            .cards
+            .get_mut(focused)
+    }
+
     fn increment_list_position(&mut self, app: &App) -> Result<()> {
         let _span = info_span!("project management", screen = "open project").entered();
         let start = Instant::now();
@@ -953,94 +2568,102 @@ impl OpenProject {
         Ok(())
     }
 
-    fn move_card_left(&mut self, app: &App) -> Result<()> {
+    /// Whether a card can be added to or moved into a list. Lists at their WIP
+    /// limit warn, and only refuse the card when `enforce_wip_limits` is set.
+    fn allow_card_into(&self, app: &mut App, list_index: usize) -> bool {
+        let list = &self.data.lists[list_index];
+        if !list.at_wip_limit() {
+            return true;
+        }
+
+        app.notify_warning(format!(
+            "'{}' is at its WIP limit of {}",
+            list.title,
+            list.wip_limit.unwrap_or_default()
+        ));
+        !app.config.modules.project_management.enforce_wip_limits
+    }
+
+    fn move_card_left(&mut self, app: &mut App) -> Result<()> {
+        if self.selected_list_index != 0 {
+            self.move_card_to_list(app, self.selected_list_index - 1)?;
+        }
+        Ok(())
+    }
+
+    fn move_card_right(&mut self, app: &mut App) -> Result<()> {
+        if self.selected_list_index + 1 < self.data.lists.len() {
+            self.move_card_to_list(app, self.selected_list_index + 1)?;
+        }
+        Ok(())
+    }
+
+    /// Move the focused card to the end of another list, which is selected
+    /// with the card focused. Returns whether the card was moved.
+    fn move_card_to_list(&mut self, app: &mut App, list_index: usize) -> Result<bool> {
         let _span = info_span!("project management", screen = "open project").entered();
         let start = Instant::now();
-        if let Some(card_index) = self
-            .list_selections
-            .get(self.selected_list_index)
-            .map(|l| l.focused)
-        {
-            if self.selected_list_index != 0 {
-                let original_list = &self.data.lists[self.selected_list_index];
-                let left_list = &self.data.lists[self.selected_list_index - 1];
-                let left_list_last_position =
-                    left_list.cards.last().map(|l| l.position).unwrap_or(-1);
-
-                app.db.execute(
-                    "UPDATE project_card SET list_id = ?1, position = ?2 where list_id = ?3 and \
-                     id = ?4",
-                    [
-                        left_list.id,
-                        left_list_last_position + 1,
-                        original_list.id,
-                        original_list.cards[card_index].id,
-                    ],
-                )?;
-
-                app.db.decrement_positions_after_where(
-                    "project_card",
-                    card_index as i32,
-                    "list_id",
-                    original_list.id,
-                )?;
-
-                self.list_selections[self.selected_list_index].focused = self.list_selections
-                    [self.selected_list_index]
-                    .focused
-                    .saturating_sub(1);
-                self.list_selections[self.selected_list_index - 1].focused =
-                    left_list_last_position as usize + 1;
-                self.selected_list_index -= 1;
-                info!("move card left query executed in {:?}", start.elapsed());
-                self.db_get_project(app)?;
-            }
+
+        let Some(card_id) = self.get_card().map(|c| c.id) else {
+            return Ok(false);
+        };
+        if !self.allow_card_into(app, list_index) {
+            return Ok(false);
         }
-        Ok(())
+
+        let list_id = self.data.lists[list_index].id;
+        positions::move_card_to_list(&app.db, card_id, list_id)?;
+
+        // The card after the moved card takes its place, unless it was the last.
+        let focused = &mut self.list_selections[self.selected_list_index].focused;
+        let remaining = self.data.lists[self.selected_list_index].cards.len() - 1;
+        *focused = cmp::min(*focused, remaining.saturating_sub(1));
+
+        info!("move card query executed in {:?}", start.elapsed());
+        self.db_get_project(app)?;
+        self.db_load_card(app, card_id)?;
+        self.focus_card(card_id);
+        Ok(true)
     }
 
-    fn move_card_right(&mut self, app: &App) -> Result<()> {
+    /// Move the focused card to the end of the next or previous list, and
+    /// notify the list it was moved to. The card stops at the ends of the
+    /// board, unless `status_stepper_wrap` is enabled.
+    fn step_card_status(&mut self, app: &mut App, forward: bool) -> Result<()> {
         let _span = info_span!("project management", screen = "open project").entered();
         let start = Instant::now();
-        if let Some(card_index) = self
-            .list_selections
-            .get(self.selected_list_index)
-            .map(|l| l.focused)
-        {
-            if self.selected_list_index + 1 != self.data.lists.len() {
-                let list = &self.data.lists[self.selected_list_index];
-                let right_list = &self.data.lists[self.selected_list_index + 1];
-                let right_list_last_position =
-                    right_list.cards.last().map(|c| c.position).unwrap_or(-1);
-
-                let query = "UPDATE project_card SET list_id = ?1, position = ?2 where list_id = \
-                             ?3 and id = ?4";
-                let params = [
-                    right_list.id,
-                    right_list_last_position + 1,
-                    list.id,
-                    list.cards[card_index].id,
-                ];
-                app.db.execute(query, params)?;
-
-                app.db.decrement_positions_after_where(
-                    "project_card",
-                    card_index as i32,
-                    "list_id",
-                    list.id,
-                )?;
-
-                self.list_selections[self.selected_list_index].focused = self.list_selections
-                    [self.selected_list_index]
-                    .focused
-                    .saturating_sub(1);
-                self.list_selections[self.selected_list_index + 1].focused =
-                    (right_list_last_position + 1) as usize;
-                self.selected_list_index += 1;
-                info!("move card right query executed in {:?}", start.elapsed());
-                self.db_get_project(app)?;
+
+        if self.get_card().is_none() {
+            return Ok(());
+        }
+
+        let list_count = self.data.lists.len();
+        if list_count < 2 {
+            return Ok(());
+        }
+        let wrap = app.config.modules.project_management.status_stepper_wrap;
+        let target_list_index = match (forward, self.selected_list_index) {
+            (true, i) if i + 1 < list_count => i + 1,
+            (true, _) if wrap => 0,
+            (false, 0) if wrap => list_count - 1,
+            (false, i) if i > 0 => i - 1,
+            _ => {
+                let message = if forward {
+                    "Already in the last list"
+                } else {
+                    "Already in the first list"
+                };
+                app.notify_info(message);
+                return Ok(());
             }
+        };
+
+        let target_title = self.data.lists[target_list_index].title.to_string();
+        if self.move_card_to_list(app, target_list_index)? {
+            app.notify_info(format!("Moved to {}", target_title));
         }
+
+        info!("step card status query executed in {:?}", start.elapsed());
         Ok(())
     }
 }
@@ -1,4 +1,6 @@
 pub mod card_editor;
+pub mod card_templates;
 pub mod card_viewer;
+pub mod debug_positions;
 pub mod list_editor;
 pub mod project_editor;
@@ -6,17 +6,26 @@ use pltx_app::{state::View, App, DefaultWidget, KeyEventHandler, Popup};
 use pltx_config::ColorsConfig;
 use pltx_database::Database;
 use pltx_utils::DateTime;
-use pltx_widgets::{Form, FormInput, FormInputState, FormWidget, Scrollable, Selection, TextInput};
+use pltx_widgets::{
+    ConfirmPopup, Dropdown, Form, FormInput, FormInputState, FormWidget, MultiSelect, Scrollable,
+    TextInput,
+};
 use ratatui::{
     layout::Rect,
-    style::{Color, Stylize},
-    text::Span,
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span},
     widgets::Paragraph,
     Frame,
 };
-use tracing::{info, info_span};
+use tracing::{error, info, info_span};
+
+use crate::{
+    open_project::{ProjectLabel, Tag},
+    priority::Priority,
+    recurrence::Recurrence,
+};
 
-use crate::open_project::ProjectLabel;
+pub const CARD_TITLE_MAX_LENGTH: usize = 50;
 
 #[derive(PartialEq)]
 pub enum SubtaskView {
@@ -28,6 +37,7 @@ pub enum SubtaskView {
 pub struct Subtask<T = Option<i32>> {
     id: T,
     value: String,
+    completed: bool,
 }
 
 pub struct SubtaskEditor {
@@ -36,6 +46,7 @@ pub struct SubtaskEditor {
     selection: Scrollable,
     input: TextInput,
     has_id: bool,
+    confirm_delete: ConfirmPopup<usize>,
 }
 
 impl FormWidget for SubtaskEditor {
@@ -51,15 +62,22 @@ impl FormWidget for SubtaskEditor {
             title: String::from("Subtasks"),
             height: 8,
             uses_insert_mode: false,
+            uses_interactive_mode: false,
             hidden: false,
             enter_back: self.view == SubtaskView::Selection,
+            multiline: false,
         }
     }
 
+    fn has_input(&self) -> bool {
+        !self.subtasks.is_empty()
+    }
+
     fn reset(&mut self) {
         self.view = SubtaskView::Selection;
         self.input.reset();
         self.subtasks.clear();
+        self.confirm_delete.close();
     }
 }
 
@@ -71,7 +89,31 @@ impl SubtaskEditor {
             selection: Scrollable::default(),
             input: TextInput::new("Subtask").view(View::Popup).max(50).prompt(),
             has_id: false,
+            confirm_delete: ConfirmPopup::init(),
+        }
+    }
+
+    /// Whether the delete confirmation is open, which is shown over the whole
+    /// editor.
+    pub fn has_popup(&self) -> bool {
+        self.confirm_delete.is_open()
+    }
+
+    /// The delete confirmation takes every key.
+    pub fn popup_key_event_handler(&mut self, app: &mut App, key_event: KeyEvent) {
+        if let Some(index) = self.confirm_delete.handle_key(key_event) {
+            self.subtasks.remove(index);
+            self.selection.focused = self.selection.focused.saturating_sub(1);
         }
+        if !self.confirm_delete.is_open() {
+            app.mode.normal();
+        }
+    }
+
+    /// The delete confirmation is rendered over the whole editor, since it
+    /// doesn't fit in the area of the subtask editor.
+    pub fn render_popups(&self, app: &App, frame: &mut Frame, area: Rect) {
+        self.confirm_delete.render(app, frame, area);
     }
 }
 
@@ -86,12 +128,8 @@ impl KeyEventHandler for SubtaskEditor {
         match key_event.code {
             KeyCode::Char('n') => {
                 if self.view == SubtaskView::Selection {
-                    if app.mode.is_delete() {
-                        app.mode.normal();
-                    } else {
-                        self.view = SubtaskView::Input;
-                        app.mode.insert();
-                    }
+                    self.view = SubtaskView::Input;
+                    app.mode.insert();
                 }
             }
             KeyCode::Char('e') => {
@@ -104,8 +142,12 @@ impl KeyEventHandler for SubtaskEditor {
                 }
             }
             KeyCode::Char('d') => {
-                if self.view == SubtaskView::Selection && !self.subtasks.is_empty() {
-                    app.mode.delete();
+                if let Some(subtask) = self.subtasks.get(self.selection.focused) {
+                    if self.view == SubtaskView::Selection {
+                        let message = format!("Delete subtask '{}'?", subtask.value);
+                        self.confirm_delete.open(message, self.selection.focused);
+                        app.mode.delete();
+                    }
                 }
             }
             KeyCode::Char('.') => {
@@ -114,15 +156,32 @@ impl KeyEventHandler for SubtaskEditor {
                     self.subtasks.push(Subtask {
                         id: None,
                         value: subtask.value.to_owned(),
+                        completed: false,
                     });
                 }
             }
-            KeyCode::Char('y') => {
-                if self.view == SubtaskView::Selection && app.mode.is_delete() {
-                    self.subtasks.remove(self.selection.focused);
-                    self.selection.focused = self.selection.focused.saturating_sub(1);
-                    app.mode.normal();
-                }
+            KeyCode::Char(' ') | KeyCode::Char('c')
+                if self.view == SubtaskView::Selection && !self.subtasks.is_empty() =>
+            {
+                let subtask = &mut self.subtasks[self.selection.focused];
+                subtask.completed = !subtask.completed;
+            }
+            KeyCode::Char('J')
+                if self.view == SubtaskView::Selection
+                    && self.selection.focused + 1 < self.subtasks.len() =>
+            {
+                let focused = self.selection.focused;
+                self.subtasks.swap(focused, focused + 1);
+                self.selection
+                    .key_event_handler(app, KeyEvent::from(KeyCode::Char('j')));
+            }
+            KeyCode::Char('K')
+                if self.view == SubtaskView::Selection && self.selection.focused != 0 =>
+            {
+                let focused = self.selection.focused;
+                self.subtasks.swap(focused, focused - 1);
+                self.selection
+                    .key_event_handler(app, KeyEvent::from(KeyCode::Char('k')));
             }
             KeyCode::Char('[') => {
                 if app.mode.is_normal() && self.view == SubtaskView::Input {
@@ -133,14 +192,12 @@ impl KeyEventHandler for SubtaskEditor {
             KeyCode::Enter => {
                 if self.view == SubtaskView::Input {
                     if self.has_id {
-                        self.subtasks[self.selection.focused] = Subtask {
-                            id: self.subtasks[self.selection.focused].id,
-                            value: self.input.input_string(),
-                        };
+                        self.subtasks[self.selection.focused].value = self.input.input_string();
                     } else {
                         self.subtasks.push(Subtask {
                             id: None,
                             value: self.input.input_string(),
+                            completed: false,
                         });
                     };
                     self.view = SubtaskView::Selection;
@@ -169,10 +226,23 @@ impl DefaultWidget for SubtaskEditor {
                 .iter()
                 .enumerate()
                 .map(|(i, subtask)| {
-                    Paragraph::new(subtask.value.to_owned()).bg(if self.selection.focused == i {
-                        colors.input_focus_bg
+                    let value = if subtask.completed {
+                        Span::from(subtask.value.to_owned())
+                            .fg(colors.secondary_fg)
+                            .add_modifier(Modifier::CROSSED_OUT)
                     } else {
-                        colors.popup_bg
+                        Span::from(subtask.value.to_owned()).fg(colors.fg)
+                    };
+                    Paragraph::new(Line::from(vec![
+                        Span::from("[").fg(colors.secondary_fg),
+                        Span::from(if subtask.completed { "x" } else { " " }).fg(colors.fg),
+                        Span::from("] ").fg(colors.secondary_fg),
+                        value,
+                    ]))
+                    .style(if self.selection.focused == i {
+                        app.config.focus_style(colors.input_focus_bg)
+                    } else {
+                        Style::new().bg(colors.popup_bg)
                     })
                 })
                 .collect::<Vec<Paragraph>>();
@@ -187,10 +257,13 @@ impl DefaultWidget for SubtaskEditor {
 struct Inputs {
     title: Rc<RefCell<TextInput>>,
     description: Rc<RefCell<TextInput>>,
-    labels: Rc<RefCell<Selection<i32>>>,
+    priority: Rc<RefCell<Dropdown<Priority>>>,
+    labels: Rc<RefCell<MultiSelect<i32>>>,
+    tags: Rc<RefCell<MultiSelect<i32>>>,
     subtasks: Rc<RefCell<SubtaskEditor>>,
     start_date: Rc<RefCell<TextInput>>,
     due_date: Rc<RefCell<TextInput>>,
+    recurrence: Rc<RefCell<Dropdown<Recurrence>>>,
     reminder: Rc<RefCell<TextInput>>,
 }
 
@@ -199,10 +272,13 @@ struct CardData {
     id: i32,
     title: String,
     description: Option<String>,
+    priority: Priority,
     start_date: Option<DateTime>,
     due_date: Option<DateTime>,
+    recurrence: Recurrence,
     reminder: Option<i32>,
-    labels: HashSet<usize>,
+    labels: HashSet<i32>,
+    tags: HashSet<i32>,
     subtasks: Vec<Subtask<i32>>,
 }
 
@@ -210,30 +286,42 @@ pub struct CardEditor {
     project_id: Option<i32>,
     list_id: Option<i32>,
     original_data: Option<CardData>,
+    /// The id of the card that was last created, so it can be focused.
+    new_card_id: Option<i32>,
     inputs: Inputs,
     form: Form,
 }
 
 impl Popup<Result<bool>> for CardEditor {
     fn init() -> Self {
-        let title = TextInput::new("Title").view(View::Popup).max(50).form();
+        let title = TextInput::new("Title")
+            .view(View::Popup)
+            .max(CARD_TITLE_MAX_LENGTH)
+            .form();
         let description = TextInput::new("Description")
             .max(4000)
+            .multiline()
             .prompt_lines(10)
             .form();
-        let labels = Selection::new("Labels", vec![]).form();
+        let priority = Dropdown::new("Priority", Priority::options()).form();
+        let labels = MultiSelect::new("Labels", vec![]).form();
+        let tags = MultiSelect::new("Tags", vec![]).form();
         let subtasks = SubtaskEditor::init().form();
         let start_date = TextInput::new("Start Date").datetime_input().form();
         let due_date = TextInput::new("Due Date").datetime_input().form();
+        let recurrence = Dropdown::new("Recurrence", Recurrence::options()).form();
         let reminder = TextInput::new("Reminder").datetime_input().form();
 
         let inputs = Inputs {
             title: Rc::clone(&title),
             description: Rc::clone(&description),
+            priority: Rc::clone(&priority),
             labels: Rc::clone(&labels),
+            tags: Rc::clone(&tags),
             subtasks: Rc::clone(&subtasks),
             start_date: Rc::clone(&start_date),
             due_date: Rc::clone(&due_date),
+            recurrence: Rc::clone(&recurrence),
             reminder: Rc::clone(&reminder),
         };
 
@@ -241,14 +329,18 @@ impl Popup<Result<bool>> for CardEditor {
             project_id: None,
             list_id: None,
             original_data: None,
+            new_card_id: None,
             inputs,
             form: Form::from([
                 FormInput(title),
                 FormInput(description),
+                FormInput(priority),
                 FormInput(labels),
+                FormInput(tags),
                 FormInput(subtasks),
                 FormInput(start_date),
                 FormInput(due_date),
+                FormInput(recurrence),
                 FormInput(reminder),
             ])
             .default_title("New Card"),
@@ -258,6 +350,16 @@ impl Popup<Result<bool>> for CardEditor {
     fn key_event_handler(&mut self, app: &mut App, key_event: KeyEvent) -> Result<bool> {
         let _span = info_span!("project management", popup = "card editor").entered();
 
+        if self.inputs.subtasks.borrow().has_popup() {
+            self.inputs
+                .subtasks
+                .borrow_mut()
+                .popup_key_event_handler(app, key_event);
+            return Ok(false);
+        }
+
+        self.form
+            .confirm_discard_new(app.config.modules.project_management.confirm_discard_new);
         let result = self.form.key_event_handler(app, key_event);
 
         if result.is_submit() {
@@ -271,38 +373,61 @@ impl Popup<Result<bool>> for CardEditor {
 
     fn render(&self, app: &App, frame: &mut Frame, area: Rect) {
         self.form.render(frame, app, area, true);
+        self.inputs
+            .subtasks
+            .borrow()
+            .render_popups(app, frame, area);
     }
 }
 
 impl CardEditor {
-    fn db_new_card(&self, db: &Database, project_id: i32, list_id: i32) -> Result<i32> {
+    /// The datetime of a date input, where shorthands like `tomorrow` are at
+    /// the configured default time.
+    fn input_datetime(app: &App, input: &Rc<RefCell<TextInput>>) -> Option<String> {
+        let default_time = &app.config.modules.project_management.default_time;
+        DateTime::from_input_or_shorthand(&(**input).borrow().input_string(), default_time)
+    }
+
+    fn db_new_card(&self, app: &App, project_id: i32, list_id: i32) -> Result<i32> {
+        let db = &app.db;
         let start = Instant::now();
 
         let highest_position = db.get_highest_position_where("project_card", "list_id", list_id)?;
 
         let query_start = Instant::now();
+        let priority = (*self.inputs.priority)
+            .borrow()
+            .value()
+            .copied()
+            .unwrap_or(Priority::None);
         let query = "INSERT INTO project_card (project_id, list_id, title, description, \
-                     important, start_date, due_date, reminder, position, created_at, updated_at) \
-                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)";
+                     important, priority, start_date, due_date, recurrence, reminder, position, \
+                     created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, \
+                     ?11, ?12, ?13)";
         let params = (
             project_id,
             list_id,
             (*self.inputs.title).borrow().input_string(),
             (*self.inputs.description).borrow().get_value_option(),
-            false,
-            DateTime::from_input((*self.inputs.start_date).borrow().input_string()),
-            DateTime::from_input((*self.inputs.due_date).borrow().input_string()),
+            priority.is_important(),
+            priority.into_db(),
+            Self::input_datetime(app, &self.inputs.start_date),
+            Self::input_datetime(app, &self.inputs.due_date),
+            (*self.inputs.recurrence)
+                .borrow()
+                .value()
+                .and_then(|r| r.into_db()),
             Option::<String>::None,
             highest_position + 1,
             DateTime::now(),
             DateTime::now(),
         );
-        db.execute(query, params)?;
+        let new_card_id = db.insert(query, params)?;
 
         info!("new card query executed in {:?}", query_start.elapsed());
 
-        let new_card_id = db.last_row_id("project_card")?;
         self.db_new_card_labels(db, project_id, new_card_id)?;
+        self.db_set_card_tags(db, &HashSet::new(), new_card_id)?;
         self.db_new_subtasks(db, project_id, new_card_id)?;
 
         info!("new card query durations totaled at {:?}", start.elapsed());
@@ -313,14 +438,13 @@ impl CardEditor {
     fn db_new_card_labels(&self, db: &Database, project_id: i32, card_id: i32) -> Result<()> {
         let start = Instant::now();
 
-        for index in (*self.inputs.labels).borrow().selected.iter() {
-            let label = (*self.inputs.labels).borrow().options[*index].clone();
+        for label_id in (*self.inputs.labels).borrow().checked() {
             let query = "INSERT INTO card_label (project_id, card_id, label_id, created_at, \
                          updated_at) VALUES (?1, ?2, ?3, ?4, ?5)";
             let params = (
                 project_id,
                 card_id,
-                label.0,
+                label_id,
                 DateTime::now(),
                 DateTime::now(),
             );
@@ -334,33 +458,68 @@ impl CardEditor {
 
     fn db_new_subtasks(&self, db: &Database, project_id: i32, card_id: i32) -> Result<()> {
         for (i, subtask) in self.inputs.subtasks.borrow().subtasks.iter().enumerate() {
-            let query = "INSERT INTO card_subtask (project_id, card_id, value, completed, \
-                         position, created_at, updated_at) values (?1, ?2, ?3, ?4, ?5, ?6, ?7)";
-            let params = (
+            Self::db_new_subtask(
+                db,
                 project_id,
                 card_id,
-                subtask.value.to_owned(),
-                false,
-                i,
-                DateTime::now(),
-                DateTime::now(),
-            );
-            db.execute(query, params)?;
+                &subtask.value,
+                subtask.completed,
+                i as i32,
+            )?;
         }
         Ok(())
     }
 
-    fn db_edit_card(&self, db: &Database, data: &CardData, project_id: i32) -> Result<i32> {
+    /// Insert a subtask into a card.
+    pub fn db_new_subtask(
+        db: &Database,
+        project_id: i32,
+        card_id: i32,
+        value: &str,
+        completed: bool,
+        position: i32,
+    ) -> Result<()> {
+        let query = "INSERT INTO card_subtask (project_id, card_id, value, completed, position, \
+                     created_at, updated_at) values (?1, ?2, ?3, ?4, ?5, ?6, ?7)";
+        let params = (
+            project_id,
+            card_id,
+            value,
+            completed,
+            position,
+            DateTime::now(),
+            DateTime::now(),
+        );
+        db.execute(query, params)?;
+        Ok(())
+    }
+
+    fn db_edit_card(&self, app: &App, data: &CardData, project_id: i32) -> Result<i32> {
+        let db = &app.db;
         let start = Instant::now();
 
+        let priority = (*self.inputs.priority)
+            .borrow()
+            .value()
+            .copied()
+            .unwrap_or(Priority::None);
+        // The day of the month that a monthly card recurs on is the day of its
+        // due date, which is read again when the due date is changed.
         let query = "UPDATE project_card SET title = ?1, description = ?2, important = ?3, \
-                     start_date = ?4, due_date = ?5, reminder = ?6, updated_at = ?7 WHERE id = ?8";
+                     priority = ?4, start_date = ?5, due_date = ?6, recurrence = ?7, \
+                     recurrence_day = CASE WHEN due_date IS ?6 THEN recurrence_day END, reminder \
+                     = ?8, updated_at = ?9 WHERE id = ?10";
         let params = (
             (*self.inputs.title).borrow().input_string(),
             (*self.inputs.description).borrow().get_value_option(),
-            false,
-            DateTime::from_input((*self.inputs.start_date).borrow().input_string()),
-            DateTime::from_input((*self.inputs.due_date).borrow().input_string()),
+            priority.is_important(),
+            priority.into_db(),
+            Self::input_datetime(app, &self.inputs.start_date),
+            Self::input_datetime(app, &self.inputs.due_date),
+            (*self.inputs.recurrence)
+                .borrow()
+                .value()
+                .and_then(|r| r.into_db()),
             Option::<String>::None,
             DateTime::now(),
             data.id,
@@ -370,6 +529,7 @@ impl CardEditor {
         info!("edit card query executed in {:?}", start.elapsed());
 
         self.db_edit_card_labels(db, data, project_id, data.id)?;
+        self.db_set_card_tags(db, &data.tags, data.id)?;
         self.db_edit_subtasks(db, data, project_id, data.id)?;
 
         info!("edit card query durations total at {:?}", start.elapsed());
@@ -385,9 +545,10 @@ impl CardEditor {
         card_id: i32,
     ) -> Result<()> {
         let start = Instant::now();
-        for (i, label) in (*self.inputs.labels).borrow().options.iter().enumerate() {
-            if (*self.inputs.labels).borrow().selected.contains(&i) {
-                if !data.labels.contains(&i) {
+        let labels = (*self.inputs.labels).borrow();
+        for label in labels.options.iter() {
+            if labels.checked().contains(&label.0) {
+                if !data.labels.contains(&label.0) {
                     let query = "INSERT INTO card_label (project_id, card_id, label_id, \
                                  created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5)";
                     let params = (
@@ -399,7 +560,7 @@ impl CardEditor {
                     );
                     db.execute(query, params)?;
                 }
-            } else {
+            } else if data.labels.contains(&label.0) {
                 let query = "DELETE FROM card_label WHERE card_id = ?1 and label_id = ?2";
                 db.execute(query, (card_id, &label.0))?;
             }
@@ -410,6 +571,27 @@ impl CardEditor {
         Ok(())
     }
 
+    /// Add the checked tags to a card and remove the unchecked ones, where
+    /// `previous` are the tags the card had before it was edited.
+    fn db_set_card_tags(&self, db: &Database, previous: &HashSet<i32>, card_id: i32) -> Result<()> {
+        let start = Instant::now();
+
+        let tags = (*self.inputs.tags).borrow();
+        for tag_id in tags.checked().difference(previous) {
+            let query = "INSERT INTO card_tag (card_id, tag_id, created_at, updated_at) VALUES \
+                         (?1, ?2, ?3, ?4)";
+            db.execute(query, (card_id, tag_id, DateTime::now(), DateTime::now()))?;
+        }
+        for tag_id in previous.difference(tags.checked()) {
+            let query = "DELETE FROM card_tag WHERE card_id = ?1 AND tag_id = ?2";
+            db.execute(query, (card_id, tag_id))?;
+        }
+
+        info!("set card tags query executed in {:?}", start.elapsed());
+
+        Ok(())
+    }
+
     fn db_edit_subtasks(
         &self,
         db: &Database,
@@ -432,25 +614,30 @@ impl CardEditor {
             }
         }
 
+        // Positions are rewritten from the order of the editor, so reordered and
+        // deleted subtasks keep a gapless order.
         for (i, subtask) in self.inputs.subtasks.borrow().subtasks.iter().enumerate() {
             if let Some(id) = subtask.id {
-                let query = "UPDATE card_subtask SET value = ?1, updated_at = ?2 WHERE project_id \
-                             = ?3 and id = ?4";
-                let params = (subtask.value.to_owned(), DateTime::now(), project_id, id);
-                db.execute(query, params)?;
-            } else {
-                let query = "INSERT INTO card_subtask (project_id, card_id, value, completed, \
-                             position, created_at, updated_at) values (?1, ?2, ?3, ?4, ?5, ?6, ?7)";
+                let query = "UPDATE card_subtask SET value = ?1, completed = ?2, position = ?3, \
+                             updated_at = ?4 WHERE project_id = ?5 and id = ?6";
                 let params = (
-                    project_id,
-                    card_id,
                     subtask.value.to_owned(),
-                    false,
-                    i,
-                    DateTime::now(),
+                    subtask.completed,
+                    i as i32,
                     DateTime::now(),
+                    project_id,
+                    id,
                 );
                 db.execute(query, params)?;
+            } else {
+                Self::db_new_subtask(
+                    db,
+                    project_id,
+                    card_id,
+                    &subtask.value,
+                    subtask.completed,
+                    i as i32,
+                )?;
             }
         }
         Ok(())
@@ -458,12 +645,21 @@ impl CardEditor {
 }
 
 impl CardEditor {
+    /// Save the card. The editor stays open with its input if saving fails.
     fn submit(&mut self, app: &mut App) -> Result<bool> {
         if let Some(project_id) = self.project_id {
-            if let Some(data) = &self.original_data {
-                self.db_edit_card(&app.db, data, project_id)?;
+            let result = if let Some(data) = &self.original_data {
+                self.db_edit_card(app, data, project_id).map(|_| ())
             } else if let Some(list_id) = self.list_id {
-                self.db_new_card(&app.db, project_id, list_id)?;
+                self.db_new_card(app, project_id, list_id)
+                    .map(|id| self.new_card_id = Some(id))
+            } else {
+                Ok(())
+            };
+            if let Err(e) = result {
+                error!("failed to save card: {e}");
+                app.notify_error("Failed to save the card");
+                return Ok(false);
             }
             self.reset();
             app.view.default();
@@ -480,13 +676,21 @@ impl CardEditor {
             labels
                 .into_iter()
                 .map(|l| {
-                    (
-                        l.id,
-                        Span::from(l.title)
-                            .fg(Color::from_str(&l.color.clone()).unwrap_or(colors.fg)),
-                    )
+                    let color = Color::from_str(&l.color).unwrap_or(colors.fg);
+                    (l.id, l.title, color)
                 })
-                .collect::<Vec<(i32, Span)>>(),
+                .collect::<Vec<(i32, String, Color)>>(),
+        )
+    }
+
+    pub fn tags(&self, colors: &ColorsConfig, tags: Vec<Tag>) {
+        (*self.inputs.tags).borrow_mut().options(
+            tags.into_iter()
+                .map(|t| {
+                    let color = Color::from_str(&t.color).unwrap_or(colors.fg);
+                    (t.id, t.title, color)
+                })
+                .collect::<Vec<(i32, String, Color)>>(),
         )
     }
 
@@ -502,18 +706,21 @@ impl CardEditor {
         let conn = db.conn();
 
         let query_start = Instant::now();
-        let query = "SELECT id, title, description, start_date, due_date, reminder FROM \
-                     project_card WHERE id = ?1";
+        let query = "SELECT id, title, description, priority, start_date, due_date, recurrence, \
+                     reminder FROM project_card WHERE id = ?1";
         let mut stmt = conn.prepare(query)?;
         let mut card = stmt.query_row([card_id], |r| {
             Ok(CardData {
                 id: r.get(0)?,
                 title: r.get(1)?,
                 description: r.get(2)?,
-                start_date: DateTime::from_db_option(r.get(3)?),
-                due_date: DateTime::from_db_option(r.get(4)?),
-                reminder: r.get(5)?,
+                priority: Priority::from_db(r.get(3)?),
+                start_date: DateTime::from_db_option(r.get(4)?),
+                due_date: DateTime::from_db_option(r.get(5)?),
+                recurrence: Recurrence::from_db(r.get(6)?),
+                reminder: r.get(7)?,
                 labels: HashSet::new(),
+                tags: HashSet::new(),
                 subtasks: vec![],
             })
         })?;
@@ -523,8 +730,10 @@ impl CardEditor {
         );
 
         self.db_get_card_labels(db, &mut card)?;
+        self.db_get_card_tags(db, &mut card)?;
         self.db_get_subtasks(db, &mut card)?;
         self.original_data = Some(card);
+        self.form.editing();
 
         if let Some(data) = &self.original_data {
             (*self.inputs.title).borrow_mut().input(data.title.clone());
@@ -547,6 +756,14 @@ impl CardEditor {
                     .input(due_date.display());
             }
 
+            (*self.inputs.priority)
+                .borrow_mut()
+                .set_value(&data.priority);
+
+            (*self.inputs.recurrence)
+                .borrow_mut()
+                .set_value(&data.recurrence);
+
             if let Some(reminder) = &data.reminder {
                 (*self.inputs.reminder)
                     .borrow_mut()
@@ -559,6 +776,60 @@ impl CardEditor {
         Ok(())
     }
 
+    /// The id of the card that was created when the editor was last submitted,
+    /// which is only read once.
+    pub fn take_new_card_id(&mut self) -> Option<i32> {
+        self.new_card_id.take()
+    }
+
+    /// Pre-fill the inputs of a new card from a card template.
+    pub fn set_template(&mut self, db: &Database, template_id: i32) -> Result<()> {
+        let start = Instant::now();
+        self.reset();
+
+        let conn = db.conn();
+
+        let query = "SELECT title, description FROM card_template WHERE id = ?1";
+        let mut stmt = conn.prepare(query)?;
+        let (title, description) = stmt.query_row([template_id], |r| {
+            Ok((
+                r.get::<usize, String>(0)?,
+                r.get::<usize, Option<String>>(1)?,
+            ))
+        })?;
+
+        (*self.inputs.title).borrow_mut().input(title);
+        if let Some(description) = description {
+            (*self.inputs.description).borrow_mut().input(description);
+        }
+
+        let label_query = "SELECT label_id FROM card_template_label WHERE template_id = ?1";
+        let mut label_stmt = conn.prepare(label_query)?;
+        let label_id_iter = label_stmt.query_map([template_id], |r| r.get::<usize, i32>(0))?;
+        let mut labels = (*self.inputs.labels).borrow_mut();
+        for label in label_id_iter {
+            // Labels that have since been removed from the project are skipped.
+            labels.check(label?);
+        }
+
+        let subtask_query =
+            "SELECT value FROM card_template_subtask WHERE template_id = ?1 ORDER BY position";
+        let mut subtask_stmt = conn.prepare(subtask_query)?;
+        let subtask_iter = subtask_stmt.query_map([template_id], |r| r.get::<usize, String>(0))?;
+        let mut subtask_editor = self.inputs.subtasks.borrow_mut();
+        for value in subtask_iter {
+            subtask_editor.subtasks.push(Subtask {
+                id: None,
+                value: value?,
+                completed: false,
+            });
+        }
+
+        info!("set card template data in {:?}", start.elapsed());
+
+        Ok(())
+    }
+
     fn db_get_card_labels(&mut self, db: &Database, data: &mut CardData) -> Result<()> {
         let start = Instant::now();
         let conn = db.conn();
@@ -568,17 +839,8 @@ impl CardEditor {
 
         for label in label_id_iter {
             let label_id = label?;
-            let index_in_project_labels = (*self.inputs.labels)
-                .borrow()
-                .options
-                .iter()
-                .position(|l| l.0 == label_id)
-                .expect("failed to get project label index");
-            (*self.inputs.labels)
-                .borrow_mut()
-                .selected
-                .insert(index_in_project_labels);
-            data.labels.insert(index_in_project_labels);
+            (*self.inputs.labels).borrow_mut().check(label_id);
+            data.labels.insert(label_id);
         }
         if let Some(start_date) = &data.start_date {
             (*self.inputs.start_date)
@@ -591,15 +853,35 @@ impl CardEditor {
         Ok(())
     }
 
+    fn db_get_card_tags(&mut self, db: &Database, data: &mut CardData) -> Result<()> {
+        let start = Instant::now();
+        let conn = db.conn();
+        let query = "SELECT tag_id FROM card_tag WHERE card_id = ?1";
+        let mut stmt = conn.prepare(query)?;
+        let tag_id_iter = stmt.query_map([data.id], |r| r.get::<usize, i32>(0))?;
+
+        for tag in tag_id_iter {
+            let tag_id = tag?;
+            (*self.inputs.tags).borrow_mut().check(tag_id);
+            data.tags.insert(tag_id);
+        }
+
+        info!("get card tags query executed in {:?}", start.elapsed());
+
+        Ok(())
+    }
+
     fn db_get_subtasks(&mut self, db: &Database, data: &mut CardData) -> Result<()> {
         let start = Instant::now();
         let conn = db.conn();
-        let query = "SELECT id, value FROM card_subtask WHERE card_id = ?1";
+        let query =
+            "SELECT id, value, completed FROM card_subtask WHERE card_id = ?1 ORDER BY position";
         let mut stmt = conn.prepare(query)?;
         let subtask_iter = stmt.query_map([data.id], |r| {
             Ok(Subtask::<i32> {
                 id: r.get(0)?,
                 value: r.get(1)?,
+                completed: r.get(2)?,
             })
         })?;
 
@@ -610,12 +892,10 @@ impl CardEditor {
             subtask_editor.subtasks.push(Subtask {
                 id: Some(st.id),
                 value: st.value.clone(),
+                completed: st.completed,
             });
 
-            data.subtasks.push(Subtask::<i32> {
-                id: st.id,
-                value: st.value,
-            });
+            data.subtasks.push(st);
         }
 
         info!("get card subtasks query executed in {:?}", start.elapsed());
@@ -0,0 +1,267 @@
+use std::time::Instant;
+
+use color_eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use pltx_app::{App, KeyEventHandler, Popup};
+use pltx_database::Database;
+use pltx_utils::{DateTime, WidgetMargin};
+use pltx_widgets::{ConfirmPopup, PopupSize, PopupWidget, Scrollable};
+use ratatui::{
+    layout::Rect,
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+use rusqlite::TransactionBehavior;
+use tracing::{info, info_span};
+
+struct CardTemplate {
+    id: i32,
+    name: String,
+    labels: i32,
+    subtasks: i32,
+}
+
+/// Lists the card templates of a project. Selecting a template returns its id
+/// so the parent can open a card editor that is pre-filled from it.
+pub struct CardTemplates {
+    project_id: Option<i32>,
+    templates: Vec<CardTemplate>,
+    selection: Scrollable,
+    confirm_delete: ConfirmPopup<i32>,
+}
+
+impl Popup<Result<Option<i32>>> for CardTemplates {
+    fn init() -> Self {
+        Self {
+            project_id: None,
+            templates: vec![],
+            selection: Scrollable::default(),
+            confirm_delete: ConfirmPopup::init(),
+        }
+    }
+
+    /// Returns the id of the template that was selected.
+    fn key_event_handler(&mut self, app: &mut App, key_event: KeyEvent) -> Result<Option<i32>> {
+        let _span = info_span!("project management", popup = "card templates").entered();
+
+        if self.confirm_delete.is_open() {
+            if let Some(template_id) = self.confirm_delete.key_event_handler(app, key_event) {
+                self.db_delete_template(&app.db, template_id)?;
+                self.db_get_templates(&app.db)?;
+            }
+            if !self.confirm_delete.is_open() {
+                app.mode.normal();
+            }
+            return Ok(None);
+        }
+
+        self.selection.key_event_handler(app, key_event);
+
+        match key_event.code {
+            KeyCode::Char('q') => {
+                app.view.default();
+                self.reset();
+            }
+            KeyCode::Char('d') => {
+                if let Some(template) = self.templates.get(self.selection.focused) {
+                    let message = format!("Delete template '{}'?", template.name);
+                    self.confirm_delete.open(message, template.id);
+                    app.mode.delete();
+                }
+            }
+            KeyCode::Enter => {
+                if let Some(template) = self.templates.get(self.selection.focused) {
+                    let template_id = template.id;
+                    self.reset();
+                    return Ok(Some(template_id));
+                }
+            }
+            _ => {}
+        }
+
+        Ok(None)
+    }
+
+    fn render(&self, app: &App, frame: &mut Frame, area: Rect) {
+        let colors = &app.config.colors;
+
+        let popup = PopupWidget::new(app, area)
+            .title_top("Card Templates")
+            .size(PopupSize::default().width(60).height(14))
+            .render(frame);
+
+        let area = WidgetMargin::horizontal(1).apply(popup.sub_area);
+
+        if self.templates.is_empty() {
+            let paragraph = Paragraph::new(Line::from(vec![
+                Span::from("You have no card templates. Press "),
+                Span::from("t").bold().fg(colors.keybind_key),
+                Span::from(" on a card to save one."),
+            ]))
+            .fg(colors.secondary_fg);
+            frame.render_widget(paragraph, area);
+            return;
+        }
+
+        let table = self
+            .templates
+            .iter()
+            .enumerate()
+            .map(|(i, template)| {
+                let style = if self.selection.focused == i {
+                    app.config
+                        .focus_style(colors.input_focus_bg)
+                        .bold()
+                        .fg(colors.fg)
+                } else {
+                    Style::new().fg(colors.secondary_fg)
+                };
+                Paragraph::new(Line::from(vec![
+                    Span::from(template.name.to_string()),
+                    Span::from(format!(
+                        "  {} labels, {} subtasks",
+                        template.labels, template.subtasks
+                    ))
+                    .fg(colors.tertiary_fg),
+                ]))
+                .style(style)
+            })
+            .collect::<Vec<Paragraph>>();
+
+        self.selection.render(frame, area, table);
+        self.confirm_delete.render(app, frame, popup.popup_area);
+    }
+}
+
+impl CardTemplates {
+    pub fn project_id(&mut self, project_id: i32) {
+        self.project_id = Some(project_id);
+    }
+
+    pub fn db_get_templates(&mut self, db: &Database) -> Result<()> {
+        let start = Instant::now();
+        self.templates.clear();
+
+        if let Some(project_id) = self.project_id {
+            let conn = db.conn();
+            let query = "SELECT id, name, (SELECT COUNT(*) FROM card_template_label WHERE \
+                         template_id = card_template.id), (SELECT COUNT(*) FROM \
+                         card_template_subtask WHERE template_id = card_template.id) FROM \
+                         card_template WHERE project_id = ?1 ORDER BY position";
+            let mut stmt = conn.prepare(query)?;
+            let template_iter = stmt.query_map([project_id], |r| {
+                Ok(CardTemplate {
+                    id: r.get(0)?,
+                    name: r.get(1)?,
+                    labels: r.get(2)?,
+                    subtasks: r.get(3)?,
+                })
+            })?;
+
+            for template in template_iter {
+                self.templates.push(template?);
+            }
+        }
+
+        if self.selection.focused >= self.templates.len() {
+            self.selection.focused = self.templates.len().saturating_sub(1);
+        }
+
+        info!("get card templates query executed in {:?}", start.elapsed());
+
+        Ok(())
+    }
+
+    /// Save a card, its labels, and its subtasks as a new template. The name of
+    /// the template is the title of the card.
+    pub fn db_new_template(&self, db: &Database, card_id: i32) -> Result<i32> {
+        let _span = info_span!("project management", popup = "card templates").entered();
+        let start = Instant::now();
+
+        // The template and its labels and subtasks are written together, and
+        // the position is read in the same transaction.
+        let mut conn = db.conn();
+        let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+        let project_id: i32 = tx
+            .prepare_cached("SELECT project_id FROM project_card WHERE id = ?1")?
+            .query_row([card_id], |r| r.get(0))?;
+        let position: i32 = tx.query_row(
+            "SELECT COALESCE(MAX(position), -1) + 1 FROM card_template WHERE project_id = ?1",
+            [project_id],
+            |r| r.get(0),
+        )?;
+
+        let query = "INSERT INTO card_template (project_id, name, title, description, position, \
+                     created_at, updated_at) SELECT project_id, title, title, description, ?1, \
+                     ?2, ?3 FROM project_card WHERE id = ?4";
+        let params = (position, DateTime::now(), DateTime::now(), card_id);
+        tx.prepare_cached(query)?.execute(params)?;
+        // The id of the row inserted by this connection, like `Database::insert`.
+        let template_id = tx.last_insert_rowid() as i32;
+
+        let label_query = "INSERT INTO card_template_label (template_id, label_id, created_at, \
+                           updated_at) SELECT ?1, label_id, ?2, ?3 FROM card_label WHERE card_id \
+                           = ?4";
+        tx.execute(
+            label_query,
+            (template_id, DateTime::now(), DateTime::now(), card_id),
+        )?;
+
+        let subtask_query = "INSERT INTO card_template_subtask (template_id, value, position, \
+                             created_at, updated_at) SELECT ?1, value, position, ?2, ?3 FROM \
+                             card_subtask WHERE card_id = ?4";
+        tx.execute(
+            subtask_query,
+            (template_id, DateTime::now(), DateTime::now(), card_id),
+        )?;
+        tx.commit()?;
+
+        info!("new card template query executed in {:?}", start.elapsed());
+
+        Ok(template_id)
+    }
+
+    fn db_delete_template(&self, db: &Database, template_id: i32) -> Result<()> {
+        let start = Instant::now();
+
+        if let Some(project_id) = self.project_id {
+            let mut conn = db.conn();
+            let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+            let original_position: i32 = tx.query_row(
+                "SELECT position FROM card_template WHERE id = ?1",
+                [template_id],
+                |r| r.get(0),
+            )?;
+
+            tx.execute("DELETE FROM card_template WHERE id = ?1", [template_id])?;
+            tx.execute(
+                "DELETE FROM card_template_label WHERE template_id = ?1",
+                [template_id],
+            )?;
+            tx.execute(
+                "DELETE FROM card_template_subtask WHERE template_id = ?1",
+                [template_id],
+            )?;
+            tx.execute(
+                "UPDATE card_template SET position = position - 1, updated_at = ?1 WHERE position \
+                 > ?2 AND project_id = ?3",
+                (DateTime::now(), original_position, project_id),
+            )?;
+            tx.commit()?;
+        }
+
+        info!(
+            "delete card template query executed in {:?}",
+            start.elapsed()
+        );
+
+        Ok(())
+    }
+
+    pub fn reset(&mut self) {
+        self.selection.reset();
+        self.confirm_delete.close();
+    }
+}
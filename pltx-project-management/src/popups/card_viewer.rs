@@ -2,10 +2,13 @@ use std::{collections::HashSet, str::FromStr, time::Instant};
 
 use color_eyre::Result;
 use crossterm::event::{KeyCode, KeyEvent};
-use pltx_app::{App, DefaultWidget, KeyEventHandler, Popup};
+use pltx_app::{state::View, App, DefaultWidget, KeyEventHandler, Popup};
 use pltx_database::Database;
 use pltx_utils::{DateTime, WidgetMargin};
-use pltx_widgets::{PopupSize, PopupWidget, Selection};
+use pltx_widgets::{
+    clipboard, markdown_lines, ConfirmPopup, PopupSize, PopupWidget, Scrollable, Selection,
+    TextInput,
+};
 use ratatui::{
     layout::{Constraint, Layout, Rect},
     style::{Color, Style, Stylize},
@@ -13,9 +16,34 @@ use ratatui::{
     widgets::Paragraph,
     Frame,
 };
+use rusqlite::TransactionBehavior;
 use tracing::{info, info_span};
 
-use crate::open_project::ProjectLabel;
+use super::card_editor::CardEditor;
+use crate::{
+    open_project::{card_markdown, OpenProject, ProjectLabel, Tag},
+    priority::Priority,
+};
+
+#[derive(PartialEq)]
+enum CardViewerView {
+    Card,
+    SubtaskTemplates,
+    NewSubtaskTemplate,
+}
+
+/// The section of the card that receives key events.
+#[derive(PartialEq)]
+enum CardViewerSection {
+    Description,
+    Subtasks,
+}
+
+struct SubtaskTemplate {
+    id: i32,
+    name: String,
+    items: i32,
+}
 
 struct Subtask {
     id: i32,
@@ -25,23 +53,79 @@ struct Subtask {
 
 struct CardData {
     id: i32,
+    project_id: i32,
     title: String,
     description: Option<String>,
+    priority: Priority,
     start_date: Option<DateTime>,
     due_date: Option<DateTime>,
     reminder: Option<DateTime>,
+    completed: bool,
     position: i32,
     created_at: DateTime,
     updated_at: DateTime,
     labels: HashSet<i32>,
+    tags: HashSet<i32>,
     subtasks: Vec<Subtask>,
 }
 
+impl CardData {
+    /// How long the card has been in progress or until it starts, and how
+    /// long until it's due or since it was due, such as "In progress for 3d
+    /// 2h, due in 6h". There is nothing to show for completed cards.
+    fn timing(&self, now: &DateTime) -> Option<String> {
+        if self.completed {
+            return None;
+        }
+
+        let humanize = DateTime::humanize_duration;
+        let mut parts = vec![];
+        if let Some(start_date) = &self.start_date {
+            let until_start = now.duration_until(start_date);
+            if until_start.num_seconds() > 0 {
+                parts.push(format!("starts in {}", humanize(until_start)));
+            } else {
+                parts.push(format!(
+                    "in progress for {}",
+                    humanize(now.duration_since(start_date))
+                ));
+            }
+        }
+        if let Some(due_date) = &self.due_date {
+            let until_due = now.duration_until(due_date);
+            if until_due.num_seconds() > 0 {
+                parts.push(format!("due in {}", humanize(until_due)));
+            } else {
+                parts.push(format!("overdue by {}", humanize(until_due)));
+            }
+        }
+
+        let timing = parts.join(", ");
+        let mut chars = timing.chars();
+        chars
+            .next()
+            .map(|first| first.to_uppercase().chain(chars).collect())
+    }
+}
+
 pub struct CardViewer {
     id: Option<i32>,
     data: Option<CardData>,
     subtasks_selection: Selection<i32>,
     labels: Vec<ProjectLabel>,
+    tags: Vec<Tag>,
+    view: CardViewerView,
+    section: CardViewerSection,
+    description_scroll: Scrollable,
+    /// Whether `y` was pressed, so the next key picks what to copy.
+    pending_yank: bool,
+    /// Show when the card was created and updated as timestamps rather than
+    /// relative to now.
+    absolute_timestamps: bool,
+    subtask_templates: Vec<SubtaskTemplate>,
+    subtask_template_selection: Scrollable,
+    subtask_template_name: TextInput,
+    confirm_delete: ConfirmPopup<i32>,
 }
 
 impl Popup<Result<bool>> for CardViewer {
@@ -51,12 +135,53 @@ impl Popup<Result<bool>> for CardViewer {
             data: None,
             subtasks_selection: Selection::new("Subtasks", vec![]).checklist(),
             labels: vec![],
+            tags: vec![],
+            view: CardViewerView::Card,
+            section: CardViewerSection::Description,
+            description_scroll: Scrollable::default(),
+            pending_yank: false,
+            absolute_timestamps: false,
+            subtask_templates: vec![],
+            subtask_template_selection: Scrollable::default(),
+            subtask_template_name: TextInput::new("Template Name")
+                .view(View::Popup)
+                .max(50)
+                .prompt(),
+            confirm_delete: ConfirmPopup::init(),
         }
     }
 
     fn key_event_handler(&mut self, app: &mut App, key_event: KeyEvent) -> Result<bool> {
         let _span = info_span!("project management", popup = "card viewer").entered();
-        self.subtasks_selection.key_event_handler(app, key_event);
+
+        match self.view {
+            CardViewerView::SubtaskTemplates => {
+                return self.subtask_templates_key_event_handler(app, key_event)
+            }
+            CardViewerView::NewSubtaskTemplate => {
+                self.new_subtask_template_key_event_handler(app, key_event)?;
+                return Ok(false);
+            }
+            CardViewerView::Card => {}
+        }
+
+        if std::mem::take(&mut self.pending_yank) {
+            self.yank(app, key_event);
+            return Ok(false);
+        }
+        if key_event.code == KeyCode::Char('y') && app.mode.is_normal() {
+            self.pending_yank = true;
+            return Ok(false);
+        }
+
+        let has_subtasks = self.data.as_ref().is_some_and(|d| !d.subtasks.is_empty());
+
+        match self.section {
+            CardViewerSection::Description => self.description_scroll.handle_key(key_event),
+            CardViewerSection::Subtasks => {
+                self.subtasks_selection.key_event_handler(app, key_event)
+            }
+        }
 
         match key_event.code {
             KeyCode::Char('q') => {
@@ -64,17 +189,26 @@ impl Popup<Result<bool>> for CardViewer {
                 self.reset();
                 return Ok(false);
             }
-            KeyCode::Char(' ') => {
-                self.db_update_subtasks(&app.db)?;
-                return Ok(true);
+            KeyCode::Tab | KeyCode::BackTab if has_subtasks => {
+                self.section = match self.section {
+                    CardViewerSection::Description => CardViewerSection::Subtasks,
+                    CardViewerSection::Subtasks => CardViewerSection::Description,
+                };
             }
-            KeyCode::Char('i') => {
-                self.db_update_subtasks(&app.db)?;
+            KeyCode::Char(' ') | KeyCode::Char('i') | KeyCode::Char('a')
+                if self.section == CardViewerSection::Subtasks =>
+            {
+                self.db_update_subtasks(app)?;
                 return Ok(true);
             }
-            KeyCode::Char('a') => {
-                self.db_update_subtasks(&app.db)?;
-                return Ok(true);
+            KeyCode::Char('t') if has_subtasks => {
+                self.view = CardViewerView::NewSubtaskTemplate;
+                app.mode.insert();
+            }
+            KeyCode::Char('A') => self.absolute_timestamps = !self.absolute_timestamps,
+            KeyCode::Char('T') => {
+                self.db_get_subtask_templates(&app.db)?;
+                self.view = CardViewerView::SubtaskTemplates;
             }
             _ => {}
         }
@@ -98,73 +232,83 @@ impl Popup<Result<bool>> for CardViewer {
 
             let area = WidgetMargin::proportional(1).apply(popup.sub_area);
 
-            let description_lines = if let Some(description) = &data.description {
-                description.chars().count().div_ceil(area.width as usize) as u16
+            let description = if let Some(desc) = &data.description {
+                markdown_lines(desc, colors, area.width)
             } else {
-                1
+                vec![Line::from(vec![Span::styled(
+                    "<empty description>",
+                    Style::new().fg(colors.tertiary_fg),
+                )])]
             };
 
             let spacing = 1;
+            let subtasks_height = if data.subtasks.is_empty() {
+                0
+            } else if data.subtasks.len() as u16 <= 5 {
+                data.subtasks.len() as u16 + spacing
+            } else {
+                5 + spacing
+            };
+            // Long descriptions scroll in the space the other sections leave.
+            let description_height = (description.len() as u16)
+                .min(
+                    area.height
+                        .saturating_sub(spacing + (1 + spacing) + subtasks_height)
+                        .saturating_sub((5 + spacing) + 3),
+                )
+                .max(1);
             let [description_area, labels_area, subtasks_area, dates_area, metadata_area] =
                 Layout::default()
                     .constraints([
-                        Constraint::Length(description_lines + spacing),
+                        Constraint::Length(description_height + spacing),
                         Constraint::Length(1 + spacing),
-                        Constraint::Length(if data.subtasks.is_empty() {
-                            0
-                        } else if data.subtasks.len() as u16 <= 5 {
-                            data.subtasks.len() as u16 + spacing
-                        } else {
-                            5 + spacing
-                        }),
-                        Constraint::Length(3 + spacing),
-                        Constraint::Length(4),
+                        Constraint::Length(subtasks_height),
+                        Constraint::Length(5 + spacing),
+                        Constraint::Length(3),
                     ])
                     .areas(area);
 
-            let line_length = description_area.width as usize;
-            let mut first_line_length = line_length;
-            let description = if let Some(desc) = &data.description {
-                if desc.chars().count() <= first_line_length {
-                    first_line_length = 0;
-                }
-                desc[first_line_length..]
-                    .chars()
-                    .collect::<Vec<char>>()
-                    .chunks(line_length)
-                    .enumerate()
-                    .flat_map(|(i, c)| {
-                        let mut text = vec![];
-                        if i == 0 {
-                            text.push(Line::from(vec![Span::from(
-                                desc[..first_line_length].to_string(),
-                            )
-                            .fg(colors.fg)]))
-                        }
-                        text.push(
-                            Line::from(Span::from(c.iter().collect::<String>().trim().to_owned()))
-                                .fg(colors.fg),
-                        );
-                        text
-                    })
-                    .collect::<Vec<Line>>()
-            } else {
-                vec![Line::from(vec![Span::styled(
-                    "<empty description>",
-                    Style::new().fg(colors.tertiary_fg),
-                )])]
-            };
-
-            frame.render_widget(Paragraph::new(description), description_area);
-
+            let scrolls = description.len() > description_height as usize;
+            let description_focused = self.section == CardViewerSection::Description;
+            let rows = description
+                .into_iter()
+                .enumerate()
+                .map(|(i, line)| {
+                    let row = Paragraph::new(line);
+                    if scrolls && description_focused && self.description_scroll.focused == i {
+                        row.style(app.config.focus_style(colors.input_focus_bg))
+                    } else {
+                        row
+                    }
+                })
+                .collect::<Vec<Paragraph>>();
+            self.description_scroll.render(
+                frame,
+                Rect {
+                    height: description_height,
+                    ..description_area
+                },
+                rows,
+            );
+
+            // The tags are shown after the labels, with a # to tell them apart.
+            let labels = self
+                .labels
+                .iter()
+                .filter(|l| data.labels.contains(&l.id))
+                .map(|l| (l.title.to_string(), &l.color));
+            let tags = self
+                .tags
+                .iter()
+                .filter(|t| data.tags.contains(&t.id))
+                .map(|t| (format!("#{}", t.title), &t.color));
             let labels = Paragraph::new(Line::from(
-                self.labels
-                    .iter()
-                    .filter(|l| data.labels.contains(&l.id))
+                labels
+                    .chain(tags)
                     .enumerate()
-                    .flat_map(|(i, l)| {
-                        let mut span = vec![Span::from(l.title.to_string())
-                            .fg(Color::from_str(&l.color).expect("failed to parse label color"))];
+                    .flat_map(|(i, (title, color))| {
+                        let mut span =
+                            vec![Span::from(title).fg(Color::from_str(color).unwrap_or(colors.fg))];
                         if i != 0 {
                             span.insert(0, Span::from(", ").fg(colors.secondary_fg));
                         }
@@ -175,14 +319,27 @@ impl Popup<Result<bool>> for CardViewer {
 
             frame.render_widget(labels, labels_area);
 
-            self.subtasks_selection
-                .render(frame, app, subtasks_area, true);
+            self.subtasks_selection.render(
+                frame,
+                app,
+                subtasks_area,
+                self.section == CardViewerSection::Subtasks,
+            );
 
+            let datetime_format = app.config.datetime.datetime_format();
             let dates = Paragraph::new(vec![
+                Line::from(vec![
+                    Span::from("Priority: "),
+                    if data.priority == Priority::None {
+                        Span::from("<empty>").fg(colors.tertiary_fg)
+                    } else {
+                        Span::from(data.priority.title())
+                    },
+                ]),
                 Line::from(vec![
                     Span::from("Start Date: "),
                     if let Some(start_date) = &data.start_date {
-                        Span::from(start_date.display())
+                        Span::from(start_date.display_format(&datetime_format))
                     } else {
                         Span::from("<empty>").fg(colors.tertiary_fg)
                     },
@@ -190,7 +347,7 @@ impl Popup<Result<bool>> for CardViewer {
                 Line::from(vec![
                     Span::from("Due Date: "),
                     if let Some(due_date) = &data.due_date {
-                        Span::from(due_date.display())
+                        Span::from(due_date.display_format(&datetime_format))
                     } else {
                         Span::from("<empty>").fg(colors.tertiary_fg)
                     },
@@ -198,11 +355,12 @@ impl Popup<Result<bool>> for CardViewer {
                 Line::from(vec![
                     Span::from("Reminder: "),
                     if let Some(reminder) = &data.reminder {
-                        Span::from(reminder.display())
+                        Span::from(reminder.display_format(&datetime_format))
                     } else {
                         Span::from("<empty>").fg(colors.tertiary_fg)
                     },
                 ]),
+                Line::from(data.timing(&DateTime::new()).unwrap_or_default()),
             ])
             .fg(colors.secondary_fg);
 
@@ -217,19 +375,168 @@ impl Popup<Result<bool>> for CardViewer {
                     Span::from("Position in List: ").bold(),
                     Span::from(data.position.to_string()),
                 ]),
-                Line::from(vec![
-                    Span::from("Created At: ").bold(),
-                    Span::from(data.created_at.display_with_seconds()),
-                ]),
-                Line::from(vec![
-                    Span::from("Updated: At: ").bold(),
-                    Span::from(data.updated_at.display_with_seconds()),
-                ]),
+                Line::from(
+                    [
+                        vec![Span::from("Created ")],
+                        self.timestamp(app, &data.created_at),
+                        vec![Span::from(", updated ")],
+                        self.timestamp(app, &data.updated_at),
+                    ]
+                    .concat(),
+                ),
             ])
             .fg(colors.secondary_fg);
 
             frame.render_widget(metadata, metadata_area);
+
+            match self.view {
+                CardViewerView::SubtaskTemplates => {
+                    self.render_subtask_templates(app, frame, popup.popup_area)
+                }
+                CardViewerView::NewSubtaskTemplate => {
+                    self.render_new_subtask_template(app, frame, popup.popup_area)
+                }
+                CardViewerView::Card => {}
+            }
+        }
+    }
+}
+
+impl CardViewer {
+    fn subtask_templates_key_event_handler(
+        &mut self,
+        app: &mut App,
+        key_event: KeyEvent,
+    ) -> Result<bool> {
+        if self.confirm_delete.is_open() {
+            if let Some(template_id) = self.confirm_delete.key_event_handler(app, key_event) {
+                self.db_delete_subtask_template(&app.db, template_id)?;
+                self.db_get_subtask_templates(&app.db)?;
+            }
+            if !self.confirm_delete.is_open() {
+                app.mode.normal();
+            }
+            return Ok(false);
+        }
+
+        self.subtask_template_selection
+            .key_event_handler(app, key_event);
+
+        match key_event.code {
+            KeyCode::Char('q') | KeyCode::Char('[') => {
+                self.view = CardViewerView::Card;
+                self.subtask_template_selection.reset();
+            }
+            KeyCode::Char('d') => {
+                if let Some(template) = self
+                    .subtask_templates
+                    .get(self.subtask_template_selection.focused)
+                {
+                    let message = format!("Delete subtask template '{}'?", template.name);
+                    self.confirm_delete.open(message, template.id);
+                    app.mode.delete();
+                }
+            }
+            KeyCode::Enter => {
+                if let (Some(data), Some(template)) = (
+                    &self.data,
+                    self.subtask_templates
+                        .get(self.subtask_template_selection.focused),
+                ) {
+                    let card_id = data.id;
+                    self.db_insert_subtask_template(&app.db, template.id)?;
+                    self.view = CardViewerView::Card;
+                    self.subtask_template_selection.reset();
+                    self.reset();
+                    self.set_data(&app.db, card_id)?;
+                    return Ok(true);
+                }
+            }
+            _ => {}
+        }
+
+        Ok(false)
+    }
+
+    fn new_subtask_template_key_event_handler(
+        &mut self,
+        app: &mut App,
+        key_event: KeyEvent,
+    ) -> Result<()> {
+        self.subtask_template_name.key_event_handler(app, key_event);
+
+        if app.mode.is_normal() && key_event.code == KeyCode::Char('q') {
+            self.view = CardViewerView::Card;
+            self.subtask_template_name.reset();
+        } else if key_event.code == KeyCode::Enter && !self.subtask_template_name.is_empty() {
+            self.db_new_subtask_template(&app.db)?;
+            self.view = CardViewerView::Card;
+            self.subtask_template_name.reset();
+            app.mode.normal();
+        }
+
+        Ok(())
+    }
+
+    fn render_subtask_templates(&self, app: &App, frame: &mut Frame, area: Rect) {
+        let colors = &app.config.colors;
+
+        let popup = PopupWidget::new(app, area)
+            .title_top("Subtask Templates")
+            .size(PopupSize::default().width(50).height(12))
+            .render(frame);
+
+        let area = WidgetMargin::horizontal(1).apply(popup.sub_area);
+
+        if self.subtask_templates.is_empty() {
+            let paragraph = Paragraph::new(Line::from(vec![
+                Span::from("You have no subtask templates. Press "),
+                Span::from("t").bold().fg(colors.keybind_key),
+                Span::from(" to save the subtasks of this card."),
+            ]))
+            .fg(colors.secondary_fg);
+            frame.render_widget(paragraph, area);
+            return;
         }
+
+        let table = self
+            .subtask_templates
+            .iter()
+            .enumerate()
+            .map(|(i, template)| {
+                let style = if self.subtask_template_selection.focused == i {
+                    app.config
+                        .focus_style(colors.input_focus_bg)
+                        .bold()
+                        .fg(colors.fg)
+                } else {
+                    Style::new().fg(colors.secondary_fg)
+                };
+                Paragraph::new(Line::from(vec![
+                    Span::from(template.name.to_string()),
+                    Span::from(format!("  {} subtasks", template.items)).fg(colors.tertiary_fg),
+                ]))
+                .style(style)
+            })
+            .collect::<Vec<Paragraph>>();
+
+        self.subtask_template_selection.render(frame, area, table);
+        self.confirm_delete.render(app, frame, popup.popup_area);
+    }
+
+    fn render_new_subtask_template(&self, app: &App, frame: &mut Frame, area: Rect) {
+        let popup = PopupWidget::new(app, area)
+            .title_top("New Subtask Template")
+            .size(PopupSize::default().width(50).height(6))
+            .render(frame);
+
+        let [name_layout] = Layout::default()
+            .margin(2)
+            .constraints([Constraint::Length(3)])
+            .areas(popup.popup_area);
+
+        self.subtask_template_name
+            .render(frame, app, name_layout, true);
     }
 }
 
@@ -238,31 +545,56 @@ impl CardViewer {
         self.labels = labels;
     }
 
+    pub fn tags(&mut self, tags: Vec<Tag>) {
+        self.tags = tags;
+    }
+
     pub fn id(&mut self, card_id: i32) {
         self.id = Some(card_id);
     }
 
+    /// Focus the section named by the `card_viewer_initial_section` config. The
+    /// description is focused if the card has no subtasks.
+    pub fn focus_initial_section(&mut self, app: &App) {
+        let has_subtasks = self.data.as_ref().is_some_and(|d| !d.subtasks.is_empty());
+        let initial_section = &app
+            .config
+            .modules
+            .project_management
+            .card_viewer_initial_section;
+        self.section = if initial_section == "subtasks" && has_subtasks {
+            CardViewerSection::Subtasks
+        } else {
+            CardViewerSection::Description
+        };
+    }
+
     pub fn set_data(&mut self, db: &Database, card_id: i32) -> Result<()> {
         let start = Instant::now();
 
         let conn = db.conn();
 
         let query_start = Instant::now();
-        let query = "SELECT id, title, description, start_date, due_date, reminder, position, \
-                     created_at, updated_at FROM project_card WHERE id = ?1";
-        let mut stmt = conn.prepare(query)?;
+        let query = "SELECT id, project_id, title, description, priority, start_date, due_date, \
+                     reminder, completed, position, created_at, updated_at FROM project_card \
+                     WHERE id = ?1";
+        let mut stmt = conn.prepare_cached(query)?;
         let mut card = stmt.query_row([card_id], |r| {
             Ok(CardData {
                 id: r.get(0)?,
-                title: r.get(1)?,
-                description: r.get(2)?,
-                start_date: DateTime::from_db_option(r.get(3)?),
-                due_date: DateTime::from_db_option(r.get(4)?),
-                reminder: DateTime::from_db_option(r.get(5)?),
-                position: r.get(6)?,
-                created_at: DateTime::from_db(r.get(7)?),
-                updated_at: DateTime::from_db(r.get(8)?),
+                project_id: r.get(1)?,
+                title: r.get(2)?,
+                description: r.get(3)?,
+                priority: Priority::from_db(r.get(4)?),
+                start_date: DateTime::from_db_option(r.get(5)?),
+                due_date: DateTime::from_db_option(r.get(6)?),
+                reminder: DateTime::from_db_option(r.get(7)?),
+                completed: r.get(8)?,
+                position: r.get(9)?,
+                created_at: DateTime::from_db(r.get(10)?),
+                updated_at: DateTime::from_db(r.get(11)?),
                 labels: HashSet::new(),
+                tags: HashSet::new(),
                 subtasks: vec![],
             })
         })?;
@@ -272,6 +604,7 @@ impl CardViewer {
         );
 
         self.db_get_card_labels(db, &mut card)?;
+        self.db_get_card_tags(db, &mut card)?;
         self.db_get_subtasks(db, &mut card)?;
         self.data = Some(card);
 
@@ -284,7 +617,7 @@ impl CardViewer {
         let start = Instant::now();
         let conn = db.conn();
         let query = "SELECT label_id from card_label WHERE card_id = ?1";
-        let mut stmt = conn.prepare(query)?;
+        let mut stmt = conn.prepare_cached(query)?;
         let label_id_iter = stmt.query_map([data.id], |r| r.get::<usize, i32>(0))?;
 
         for label in label_id_iter {
@@ -297,11 +630,28 @@ impl CardViewer {
         Ok(())
     }
 
+    fn db_get_card_tags(&mut self, db: &Database, data: &mut CardData) -> Result<()> {
+        let start = Instant::now();
+        let conn = db.conn();
+        let query = "SELECT tag_id FROM card_tag WHERE card_id = ?1";
+        let mut stmt = conn.prepare_cached(query)?;
+        let tag_id_iter = stmt.query_map([data.id], |r| r.get::<usize, i32>(0))?;
+
+        for tag in tag_id_iter {
+            data.tags.insert(tag?);
+        }
+
+        info!("get card tags query executed in {:?}", start.elapsed());
+
+        Ok(())
+    }
+
     fn db_get_subtasks(&mut self, db: &Database, data: &mut CardData) -> Result<()> {
         let start = Instant::now();
         let conn = db.conn();
-        let query = "SELECT id, value, completed FROM card_subtask WHERE card_id = ?1";
-        let mut stmt = conn.prepare(query)?;
+        let query =
+            "SELECT id, value, completed FROM card_subtask WHERE card_id = ?1 ORDER BY position";
+        let mut stmt = conn.prepare_cached(query)?;
         let subtask_iter = stmt.query_map([data.id], |r| {
             Ok(Subtask {
                 id: r.get(0)?,
@@ -332,15 +682,245 @@ impl CardViewer {
         Ok(())
     }
 
+    fn db_get_subtask_templates(&mut self, db: &Database) -> Result<()> {
+        let start = Instant::now();
+        self.subtask_templates.clear();
+
+        if let Some(data) = &self.data {
+            let conn = db.conn();
+            let query = "SELECT id, name, (SELECT COUNT(*) FROM subtask_template_item WHERE \
+                         template_id = subtask_template.id) FROM subtask_template WHERE \
+                         project_id = ?1 ORDER BY position";
+            let mut stmt = conn.prepare_cached(query)?;
+            let template_iter = stmt.query_map([data.project_id], |r| {
+                Ok(SubtaskTemplate {
+                    id: r.get(0)?,
+                    name: r.get(1)?,
+                    items: r.get(2)?,
+                })
+            })?;
+
+            for template in template_iter {
+                self.subtask_templates.push(template?);
+            }
+        }
+
+        if self.subtask_template_selection.focused >= self.subtask_templates.len() {
+            self.subtask_template_selection.focused =
+                self.subtask_templates.len().saturating_sub(1);
+        }
+
+        info!(
+            "get subtask templates query executed in {:?}",
+            start.elapsed()
+        );
+
+        Ok(())
+    }
+
+    /// Save the subtasks of the card as a new template.
+    fn db_new_subtask_template(&self, db: &Database) -> Result<()> {
+        let start = Instant::now();
+
+        if let Some(data) = &self.data {
+            // The template and its items are written together, and the
+            // position is read in the same transaction.
+            let mut conn = db.conn();
+            let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+            let position: i32 = tx.query_row(
+                "SELECT COALESCE(MAX(position), -1) + 1 FROM subtask_template WHERE project_id = \
+                 ?1",
+                [data.project_id],
+                |r| r.get(0),
+            )?;
+
+            let query = "INSERT INTO subtask_template (project_id, name, position, created_at, \
+                         updated_at) VALUES (?1, ?2, ?3, ?4, ?5)";
+            let params = (
+                data.project_id,
+                self.subtask_template_name.input_string(),
+                position,
+                DateTime::now(),
+                DateTime::now(),
+            );
+            tx.prepare_cached(query)?.execute(params)?;
+            // The id of the row inserted by this connection, like `Database::insert`.
+            let template_id = tx.last_insert_rowid() as i32;
+
+            for (i, subtask) in data.subtasks.iter().enumerate() {
+                let item_query = "INSERT INTO subtask_template_item (template_id, value, \
+                                  position, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5)";
+                let params = (
+                    template_id,
+                    &subtask.value,
+                    i,
+                    DateTime::now(),
+                    DateTime::now(),
+                );
+                tx.prepare_cached(item_query)?.execute(params)?;
+            }
+            tx.commit()?;
+        }
+
+        info!(
+            "new subtask template query executed in {:?}",
+            start.elapsed()
+        );
+
+        Ok(())
+    }
+
+    /// Append the items of a template to the subtasks of the card.
+    fn db_insert_subtask_template(&self, db: &Database, template_id: i32) -> Result<()> {
+        let start = Instant::now();
+
+        if let Some(data) = &self.data {
+            let conn = db.conn();
+            let query =
+                "SELECT value FROM subtask_template_item WHERE template_id = ?1 ORDER BY position";
+            let mut stmt = conn.prepare_cached(query)?;
+            let item_iter = stmt.query_map([template_id], |r| r.get::<usize, String>(0))?;
+
+            let highest_position =
+                db.get_highest_position_where("card_subtask", "card_id", data.id)?;
+
+            for (i, value) in item_iter.enumerate() {
+                CardEditor::db_new_subtask(
+                    db,
+                    data.project_id,
+                    data.id,
+                    &value?,
+                    false,
+                    highest_position + 1 + i as i32,
+                )?;
+            }
+        }
+
+        info!(
+            "insert subtask template query executed in {:?}",
+            start.elapsed()
+        );
+
+        Ok(())
+    }
+
+    fn db_delete_subtask_template(&self, db: &Database, template_id: i32) -> Result<()> {
+        let start = Instant::now();
+
+        if let Some(data) = &self.data {
+            let mut conn = db.conn();
+            let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+            let original_position: i32 = tx.query_row(
+                "SELECT position FROM subtask_template WHERE id = ?1",
+                [template_id],
+                |r| r.get(0),
+            )?;
+
+            tx.execute("DELETE FROM subtask_template WHERE id = ?1", [template_id])?;
+            tx.execute(
+                "DELETE FROM subtask_template_item WHERE template_id = ?1",
+                [template_id],
+            )?;
+            tx.execute(
+                "UPDATE subtask_template SET position = position - 1, updated_at = ?1 WHERE \
+                 position > ?2 AND project_id = ?3",
+                (DateTime::now(), original_position, data.project_id),
+            )?;
+            tx.commit()?;
+        }
+
+        info!(
+            "delete subtask template query executed in {:?}",
+            start.elapsed()
+        );
+
+        Ok(())
+    }
+
+    /// A creation or update time, relative to now unless absolute timestamps
+    /// are toggled on.
+    fn timestamp(&self, app: &App, datetime: &DateTime) -> Vec<Span<'static>> {
+        let colors = &app.config.colors;
+        if self.absolute_timestamps {
+            vec![
+                Span::from(datetime.display_format(&app.config.datetime.date_format))
+                    .fg(colors.date_fg),
+                Span::from(" "),
+                Span::from(datetime.display_time_with_seconds()).fg(colors.time_fg),
+            ]
+        } else {
+            vec![Span::from(datetime.relative()).fg(colors.date_fg)]
+        }
+    }
+
+    /// Copy the title (`t`), description (`d`), or the whole card as Markdown
+    /// (`a`) after `y` was pressed.
+    fn yank(&self, app: &mut App, key_event: KeyEvent) {
+        let Some(data) = &self.data else {
+            return;
+        };
+        let (text, what) = match key_event.code {
+            KeyCode::Char('t') => (data.title.clone(), "title"),
+            KeyCode::Char('d') => match &data.description {
+                Some(description) => (description.clone(), "description"),
+                None => {
+                    app.notify_warning("The card has no description to copy");
+                    return;
+                }
+            },
+            KeyCode::Char('a') => {
+                let labels = self
+                    .labels
+                    .iter()
+                    .filter(|l| data.labels.contains(&l.id))
+                    .map(|l| l.title.as_str())
+                    .collect::<Vec<&str>>();
+                let subtasks = data
+                    .subtasks
+                    .iter()
+                    .map(|s| (s.completed, s.value.as_str()))
+                    .collect::<Vec<(bool, &str)>>();
+                let markdown = card_markdown(
+                    &data.title,
+                    data.completed,
+                    &labels,
+                    data.description.as_deref(),
+                    &subtasks,
+                );
+                (markdown, "card as Markdown")
+            }
+            _ => return,
+        };
+
+        if !clipboard::is_available() {
+            app.notify_warning("The clipboard isn't available in this build");
+            return;
+        }
+        clipboard::set_text(text);
+        app.notify_info(format!("Copied the {}", what));
+    }
+
     pub fn reset(&mut self) {
         self.data = None;
+        self.view = CardViewerView::Card;
+        self.section = CardViewerSection::Description;
+        self.description_scroll.reset();
+        self.pending_yank = false;
         self.subtasks_selection.reset();
         self.subtasks_selection.options.clear();
+        self.confirm_delete.close();
     }
 
-    fn db_update_subtasks(&self, db: &Database) -> Result<()> {
+    fn db_update_subtasks(&self, app: &App) -> Result<()> {
+        let db = &app.db;
         let start = Instant::now();
         if let Some(data) = &self.data {
+            let unchecked = data
+                .subtasks
+                .iter()
+                .enumerate()
+                .any(|(i, st)| st.completed && !self.subtasks_selection.selected.contains(&i));
+
             for (_, subtask) in data.subtasks.iter().enumerate().filter(|(i, st)| {
                 (st.completed && !self.subtasks_selection.selected.contains(i))
                     || (!st.completed && self.subtasks_selection.selected.contains(i))
@@ -355,6 +935,15 @@ impl CardViewer {
                 );
                 db.execute(query, params)?;
             }
+
+            let config = &app.config.modules.project_management;
+            let all_completed = !data.subtasks.is_empty()
+                && self.subtasks_selection.selected.len() == data.subtasks.len();
+            if all_completed && config.auto_complete_on_all_subtasks {
+                self.db_set_card_completed(db, true)?;
+            } else if unchecked && config.reopen_on_unchecked_subtask {
+                self.db_set_card_completed(db, false)?;
+            }
         }
         info!(
             "update card subtasks query executed in {:?}",
@@ -362,4 +951,30 @@ impl CardViewer {
         );
         Ok(())
     }
+
+    /// Complete or reopen the card, if it isn't already. A recurring card that
+    /// is completed creates its next occurrence, the same as completing it
+    /// from the board.
+    fn db_set_card_completed(&self, db: &Database, completed: bool) -> Result<()> {
+        let Some(card_id) = self.id else {
+            return Ok(());
+        };
+
+        let query = "UPDATE project_card SET completed = ?1, completed_at = ?2, updated_at = ?3 \
+                     WHERE id = ?4 AND completed != ?1";
+        let completed_at = if completed {
+            Some(DateTime::now())
+        } else {
+            None
+        };
+        let mut conn = db.conn();
+        let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+        let changed = tx.execute(query, (completed, completed_at, DateTime::now(), card_id))?;
+        if changed > 0 && completed {
+            OpenProject::db_new_recurring_card(&tx, card_id)?;
+        }
+        tx.commit()?;
+
+        Ok(())
+    }
 }
@@ -0,0 +1,179 @@
+use std::time::Instant;
+
+use color_eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use pltx_app::{App, KeyEventHandler, Popup};
+use pltx_database::Database;
+use pltx_utils::WidgetMargin;
+use pltx_widgets::{PopupSize, PopupWidget, Scrollable};
+use ratatui::{
+    layout::Rect,
+    style::{Style, Stylize},
+    widgets::Paragraph,
+    Frame,
+};
+use tracing::{info, info_span};
+
+#[derive(PartialEq)]
+enum PositionIssue {
+    Duplicate,
+    Gap,
+    None,
+}
+
+struct CardPosition {
+    id: i32,
+    list_id: i32,
+    list_title: String,
+    position: i32,
+    title: String,
+    issue: PositionIssue,
+}
+
+/// Lists every card of a project with its stored position and list, read
+/// directly from the database. Duplicate positions and gaps within a list are
+/// highlighted, since they cause cards to be ordered incorrectly.
+pub struct DebugPositions {
+    cards: Vec<CardPosition>,
+    selection: Scrollable,
+}
+
+impl Popup<Result<bool>> for DebugPositions {
+    fn init() -> Self {
+        Self {
+            cards: vec![],
+            selection: Scrollable::default().cols([6, 9, 14, 10, 11, 16]),
+        }
+    }
+
+    fn key_event_handler(&mut self, app: &mut App, key_event: KeyEvent) -> Result<bool> {
+        self.selection.key_event_handler(app, key_event);
+
+        if key_event.code == KeyCode::Char('q') {
+            app.view.default();
+            self.reset();
+        }
+
+        Ok(false)
+    }
+
+    fn render(&self, app: &App, frame: &mut Frame, area: Rect) {
+        let colors = &app.config.colors;
+
+        let issue_count = self
+            .cards
+            .iter()
+            .filter(|c| c.issue != PositionIssue::None)
+            .count();
+        let title = format!("Card Positions ({} issues)", issue_count);
+        let popup = PopupWidget::new(app, area)
+            .title_top(&title)
+            .size(PopupSize::default().width(70).height(24))
+            .render(frame);
+
+        let area = WidgetMargin::horizontal(1).apply(popup.sub_area);
+
+        if self.cards.is_empty() {
+            frame.render_widget(
+                Paragraph::new("There are no cards in this project.").fg(colors.secondary_fg),
+                area,
+            );
+            return;
+        }
+
+        let header = ["id", "list id", "list", "position", "issue", "title"]
+            .into_iter()
+            .map(|h| Paragraph::new(h).bold().fg(colors.secondary_fg))
+            .collect::<Vec<Paragraph>>();
+
+        let table = self
+            .cards
+            .iter()
+            .enumerate()
+            .map(|(i, card)| {
+                let style = if self.selection.focused == i {
+                    app.config.focus_style(colors.input_focus_bg).fg(colors.fg)
+                } else if card.issue != PositionIssue::None {
+                    Style::new().fg(colors.danger)
+                } else {
+                    Style::new().fg(colors.secondary_fg)
+                };
+                let issue = match card.issue {
+                    PositionIssue::Duplicate => "duplicate",
+                    PositionIssue::Gap => "gap",
+                    PositionIssue::None => "",
+                };
+                [
+                    card.id.to_string(),
+                    card.list_id.to_string(),
+                    card.list_title.to_string(),
+                    card.position.to_string(),
+                    issue.to_string(),
+                    card.title.to_string(),
+                ]
+                .into_iter()
+                .map(|cell| Paragraph::new(cell).style(style))
+                .collect::<Vec<Paragraph>>()
+            })
+            .collect::<Vec<Vec<Paragraph>>>();
+
+        self.selection.render_with_cols(frame, area, header, table);
+    }
+}
+
+impl DebugPositions {
+    pub fn db_get_cards(&mut self, db: &Database, project_id: i32) -> Result<()> {
+        let _span = info_span!("project management", popup = "debug positions").entered();
+        let start = Instant::now();
+
+        let conn = db.conn();
+        let query = "SELECT c.id, c.list_id, l.title, c.position, c.title FROM project_card c \
+                     JOIN project_list l ON l.id = c.list_id WHERE c.project_id = ?1 AND \
+                     c.deleted_at IS NULL ORDER BY l.position, c.list_id, c.position, c.id";
+        let mut stmt = conn.prepare(query)?;
+        let card_iter = stmt.query_map([project_id], |r| {
+            Ok(CardPosition {
+                id: r.get(0)?,
+                list_id: r.get(1)?,
+                list_title: r.get(2)?,
+                position: r.get(3)?,
+                title: r.get(4)?,
+                issue: PositionIssue::None,
+            })
+        })?;
+
+        self.cards.clear();
+        for card in card_iter {
+            let mut card = card?;
+
+            // Positions within a list are expected to start at 0 and increase by 1.
+            let previous_position = self
+                .cards
+                .last()
+                .filter(|c| c.list_id == card.list_id)
+                .map(|c| c.position)
+                .unwrap_or(-1);
+            card.issue = if card.position == previous_position {
+                PositionIssue::Duplicate
+            } else if card.position != previous_position + 1 {
+                PositionIssue::Gap
+            } else {
+                PositionIssue::None
+            };
+
+            self.cards.push(card);
+        }
+
+        info!(
+            "get debug card positions query executed in {:?}",
+            start.elapsed()
+        );
+
+        Ok(())
+    }
+
+    pub fn reset(&mut self) {
+        self.cards.clear();
+        self.selection.reset();
+    }
+}
@@ -10,24 +10,33 @@ use ratatui::{
     layout::{Constraint, Layout, Rect},
     Frame,
 };
-use tracing::{info, info_span};
+use tracing::{error, info, info_span};
 
 #[derive(Clone)]
 struct ListData {
     id: i32,
     title: String,
+    wip_limit: Option<i32>,
+}
+
+#[derive(PartialEq)]
+enum FocusedInput {
+    Title,
+    WipLimit,
 }
 
 pub struct ListEditor {
     project_id: Option<i32>,
     original_data: Option<ListData>,
     title_input: TextInput,
+    wip_limit_input: TextInput,
+    focused_input: FocusedInput,
     size: PopupSize,
 }
 
 impl Popup<Result<bool>> for ListEditor {
     fn init() -> ListEditor {
-        let size = PopupSize::default().width(60).height(6);
+        let size = PopupSize::default().width(60).height(10);
 
         ListEditor {
             project_id: None,
@@ -37,29 +46,56 @@ impl Popup<Result<bool>> for ListEditor {
                 .max(50)
                 .size((size.width - 2, size.height - 2))
                 .prompt(),
+            wip_limit_input: TextInput::new("WIP Limit")
+                .view(View::Popup)
+                .numeric()
+                .bounds(0, 999)
+                .size((size.width - 2, size.height - 2))
+                .prompt(),
+            focused_input: FocusedInput::Title,
             size,
         }
     }
 
     /// Returns whether the data is the database was modified.
     fn key_event_handler(&mut self, app: &mut App, key_event: KeyEvent) -> Result<bool> {
-        self.title_input.key_event_handler(app, key_event);
+        match self.focused_input {
+            FocusedInput::Title => self.title_input.key_event_handler(app, key_event),
+            FocusedInput::WipLimit => self.wip_limit_input.key_event_handler(app, key_event),
+        }
 
         if app.mode.is_normal() && key_event.code == KeyCode::Char('q') {
             self.reset(app);
             return Ok(false);
         }
 
-        if key_event.code == KeyCode::Enter {
-            if self.original_data.is_some() {
-                self.db_edit_list(&app.db)?;
+        if key_event.code == KeyCode::Tab {
+            self.focused_input = match self.focused_input {
+                FocusedInput::Title => FocusedInput::WipLimit,
+                FocusedInput::WipLimit => FocusedInput::Title,
+            };
+        }
+
+        if key_event.code == KeyCode::Enter && self.focused_input == FocusedInput::Title {
+            self.focused_input = FocusedInput::WipLimit;
+        } else if key_event.code == KeyCode::Enter && self.wip_limit_input.is_valid() {
+            let result = if self.original_data.is_some() {
+                self.db_edit_list(&app.db).map(Some)
             } else if let Some(project_id) = self.project_id {
-                self.db_new_list(app, project_id)?;
+                self.db_new_list(app, project_id)
             } else {
-                panic!("error")
+                Err(eyre!("the list editor has no list or project"))
+            };
+            match result {
+                Ok(list_id) => {
+                    self.reset(app);
+                    return Ok(list_id.is_some());
+                }
+                Err(e) => {
+                    error!("failed to save list: {e}");
+                    app.notify_error("Failed to save the list");
+                }
             }
-            self.reset(app);
-            return Ok(true);
         }
 
         Ok(false)
@@ -75,17 +111,30 @@ impl Popup<Result<bool>> for ListEditor {
             .size(self.size)
             .render(frame);
 
-        let [title_layout] = Layout::default()
+        let [title_layout, wip_limit_layout] = Layout::default()
             .margin(2)
-            .constraints([Constraint::Length(3)])
+            .constraints([Constraint::Length(3), Constraint::Length(3)])
             .areas(popup.popup_area);
 
-        self.title_input.render(frame, app, title_layout, true);
+        self.title_input.render(
+            frame,
+            app,
+            title_layout,
+            self.focused_input == FocusedInput::Title,
+        );
+        self.wip_limit_input.render(
+            frame,
+            app,
+            wip_limit_layout,
+            self.focused_input == FocusedInput::WipLimit,
+        );
     }
 }
 
 impl ListEditor {
-    fn db_new_list(&self, app: &mut App, project_id: i32) -> Result<i32> {
+    /// Returns the id of the new list, or none if the project already has the
+    /// maximum number of lists.
+    fn db_new_list(&self, app: &mut App, project_id: i32) -> Result<Option<i32>> {
         let _span = info_span!("project management", popup = "list editor").entered();
         let start = Instant::now();
 
@@ -94,16 +143,20 @@ impl ListEditor {
                 .get_highest_position_where("project_list", "project_id", project_id)?;
 
         let max_lists = app.config.modules.project_management.max_lists;
-        if highest_position == max_lists - 1 {
-            // TODO: Replace with error notification
-            return Err(eyre!("cannot create more than {} lists", max_lists));
+        if highest_position >= max_lists - 1 {
+            app.notify_warning(format!(
+                "A project can't have more than {} lists",
+                max_lists
+            ));
+            return Ok(None);
         }
 
-        let query = "INSERT INTO project_list (project_id, title, position, created_at, \
-                     updated_at) VALUES (?1, ?2, ?3, ?4, ?5)";
+        let query = "INSERT INTO project_list (project_id, title, wip_limit, position, \
+                     created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6)";
         let params = (
             project_id,
             self.title_input.input_string(),
+            self.wip_limit(),
             highest_position + 1,
             DateTime::now(),
             DateTime::now(),
@@ -114,19 +167,28 @@ impl ListEditor {
 
         info!("new list query executed in {:?}", start.elapsed());
 
-        Ok(new_list_id)
+        Ok(Some(new_list_id))
     }
 
     fn db_edit_list(&self, db: &Database) -> Result<i32> {
         let _span = info_span!("project management", popup = "list editor").entered();
         let start = Instant::now();
 
-        let data = self.original_data.as_ref().expect("list data was not set");
+        let data = self
+            .original_data
+            .as_ref()
+            .ok_or_else(|| eyre!("list data was not set"))?;
 
-        let query = "UPDATE project_list SET title = ?1, updated_at = ?2 WHERE id = ?3";
+        let query =
+            "UPDATE project_list SET title = ?1, wip_limit = ?2, updated_at = ?3 WHERE id = ?4";
         db.execute(
             query,
-            (&self.title_input.input_string(), DateTime::now(), data.id),
+            (
+                &self.title_input.input_string(),
+                self.wip_limit(),
+                DateTime::now(),
+                data.id,
+            ),
         )?;
 
         info!("edit list query executed in {:?}", start.elapsed());
@@ -136,23 +198,31 @@ impl ListEditor {
 }
 
 impl ListEditor {
+    /// The WIP limit of the input, where an empty input or 0 is no limit.
+    fn wip_limit(&self) -> Option<i32> {
+        self.wip_limit_input.input_i32().filter(|limit| *limit > 0)
+    }
+
     pub fn project_id(&mut self, project_id: i32) {
         self.project_id = Some(project_id)
     }
 
     pub fn set(&mut self, db: &Database, list_id: i32) -> Result<()> {
         let conn = db.conn();
-        let query = "SELECT id, title FROM project_list WHERE id = ?1";
+        let query = "SELECT id, title, wip_limit FROM project_list WHERE id = ?1";
         let mut stmt = conn.prepare(query)?;
         let list = stmt.query_row([list_id], |r| {
             Ok(ListData {
                 id: r.get(0)?,
                 title: r.get(1)?,
+                wip_limit: r.get(2)?,
             })
         })?;
 
         self.original_data = Some(list.clone());
         self.title_input.input(list.title);
+        self.wip_limit_input
+            .input(list.wip_limit.map(|l| l.to_string()).unwrap_or_default());
 
         Ok(())
     }
@@ -162,5 +232,7 @@ impl ListEditor {
         app.mode.normal();
         self.original_data = None;
         self.title_input.reset();
+        self.wip_limit_input.reset();
+        self.focused_input = FocusedInput::Title;
     }
 }
@@ -1,24 +1,59 @@
 use std::{cell::RefCell, rc::Rc, str::FromStr};
 
-use color_eyre::Result;
+use color_eyre::{eyre::eyre, Result};
 use crossterm::event::{KeyCode, KeyEvent};
 use pltx_app::{state::View, App, DefaultWidget, KeyEventHandler, Popup};
+use pltx_config::ColorsConfig;
 use pltx_database::Database;
 use pltx_utils::DateTime;
-use pltx_widgets::{Form, FormInput, FormInputState, FormWidget, Scrollable, TextInput};
+use pltx_widgets::{
+    color_palette, ConfirmPopup, Form, FormInput, FormInputState, FormWidget, ListPicker,
+    PopupSize, Scrollable, Selection, TextInput,
+};
 use ratatui::{
-    layout::{Constraint, Layout, Rect},
-    style::{Color, Stylize},
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style, Stylize},
     text::{Line, Span},
     widgets::Paragraph,
     Frame,
 };
+use rusqlite::Transaction;
+use tracing::error;
 
 const PROJECT_TITLE_MAX_LENGTH: usize = 50;
 const PROJECT_DESCRIPTION_MAX_LENGTH: usize = 160;
 const LABEL_TITLE_MAX_LENGTH: usize = 15;
 const LABEL_COLOR_REQUIRED_LENGTH: usize = 7;
 
+/// The lists that a new project can start with. Projects start without lists
+/// unless a template is selected.
+const LIST_TEMPLATES: [(&str, &[&str]); 3] = [
+    ("Empty", &[]),
+    ("Kanban", &["To Do", "Doing", "Done"]),
+    ("GTD", &["Inbox", "Next", "Waiting", "Done"]),
+];
+
+fn list_template_options(colors: &ColorsConfig) -> Vec<(usize, Span<'static>)> {
+    LIST_TEMPLATES
+        .iter()
+        .enumerate()
+        .map(|(i, (name, lists))| {
+            let span = if lists.is_empty() {
+                Span::from(name.to_string())
+            } else {
+                Span::from(format!("{}: {}", name, lists.join(", ")))
+            };
+            (i, span.fg(colors.fg))
+        })
+        .collect()
+}
+
+fn validate_label_color(color: &str) -> Result<(), String> {
+    Color::from_str(color)
+        .map(|_| ())
+        .map_err(|_| String::from("Enter a hex color or a color name, such as #61a4ff or blue"))
+}
+
 #[derive(PartialEq)]
 pub enum LabelView {
     Selection,
@@ -49,6 +84,9 @@ pub struct LabelEditor {
     inputs: LabelInputs,
     focused_input: FocusedLabelInput,
     has_id: bool,
+    color_picker: ListPicker<String>,
+    /// Confirms deleting a label that cards use, with the index of the label.
+    confirm_delete: ConfirmPopup<usize>,
 }
 
 impl FormWidget for LabelEditor {
@@ -64,17 +102,29 @@ impl FormWidget for LabelEditor {
             title: String::from("Labels"),
             height: 8,
             uses_insert_mode: false,
+            uses_interactive_mode: false,
             hidden: false,
             enter_back: self.view == LabelView::Selection,
+            multiline: false,
         }
     }
 
+    fn has_input(&self) -> bool {
+        !self.labels.is_empty()
+    }
+
+    fn is_valid(&self) -> bool {
+        self.is_valid()
+    }
+
     fn reset(&mut self) {
         self.view = LabelView::Selection;
         self.focused_input = FocusedLabelInput::Title;
         self.inputs.title.reset();
         self.inputs.color.reset();
         self.labels.clear();
+        self.color_picker.close();
+        self.confirm_delete.close();
     }
 }
 
@@ -92,11 +142,131 @@ impl LabelEditor {
                 color: TextInput::new("Label Color")
                     .view(View::Popup)
                     .max(LABEL_COLOR_REQUIRED_LENGTH)
+                    .placeholder("Hex color, or p in normal mode for the palette")
+                    .validator(validate_label_color)
                     .prompt(),
             },
             focused_input: FocusedLabelInput::Title,
             has_id: false,
+            color_picker: ListPicker::init()
+                .title("Label Color")
+                .placeholder("Filter colors")
+                .size(PopupSize::default().width(40).height(20)),
+            confirm_delete: ConfirmPopup::init(),
+        }
+    }
+
+    /// Swap the focused label with the next or previous label, keeping it
+    /// focused. The positions are saved from the order of the labels.
+    fn move_label(&mut self, down: bool) {
+        let index = self.selection.focused;
+        let other_index = if down {
+            index + 1
+        } else {
+            match index.checked_sub(1) {
+                Some(i) => i,
+                None => return,
+            }
+        };
+        if other_index < self.labels.len() {
+            self.labels.swap(index, other_index);
+            self.selection.focus(other_index);
+        }
+    }
+
+    /// Delete the focused label. Labels that are used by cards are only deleted
+    /// once the number of cards is confirmed. Deleted labels are removed from
+    /// the project when it's saved.
+    fn delete_label(&mut self, app: &mut App) {
+        let index = self.selection.focused;
+        let Some(label) = self.labels.get(index) else {
+            return;
+        };
+
+        let card_count = match label.id {
+            Some(id) => match Self::db_count_label_cards(&app.db, id) {
+                Ok(count) => count,
+                Err(e) => {
+                    error!("failed to count the cards of a label: {e}");
+                    app.notify_error("Failed to count the cards with the label");
+                    return;
+                }
+            },
+            None => 0,
+        };
+
+        if card_count == 0 {
+            self.remove_label(index);
+        } else {
+            let message = format!(
+                "'{}' is used by {} {}. Delete it?",
+                label.title,
+                card_count,
+                if card_count == 1 { "card" } else { "cards" }
+            );
+            self.confirm_delete.open(message, index);
+            app.mode.delete();
+        }
+    }
+
+    fn remove_label(&mut self, index: usize) {
+        self.labels.remove(index);
+        self.selection.focused = self.selection.focused.saturating_sub(1);
+    }
+
+    fn db_count_label_cards(db: &Database, label_id: i32) -> Result<i32> {
+        let count = db.conn().query_row(
+            "SELECT COUNT(*) FROM card_label cl JOIN project_card c ON c.id = cl.card_id WHERE \
+             cl.label_id = ?1 AND c.deleted_at IS NULL",
+            [label_id],
+            |r| r.get(0),
+        )?;
+        Ok(count)
+    }
+
+    /// Whether the color picker or the delete confirmation is open, which are
+    /// shown over the whole editor.
+    pub fn has_popup(&self) -> bool {
+        self.color_picker.is_open() || self.confirm_delete.is_open()
+    }
+
+    /// The open popup takes every key. The picked color replaces the color
+    /// input.
+    pub fn popup_key_event_handler(&mut self, app: &mut App, key_event: KeyEvent) {
+        if self.confirm_delete.is_open() {
+            if let Some(index) = self.confirm_delete.handle_key(key_event) {
+                self.remove_label(index);
+            }
+            if !self.confirm_delete.is_open() {
+                app.mode.normal();
+            }
+            return;
         }
+
+        if let Some(color) = self
+            .color_picker
+            .handle_key(key_event)
+            .and_then(|picked| picked.into_iter().next())
+        {
+            self.inputs.color.input(color);
+        }
+        if !self.color_picker.is_open() {
+            app.mode.normal();
+        }
+    }
+
+    /// The popups are rendered over the whole editor, since they don't fit in
+    /// the area of the label editor.
+    pub fn render_popups(&self, app: &App, frame: &mut Frame, area: Rect) {
+        self.color_picker.render(app, frame, area);
+        self.confirm_delete.render(app, frame, area);
+    }
+
+    /// Whether the colors of all labels are valid.
+    pub fn is_valid(&self) -> bool {
+        self.labels
+            .iter()
+            .all(|l| validate_label_color(&l.color).is_ok())
     }
 }
 
@@ -112,14 +282,19 @@ impl KeyEventHandler for LabelEditor {
         }
 
         match key_event.code {
+            KeyCode::Char('p')
+                if self.view == LabelView::Input
+                    && self.focused_input == FocusedLabelInput::Color
+                    && app.mode.is_normal() =>
+            {
+                self.color_picker.open(color_palette(&app.config.colors));
+                // Typed keys go to the filter of the picker.
+                app.mode.insert();
+            }
             KeyCode::Char('n') => {
                 if self.view == LabelView::Selection {
-                    if app.mode.is_delete() {
-                        app.mode.normal();
-                    } else {
-                        self.view = LabelView::Input;
-                        app.mode.insert();
-                    }
+                    self.view = LabelView::Input;
+                    app.mode.insert();
                 }
             }
             KeyCode::Char('e') => {
@@ -134,9 +309,15 @@ impl KeyEventHandler for LabelEditor {
             }
             KeyCode::Char('d') => {
                 if self.view == LabelView::Selection && !self.labels.is_empty() {
-                    app.mode.delete();
+                    self.delete_label(app);
                 }
             }
+            KeyCode::Char('J') if self.view == LabelView::Selection && app.mode.is_normal() => {
+                self.move_label(true)
+            }
+            KeyCode::Char('K') if self.view == LabelView::Selection && app.mode.is_normal() => {
+                self.move_label(false)
+            }
             KeyCode::Char('.') => {
                 if self.view == LabelView::Selection && !self.labels.is_empty() {
                     let label = &self.labels[self.selection.focused];
@@ -147,13 +328,6 @@ impl KeyEventHandler for LabelEditor {
                     });
                 }
             }
-            KeyCode::Char('y') => {
-                if self.view == LabelView::Selection && app.mode.is_delete() {
-                    self.labels.remove(self.selection.focused);
-                    self.selection.focused = self.selection.focused.saturating_sub(1);
-                    app.mode.normal();
-                }
-            }
             KeyCode::Char('[') => {
                 if app.mode.is_normal() && self.view == LabelView::Input {
                     self.view = LabelView::Selection;
@@ -171,7 +345,7 @@ impl KeyEventHandler for LabelEditor {
                 if self.view == LabelView::Input {
                     if self.focused_input == FocusedLabelInput::Title {
                         self.focused_input = FocusedLabelInput::Color;
-                    } else {
+                    } else if self.inputs.color.is_valid() {
                         if self.has_id {
                             self.labels[self.selection.focused] = Label {
                                 id: self.labels[self.selection.focused].id,
@@ -214,13 +388,19 @@ impl DefaultWidget for LabelEditor {
                 .iter()
                 .enumerate()
                 .map(|(i, label)| {
-                    Paragraph::new(label.title.to_owned())
-                        .fg(Color::from_str(&label.color).unwrap_or(colors.fg))
-                        .bg(if self.selection.focused == i {
-                            colors.input_focus_bg
-                        } else {
-                            colors.popup_bg
-                        })
+                    let line = match Color::from_str(&label.color) {
+                        Ok(color) => Line::from(label.title.to_owned()).fg(color),
+                        Err(_) => Line::from(vec![
+                            Span::from(label.title.to_owned()).fg(colors.fg),
+                            Span::from(format!(" (invalid color: {})", label.color))
+                                .fg(colors.danger),
+                        ]),
+                    };
+                    Paragraph::new(line).style(if self.selection.focused == i {
+                        app.config.focus_style(colors.input_focus_bg)
+                    } else {
+                        Style::new().bg(colors.popup_bg)
+                    })
                 })
                 .collect::<Vec<Paragraph>>();
 
@@ -234,20 +414,33 @@ impl DefaultWidget for LabelEditor {
                 ])
                 .areas(area);
 
+            let [preview_layout, swatch_layout] = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Fill(1), Constraint::Length(16)])
+                .areas(preview_layout);
+
+            let color_input = self.inputs.color.input_string();
+            let parsed_color = Color::from_str(&color_input).ok();
+
             let preview = Paragraph::new(Line::from(vec![
                 Span::from("Preview: ").fg(colors.secondary_fg),
-                Span::from(self.inputs.title.input_string()).fg(
-                    if self.inputs.color.input_string().chars().count()
-                        == LABEL_COLOR_REQUIRED_LENGTH
-                    {
-                        Color::from_str(&self.inputs.color.input_string()).unwrap_or(colors.fg)
-                    } else {
-                        colors.fg
-                    },
-                ),
+                // The error is shown below the color input.
+                Span::from(self.inputs.title.input_string()).fg(parsed_color.unwrap_or(colors.fg)),
             ]));
 
+            let swatch = Paragraph::new(Line::from(match parsed_color {
+                Some(color) => vec![
+                    Span::from("███").fg(color),
+                    Span::from(format!(" {}", color_input)).fg(colors.secondary_fg),
+                ],
+                None => vec![
+                    Span::from(" ⚠ ").bold().fg(colors.danger),
+                    Span::from(format!(" {}", color_input)).fg(colors.danger),
+                ],
+            }));
+
             frame.render_widget(preview, preview_layout);
+            frame.render_widget(swatch, swatch_layout);
 
             self.inputs.title.render(
                 frame,
@@ -269,6 +462,7 @@ struct Inputs {
     title: Rc<RefCell<TextInput>>,
     description: Rc<RefCell<TextInput>>,
     labels: Rc<RefCell<LabelEditor>>,
+    list_template: Rc<RefCell<Selection<usize>>>,
 }
 
 struct ProjectData {
@@ -293,9 +487,16 @@ impl Popup<Result<bool>> for ProjectEditor {
         let description = TextInput::new("Description")
             .view(View::Popup)
             .max(PROJECT_DESCRIPTION_MAX_LENGTH)
+            .multiline()
             .prompt_lines(3)
             .form();
         let label_editor = LabelEditor::init().form();
+        // The options are set when a new project is opened, since they're
+        // hidden while editing a project.
+        let list_template = Selection::new("Lists", vec![])
+            .single()
+            .default_height(LIST_TEMPLATES.len() as u16)
+            .form();
 
         Self {
             original_data: None,
@@ -303,11 +504,13 @@ impl Popup<Result<bool>> for ProjectEditor {
                 title: Rc::clone(&title),
                 description: Rc::clone(&description),
                 labels: Rc::clone(&label_editor),
+                list_template: Rc::clone(&list_template),
             },
             form: Form::from([
                 FormInput(title),
                 FormInput(description),
                 FormInput(label_editor),
+                FormInput(list_template),
             ])
             .default_title("New Project"),
         }
@@ -316,13 +519,29 @@ impl Popup<Result<bool>> for ProjectEditor {
     // Returns whether the project data in the database was modified. And the page
     // should be set to list projects.
     fn key_event_handler(&mut self, app: &mut App, key_event: KeyEvent) -> Result<bool> {
+        if self.inputs.labels.borrow().has_popup() {
+            self.inputs
+                .labels
+                .borrow_mut()
+                .popup_key_event_handler(app, key_event);
+            return Ok(false);
+        }
+
+        self.form
+            .confirm_discard_new(app.config.modules.project_management.confirm_discard_new);
         let result = self.form.key_event_handler(app, key_event);
 
         if result.is_submit() {
-            if self.original_data.is_some() {
-                self.db_edit_project(&app.db)?;
+            let result = if self.original_data.is_some() {
+                self.db_edit_project(&app.db)
             } else {
-                self.db_new_project(&app.db)?;
+                self.db_new_project(&app.db)
+            };
+            // The editor stays open with its input if saving fails.
+            if let Err(e) = result {
+                error!("failed to save project: {e}");
+                app.notify_error("Failed to save the project");
+                return Ok(false);
             }
 
             self.reset();
@@ -337,10 +556,19 @@ impl Popup<Result<bool>> for ProjectEditor {
 
     fn render(&self, app: &App, frame: &mut Frame, area: Rect) {
         self.form.render(frame, app, area, true);
+        self.inputs.labels.borrow().render_popups(app, frame, area);
     }
 }
 
 impl ProjectEditor {
+    /// Show the list templates, which are only picked for new projects.
+    pub fn new_project(&mut self, app: &App) {
+        self.inputs
+            .list_template
+            .borrow_mut()
+            .options(list_template_options(&app.config.colors));
+    }
+
     pub fn set_project(&mut self, db: &Database, project_id: i32) -> Result<()> {
         let conn = db.conn();
         let project_query = "SELECT id, title, description FROM project WHERE id = ?1";
@@ -358,6 +586,7 @@ impl ProjectEditor {
 
         self.original_data = Some(project);
         self.form.title("Edit Project");
+        self.form.editing();
 
         if let Some(original_data) = &self.original_data {
             self.inputs
@@ -406,9 +635,14 @@ impl ProjectEditor {
         Ok(labels)
     }
 
+    /// Insert the project with its labels and the lists of the selected
+    /// template, in one transaction.
     fn db_new_project(&self, db: &Database) -> Result<()> {
         let highest_position = db.get_highest_position("project")?;
-        db.execute(
+
+        let mut conn = db.conn();
+        let tx = conn.transaction()?;
+        tx.execute(
             "INSERT INTO project (title, description, position, created_at, updated_at) VALUES \
              (?1, ?2, ?3, ?4, ?5)",
             (
@@ -419,15 +653,8 @@ impl ProjectEditor {
                 DateTime::now(),
             ),
         )?;
+        let project_id = tx.last_insert_rowid();
 
-        let new_project_id = db.last_row_id("project")?;
-
-        self.db_new_labels(db, new_project_id)?;
-
-        Ok(())
-    }
-
-    fn db_new_labels(&self, db: &Database, project_id: i32) -> Result<()> {
         for (i, label) in self.inputs.labels.borrow().labels.iter().enumerate() {
             let query = "INSERT INTO project_label (project_id, title, color, position, \
                          created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6)";
@@ -439,14 +666,41 @@ impl ProjectEditor {
                 DateTime::now(),
                 DateTime::now(),
             );
-            db.execute(query, params)?;
+            tx.execute(query, params)?;
+        }
+
+        for (i, title) in self.list_template_lists().iter().enumerate() {
+            let query = "INSERT INTO project_list (project_id, title, position, created_at, \
+                         updated_at) VALUES (?1, ?2, ?3, ?4, ?5)";
+            tx.execute(
+                query,
+                (project_id, title, i, DateTime::now(), DateTime::now()),
+            )?;
         }
 
+        tx.commit()?;
+
         Ok(())
     }
 
+    /// The lists of the selected template, which are none if no template is
+    /// selected.
+    fn list_template_lists(&self) -> &'static [&'static str] {
+        let selection = self.inputs.list_template.borrow();
+        selection
+            .selected
+            .iter()
+            .next()
+            .and_then(|i| selection.options.get(*i))
+            .map_or(&[], |(template, _)| LIST_TEMPLATES[*template].1)
+    }
+
+    /// Update the project and its labels in one transaction, so the positions
+    /// of the labels are never half updated.
     fn db_edit_project(&self, db: &Database) -> Result<()> {
         if let Some(data) = &self.original_data {
+            let mut conn = db.conn();
+            let tx = conn.transaction()?;
             let query =
                 "UPDATE project SET title = ?1, description = ?2, updated_at = ?3 WHERE id = ?4";
             let params = (
@@ -455,16 +709,17 @@ impl ProjectEditor {
                 DateTime::now(),
                 data.id,
             );
-            db.execute(query, params)?;
-            self.db_edit_labels(db, data.id)?;
+            tx.execute(query, params)?;
+            self.db_edit_labels(&tx, data.id)?;
+            tx.commit()?;
         } else {
-            panic!("project data was not set")
+            return Err(eyre!("project data was not set"));
         }
 
         Ok(())
     }
 
-    fn db_edit_labels(&self, db: &Database, project_id: i32) -> Result<()> {
+    fn db_edit_labels(&self, tx: &Transaction, project_id: i32) -> Result<()> {
         if let Some(data) = &self.original_data {
             let input_label_ids = self
                 .inputs
@@ -477,23 +732,24 @@ impl ProjectEditor {
             for label in &data.labels {
                 if !input_label_ids.contains(&Some(label.id)) {
                     let query = "DELETE FROM project_label WHERE project_id = ?1 and id = ?2";
-                    db.execute(query, (project_id, label.id))?;
+                    tx.execute(query, (project_id, label.id))?;
                 }
             }
         }
 
         for (i, label) in self.inputs.labels.borrow().labels.iter().enumerate() {
             if let Some(label_id) = label.id {
-                let query = "UPDATE project_label SET title = ?1, color = ?2, updated_at = ?3 \
-                             WHERE project_id = ?4 and id = ?5";
+                let query = "UPDATE project_label SET title = ?1, color = ?2, position = ?3, \
+                             updated_at = ?4 WHERE project_id = ?5 and id = ?6";
                 let params = (
                     label.title.to_owned(),
                     label.color.to_owned(),
+                    i,
                     DateTime::now(),
                     project_id,
                     label_id,
                 );
-                db.execute(query, params)?;
+                tx.execute(query, params)?;
             } else {
                 let query = "INSERT INTO project_label (project_id, title, color, position, \
                              created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6)";
@@ -505,7 +761,7 @@ impl ProjectEditor {
                     DateTime::now(),
                     DateTime::now(),
                 );
-                db.execute(query, params)?;
+                tx.execute(query, params)?;
             }
         }
 
@@ -515,5 +771,6 @@ impl ProjectEditor {
     pub fn reset(&mut self) {
         self.original_data = None;
         self.form.reset();
+        self.inputs.list_template.borrow_mut().options(vec![]);
     }
 }
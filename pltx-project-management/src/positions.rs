@@ -0,0 +1,207 @@
+//! Position updates that keep the positions of the cards in each list
+//! contiguous, from 0 to the number of cards.
+
+use color_eyre::Result;
+use pltx_database::Database;
+use pltx_utils::DateTime;
+use rusqlite::TransactionBehavior;
+
+/// Move a card to the end of another list, and close the gap that it leaves in
+/// its list, in one transaction. Returns the position of the card in the other
+/// list, which is also its index there, as the positions are contiguous.
+///
+/// ```
+/// # use pltx_database::Database;
+/// # use pltx_project_management::{positions, ProjectManagement};
+/// let db = Database::in_memory();
+/// ProjectManagement::init_db(&db).unwrap();
+/// let now = "2024-01-01 00:00:00";
+/// db.execute(
+///     "INSERT INTO project (id, title, position, created_at, updated_at) VALUES (1, 'P', 0, ?1, \
+///      ?1)",
+///     [now],
+/// )
+/// .unwrap();
+/// db.execute(
+///     "INSERT INTO project_list (id, project_id, title, position, created_at, updated_at) \
+///      VALUES (1, 1, 'A', 0, ?1, ?1), (2, 1, 'B', 1, ?1, ?1)",
+///     [now],
+/// )
+/// .unwrap();
+/// for (id, list_id, position) in [(1, 1, 0), (2, 1, 1), (3, 1, 2), (4, 1, 3), (5, 2, 0)] {
+///     db.execute(
+///         "INSERT INTO project_card (id, project_id, list_id, title, important, position, \
+///          created_at, updated_at) VALUES (?1, 1, ?2, 'C', 0, ?3, ?4, ?4)",
+///         (id, list_id, position, now),
+///     )
+///     .unwrap();
+/// }
+/// let cards = |list_id: i32| {
+///     let conn = db.conn();
+///     let mut stmt = conn
+///         .prepare("SELECT id, position FROM project_card WHERE list_id = ?1 ORDER BY position")
+///         .unwrap();
+///     stmt.query_map([list_id], |r| Ok((r.get(0)?, r.get(1)?)))
+///         .unwrap()
+///         .map(|r| r.unwrap())
+///         .collect::<Vec<(i32, i32)>>()
+/// };
+///
+/// let index = |card_id: i32, list_id: i32| {
+///     cards(list_id)
+///         .iter()
+///         .position(|(id, _)| *id == card_id)
+///         .map(|i| i as i32)
+/// };
+///
+/// // The middle, first, and last cards.
+/// let position = positions::move_card_to_list(&db, 2, 2).unwrap();
+/// assert_eq!(position, 1);
+/// assert_eq!(index(2, 2), Some(position));
+/// assert_eq!(cards(1), vec![(1, 0), (3, 1), (4, 2)]);
+/// assert_eq!(cards(2), vec![(5, 0), (2, 1)]);
+///
+/// assert_eq!(positions::move_card_to_list(&db, 1, 2).unwrap(), 2);
+/// assert_eq!(cards(1), vec![(3, 0), (4, 1)]);
+///
+/// let position = positions::move_card_to_list(&db, 4, 2).unwrap();
+/// assert_eq!(position, 3);
+/// assert_eq!(index(4, 2), Some(position));
+/// assert_eq!(cards(1), vec![(3, 0)]);
+/// assert_eq!(cards(2), vec![(5, 0), (2, 1), (1, 2), (4, 3)]);
+///
+/// // Back to the other list, which only has one card.
+/// assert_eq!(positions::move_card_to_list(&db, 5, 1).unwrap(), 1);
+/// assert_eq!(cards(1), vec![(3, 0), (5, 1)]);
+/// assert_eq!(cards(2), vec![(2, 0), (1, 1), (4, 2)]);
+/// ```
+pub fn move_card_to_list(db: &Database, card_id: i32, list_id: i32) -> Result<i32> {
+    let mut conn = db.conn();
+    // The transaction reads before it writes, so it takes the write lock first
+    // rather than failing if another connection writes in between.
+    let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+    let (from_list_id, from_position): (i32, i32) = tx.query_row(
+        "SELECT list_id, position FROM project_card WHERE id = ?1",
+        [card_id],
+        |r| Ok((r.get(0)?, r.get(1)?)),
+    )?;
+    let position: i32 = tx.query_row(
+        "SELECT COALESCE(MAX(position), -1) + 1 FROM project_card WHERE list_id = ?1",
+        [list_id],
+        |r| r.get(0),
+    )?;
+
+    tx.execute(
+        "UPDATE project_card SET list_id = ?1, position = ?2, updated_at = ?3 WHERE id = ?4",
+        (list_id, position, DateTime::now(), card_id),
+    )?;
+    tx.execute(
+        "UPDATE project_card SET position = position - 1, updated_at = ?1 WHERE position > ?2 AND \
+         list_id = ?3",
+        (DateTime::now(), from_position, from_list_id),
+    )?;
+    tx.commit()?;
+
+    Ok(position)
+}
+
+/// Move a card to the trash, and close the gap that it leaves in its list, in
+/// one transaction.
+///
+/// ```
+/// # use pltx_database::Database;
+/// # use pltx_project_management::{positions, ProjectManagement};
+/// let db = Database::in_memory();
+/// ProjectManagement::init_db(&db).unwrap();
+/// let now = "2024-01-01 00:00:00";
+/// db.execute(
+///     "INSERT INTO project (id, title, position, created_at, updated_at) VALUES (1, 'P', 0, ?1, \
+///      ?1)",
+///     [now],
+/// )
+/// .unwrap();
+/// db.execute(
+///     "INSERT INTO project_list (id, project_id, title, position, created_at, updated_at) \
+///      VALUES (1, 1, 'Todo', 0, ?1, ?1), (2, 1, 'Doing', 1, ?1, ?1)",
+///     [now],
+/// )
+/// .unwrap();
+/// // New cards are added after the highest position of their list, like the
+/// // card editor adds them.
+/// let new_card = |list_id: i32| {
+///     let position = db
+///         .get_highest_position_where("project_card", "list_id", list_id)
+///         .unwrap()
+///         + 1;
+///     db.insert(
+///         "INSERT INTO project_card (project_id, list_id, title, important, position, \
+///          created_at, updated_at) VALUES (1, ?1, 'C', 0, ?2, ?3, ?3)",
+///         (list_id, position, now),
+///     )
+///     .unwrap()
+/// };
+/// let cards = |list_id: i32| {
+///     let conn = db.conn();
+///     let mut stmt = conn
+///         .prepare(
+///             "SELECT id, position FROM project_card WHERE list_id = ?1 AND deleted_at IS NULL \
+///              ORDER BY position",
+///         )
+///         .unwrap();
+///     stmt.query_map([list_id], |r| Ok((r.get(0)?, r.get(1)?)))
+///         .unwrap()
+///         .map(|r| r.unwrap())
+///         .collect::<Vec<(i32, i32)>>()
+/// };
+///
+/// let (a, b, c) = (new_card(1), new_card(1), new_card(1));
+/// let (d, e) = (new_card(2), new_card(2));
+/// assert_eq!(cards(1), vec![(a, 0), (b, 1), (c, 2)]);
+///
+/// positions::delete_card(&db, b).unwrap();
+/// assert_eq!(cards(1), vec![(a, 0), (c, 1)]);
+/// let deleted: i32 = db
+///     .conn()
+///     .query_row(
+///         "SELECT COUNT(*) FROM project_card WHERE deleted_at IS NOT NULL",
+///         [],
+///         |r| r.get(0),
+///     )
+///     .unwrap();
+/// assert_eq!(deleted, 1);
+///
+/// // Moving a card to the list on its left puts it after the last card there.
+/// let left_last_position = db
+///     .get_highest_position_where("project_card", "list_id", 1)
+///     .unwrap();
+/// let position = positions::move_card_to_list(&db, d, 1).unwrap();
+/// assert_eq!(position, left_last_position + 1);
+/// assert_eq!(cards(1), vec![(a, 0), (c, 1), (d, 2)]);
+/// assert_eq!(cards(2), vec![(e, 0)]);
+///
+/// // The position of a new card follows the moved card.
+/// let f = new_card(1);
+/// assert_eq!(cards(1).last(), Some(&(f, 3)));
+/// ```
+pub fn delete_card(db: &Database, card_id: i32) -> Result<()> {
+    let mut conn = db.conn();
+    let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+    let (list_id, position): (i32, i32) = tx.query_row(
+        "SELECT list_id, position FROM project_card WHERE id = ?1",
+        [card_id],
+        |r| Ok((r.get(0)?, r.get(1)?)),
+    )?;
+
+    tx.execute(
+        "UPDATE project_card SET deleted_at = ?1, position = -1, updated_at = ?1 WHERE id = ?2",
+        (DateTime::now(), card_id),
+    )?;
+    tx.execute(
+        "UPDATE project_card SET position = position - 1, updated_at = ?1 WHERE position > ?2 AND \
+         list_id = ?3",
+        (DateTime::now(), position, list_id),
+    )?;
+    tx.commit()?;
+
+    Ok(())
+}
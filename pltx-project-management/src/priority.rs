@@ -0,0 +1,61 @@
+/// How urgent a card is. Stored as an integer, so priorities can be compared
+/// and sorted in queries.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Priority {
+    None,
+    Low,
+    Medium,
+    High,
+    Urgent,
+}
+
+impl Priority {
+    pub fn options() -> Vec<(Self, String)> {
+        [
+            Self::None,
+            Self::Low,
+            Self::Medium,
+            Self::High,
+            Self::Urgent,
+        ]
+        .into_iter()
+        .map(|priority| (priority, priority.title().to_string()))
+        .collect()
+    }
+
+    pub fn from_db(value: i32) -> Self {
+        match value {
+            1 => Self::Low,
+            2 => Self::Medium,
+            3 => Self::High,
+            4 => Self::Urgent,
+            _ => Self::None,
+        }
+    }
+
+    pub fn into_db(self) -> i32 {
+        match self {
+            Self::None => 0,
+            Self::Low => 1,
+            Self::Medium => 2,
+            Self::High => 3,
+            Self::Urgent => 4,
+        }
+    }
+
+    /// Whether the card is important, which is kept in its own column for the
+    /// queries that only need to know that.
+    pub fn is_important(self) -> bool {
+        self >= Self::High
+    }
+
+    pub fn title(self) -> &'static str {
+        match self {
+            Self::None => "None",
+            Self::Low => "Low",
+            Self::Medium => "Medium",
+            Self::High => "High",
+            Self::Urgent => "Urgent",
+        }
+    }
+}
@@ -1,7 +1,8 @@
 use color_eyre::Result;
-use crossterm::event::{KeyCode, KeyEvent};
-use pltx_app::{App, Popup, Screen};
+use crossterm::event::{KeyCode, KeyEvent, MouseEvent};
+use pltx_app::{App, KeybindSection, Popup, Screen};
 use ratatui::{layout::Rect, Frame};
+use tracing::error;
 
 use crate::{
     list_projects::ListProjects, open_project::OpenProject, popups::project_editor::ProjectEditor,
@@ -44,12 +45,17 @@ impl Screen<Result<()>> for Projects {
         if app.mode.is_normal() && self.page == Page::ListProjects {
             match key_event.code {
                 KeyCode::Char('n') => {
+                    self.pages.new_project.new_project(app);
                     self.page = Page::NewProject;
                     app.view.popup();
                 }
                 KeyCode::Char('e') => {
                     if let Some(id) = self.pages.list_projects.get_id() {
-                        self.pages.edit_project.set_project(&app.db, id)?;
+                        if let Err(e) = self.pages.edit_project.set_project(&app.db, id) {
+                            error!("failed to get project: {e}");
+                            app.notify_error("Failed to open the project");
+                            return Ok(());
+                        }
                         self.page = Page::EditProject;
                         app.view.popup();
                     }
@@ -71,7 +77,11 @@ impl Screen<Result<()>> for Projects {
             Page::ListProjects => self.pages.list_projects.key_event_handler(app, key_event)?,
             Page::NewProject => self.pages.new_project.key_event_handler(app, key_event)?,
             Page::EditProject => self.pages.edit_project.key_event_handler(app, key_event)?,
-            Page::OpenProject => self.pages.open_project.key_event_handler(app, key_event)?,
+            Page::OpenProject => {
+                let result = self.pages.open_project.key_event_handler(app, key_event)?;
+                self.pages.open_project.db_load_more_cards(app)?;
+                result
+            }
         };
 
         if result {
@@ -91,3 +101,92 @@ impl Screen<Result<()>> for Projects {
         }
     }
 }
+
+impl Projects {
+    /// Open the card positions of the open project, if there is one.
+    pub fn debug_positions(&mut self, app: &mut App) -> Result<()> {
+        if self.page == Page::OpenProject {
+            self.pages.open_project.debug_positions(app)?;
+        }
+        Ok(())
+    }
+
+    /// The keybinds of the current page, for the help popup.
+    pub fn keybinds(&self, app: &App) -> Vec<KeybindSection> {
+        match self.page {
+            Page::ListProjects => vec![KeybindSection::new("Projects")
+                .keybind("enter l", "Open the project")
+                .keybind("n", "New project")
+                .keybind("e", "Edit the project")
+                .keybind("d", "Delete the project")
+                .keybind("D", "Clone the project, with or without its cards")
+                .keybind("a", "Archive or restore the project")
+                .keybind("A", "Show or hide archived projects")
+                .keybind("J K", "Move the project down or up")
+                .keybind("s", "Sort by position, title, newest, or most cards")],
+            Page::NewProject | Page::EditProject => vec![],
+            Page::OpenProject => self.pages.open_project.keybinds(app),
+        }
+    }
+
+    /// Mouse support is limited to the board of the open project.
+    pub fn mouse_event_handler(&mut self, mouse_event: MouseEvent) {
+        if self.page == Page::OpenProject {
+            self.pages.open_project.mouse_event_handler(mouse_event);
+        }
+    }
+
+    /// Reload the list of projects, and the open project if there is one.
+    pub fn reload(&mut self, app: &mut App) -> Result<()> {
+        self.pages.list_projects.db_get_projects(app)?;
+        if self.page == Page::OpenProject {
+            self.pages.open_project.db_get_project(app)?;
+        }
+        Ok(())
+    }
+
+    /// Export the open project to Markdown, if there is one.
+    pub fn export_project(&mut self, app: &mut App) {
+        if self.page == Page::OpenProject {
+            self.pages.open_project.export_markdown(app);
+        } else {
+            app.notify_warning("Open a project to export it");
+        }
+    }
+
+    /// Open the editor of a new card in the open project.
+    pub fn new_card(&mut self, app: &mut App) {
+        if self.page == Page::OpenProject {
+            self.pages.open_project.new_card(app);
+        } else {
+            app.notify_warning("Open a project to add a card");
+        }
+    }
+
+    /// Open the editor of a new list in the open project.
+    pub fn new_list(&mut self, app: &mut App) {
+        if self.page == Page::OpenProject {
+            self.pages.open_project.new_list(app);
+        } else {
+            app.notify_warning("Open a project to add a list");
+        }
+    }
+
+    /// Open a project, leaving whichever page is currently shown.
+    pub fn open_project(&mut self, app: &mut App, project_id: i32) -> Result<()> {
+        app.view.default();
+        self.pages.open_project.reset(app);
+        self.pages.open_project.set_project_id(project_id);
+        self.pages.open_project.db_get_project(app)?;
+        self.page = Page::OpenProject;
+        Ok(())
+    }
+
+    /// Open a project with one of its cards focused.
+    pub fn open_card(&mut self, app: &mut App, project_id: i32, card_id: i32) -> Result<()> {
+        self.open_project(app, project_id)?;
+        self.pages.open_project.db_load_card(app, card_id)?;
+        self.pages.open_project.focus_card(card_id);
+        Ok(())
+    }
+}
@@ -0,0 +1,55 @@
+use pltx_utils::DateTime;
+
+/// How often a card repeats. When a recurring card is completed, a new
+/// uncompleted copy is created with its dates advanced by the interval.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Recurrence {
+    None,
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+impl Recurrence {
+    pub fn options() -> Vec<(Self, String)> {
+        vec![
+            (Self::None, String::from("None")),
+            (Self::Daily, String::from("Daily")),
+            (Self::Weekly, String::from("Weekly")),
+            (Self::Monthly, String::from("Monthly")),
+        ]
+    }
+
+    pub fn from_db(value: Option<String>) -> Self {
+        match value.as_deref() {
+            Some("daily") => Self::Daily,
+            Some("weekly") => Self::Weekly,
+            Some("monthly") => Self::Monthly,
+            _ => Self::None,
+        }
+    }
+
+    pub fn into_db(self) -> Option<&'static str> {
+        match self {
+            Self::None => None,
+            Self::Daily => Some("daily"),
+            Self::Weekly => Some("weekly"),
+            Self::Monthly => Some("monthly"),
+        }
+    }
+
+    /// Advance a datetime by one interval of local days or months, so the card
+    /// keeps its local time across daylight saving changes. Monthly
+    /// recurrences are advanced to
+    /// `month_day`, the day of the month of the first occurrence, so a day
+    /// that was moved to the end of a short month isn't kept for the next
+    /// months.
+    pub fn advance(self, datetime: &DateTime, month_day: u32) -> DateTime {
+        match self {
+            Self::None => datetime.clone(),
+            Self::Daily => datetime.add_days(1),
+            Self::Weekly => datetime.add_days(7),
+            Self::Monthly => datetime.add_months_on_day(1, month_day),
+        }
+    }
+}
@@ -0,0 +1,416 @@
+use std::{cmp::Reverse, time::Instant};
+
+use color_eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use pltx_app::{App, KeyEventHandler, Popup, Screen};
+use pltx_database::Database;
+use pltx_utils::{DateTime, WidgetMargin};
+use pltx_widgets::{ConfirmPopup, Scrollable};
+use ratatui::{
+    layout::Rect,
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+use rusqlite::{ToSql, Transaction, TransactionBehavior};
+use tracing::{info, info_span};
+
+/// The tables of the rows that belong to a card. Foreign keys aren't enforced,
+/// so their `ON DELETE CASCADE` doesn't run, and the rows are deleted with the
+/// card instead.
+const CARD_TABLES: [&str; 3] = ["card_label", "card_subtask", "card_tag"];
+
+/// The cards whose list no longer exists, which were left behind by lists that
+/// were deleted before their cards were deleted with them.
+const ORPHANED_CARDS: &str = "list_id NOT IN (SELECT id FROM project_list)";
+
+/// Permanently delete the cards that match a condition, and the rows that
+/// belong to them.
+fn delete_cards_where(
+    tx: &Transaction,
+    condition: &str,
+    params: &[&dyn ToSql],
+) -> rusqlite::Result<usize> {
+    for table in CARD_TABLES {
+        tx.execute(
+            &format!(
+                "DELETE FROM {} WHERE card_id IN (SELECT id FROM project_card WHERE {})",
+                table, condition
+            ),
+            params,
+        )?;
+    }
+    tx.execute(
+        &format!("DELETE FROM project_card WHERE {}", condition),
+        params,
+    )
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum TrashKind {
+    List,
+    Card,
+}
+
+struct TrashItem {
+    kind: TrashKind,
+    id: i32,
+    project_title: String,
+    /// The list of a card. Lists show the number of cards deleted with them.
+    list_title: String,
+    title: String,
+    deleted_at: DateTime,
+}
+
+#[derive(Clone, Copy)]
+enum DeleteSelection {
+    Item(TrashKind, i32),
+    All,
+}
+
+/// Lists and cards that have been deleted, which can be restored or deleted
+/// permanently.
+pub struct Trash {
+    items: Vec<TrashItem>,
+    selection: Scrollable,
+    confirm_delete: ConfirmPopup<DeleteSelection>,
+}
+
+impl Screen<Result<()>> for Trash {
+    fn init(app: &App) -> Result<Self> {
+        let mut trash = Self {
+            items: vec![],
+            selection: Scrollable::default().cols([7, 20, 20, 40, 18]),
+            confirm_delete: ConfirmPopup::init(),
+        };
+        trash.db_get_items(&app.db)?;
+        Ok(trash)
+    }
+
+    fn key_event_handler(&mut self, app: &mut App, key_event: KeyEvent) -> Result<()> {
+        if app.mode.is_delete() {
+            match self.confirm_delete.key_event_handler(app, key_event) {
+                Some(DeleteSelection::Item(kind, id)) => {
+                    self.db_delete_item(&app.db, kind, id)?;
+                    self.db_get_items(&app.db)?;
+                }
+                Some(DeleteSelection::All) => {
+                    Self::db_empty(&app.db)?;
+                    self.db_get_items(&app.db)?;
+                }
+                None => {}
+            }
+            if !self.confirm_delete.is_open() {
+                app.mode.normal();
+            }
+            return Ok(());
+        }
+
+        if !app.view.is_default() || !app.mode.is_normal() {
+            return Ok(());
+        }
+
+        self.selection.key_event_handler(app, key_event);
+
+        match key_event.code {
+            KeyCode::Char('r') => {
+                if let Some(item) = self.items.get(self.selection.focused) {
+                    let (kind, id, title) = (item.kind, item.id, item.title.clone());
+                    if self.db_restore_item(app, kind, id)? {
+                        app.notify_info(format!("Restored '{}'", title));
+                        self.db_get_items(&app.db)?;
+                    }
+                }
+            }
+            KeyCode::Char('d') => {
+                if let Some(item) = self.items.get(self.selection.focused) {
+                    let message = format!("Permanently delete '{}'?", item.title);
+                    self.confirm_delete
+                        .open(message, DeleteSelection::Item(item.kind, item.id));
+                    app.mode.delete();
+                }
+            }
+            KeyCode::Char('D') if !self.items.is_empty() => {
+                let message = format!("Permanently delete all {} items?", self.items.len());
+                self.confirm_delete.open(message, DeleteSelection::All);
+                app.mode.delete();
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn render(&self, app: &App, frame: &mut Frame, area: Rect) {
+        let colors = &app.config.colors;
+
+        let area = WidgetMargin::uniform(1).apply(area);
+
+        if self.items.is_empty() {
+            frame.render_widget(
+                Paragraph::new("The trash is empty.").fg(colors.secondary_fg),
+                area,
+            );
+        } else {
+            let header = ["", "Project", "List", "Title", "Deleted"]
+                .into_iter()
+                .map(|h| Paragraph::new(h).fg(colors.secondary_fg))
+                .collect::<Vec<Paragraph>>();
+
+            let table = self
+                .items
+                .iter()
+                .enumerate()
+                .map(|(i, item)| {
+                    let style = if self.selection.focused == i {
+                        app.config.focus_style(colors.input_focus_bg).fg(colors.fg)
+                    } else {
+                        Style::new().fg(colors.fg)
+                    };
+                    let kind = match item.kind {
+                        TrashKind::List => "list",
+                        TrashKind::Card => "card",
+                    };
+                    [
+                        kind.to_string(),
+                        item.project_title.to_string(),
+                        item.list_title.to_string(),
+                        item.title.to_string(),
+                        item.deleted_at.relative(),
+                    ]
+                    .into_iter()
+                    .map(|cell| Paragraph::new(cell).style(style))
+                    .collect::<Vec<Paragraph>>()
+                })
+                .collect::<Vec<Vec<Paragraph>>>();
+
+            self.selection.render_with_cols(frame, area, header, table);
+        }
+
+        if self.confirm_delete.is_open() {
+            self.confirm_delete.render(app, frame, area);
+        } else if !self.items.is_empty() {
+            let keybind = |key: &'static str| Span::from(key).fg(colors.keybind_key);
+            let hint = Paragraph::new(Line::from(vec![
+                keybind("r"),
+                Span::from(" restore  ").fg(colors.keybind_fg),
+                keybind("d"),
+                Span::from(" delete  ").fg(colors.keybind_fg),
+                keybind("D"),
+                Span::from(" empty trash").fg(colors.keybind_fg),
+            ]));
+            let hint_area = Rect {
+                y: area.bottom().saturating_sub(1),
+                height: 1,
+                ..area
+            };
+            frame.render_widget(hint, hint_area);
+        }
+    }
+}
+
+impl Trash {
+    pub fn db_get_items(&mut self, db: &Database) -> Result<()> {
+        let _span = info_span!("project management", screen = "trash").entered();
+        let start = Instant::now();
+
+        let conn = db.conn();
+        let mut items = vec![];
+
+        let list_query = "SELECT l.id, p.title, l.title, l.deleted_at, (SELECT COUNT(*) FROM \
+                          project_card c WHERE c.list_id = l.id AND c.deleted_at = l.deleted_at) \
+                          FROM project_list l JOIN project p ON p.id = l.project_id WHERE \
+                          l.deleted_at IS NOT NULL";
+        let mut list_stmt = conn.prepare(list_query)?;
+        let list_iter = list_stmt.query_map([], |r| {
+            let cards: i32 = r.get(4)?;
+            Ok(TrashItem {
+                kind: TrashKind::List,
+                id: r.get(0)?,
+                project_title: r.get(1)?,
+                list_title: format!("{} {}", cards, if cards == 1 { "card" } else { "cards" }),
+                title: r.get(2)?,
+                deleted_at: DateTime::from_db(r.get(3)?),
+            })
+        })?;
+        for list in list_iter {
+            items.push(list?);
+        }
+
+        // Cards that were deleted along with their list are restored with it,
+        // so they're only listed through the list.
+        let card_query = "SELECT c.id, p.title, l.title, c.title, c.deleted_at FROM project_card \
+                          c JOIN project_list l ON l.id = c.list_id JOIN project p ON p.id = \
+                          c.project_id WHERE c.deleted_at IS NOT NULL AND (l.deleted_at IS NULL \
+                          OR l.deleted_at != c.deleted_at)";
+        let mut card_stmt = conn.prepare(card_query)?;
+        let card_iter = card_stmt.query_map([], |r| {
+            Ok(TrashItem {
+                kind: TrashKind::Card,
+                id: r.get(0)?,
+                project_title: r.get(1)?,
+                list_title: r.get(2)?,
+                title: r.get(3)?,
+                deleted_at: DateTime::from_db(r.get(4)?),
+            })
+        })?;
+        for card in card_iter {
+            items.push(card?);
+        }
+
+        items.sort_by_key(|i| Reverse(i.deleted_at.datetime));
+        self.items = items;
+
+        let last_index = self.items.len().saturating_sub(1);
+        if self.selection.focused > last_index {
+            self.selection.focused = last_index;
+        }
+
+        info!("get trash items query executed in {:?}", start.elapsed());
+
+        Ok(())
+    }
+
+    /// Restore an item to the end of its project or list. Returns whether it
+    /// was restored.
+    fn db_restore_item(&self, app: &mut App, kind: TrashKind, id: i32) -> Result<bool> {
+        let start = Instant::now();
+        let db = &app.db;
+
+        // The restores read the positions before they write, so they take the
+        // write lock first.
+        let mut conn = db.conn();
+        let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+        match kind {
+            TrashKind::List => {
+                let (project_id, deleted_at): (i32, String) = tx.query_row(
+                    "SELECT project_id, deleted_at FROM project_list WHERE id = ?1",
+                    [id],
+                    |r| Ok((r.get(0)?, r.get(1)?)),
+                )?;
+                let position: i32 = tx.query_row(
+                    "SELECT COALESCE(MAX(position), -1) + 1 FROM project_list WHERE project_id = \
+                     ?1",
+                    [project_id],
+                    |r| r.get(0),
+                )?;
+                tx.execute(
+                    "UPDATE project_list SET deleted_at = NULL, position = ?1, updated_at = ?2 \
+                     WHERE id = ?3",
+                    (position, DateTime::now(), id),
+                )?;
+                tx.execute(
+                    "UPDATE project_card SET deleted_at = NULL, updated_at = ?1 WHERE list_id = \
+                     ?2 AND deleted_at = ?3",
+                    (DateTime::now(), id, deleted_at),
+                )?;
+            }
+            TrashKind::Card => {
+                let (list_id, list_title, list_deleted): (i32, String, bool) = tx.query_row(
+                    "SELECT l.id, l.title, l.deleted_at IS NOT NULL FROM project_card c JOIN \
+                     project_list l ON l.id = c.list_id WHERE c.id = ?1",
+                    [id],
+                    |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)),
+                )?;
+                if list_deleted {
+                    app.notify_warning(format!(
+                        "Restore the list '{}' before its card",
+                        list_title
+                    ));
+                    return Ok(false);
+                }
+                let position: i32 = tx.query_row(
+                    "SELECT COALESCE(MAX(position), -1) + 1 FROM project_card WHERE list_id = ?1",
+                    [list_id],
+                    |r| r.get(0),
+                )?;
+                tx.execute(
+                    "UPDATE project_card SET deleted_at = NULL, position = ?1, updated_at = ?2 \
+                     WHERE id = ?3",
+                    (position, DateTime::now(), id),
+                )?;
+            }
+        }
+        tx.commit()?;
+
+        info!("restore trash item query executed in {:?}", start.elapsed());
+
+        Ok(true)
+    }
+
+    /// Permanently delete an item. The cards of a list are deleted with it.
+    fn db_delete_item(&self, db: &Database, kind: TrashKind, id: i32) -> Result<()> {
+        let start = Instant::now();
+
+        let mut conn = db.conn();
+        let tx = conn.transaction()?;
+        match kind {
+            TrashKind::List => {
+                delete_cards_where(&tx, "list_id = ?1", &[&id])?;
+                tx.execute("DELETE FROM project_list WHERE id = ?1", [id])?;
+            }
+            TrashKind::Card => {
+                delete_cards_where(&tx, "id = ?1", &[&id])?;
+            }
+        }
+        tx.commit()?;
+
+        info!("delete trash item query executed in {:?}", start.elapsed());
+
+        Ok(())
+    }
+
+    fn db_empty(db: &Database) -> Result<()> {
+        let start = Instant::now();
+
+        let mut conn = db.conn();
+        let tx = conn.transaction()?;
+        let condition = format!(
+            "deleted_at IS NOT NULL OR list_id IN (SELECT id FROM project_list WHERE deleted_at \
+             IS NOT NULL) OR {}",
+            ORPHANED_CARDS
+        );
+        delete_cards_where(&tx, &condition, &[])?;
+        tx.execute("DELETE FROM project_list WHERE deleted_at IS NOT NULL", ())?;
+        tx.commit()?;
+
+        info!("empty trash query executed in {:?}", start.elapsed());
+
+        Ok(())
+    }
+
+    /// Permanently delete the items that have been in the trash for longer than
+    /// `retention_days`. Nothing is deleted when it's 0.
+    pub fn db_purge(db: &Database, retention_days: i32) -> Result<()> {
+        if retention_days <= 0 {
+            return Ok(());
+        }
+
+        let start = Instant::now();
+
+        let cutoff = DateTime::new().add_days(-(retention_days as i64)).into_db();
+        let mut conn = db.conn();
+        let tx = conn.transaction()?;
+        let condition = format!(
+            "(deleted_at IS NOT NULL AND deleted_at < ?1) OR list_id IN (SELECT id FROM \
+             project_list WHERE deleted_at IS NOT NULL AND deleted_at < ?1) OR {}",
+            ORPHANED_CARDS
+        );
+        let cards = delete_cards_where(&tx, &condition, &[&cutoff])?;
+        let lists = tx.execute(
+            "DELETE FROM project_list WHERE deleted_at IS NOT NULL AND deleted_at < ?1",
+            [&cutoff],
+        )?;
+        tx.commit()?;
+
+        info!(
+            "purged {} lists and {} cards from the trash in {:?}",
+            lists,
+            cards,
+            start.elapsed()
+        );
+
+        Ok(())
+    }
+}
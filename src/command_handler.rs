@@ -1,37 +1,35 @@
 use std::time::Instant;
 
-use crossterm::event::{KeyCode, KeyEvent};
+use color_eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use nucleo::{
     pattern::{Atom, AtomKind, CaseMatching, Normalization},
     Matcher,
 };
 use pltx_app::{
-    state::{AppModule, View},
-    App, DefaultWidget, KeyEventHandler,
+    state::{Navigation, View},
+    App, CommandRegistry, DefaultWidget, KeyEventHandler,
 };
 use pltx_widgets::{PopupSize, PopupWidget, TextInput};
 use ratatui::{
     layout::{Constraint, Layout, Rect},
     style::{Style, Stylize},
-    text::{Line, Text},
+    text::{Line, Span, Text},
     widgets::{Block, BorderType, Borders, Padding, Paragraph},
     Frame,
 };
-use tracing::info;
-
-use crate::ui::Interface;
-
-#[derive(PartialEq, Clone)]
-/// The list of available commands. Each must be added to the [`command_data`]
-/// function.
-enum Command {
-    Dashboard,
-    Help,
-    Home,
-    ProjectManagement,
-    Quit,
-    Settings,
-    None,
+use tracing::{error, info};
+
+/// A project that can be opened with the `open project <name>` command.
+struct ProjectOption {
+    id: i32,
+    title: String,
+}
+
+impl AsRef<str> for ProjectOption {
+    fn as_ref(&self) -> &str {
+        &self.title
+    }
 }
 
 #[derive(PartialEq)]
@@ -45,33 +43,58 @@ enum FocusedPane {
     Input,
     Options,
 }
-pub struct CommandHandler<'a> {
+pub struct CommandHandler {
+    commands: CommandRegistry,
     command: TextInput,
     size: PopupSize,
     command_view: CommandView,
     focused_pane: FocusedPane,
-    command_options: Vec<&'a str>,
+    command_options: Vec<String>,
+    /// The project of each option when the input starts with
+    /// [`OPEN_PROJECT_PREFIX`], otherwise empty.
+    project_ids: Vec<i32>,
     selected_option: usize,
     matcher: Matcher,
+    /// The executed commands of this session, from oldest to newest.
+    history: Vec<String>,
+    /// The history entry that is in the input, if one was recalled.
+    history_index: Option<usize>,
 }
 
-// NOTE: Add commands here.
-fn command_data<'a>() -> [(Command, &'a str); 6] {
-    [
-        (Command::Dashboard, "dashboard"),
-        (Command::Help, "help"),
-        (Command::Home, "home"),
-        (Command::ProjectManagement, "project management"),
-        (Command::Settings, "settings"),
-        (Command::Quit, "quit"),
-    ]
-}
+/// The maximum number of executed commands that are kept in the history.
+const HISTORY_LIMIT: usize = 50;
+
+/// The command that lists the projects to open. It's handled by the palette
+/// itself, since the options depend on the input.
+const OPEN_PROJECT_COMMAND: &str = "open project";
+/// The input prefix after which the options are the matching projects.
+const OPEN_PROJECT_PREFIX: &str = "open project ";
 
-impl<'a> CommandHandler<'a> {
-    pub fn init() -> CommandHandler<'a> {
+impl CommandHandler {
+    /// Create the command palette with the commands registered by the modules.
+    pub fn init(mut commands: CommandRegistry) -> CommandHandler {
         let start = Instant::now();
+
+        commands.register(OPEN_PROJECT_COMMAND, "Open a project by name", |_| {});
+        commands.register("quit", "Quit the application", |app| app.exit());
+        commands.register("switch profile", "Switch to another profile", |app| {
+            app.navigation = Some(Navigation::SwitchProfile)
+        });
+        commands.register("search", "Search all projects and cards", |app| {
+            app.navigation = Some(Navigation::Search)
+        });
+        commands.leader('o', OPEN_PROJECT_COMMAND);
+        commands.leader('q', "quit");
+        commands.leader('s', "search");
+
         let size = PopupSize::default().width(60).height(20);
+        let command_options = commands
+            .commands()
+            .iter()
+            .map(|c| c.name.to_string())
+            .collect();
         let command_handler = CommandHandler {
+            commands,
             command: TextInput::new("Command")
                 .view(View::Command)
                 .size((size.width - 2, size.height - 2))
@@ -80,31 +103,42 @@ impl<'a> CommandHandler<'a> {
             size,
             command_view: CommandView::Input,
             focused_pane: FocusedPane::Input,
-            command_options: command_data().iter().map(|s| s.1).collect(),
+            command_options,
+            project_ids: vec![],
             selected_option: 0,
             matcher: Matcher::default(),
+            history: vec![],
+            history_index: None,
         };
         info!("initialized command handler in {:?}", start.elapsed());
         command_handler
     }
 
-    pub fn key_event_handler(
-        &mut self,
-        app: &mut App,
-        interface: &mut Interface,
-        key_event: KeyEvent,
-    ) {
+    pub fn key_event_handler(&mut self, app: &mut App, key_event: KeyEvent) {
         if self.focused_pane == FocusedPane::Input {
+            match (key_event.code, key_event.modifiers) {
+                (KeyCode::Up, _) | (KeyCode::Char('p'), KeyModifiers::CONTROL) => {
+                    self.recall_history(app, true);
+                    return;
+                }
+                (KeyCode::Down, _) | (KeyCode::Char('n'), KeyModifiers::CONTROL) => {
+                    self.recall_history(app, false);
+                    return;
+                }
+                _ => {}
+            }
+
             self.command.key_event_handler(app, key_event);
-            self.update_options();
+            self.update_options(app);
+            self.history_index = None;
         }
 
         if app.mode.is_normal() {
             match key_event.code {
-                KeyCode::Enter => self.execute_command(app, interface),
+                KeyCode::Enter => self.execute_command(app),
                 KeyCode::Char('q') => {
                     app.view.default();
-                    self.reset();
+                    self.reset(app);
                 }
                 KeyCode::Char('j') => {
                     if self.command_view == CommandView::Input {
@@ -130,7 +164,7 @@ impl<'a> CommandHandler<'a> {
             }
         } else if app.mode.is_insert() {
             match key_event.code {
-                KeyCode::Enter => self.execute_command(app, interface),
+                KeyCode::Enter => self.execute_command(app),
                 KeyCode::Esc => app.view.command(),
                 _ => {}
             }
@@ -163,14 +197,27 @@ impl<'a> CommandHandler<'a> {
                     .iter()
                     .enumerate()
                     .map(|(i, o)| {
-                        Line::from(format!(" {o} ")).style(if i == self.selected_option {
-                            Style::new()
-                                .bold()
-                                .fg(colors.active_fg)
-                                .bg(colors.active_bg)
+                        let description = self
+                            .commands
+                            .get(o)
+                            .map(|c| format!(" {}", c.description))
+                            .unwrap_or_default();
+                        if i == self.selected_option {
+                            Line::from(vec![
+                                Span::from(format!(" {o} ")).style(
+                                    app.config
+                                        .focus_style(colors.active_bg)
+                                        .bold()
+                                        .fg(colors.active_fg),
+                                ),
+                                Span::from(description).fg(colors.fg),
+                            ])
                         } else {
-                            Style::new().fg(colors.secondary_fg)
-                        })
+                            Line::from(vec![
+                                Span::from(format!(" {o} ")).fg(colors.secondary_fg),
+                                Span::from(description).fg(colors.tertiary_fg),
+                            ])
+                        }
                     })
                     .collect::<Vec<Line>>(),
             )
@@ -181,98 +228,156 @@ impl<'a> CommandHandler<'a> {
                 .padding(Padding::horizontal(1))
                 .borders(Borders::ALL)
                 .border_type(BorderType::Rounded)
-                .border_style(
-                    Style::new().fg(if self.focused_pane == FocusedPane::Options {
-                        colors.border_active
-                    } else {
-                        colors.border
-                    }),
-                ),
+                .border_style(if self.focused_pane == FocusedPane::Options {
+                    app.config.active_style(colors.border_active)
+                } else {
+                    Style::new().fg(colors.border)
+                }),
         );
 
         frame.render_widget(command_list, command_list_layout);
     }
 }
 
-impl<'a> CommandHandler<'a> {
-    fn reset(&mut self) {
-        self.focused_pane = FocusedPane::Input;
-        self.command.reset();
-        self.update_options();
+impl CommandHandler {
+    /// The keys of the leader menu and the descriptions of their commands.
+    pub fn leader_keys(&self) -> Vec<(char, String)> {
+        self.commands
+            .leader_commands()
+            .into_iter()
+            .filter_map(|c| Some((c.leader_key?, c.description.to_string())))
+            .collect()
     }
 
-    fn parse_command(&self) -> (Command, &str) {
-        if self.command_options.is_empty() {
-            return (Command::None, "none");
-        }
-        let command_str = self.command_options[self.selected_option];
-        for command in command_data() {
-            if command.1.contains(command_str) {
-                return (command.0, command_str);
-            }
+    /// Execute the command of a key from the leader menu. Returns false if no
+    /// command has the key.
+    pub fn execute_leader(&mut self, app: &mut App, key: char) -> bool {
+        let Some(name) = self
+            .commands
+            .leader_commands()
+            .into_iter()
+            .find(|c| c.leader_key == Some(key))
+            .map(|c| c.name.to_string())
+        else {
+            return false;
+        };
+
+        info!("executing {:?} from the leader menu", name);
+        if name == OPEN_PROJECT_COMMAND {
+            // The projects are listed in the palette to choose from.
+            app.mode.insert();
+            app.view.command();
+            self.focused_pane = FocusedPane::Input;
+            self.command.input(OPEN_PROJECT_PREFIX.to_string());
+            self.update_options(app);
+        } else {
+            self.commands.execute(&name, app);
         }
-        (Command::None, "none")
+        true
     }
 
-    fn execute_command(&mut self, app: &mut App, interface: &mut Interface) {
+    fn reset(&mut self, app: &App) {
+        self.focused_pane = FocusedPane::Input;
+        self.history_index = None;
+        self.command.reset();
+        self.update_options(app);
+    }
+
+    fn execute_command(&mut self, app: &mut App) {
         let start = Instant::now();
 
-        let (command, command_str) = self.parse_command();
+        let Some(command_str) = self.command_options.get(self.selected_option).cloned() else {
+            return;
+        };
 
         let _span = tracing::info_span!("command handler", command = command_str).entered();
 
-        match command {
-            Command::Dashboard => {
-                app.view.default();
-                app.mode.normal();
-                app.module = AppModule::Home;
-                interface.modules.home.dashboard();
-            }
-            Command::Settings => {
-                app.view.default();
-                app.mode.normal();
-                app.module = AppModule::Home;
-                interface.modules.home.settings();
-            }
-            Command::Help => {
-                app.view.default();
-                app.mode.normal();
-                app.module = AppModule::Home;
-                interface.modules.home.help();
-            }
-            Command::Home => {
-                app.view.default();
-                app.mode.normal();
-                app.module = AppModule::Home;
-                interface.modules.home.dashboard();
+        if let Some(&project_id) = self.project_ids.get(self.selected_option) {
+            app.navigation = Some(Navigation::OpenProject { project_id });
+        } else if command_str == OPEN_PROJECT_COMMAND {
+            // Without a name, list the projects to choose from.
+            app.mode.insert();
+            self.focused_pane = FocusedPane::Input;
+            self.command.input(OPEN_PROJECT_PREFIX.to_string());
+            self.update_options(app);
+            return;
+        } else if self.commands.get(&command_str).is_none() {
+            return;
+        }
+
+        app.view.default();
+        app.mode.normal();
+        self.commands.execute(&command_str, app);
+        self.push_history(command_str);
+
+        self.reset(app);
+        info!("executed command in {:?}", start.elapsed());
+    }
+
+    fn push_history(&mut self, command: String) {
+        if self.history.last() == Some(&command) {
+            return;
+        }
+        self.history.push(command);
+        if self.history.len() > HISTORY_LIMIT {
+            self.history.remove(0);
+        }
+    }
+
+    /// Put an older or newer command from the history in the input. Going newer
+    /// than the newest command clears the input.
+    fn recall_history(&mut self, app: &App, older: bool) {
+        let index = if older {
+            match self.history_index {
+                Some(index) => Some(index.saturating_sub(1)),
+                None if !self.history.is_empty() => Some(self.history.len() - 1),
+                None => return,
             }
-            Command::ProjectManagement => {
-                app.view.default();
-                app.mode.normal();
-                app.module = AppModule::ProjectManagement;
+        } else {
+            match self.history_index {
+                Some(index) if index + 1 < self.history.len() => Some(index + 1),
+                Some(_) => None,
+                None => return,
             }
-            Command::Quit => app.exit(),
-            Command::None => {}
-        }
+        };
 
-        if command != Command::None {
-            self.reset();
-            info!("executed command in {:?}", start.elapsed());
+        match index {
+            Some(index) => self.command.input(self.history[index].to_string()),
+            None => self.command.reset(),
         }
+        self.history_index = index;
+        self.update_options(app);
     }
 
-    fn update_options(&mut self) {
+    fn update_options(&mut self, app: &App) {
         self.selected_option = 0;
+
+        if let Some(name) = self
+            .command
+            .input_string()
+            .strip_prefix(OPEN_PROJECT_PREFIX)
+        {
+            self.update_project_options(app, name);
+            return;
+        }
+        self.project_ids.clear();
+
+        let command_names = self
+            .commands
+            .commands()
+            .iter()
+            .map(|c| c.name.to_string())
+            .collect::<Vec<String>>();
         let is_longer_than_longest_option = self.command.input_string().chars().count()
-            > command_data()
+            > command_names
                 .iter()
-                .map(|c| c.1.chars().count())
+                .map(|c| c.chars().count())
                 .max()
                 .unwrap_or(0);
         if is_longer_than_longest_option {
             self.command_options = vec![];
         } else if self.command.input_string().chars().count() == 0 {
-            self.command_options = command_data().iter().map(|c| c.1).collect::<Vec<&str>>();
+            self.command_options = command_names;
         } else {
             let pattern = Atom::new(
                 &self.command.input_string(),
@@ -282,13 +387,54 @@ impl<'a> CommandHandler<'a> {
                 false,
             );
             self.command_options = pattern
-                .match_list(
-                    command_data().iter().map(|c| c.1).collect::<Vec<&str>>(),
-                    &mut self.matcher,
-                )
-                .iter()
+                .match_list(command_names, &mut self.matcher)
+                .into_iter()
                 .map(|s| s.0)
-                .collect::<Vec<&str>>();
+                .collect::<Vec<String>>();
+        }
+    }
+
+    /// Set the options to the projects with a title that matches the name.
+    fn update_project_options(&mut self, app: &App, name: &str) {
+        let projects = match Self::db_get_projects(app) {
+            Ok(projects) => projects,
+            Err(e) => {
+                error!("failed to get projects: {e}");
+                vec![]
+            }
+        };
+
+        let pattern = Atom::new(
+            name,
+            CaseMatching::Smart,
+            Normalization::Smart,
+            AtomKind::Fuzzy,
+            false,
+        );
+        let matches = pattern.match_list(projects, &mut self.matcher);
+
+        self.project_ids = matches.iter().map(|p| p.0.id).collect();
+        self.command_options = matches
+            .into_iter()
+            .map(|p| format!("{}{}", OPEN_PROJECT_PREFIX, p.0.title))
+            .collect();
+    }
+
+    fn db_get_projects(app: &App) -> Result<Vec<ProjectOption>> {
+        let conn = app.db.conn();
+        let mut stmt =
+            conn.prepare("SELECT id, title FROM project WHERE archived = 0 ORDER BY position")?;
+        let project_iter = stmt.query_map([], |r| {
+            Ok(ProjectOption {
+                id: r.get(0)?,
+                title: r.get(1)?,
+            })
+        })?;
+
+        let mut projects = vec![];
+        for project in project_iter {
+            projects.push(project?);
         }
+        Ok(projects)
     }
 }
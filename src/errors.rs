@@ -4,25 +4,29 @@ use color_eyre::{config::HookBuilder, eyre, Result};
 
 use crate::tui::Tui;
 
-/// This replaces the standard color_eyre panic and error hooks with hooks that
-/// restore the terminal before printing the panic or error.
+/// This replaces the standard color_eyre panic and error hooks. The panic hook
+/// restores the terminal before printing the panic. The error hook doesn't,
+/// since reports are also created for errors that are only notified while the
+/// interface keeps running, so [`run_tui`](crate::run_tui) restores the
+/// terminal before it returns an error.
 pub fn install_hooks() -> Result<()> {
     let (panic_hook, eyre_hook) = HookBuilder::default().into_hooks();
 
     // Convert from a color_eyre PanicHook to a standard panic hook.
     let panic_hook = panic_hook.into_panic_hook();
+    // Panicking again within the hook would abort before the panic is printed,
+    // so a failure to restore the terminal is only reported.
     panic::set_hook(Box::new(move |panic_info| {
-        Tui::restore().expect("failed to restore the terminal");
+        if let Err(e) = Tui::restore() {
+            eprintln!("failed to restore the terminal: {e}");
+        }
         panic_hook(panic_info);
     }));
 
     // Convert from a color_eyre EyreHook to a eyre ErrorHook
     let eyre_hook = eyre_hook.into_eyre_hook();
     eyre::set_hook(Box::new(
-        move |error: &(dyn std::error::Error + 'static)| {
-            Tui::restore().expect("failed to restore the terminal");
-            eyre_hook(error)
-        },
+        move |error: &(dyn std::error::Error + 'static)| eyre_hook(error),
     ))?;
 
     Ok(())
@@ -5,18 +5,22 @@ use std::{
 };
 
 use color_eyre::{eyre::Context, Result};
-use crossterm::event::{self, Event as CrosstermEvent, KeyCode, KeyEvent, KeyEventKind};
+use crossterm::event::{
+    self, Event as CrosstermEvent, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseEvent,
+};
 use pltx_app::{
-    state::{AppModule, View},
-    App, Module,
+    state::{AppModule, AppPopup, View},
+    App, Module, Popup,
 };
 
 use crate::{command_handler::CommandHandler, ui::Interface};
 
 pub enum Event {
     Tick,
+    /// Sent every `refresh_interval` seconds, when it's enabled.
+    Refresh,
     Key(KeyEvent),
-    // Mouse(MouseEvent),
+    Mouse(MouseEvent),
     // Resize(u16, u16),
     FocusGained,
     FocusLost,
@@ -32,8 +36,10 @@ pub struct EventHandler {
 const TICK_RATE: u64 = 500;
 
 impl EventHandler {
-    pub fn init() -> Self {
+    pub fn init(refresh_interval: u64) -> Self {
         let tick_rate = Duration::from_millis(TICK_RATE);
+        let refresh_interval =
+            (refresh_interval > 0).then(|| Duration::from_secs(refresh_interval));
         let (sender, receiver) = mpsc::channel();
 
         // handler
@@ -41,6 +47,7 @@ impl EventHandler {
             let sender = sender.clone();
             thread::spawn(move || {
                 let mut last_tick = Instant::now();
+                let mut last_refresh = Instant::now();
                 loop {
                     let timeout = tick_rate
                         .checked_sub(last_tick.elapsed())
@@ -53,7 +60,11 @@ impl EventHandler {
                                     .send(Event::Key(e))
                                     .expect("failed to send key event");
                             }
-                            // CrosstermEvent::Mouse(e) => sender.send(Event::Mouse(e)),
+                            CrosstermEvent::Mouse(e) => {
+                                sender
+                                    .send(Event::Mouse(e))
+                                    .expect("failed to send mouse event");
+                            }
                             // CrosstermEvent::Resize(w, h) => sender.send(Event::Resize(w, h)),
                             CrosstermEvent::FocusGained => {
                                 sender
@@ -74,6 +85,13 @@ impl EventHandler {
                         sender.send(Event::Tick).expect("failed to send tick event");
                         last_tick = Instant::now();
                     }
+
+                    if refresh_interval.is_some_and(|i| last_refresh.elapsed() >= i) {
+                        sender
+                            .send(Event::Refresh)
+                            .expect("failed to send refresh event");
+                        last_refresh = Instant::now();
+                    }
                 }
             })
         };
@@ -104,6 +122,48 @@ impl EventHandler {
         }
     }
 
+    /// Mouse events are only handled when the keyboard could do the same, so
+    /// they never interrupt popups or typing.
+    pub fn mouse_events(
+        &mut self,
+        app: &mut App,
+        interface: &mut Interface,
+        mouse_event: MouseEvent,
+    ) -> Result<()> {
+        if !app.view.is_default() || !app.mode.is_normal() {
+            return Ok(());
+        }
+
+        if app.module == AppModule::ProjectManagement {
+            interface
+                .modules
+                .project_management
+                .mouse_event_handler(mouse_event);
+        }
+
+        Ok(())
+    }
+
+    /// The configured navigation keys are turned into the vim keys that the
+    /// screens match on. This is only done in normal mode, so they can still
+    /// be typed.
+    fn remap_navigation(app: &App, key_event: KeyEvent) -> KeyEvent {
+        if !app.mode.is_normal() {
+            return key_event;
+        }
+
+        let keybinds = &app.config.keybinds;
+        [
+            (keybinds.up, 'k'),
+            (keybinds.down, 'j'),
+            (keybinds.left, 'h'),
+            (keybinds.right, 'l'),
+        ]
+        .into_iter()
+        .find(|(keybind, _)| keybind.matches(&key_event))
+        .map_or(key_event, |(_, key)| KeyEvent::from(KeyCode::Char(key)))
+    }
+
     fn key_event_handler(
         &mut self,
         app: &mut App,
@@ -111,6 +171,18 @@ impl EventHandler {
         command_handler: &mut CommandHandler,
         key_event: KeyEvent,
     ) -> Result<()> {
+        // Inputs cut with ctrl-x, so notifications are only dismissed in normal
+        // mode.
+        if app.mode.is_normal()
+            && key_event.modifiers == KeyModifiers::CONTROL
+            && key_event.code == KeyCode::Char('x')
+            && app.dismiss_notification()
+        {
+            return Ok(());
+        }
+
+        let key_event = Self::remap_navigation(app, key_event);
+
         if app.mode.is_normal() {
             match key_event.code {
                 KeyCode::Char('`') => app.debug.toggle(),
@@ -122,25 +194,88 @@ impl EventHandler {
 
         match app.view {
             View::Default => {
-                if app.mode.is_normal() && key_event.code == KeyCode::Char(':') {
-                    app.mode.insert();
-                    app.view.command();
+                if app.mode.is_normal() {
+                    let keybinds = &app.config.keybinds;
+                    if keybinds.command_palette.matches(&key_event) {
+                        app.mode.insert();
+                        app.view.command();
+                    } else if keybinds.quit.matches(&key_event) {
+                        app.exit();
+                        return Ok(());
+                    } else if keybinds.help.matches(&key_event) {
+                        interface.help(app);
+                        return Ok(());
+                    } else if keybinds.leader.matches(&key_event) {
+                        let keys = command_handler.leader_keys();
+                        interface.popups.which_key.open(app, keys);
+                        return Ok(());
+                    }
                 }
             }
             View::Popup => {
-                if app.mode.is_normal() && key_event.code == KeyCode::Char(':') {
+                // Global popups are dismissed before using the command palette.
+                if app.mode.is_normal()
+                    && app.popup == AppPopup::None
+                    && app.config.keybinds.command_palette.matches(&key_event)
+                {
                     app.mode.insert();
                     app.view.command();
                 }
             }
             View::Command => {
-                command_handler.key_event_handler(app, interface, key_event);
-                return Ok(());
+                command_handler.key_event_handler(app, key_event);
+                return interface.navigate(app);
+            }
+        }
+
+        if app.view.is_popup() {
+            match app.popup {
+                AppPopup::WhatsNew => {
+                    return interface.popups.whats_new.key_event_handler(app, key_event);
+                }
+                AppPopup::WhichKey => {
+                    if let Some(key) = interface.popups.which_key.key_event_handler(app, key_event)
+                    {
+                        command_handler.execute_leader(app, key);
+                    }
+                    return interface.navigate(app);
+                }
+                AppPopup::SwitchProfile => {
+                    let picked = interface
+                        .popups
+                        .switch_profile
+                        .key_event_handler(app, key_event);
+                    if !interface.popups.switch_profile.is_open() {
+                        app.popup = AppPopup::None;
+                        app.view.default();
+                    }
+                    if let Some(name) = picked.and_then(|p| p.into_iter().next()) {
+                        interface.switch_profile(app, name)?;
+                    }
+                    return Ok(());
+                }
+                AppPopup::Search => {
+                    let navigation = interface.popups.search.key_event_handler(app, key_event);
+                    if !interface.popups.search.is_open() {
+                        app.popup = AppPopup::None;
+                        app.view.default();
+                        app.mode.normal();
+                    }
+                    if navigation.is_some() {
+                        app.navigation = navigation;
+                    }
+                    return interface.navigate(app);
+                }
+                AppPopup::Help => {
+                    interface.popups.help.key_event_handler(app, key_event);
+                    return Ok(());
+                }
+                AppPopup::None => {}
             }
         }
 
         match app.module {
-            AppModule::Home => interface.modules.home.key_event_handler(app, key_event),
+            AppModule::Home => interface.modules.home.key_event_handler(app, key_event)?,
             AppModule::ProjectManagement => interface
                 .modules
                 .project_management
@@ -148,6 +283,6 @@ impl EventHandler {
             _ => {}
         }
 
-        Ok(())
+        interface.navigate(app)
     }
 }
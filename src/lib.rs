@@ -1,11 +1,13 @@
 //! The main src crate contains code that isn't a dependency of any workspace
 //! crates.
 
-use std::time::Instant;
+use std::{path::Path, time::Instant};
 
 use color_eyre::Result;
 use keybinds::Event;
-use pltx_app::App;
+use pltx_app::{App, CommandRegistry};
+use pltx_home::Home;
+use pltx_project_management::ProjectManagement;
 
 mod command_handler;
 pub mod errors;
@@ -15,16 +17,44 @@ mod tui;
 mod ui;
 
 use command_handler::CommandHandler;
-use tracing::info;
+use tracing::{info, warn};
 use tui::Tui;
 use ui::Interface;
 
 /// Initialize and run the terminal user interface
 pub fn run_tui(app: &mut App, application_start: Instant) -> Result<()> {
-    let mut tui = Tui::new()?;
+    let mut tui = Tui::new(app.config.mouse, app.config.refresh_interval)?;
+    // The terminal is restored before an error is returned, so the report is
+    // printed to the normal screen.
+    let result = run_interface(app, &mut tui, application_start);
+    Tui::restore()?;
+    result?;
+
+    info!(
+        "application finished after {:?}",
+        application_start.elapsed()
+    );
+
+    Ok(())
+}
+
+fn run_interface(app: &mut App, tui: &mut Tui, application_start: Instant) -> Result<()> {
     app.db.start_session()?;
     let mut interface = Interface::init(app)?;
-    let mut command_handler = CommandHandler::init();
+
+    let mut commands = CommandRegistry::default();
+    Home::register_commands(&mut commands);
+    ProjectManagement::register_commands(&mut commands);
+    let mut command_handler = CommandHandler::init(commands);
+
+    info!("terminal colors: {}", app.color_support);
+    if let Some(warning) = app.config.unknown_keys_warning() {
+        warn!("{}", warning);
+        app.notify_warning(warning);
+    }
+    if !Path::new(&app.profile.config_file).exists() {
+        app.notify_info("Run `pltx --init` to create a config file with every config");
+    }
 
     info!(
         "initialized application in {:?}",
@@ -37,24 +67,23 @@ pub fn run_tui(app: &mut App, application_start: Instant) -> Result<()> {
         })?;
 
         match tui.events.next()? {
-            Event::Tick => app.tick(),
+            Event::Tick => {
+                app.tick();
+                interface.tick(app);
+            }
+            Event::Refresh => interface.refresh(app)?,
             Event::Key(key_event) => {
                 tui.events
                     .key_events(app, &mut interface, &mut command_handler, key_event)?
             }
-            // Event::Mouse(_) => {}
+            Event::Mouse(mouse_event) => {
+                tui.events.mouse_events(app, &mut interface, mouse_event)?
+            }
             // Event::Resize(_, _) => {}
             Event::FocusGained => {}
             Event::FocusLost => {} // Event::Paste(_) => {}
         }
     }
 
-    Tui::restore()?;
-
-    info!(
-        "application finished after {:?}",
-        application_start.elapsed()
-    );
-
     Ok(())
 }
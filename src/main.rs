@@ -1,14 +1,14 @@
 //! pltx
-use std::time::Instant;
+use std::{env, path::PathBuf, process, time::Instant};
 
 use clap::{Parser, Subcommand};
 use color_eyre::Result;
 use pltx::{errors, run_tui};
 use pltx_app::App;
-use pltx_config::{init_config, ProfileConfig};
+use pltx_config::{init_config, init_config_file, Config, ProfileConfig};
 use pltx_utils::dirs;
 use tracing_error::ErrorLayer;
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, Layer};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, Layer, Registry};
 
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 static HELP_TEMPLATE: &str = "\
@@ -35,6 +35,18 @@ struct Cli {
     /// Use a profile
     #[arg(short, long)]
     profile: Option<String>,
+    /// Use another config directory, such as for a portable install
+    #[arg(long, value_name = "PATH")]
+    config_dir: Option<PathBuf>,
+    /// Use the default colors of the terminal
+    #[arg(long)]
+    monochrome: bool,
+    /// Print the config that is in effect and the files of the profile
+    #[arg(long)]
+    print_config: bool,
+    /// Create a config file of the profile with every config and its default
+    #[arg(long)]
+    init: bool,
 }
 
 #[derive(Subcommand, Debug)]
@@ -48,8 +60,34 @@ fn main() -> Result<()> {
 
     errors::install_hooks()?;
     let cli = Cli::parse();
-    let (config, profile) = init_config(cli.profile.clone())?;
-    init_tracing(&config.log_level, &profile)?;
+    if let Some(config_dir) = &cli.config_dir {
+        dirs::set_config_dir(env::current_dir()?.join(config_dir));
+    }
+    // A config with a typo is a common error, which is shown without the
+    // report of the error hook.
+    let (mut config, profile) = match init_config(cli.profile.clone()) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            process::exit(1);
+        }
+    };
+    if cli.monochrome {
+        config.set_monochrome();
+    }
+    if cli.init {
+        if init_config_file(&profile)? {
+            println!("Created the config file {}", profile.config_file);
+        } else {
+            println!("The config file {} already exists", profile.config_file);
+        }
+        return Ok(());
+    }
+    if cli.print_config {
+        print_config(&config, &profile)?;
+        return Ok(());
+    }
+    init_tracing(&config, &profile)?;
 
     let mut app = App::new(config, profile);
 
@@ -66,21 +104,44 @@ fn main() -> Result<()> {
     Ok(())
 }
 
-/// Initialize the tracing crate.
-pub fn init_tracing(log_level: &str, profile: &ProfileConfig) -> Result<()> {
-    std::env::set_var("RUST_LOG", log_level.to_uppercase());
-    let log_path = dirs::cache_dir().join(&profile.log_file);
-    let log_file = std::fs::File::create(log_path)?;
+/// Print the merged config as TOML, after the profile and its files as
+/// comments.
+fn print_config(config: &Config, profile: &ProfileConfig) -> Result<()> {
+    println!("# profile: {}", profile.name);
+    println!("# config_file: {}", profile.config_file);
+    println!("# db_file: {}", profile.db_file);
+    println!("# log_file: {}", profile.log_file);
+    if !config.unknown_keys.is_empty() {
+        println!("# unknown keys: {}", config.unknown_keys.join(", "));
+    }
+    println!();
+    print!("{}", config.to_toml()?);
+    Ok(())
+}
+
+/// Initialize the tracing crate. The logs are written as text, or as JSON
+/// lines with the fields of the current spans.
+pub fn init_tracing(config: &Config, profile: &ProfileConfig) -> Result<()> {
+    let log_file = std::fs::File::create(&profile.log_file)?;
 
     let file_subscriber = tracing_subscriber::fmt::layer()
         .with_file(true)
         .with_line_number(true)
         .with_writer(log_file)
         .with_target(false)
-        .with_ansi(false)
-        .with_filter(tracing_subscriber::filter::EnvFilter::from_default_env());
+        .with_ansi(false);
+    let file_subscriber: Box<dyn Layer<Registry> + Send + Sync> = if config.log_format == "json" {
+        Box::new(
+            file_subscriber
+                .json()
+                .with_current_span(true)
+                .with_span_list(true),
+        )
+    } else {
+        Box::new(file_subscriber)
+    };
     tracing_subscriber::registry()
-        .with(file_subscriber)
+        .with(file_subscriber.with_filter(config.log_filter()?))
         .with(ErrorLayer::default())
         .init();
     Ok(())
@@ -1 +1,9 @@
+mod help;
+mod search;
+mod whats_new;
+mod which_key;
 
+pub use help::*;
+pub use search::*;
+pub use whats_new::*;
+pub use which_key::*;
@@ -0,0 +1,119 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use pltx_app::{state::AppPopup, App, KeybindSection, Popup};
+use pltx_widgets::{PopupSize, PopupWidget, Scrollable};
+use ratatui::{
+    layout::Rect,
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+/// The width of the key column.
+const KEY_WIDTH: usize = 14;
+
+/// Lists the keybinds of the current module and screen, grouped by section.
+pub struct Help {
+    sections: Vec<KeybindSection>,
+    selection: Scrollable,
+}
+
+impl Popup<()> for Help {
+    fn init() -> Self {
+        Self {
+            sections: vec![],
+            selection: Scrollable::default(),
+        }
+    }
+
+    fn key_event_handler(&mut self, app: &mut App, key_event: KeyEvent) {
+        match key_event.code {
+            KeyCode::Char('q') | KeyCode::Esc | KeyCode::Enter => {
+                app.popup = AppPopup::None;
+                app.view.default();
+                self.sections.clear();
+            }
+            KeyCode::Down => self
+                .selection
+                .handle_key(KeyEvent::from(KeyCode::Char('j'))),
+            KeyCode::Up => self
+                .selection
+                .handle_key(KeyEvent::from(KeyCode::Char('k'))),
+            _ => self.selection.handle_key(key_event),
+        }
+    }
+
+    fn render(&self, app: &App, frame: &mut Frame, area: Rect) {
+        let colors = &app.config.colors;
+
+        let popup = PopupWidget::new(app, area)
+            .title_top("Keybinds")
+            .size(PopupSize::default().width(70).height(24))
+            .render(frame);
+
+        let mut lines = vec![];
+        for (i, section) in self.sections.iter().enumerate() {
+            if i != 0 {
+                lines.push(Line::from(""));
+            }
+            lines.push(
+                Line::from(format!(" {}", section.title))
+                    .bold()
+                    .fg(colors.primary),
+            );
+            for (key, description) in section.keybinds.iter() {
+                lines.push(Line::from(vec![
+                    Span::from(format!(" {:<1$}", key, KEY_WIDTH)).fg(colors.keybind_key),
+                    Span::from(*description).fg(colors.keybind_fg),
+                ]));
+            }
+        }
+
+        let table = lines
+            .into_iter()
+            .enumerate()
+            .map(|(i, line)| {
+                Paragraph::new(line).style(if self.selection.focused == i {
+                    app.config.focus_style(colors.input_focus_bg)
+                } else {
+                    Style::new().bg(colors.popup_bg)
+                })
+            })
+            .collect::<Vec<Paragraph>>();
+
+        self.selection.render(frame, popup.sub_area, table);
+    }
+}
+
+impl Help {
+    /// Show the keybinds of the given sections.
+    pub fn open(&mut self, sections: Vec<KeybindSection>) {
+        self.sections = sections;
+        self.selection.reset();
+    }
+
+    /// The keybinds that work everywhere, which reflect the configured keys.
+    pub fn global_sections(app: &App) -> Vec<KeybindSection> {
+        let keybinds = &app.config.keybinds;
+        vec![
+            KeybindSection::new("Global")
+                .keybind(keybinds.command_palette, "Open the command palette")
+                .keybind(keybinds.help, "Show the keybinds")
+                .keybind(keybinds.leader, "Show the leader menu")
+                .keybind(keybinds.quit, "Quit")
+                .keybind("{ }", "Previous or next tab")
+                .keybind("ctrl-x", "Dismiss a notification"),
+            KeybindSection::new("Navigation")
+                .keybind(
+                    format!("{} {}", keybinds.up, keybinds.down),
+                    "Move up or down",
+                )
+                .keybind(
+                    format!("{} {}", keybinds.left, keybinds.right),
+                    "Move left or right",
+                )
+                .keybind("gg G", "Go to the first or last item")
+                .keybind("[", "Go back"),
+        ]
+    }
+}
@@ -0,0 +1,334 @@
+use std::{cmp::Reverse, time::Instant};
+
+use color_eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use nucleo::{
+    pattern::{Atom, AtomKind, CaseMatching, Normalization},
+    Matcher, Utf32Str,
+};
+use pltx_app::{
+    state::{Navigation, View},
+    App, DefaultWidget, Popup,
+};
+use pltx_database::Database;
+use pltx_widgets::{PopupSize, PopupWidget, Scrollable, TextInput};
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+use tracing::info;
+
+/// The maximum number of matches that are shown. The number of the others is
+/// shown below them.
+const SEARCH_LIMIT: usize = 50;
+
+/// A project or a card that can be searched.
+struct SearchItem {
+    project_id: i32,
+    /// The card, or none for the project itself.
+    card_id: Option<i32>,
+    title: String,
+    description: String,
+}
+
+/// A project with its matching cards below it, or one of the cards.
+struct SearchRow {
+    project_id: i32,
+    card_id: Option<i32>,
+    title: String,
+    /// Whether the project or card itself matched. Projects are also shown
+    /// when only their cards match.
+    matched: bool,
+    /// Whether only the description matched, rather than the title.
+    in_description: bool,
+}
+
+/// Search the titles and descriptions of all projects and cards, except the
+/// archived projects and the deleted cards. The matches are grouped by
+/// project, with the best matches first, and `enter` opens the project or card.
+pub struct Search {
+    input: TextInput,
+    items: Vec<SearchItem>,
+    /// The titles of the projects, by id.
+    project_titles: Vec<(i32, String)>,
+    rows: Vec<SearchRow>,
+    /// The number of matches that aren't shown.
+    hidden: usize,
+    selection: Scrollable,
+    matcher: Matcher,
+    open: bool,
+    size: PopupSize,
+}
+
+impl Popup<Option<Navigation>> for Search {
+    fn init() -> Self {
+        let size = PopupSize::default().width(70).height(24);
+        Self {
+            input: TextInput::new("Search")
+                .view(View::Popup)
+                .size((size.width - 2, size.height - 2))
+                .placeholder("Search projects and cards..."),
+            items: vec![],
+            project_titles: vec![],
+            rows: vec![],
+            hidden: 0,
+            selection: Scrollable::default(),
+            matcher: Matcher::default(),
+            open: false,
+            size,
+        }
+    }
+
+    /// Returns the navigation to the project or card that was picked.
+    fn key_event_handler(&mut self, _: &mut App, key_event: KeyEvent) -> Option<Navigation> {
+        let ctrl = key_event.modifiers == KeyModifiers::CONTROL;
+        match key_event.code {
+            KeyCode::Esc => self.close(),
+            KeyCode::Enter => {
+                let row = self.rows.get(self.selection.focused)?;
+                let navigation = match row.card_id {
+                    Some(card_id) => Navigation::OpenCard {
+                        project_id: row.project_id,
+                        card_id,
+                    },
+                    None => Navigation::OpenProject {
+                        project_id: row.project_id,
+                    },
+                };
+                self.close();
+                return Some(navigation);
+            }
+            KeyCode::Down => self
+                .selection
+                .handle_key(KeyEvent::from(KeyCode::Char('j'))),
+            KeyCode::Char('n') if ctrl => self
+                .selection
+                .handle_key(KeyEvent::from(KeyCode::Char('j'))),
+            KeyCode::Up => self
+                .selection
+                .handle_key(KeyEvent::from(KeyCode::Char('k'))),
+            KeyCode::Char('p') if ctrl => self
+                .selection
+                .handle_key(KeyEvent::from(KeyCode::Char('k'))),
+            _ => {
+                let query = self.input.input_string();
+                self.input.insert_key(key_event);
+                if self.input.input_string() != query {
+                    self.selection.reset();
+                    self.update_rows();
+                }
+            }
+        }
+        None
+    }
+
+    fn render(&self, app: &App, frame: &mut Frame, area: Rect) {
+        let colors = &app.config.colors;
+
+        let popup = PopupWidget::new(app, area)
+            .title_top("Search")
+            .size(self.size)
+            .render(frame);
+
+        let [input_layout, list_layout, more_layout] = Layout::default()
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Fill(1),
+                Constraint::Length(1),
+            ])
+            .areas(popup.sub_area);
+
+        self.input.render(frame, app, input_layout, true);
+
+        if self.rows.is_empty() {
+            let message = if self.input.input_string().trim().is_empty() {
+                " Type to search the titles and descriptions of all projects and cards."
+            } else {
+                " No matches."
+            };
+            frame.render_widget(Paragraph::new(message).fg(colors.secondary_fg), list_layout);
+            return;
+        }
+
+        let table = self
+            .rows
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let mut spans = if row.card_id.is_some() {
+                    vec![Span::from(format!("   {}", row.title)).fg(colors.fg)]
+                } else {
+                    let title = Span::from(format!(" {}", row.title)).bold();
+                    vec![if row.matched {
+                        title.fg(colors.primary)
+                    } else {
+                        title.fg(colors.secondary_fg)
+                    }]
+                };
+                if row.in_description {
+                    spans.push(Span::from(" (description)").fg(colors.tertiary_fg));
+                }
+                Paragraph::new(Line::from(spans)).style(if self.selection.focused == i {
+                    app.config.focus_style(colors.input_focus_bg)
+                } else {
+                    Style::new().bg(colors.popup_bg)
+                })
+            })
+            .collect::<Vec<Paragraph>>();
+
+        self.selection.render(frame, list_layout, table);
+
+        if self.hidden > 0 {
+            frame.render_widget(
+                Paragraph::new(format!(" {} more", self.hidden)).fg(colors.secondary_fg),
+                more_layout,
+            );
+        }
+    }
+}
+
+impl Search {
+    /// Open the search with the projects and cards as they are now.
+    pub fn open(&mut self, db: &Database) -> Result<()> {
+        self.db_get_items(db)?;
+        self.input.reset();
+        self.selection.reset();
+        self.update_rows();
+        self.open = true;
+        Ok(())
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn close(&mut self) {
+        self.open = false;
+        self.items.clear();
+        self.project_titles.clear();
+        self.rows.clear();
+        self.hidden = 0;
+    }
+
+    fn db_get_items(&mut self, db: &Database) -> Result<()> {
+        let start = Instant::now();
+
+        let conn = db.conn();
+        let query = "SELECT p.id, NULL, p.title, p.description FROM project p WHERE p.archived = \
+                     0 UNION ALL SELECT c.project_id, c.id, c.title, c.description FROM \
+                     project_card c JOIN project_list l ON l.id = c.list_id JOIN project p ON \
+                     p.id = c.project_id WHERE c.deleted_at IS NULL AND l.deleted_at IS NULL AND \
+                     p.archived = 0";
+        let mut stmt = conn.prepare(query)?;
+        let item_iter = stmt.query_map([], |r| {
+            Ok(SearchItem {
+                project_id: r.get(0)?,
+                card_id: r.get(1)?,
+                title: r.get(2)?,
+                description: r.get::<_, Option<String>>(3)?.unwrap_or_default(),
+            })
+        })?;
+
+        self.items.clear();
+        for item in item_iter {
+            self.items.push(item?);
+        }
+        self.project_titles = self
+            .items
+            .iter()
+            .filter(|i| i.card_id.is_none())
+            .map(|i| (i.project_id, i.title.to_string()))
+            .collect();
+
+        info!("get search items query executed in {:?}", start.elapsed());
+
+        Ok(())
+    }
+
+    /// Rank the items by how well their title or description matches the
+    /// query, and group the best matches by project.
+    fn update_rows(&mut self) {
+        self.rows.clear();
+        self.hidden = 0;
+
+        let query = self.input.input_string();
+        let query = query.trim();
+        if query.is_empty() {
+            self.selection.clamp(0);
+            return;
+        }
+
+        let pattern = Atom::new(
+            query,
+            CaseMatching::Smart,
+            Normalization::Smart,
+            AtomKind::Fuzzy,
+            false,
+        );
+        let mut buf = vec![];
+        let mut matches = self
+            .items
+            .iter()
+            .filter_map(|item| {
+                let title = pattern.score(Utf32Str::new(&item.title, &mut buf), &mut self.matcher);
+                let description = pattern.score(
+                    Utf32Str::new(&item.description, &mut buf),
+                    &mut self.matcher,
+                );
+                let score = title.max(description)?;
+                Some((item, score, title.is_none()))
+            })
+            .collect::<Vec<(&SearchItem, u16, bool)>>();
+        matches.sort_by_key(|(_, score, _)| Reverse(*score));
+        self.hidden = matches.len().saturating_sub(SEARCH_LIMIT);
+        matches.truncate(SEARCH_LIMIT);
+
+        // The projects are ordered by their best match, which is either the
+        // project itself or one of its cards.
+        let mut project_ids = vec![];
+        for (item, _, _) in &matches {
+            if !project_ids.contains(&item.project_id) {
+                project_ids.push(item.project_id);
+            }
+        }
+
+        for project_id in project_ids {
+            let project_match = matches
+                .iter()
+                .find(|(i, _, _)| i.project_id == project_id && i.card_id.is_none());
+            let title = self
+                .project_titles
+                .iter()
+                .find(|(id, _)| *id == project_id)
+                .map_or(String::new(), |(_, title)| title.to_string());
+            self.rows.push(SearchRow {
+                project_id,
+                card_id: None,
+                title,
+                matched: project_match.is_some(),
+                in_description: project_match.is_some_and(|(_, _, d)| *d),
+            });
+            for (item, _, in_description) in matches
+                .iter()
+                .filter(|(i, _, _)| i.project_id == project_id && i.card_id.is_some())
+            {
+                self.rows.push(SearchRow {
+                    project_id,
+                    card_id: item.card_id,
+                    title: item.title.to_string(),
+                    matched: true,
+                    in_description: *in_description,
+                });
+            }
+        }
+
+        self.selection.clamp(self.rows.len());
+        // The best match is focused, rather than the project above it.
+        if let Some(index) = self.rows.iter().position(|r| r.matched) {
+            self.selection.focus(index);
+        }
+    }
+}
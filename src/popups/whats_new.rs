@@ -0,0 +1,127 @@
+use color_eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use pltx_app::{state::AppPopup, App, Popup};
+use pltx_database::Database;
+use pltx_utils::WidgetMargin;
+use pltx_widgets::{PopupSize, PopupWidget};
+use ratatui::{
+    layout::Rect,
+    style::Stylize,
+    text::{Line, Span},
+    widgets::{Paragraph, Wrap},
+    Frame,
+};
+use tracing::info;
+
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+const LAST_SEEN_VERSION_KEY: &str = "last_seen_version";
+
+/// The changes of each version, from newest to oldest.
+const CHANGELOG: &[(&str, &[&str])] = &[(
+    "0.0.1",
+    &[
+        "Upcoming cards are shown in an agenda on the dashboard.",
+        "Open a project by name with the \"open project\" command.",
+        "Zoom into a list on a board to show it at the full width.",
+        "Complete a card when all of its subtasks are checked.",
+        "Notifications are shown for saves, warnings, and errors.",
+        "Deleting a project, list, or card asks for confirmation.",
+    ],
+)];
+
+/// Shows the changes since the last version that was run. It is shown once
+/// after upgrading, and dismissing it records the running version.
+pub struct WhatsNew {
+    changes: Vec<(&'static str, &'static [&'static str])>,
+}
+
+impl Popup<Result<()>> for WhatsNew {
+    fn init() -> Self {
+        Self { changes: vec![] }
+    }
+
+    fn key_event_handler(&mut self, app: &mut App, key_event: KeyEvent) -> Result<()> {
+        if matches!(
+            key_event.code,
+            KeyCode::Char('q') | KeyCode::Esc | KeyCode::Enter
+        ) {
+            app.db.set_state(LAST_SEEN_VERSION_KEY, VERSION)?;
+            app.popup = AppPopup::None;
+            app.view.default();
+            self.changes.clear();
+        }
+
+        Ok(())
+    }
+
+    fn render(&self, app: &App, frame: &mut Frame, area: Rect) {
+        let colors = &app.config.colors;
+
+        let title = format!("What's New in {VERSION}");
+        let popup = PopupWidget::new(app, area)
+            .title_top(&title)
+            .size(PopupSize::default().width(70).height(20))
+            .render(frame);
+
+        let mut lines = vec![];
+        for (version, changes) in self.changes.iter() {
+            lines.push(Line::from(*version).bold().fg(colors.primary));
+            for change in changes.iter() {
+                lines.push(Line::from(vec![
+                    Span::from(" • ").fg(colors.secondary_fg),
+                    Span::from(*change).fg(colors.fg),
+                ]));
+            }
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::from(vec![
+            Span::from("Press ").fg(colors.secondary_fg),
+            Span::from("q").fg(colors.keybind_key),
+            Span::from(" to close").fg(colors.secondary_fg),
+        ]));
+
+        frame.render_widget(
+            Paragraph::new(lines).wrap(Wrap { trim: false }),
+            WidgetMargin::proportional(1).apply(popup.sub_area),
+        );
+    }
+}
+
+impl WhatsNew {
+    /// Get the changes since the last seen version. Returns whether there are
+    /// any to show. On the first run, the running version is recorded instead,
+    /// since there is nothing to upgrade from.
+    pub fn db_get_changes(&mut self, db: &Database) -> Result<bool> {
+        let Some(last_seen) = db.get_state(LAST_SEEN_VERSION_KEY)? else {
+            db.set_state(LAST_SEEN_VERSION_KEY, VERSION)?;
+            return Ok(false);
+        };
+
+        let last_seen = parse_version(&last_seen);
+        let current = parse_version(VERSION);
+        self.changes = CHANGELOG
+            .iter()
+            .filter(|(version, _)| {
+                let version = parse_version(version);
+                version > last_seen && version <= current
+            })
+            .copied()
+            .collect();
+
+        if self.changes.is_empty() {
+            // There may be no changelog for a patch release.
+            db.set_state(LAST_SEEN_VERSION_KEY, VERSION)?;
+        } else {
+            info!("showing what's new since version {:?}", last_seen);
+        }
+
+        Ok(!self.changes.is_empty())
+    }
+}
+
+fn parse_version(version: &str) -> Vec<u32> {
+    version
+        .split('.')
+        .map(|part| part.parse().unwrap_or(0))
+        .collect()
+}
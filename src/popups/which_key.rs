@@ -0,0 +1,95 @@
+use std::time::{Duration, Instant};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use pltx_app::{state::AppPopup, App, Popup};
+use pltx_utils::WidgetMargin;
+use pltx_widgets::{PopupSize, PopupWidget};
+use ratatui::{
+    layout::Rect,
+    style::Stylize,
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+/// The menu that is shown after the leader key, with the keys that can be
+/// pressed next. It's closed by choosing a key or pressing `esc`, or after the
+/// `leader_timeout` config.
+pub struct WhichKey {
+    keys: Vec<(char, String)>,
+    opened_at: Option<Instant>,
+}
+
+impl Popup<Option<char>> for WhichKey {
+    fn init() -> Self {
+        Self {
+            keys: vec![],
+            opened_at: None,
+        }
+    }
+
+    /// Returns the key that was chosen, if it's in the menu.
+    fn key_event_handler(&mut self, app: &mut App, key_event: KeyEvent) -> Option<char> {
+        let chosen = match key_event.code {
+            KeyCode::Char(char) => self.keys.iter().find(|(key, _)| *key == char),
+            _ => None,
+        }
+        .map(|(key, _)| *key);
+        self.close(app);
+        chosen
+    }
+
+    fn render(&self, app: &App, frame: &mut Frame, area: Rect) {
+        let colors = &app.config.colors;
+
+        let height = self.keys.len() as u16 + 4;
+        let popup = PopupWidget::new(app, area)
+            .title_top("Leader")
+            .size(PopupSize::default().width(50).height(height))
+            .render(frame);
+
+        let lines = self
+            .keys
+            .iter()
+            .map(|(key, description)| {
+                Line::from(vec![
+                    Span::from(format!("{key}  ")).fg(colors.keybind_key),
+                    Span::from(description.as_str()).fg(colors.keybind_fg),
+                ])
+            })
+            .collect::<Vec<Line>>();
+
+        frame.render_widget(
+            Paragraph::new(lines),
+            WidgetMargin::proportional(1).apply(popup.sub_area),
+        );
+    }
+}
+
+impl WhichKey {
+    /// Show the keys and the descriptions of their commands.
+    pub fn open(&mut self, app: &mut App, keys: Vec<(char, String)>) {
+        self.keys = keys;
+        self.opened_at = Some(Instant::now());
+        app.popup = AppPopup::WhichKey;
+        app.view.popup();
+    }
+
+    fn close(&mut self, app: &mut App) {
+        self.keys.clear();
+        self.opened_at = None;
+        app.popup = AppPopup::None;
+        app.view.default();
+    }
+
+    /// Close the menu if it has been open for longer than the
+    /// `leader_timeout` config.
+    pub fn tick(&mut self, app: &mut App) {
+        let timeout = app.config.keybinds.leader_timeout;
+        if let Some(opened_at) = self.opened_at {
+            if timeout != 0 && opened_at.elapsed() >= Duration::from_millis(timeout) {
+                self.close(app);
+            }
+        }
+    }
+}
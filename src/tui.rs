@@ -3,7 +3,10 @@ use std::{
     time::Instant,
 };
 
-use crossterm::terminal::{self, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{
+    event::{DisableMouseCapture, EnableMouseCapture},
+    terminal::{self, EnterAlternateScreen, LeaveAlternateScreen},
+};
 use ratatui::{backend::CrosstermBackend, Terminal};
 use tracing::info;
 
@@ -17,18 +20,24 @@ pub struct Tui {
 }
 
 impl Tui {
-    pub fn new() -> io::Result<Self> {
+    /// The mouse is only captured when `mouse` is enabled, since it stops the
+    /// terminal from selecting text. Refresh events are sent every
+    /// `refresh_interval` seconds, unless it's 0.
+    pub fn new(mouse: bool, refresh_interval: u64) -> io::Result<Self> {
         let start = Instant::now();
         let backend = CrosstermBackend::new(io::stdout());
         let mut terminal = Terminal::new(backend)?;
 
         terminal::enable_raw_mode()?;
         crossterm::execute!(stdout(), EnterAlternateScreen)?;
+        if mouse {
+            crossterm::execute!(stdout(), EnableMouseCapture)?;
+        }
         terminal.clear()?;
 
         let tui = Self {
             terminal,
-            events: EventHandler::init(),
+            events: EventHandler::init(refresh_interval),
         };
 
         info!("initialized terminal backend in {:?}", start.elapsed());
@@ -38,7 +47,7 @@ impl Tui {
     pub fn restore() -> io::Result<()> {
         let start = Instant::now();
         terminal::disable_raw_mode()?;
-        crossterm::execute!(stdout(), LeaveAlternateScreen)?;
+        crossterm::execute!(stdout(), DisableMouseCapture, LeaveAlternateScreen)?;
         info!("restored the terminal in {:?}", start.elapsed());
         Ok(())
     }
@@ -1,14 +1,15 @@
-use std::{str::FromStr, time::Instant};
+use std::{cmp, mem, str::FromStr, time::Instant};
 
 use color_eyre::Result;
 use pltx_app::{
-    state::{AppModule, AppPopup},
-    App, DebugPosition, Module,
+    state::{AppModule, AppPopup, Navigation},
+    App, DebugPosition, Module, NotificationLevel, Popup,
 };
-use pltx_config::ColorsConfig;
+use pltx_config::init_config;
 use pltx_home::Home;
 use pltx_project_management::ProjectManagement;
-use pltx_utils::DateTime;
+use pltx_utils::{DateTime, DurationSince};
+use pltx_widgets::ListPicker;
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Style, Stylize},
@@ -16,9 +17,12 @@ use ratatui::{
     widgets::{Block, BorderType, Borders, Clear, Padding, Paragraph},
     Frame,
 };
-use tracing::info;
+use tracing::{error, info, warn};
 
-use crate::command_handler::CommandHandler;
+use crate::{
+    command_handler::CommandHandler,
+    popups::{Help, Search, WhatsNew, WhichKey},
+};
 
 /// States for each module.
 pub struct InterfaceModule {
@@ -27,30 +31,201 @@ pub struct InterfaceModule {
 }
 
 /// States for each popup.
-pub struct PopupState {}
+pub struct PopupState {
+    pub help: Help,
+    pub whats_new: WhatsNew,
+    pub which_key: WhichKey,
+    pub switch_profile: ListPicker<String>,
+    pub search: Search,
+}
 
 pub struct Interface {
     pub modules: InterfaceModule,
     /// Global popups. Module popups are located within the modules own
     /// directories.
-    // TODO: Remove underscore when popups are added.
-    pub _popups: PopupState,
+    pub popups: PopupState,
 }
 
 impl Interface {
     pub fn init(app: &mut App) -> Result<Self> {
         let start = Instant::now();
-        let interface = Self {
+        // The project management module creates the tables that the home
+        // dashboard reads from, so it's initialized first.
+        let project_management = ProjectManagement::init(app)?;
+        let mut interface = Self {
             modules: InterfaceModule {
                 home: Home::init(app)?,
-                project_management: ProjectManagement::init(app)?,
+                project_management,
+            },
+            popups: PopupState {
+                help: Help::init(),
+                whats_new: WhatsNew::init(),
+                which_key: WhichKey::init(),
+                switch_profile: ListPicker::init().title("Switch Profile"),
+                search: Search::init(),
             },
-            _popups: PopupState {},
         };
+        if interface.popups.whats_new.db_get_changes(&app.db)? {
+            app.popup = AppPopup::WhatsNew;
+            app.view.popup();
+        }
         info!("initialized interface in {:?}", start.elapsed());
         Ok(interface)
     }
 
+    /// Open the help popup with the keybinds of the current module and screen.
+    pub fn help(&mut self, app: &mut App) {
+        let mut sections = Help::global_sections(app);
+        sections.extend(match app.module {
+            AppModule::Home => self.modules.home.keybinds(),
+            AppModule::ProjectManagement => self.modules.project_management.keybinds(app),
+            AppModule::None => vec![],
+        });
+        self.popups.help.open(sections);
+        app.popup = AppPopup::Help;
+        app.view.popup();
+    }
+
+    /// Update the state that changes over time.
+    pub fn tick(&mut self, app: &mut App) {
+        if app.popup == AppPopup::WhichKey {
+            self.popups.which_key.tick(app);
+        }
+        if app.module == AppModule::Home {
+            self.modules.home.tick(app);
+        }
+    }
+
+    /// Reload the screens that show state which changes over time, such as
+    /// which cards are due soon. Nothing is reloaded while a popup or an input
+    /// is open, so nothing that's being edited is reset.
+    pub fn refresh(&mut self, app: &mut App) -> Result<()> {
+        if app.popup != AppPopup::None || !app.view.is_default() || !app.mode.is_normal() {
+            return Ok(());
+        }
+        self.modules.home.refresh(app)?;
+        self.modules.project_management.refresh(app)
+    }
+
+    /// Switch to another profile. The config is read again and the database of
+    /// the profile is opened, and every screen is reset so nothing of the
+    /// previous profile is shown.
+    pub fn switch_profile(&mut self, app: &mut App, name: String) -> Result<()> {
+        if name == app.profile.name {
+            return Ok(());
+        }
+
+        let (config, profile) = match init_config(Some(name.to_string())) {
+            Ok(config) => config,
+            Err(e) => {
+                error!("failed to switch to profile {:?}: {e}", name);
+                app.notify_error(format!("Failed to switch profiles: {e}"));
+                return Ok(());
+            }
+        };
+        info!("switching to profile {:?}", name);
+        app.switch_profile(config, profile)?;
+        *self = Self::init(app)?;
+        app.notify_info(format!("Switched to the '{}' profile", name));
+        if let Some(warning) = app.config.unknown_keys_warning() {
+            warn!("{}", warning);
+            app.notify_warning(warning);
+        }
+
+        Ok(())
+    }
+
+    /// Handle the pending navigation request of the app, if there is one.
+    pub fn navigate(&mut self, app: &mut App) -> Result<()> {
+        let Some(navigation) = app.navigation.take() else {
+            return Ok(());
+        };
+
+        match navigation {
+            Navigation::Dashboard => {
+                app.module = AppModule::Home;
+                self.modules.home.dashboard();
+            }
+            Navigation::Calendar => {
+                app.module = AppModule::Home;
+                self.modules.home.calendar(app)?;
+            }
+            Navigation::Settings => {
+                app.module = AppModule::Home;
+                self.modules.home.settings(app)?;
+            }
+            Navigation::Help => {
+                app.module = AppModule::Home;
+                self.modules.home.help();
+            }
+            Navigation::Logs => {
+                app.module = AppModule::Home;
+                self.modules.home.logs(app);
+            }
+            Navigation::OpenProject { project_id } => {
+                app.module = AppModule::ProjectManagement;
+                self.modules
+                    .project_management
+                    .open_project(app, project_id)?;
+            }
+            Navigation::DebugPositions => {
+                app.module = AppModule::ProjectManagement;
+                self.modules.project_management.debug_positions(app)?;
+            }
+            Navigation::SwitchProfile => {
+                let profiles = app
+                    .config
+                    .profiles
+                    .iter()
+                    .map(|p| {
+                        let title = if p.name == app.profile.name {
+                            format!("{} (active)", p.name)
+                        } else {
+                            p.name.to_string()
+                        };
+                        (p.name.to_string(), title)
+                    })
+                    .collect();
+                self.popups.switch_profile.open(profiles);
+                app.popup = AppPopup::SwitchProfile;
+                app.view.popup();
+            }
+            Navigation::NewCard => {
+                app.module = AppModule::ProjectManagement;
+                self.modules.project_management.new_card(app);
+            }
+            Navigation::NewList => {
+                app.module = AppModule::ProjectManagement;
+                self.modules.project_management.new_list(app);
+            }
+            Navigation::ExportProject => {
+                app.module = AppModule::ProjectManagement;
+                self.modules.project_management.export_project(app);
+            }
+            Navigation::ImportJson => {
+                app.module = AppModule::ProjectManagement;
+                self.modules.project_management.import_json(app)?;
+            }
+            Navigation::OpenCard {
+                project_id,
+                card_id,
+            } => {
+                app.module = AppModule::ProjectManagement;
+                self.modules
+                    .project_management
+                    .open_card(app, project_id, card_id)?;
+            }
+            Navigation::Search => {
+                self.popups.search.open(&app.db)?;
+                app.popup = AppPopup::Search;
+                app.view.popup();
+                app.mode.insert();
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn render(
         &mut self,
         frame: &mut Frame,
@@ -80,7 +255,7 @@ impl Interface {
             ])
             .areas(area);
 
-        frame.render_widget(self.title_bar(colors), title_bar_layout);
+        frame.render_widget(self.title_bar(app), title_bar_layout);
         frame.render_widget(Block::new().bg(colors.bg).fg(colors.fg), module_layout);
 
         self.status_bar(app, frame, status_bar_layout);
@@ -97,6 +272,13 @@ impl Interface {
 
         if app.view.is_popup() {
             match app.popup {
+                AppPopup::WhatsNew => self.popups.whats_new.render(app, frame, module_layout),
+                AppPopup::Help => self.popups.help.render(app, frame, module_layout),
+                AppPopup::WhichKey => self.popups.which_key.render(app, frame, module_layout),
+                AppPopup::SwitchProfile => {
+                    self.popups.switch_profile.render(app, frame, module_layout)
+                }
+                AppPopup::Search => self.popups.search.render(app, frame, module_layout),
                 AppPopup::None => {}
             }
         }
@@ -105,6 +287,8 @@ impl Interface {
             command_handler.render(app, frame, area);
         }
 
+        self.notifications(app, frame, module_layout);
+
         if app.debug.enabled && app.debug.show {
             let debug_lines = vec![
                 Line::from("~ = rotate position, ! = toggle min preview"),
@@ -153,10 +337,50 @@ impl Interface {
         }
     }
 
-    fn title_bar(&self, colors: &ColorsConfig) -> Paragraph {
-        let title_bar_content = vec![Line::from(
-            vec![Span::from(" Privacy Life Tracker ").bold()],
-        )];
+    /// Render the notifications as toasts in the bottom right corner, with the
+    /// most recent at the bottom.
+    fn notifications(&self, app: &App, frame: &mut Frame, area: Rect) {
+        let colors = &app.config.colors;
+        let max_width = 50;
+        let height = 3;
+
+        let mut y = area.y + area.height;
+        for notification in app.notifications.iter().rev().filter(|n| !n.is_expired()) {
+            if y < area.y + height {
+                break;
+            }
+            y -= height;
+
+            let color = match notification.level {
+                NotificationLevel::Info => colors.primary,
+                NotificationLevel::Warning => colors.warning,
+                NotificationLevel::Error => colors.danger,
+            };
+            let width = cmp::min(notification.message.chars().count() as u16 + 4, max_width)
+                .min(area.width);
+            let toast_area = Rect::new(area.x + area.width - width, y, width, height);
+
+            let toast = Paragraph::new(notification.message.as_str())
+                .fg(color)
+                .block(
+                    Block::new()
+                        .borders(Borders::ALL)
+                        .border_type(BorderType::Rounded)
+                        .border_style(Style::new().fg(color))
+                        .padding(Padding::horizontal(1))
+                        .bg(colors.popup_bg),
+                );
+            frame.render_widget(Clear, toast_area);
+            frame.render_widget(toast, toast_area);
+        }
+    }
+
+    fn title_bar(&self, app: &App) -> Paragraph {
+        let colors = &app.config.colors;
+        let title_bar_content = vec![Line::from(vec![
+            Span::from(" Privacy Life Tracker ").bold(),
+            Span::from(format!("[{}] ", app.profile.name)),
+        ])];
         Paragraph::new(title_bar_content)
             .alignment(Alignment::Center)
             .style(Style::new().fg(colors.title_bar_fg).bg(colors.title_bar_bg))
@@ -175,22 +399,19 @@ impl Interface {
             ])
             .areas(area);
 
-        let mut mode_fg = mode_colors.fg;
-        let mut mode_bg = mode_colors.bg;
-        let mut status_bar_fg = colors.status_bar_fg;
-        let mut status_bar_bg = colors.status_bar_bg;
+        // In monochrome mode the mode is reversed, since it has no background.
+        let mut mode_style = app.config.focus_style(mode_colors.bg).fg(mode_colors.fg);
+        let mut status_bar_style = Style::new()
+            .fg(colors.status_bar_fg)
+            .bg(colors.status_bar_bg);
         if app.mode.is_delete() {
-            mode_fg = colors.status_bar_fg;
-            mode_bg = colors.status_bar_bg;
-            status_bar_fg = mode_colors.fg;
-            status_bar_bg = mode_colors.bg;
+            mem::swap(&mut mode_style, &mut status_bar_style);
         }
         let left_text = vec![Line::from(vec![
             Span::from(format!(" {} ", app.mode.to_string().to_uppercase()))
-                .bold()
-                .fg(mode_fg)
-                .bg(mode_bg),
-            Span::from("").fg(mode_bg),
+                .style(mode_style)
+                .bold(),
+            Span::from("").fg(mode_style.bg.unwrap_or_default()),
             if app.mode.is_delete() {
                 Span::from(" Confirm Deletion (y/n)").bold()
             } else {
@@ -199,30 +420,33 @@ impl Interface {
         ])];
         let left_content = Paragraph::new(left_text)
             .alignment(Alignment::Left)
-            .style(Style::new().fg(status_bar_fg).bg(status_bar_bg));
+            .style(status_bar_style);
         frame.render_widget(left_content, left_layout);
 
         let center_text = vec![Line::from(vec![Span::from(format!(
             "Session duration: {}",
             if let Some(started) = &app.db.started {
-                DateTime::new().duration_since(started).to_string()
+                DurationSince::from(DateTime::new().duration_since(started)).to_string()
             } else {
                 "<pending>".to_string()
             }
         ))])];
         let center_content = Paragraph::new(center_text)
             .alignment(Alignment::Center)
-            .style(Style::new().fg(status_bar_fg).bg(status_bar_bg));
+            .style(status_bar_style);
         frame.render_widget(center_content, center_layout);
 
-        let right_text = vec![Line::from(vec![Span::from("Press ? for help ")])];
+        let right_text = vec![Line::from(vec![Span::from(format!(
+            "Press {} for help ",
+            app.config.keybinds.help
+        ))])];
         let right_content = Paragraph::new(if app.mode.is_delete() {
             vec![Line::from("")]
         } else {
             right_text
         })
         .alignment(Alignment::Right)
-        .style(Style::new().fg(status_bar_fg).bg(status_bar_bg));
+        .style(status_bar_style);
         frame.render_widget(right_content, right_layout);
     }
 }